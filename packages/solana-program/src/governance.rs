@@ -0,0 +1,130 @@
+// governance.rs
+use anchor_lang::prelude::*;
+
+use crate::state::{LcState, RelayerSet, VerifyingKey};
+use crate::zk_verifier::ZkError;
+use crate::{ClaimTipUpdated, FeeBpsUpdated, GovernanceDelayUpdated, RelayerAdded, RelayerRemoved, VkRotated};
+
+/// Critical parameter changes that go through propose → execute once
+/// `LcState.governance_delay_secs` is non-zero
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum GovernanceAction {
+    RotateVerifyingKey { new_vk_id: u32, deprecation_window_secs: i64 },
+    AddRelayer { relayer: Pubkey },
+    RemoveRelayer { relayer: Pubkey },
+    SetFeeBps { fee_bps: u16 },
+    SetGovernanceDelay { delay_secs: i64 },
+    SetClaimTipBps { tip_bps: u16 },
+}
+
+impl GovernanceAction {
+    /// Largest serialized variant: tag + Pubkey
+    pub const MAX_SIZE: usize = 1 + 32;
+}
+
+// A proposed action, publicly visible until executed or cancelled
+#[account]
+pub struct PendingAction {
+    pub id: u64,
+    pub action: GovernanceAction,
+    pub proposed_at: i64,
+    pub executable_at: i64,
+    pub executed: bool,
+    pub cancelled: bool,
+}
+
+impl PendingAction {
+    pub const SEED: &'static [u8] = b"gov_action";
+    pub const SIZE: usize = 8 + GovernanceAction::MAX_SIZE + 8 + 8 + 1 + 1;
+}
+
+pub fn rotate_verifying_key(
+    state: &mut LcState,
+    new_verifying_key: &VerifyingKey,
+    new_vk_id: u32,
+    deprecation_window_secs: i64,
+) -> Result<()> {
+    require!(new_verifying_key.vk_id == new_vk_id, ZkError::InvalidVerifyingKey);
+    require!(new_verifying_key.is_finalized(), ZkError::VerifyingKeyNotFinalized);
+    require!(deprecation_window_secs >= 0, ZkError::InvalidDeprecationWindow);
+    require!(new_vk_id != state.vk_id, ZkError::InvalidVerifyingKey);
+
+    let now = Clock::get()?.unix_timestamp;
+    state.previous_vk_id = state.vk_id;
+    state.previous_vk_expires_at = now.saturating_add(deprecation_window_secs);
+    state.vk_id = new_vk_id;
+
+    emit!(VkRotated {
+        previous_vk_id: state.previous_vk_id,
+        new_vk_id,
+        previous_vk_expires_at: state.previous_vk_expires_at,
+    });
+    msg!(
+        "Verifying key rotated {} -> {}, old key valid until {}",
+        state.previous_vk_id,
+        new_vk_id,
+        state.previous_vk_expires_at
+    );
+    Ok(())
+}
+
+pub fn add_relayer(set: &mut RelayerSet, relayer: Pubkey) -> Result<()> {
+    require!(!set.contains(&relayer), ZkError::RelayerAlreadyExists);
+    require!(set.relayers.len() < RelayerSet::MAX_RELAYERS, ZkError::RelayerSetFull);
+    set.relayers.push(relayer);
+
+    emit!(RelayerAdded { relayer });
+    msg!("Relayer {} added ({} total)", relayer, set.relayers.len());
+    Ok(())
+}
+
+pub fn remove_relayer(state: &LcState, set: &mut RelayerSet, relayer: Pubkey) -> Result<()> {
+    let index = set
+        .relayers
+        .iter()
+        .position(|r| *r == relayer)
+        .ok_or(ZkError::RelayerNotFound)?;
+    require!(
+        set.relayers.len() > state.root_approval_threshold as usize,
+        ZkError::InvalidApprovalThreshold
+    );
+    set.relayers.swap_remove(index);
+
+    emit!(RelayerRemoved { relayer });
+    msg!("Relayer {} removed ({} left)", relayer, set.relayers.len());
+    Ok(())
+}
+
+pub fn set_fee_bps(state: &mut LcState, fee_bps: u16) -> Result<()> {
+    require!(fee_bps <= LcState::MAX_FEE_BPS, ZkError::InvalidFee);
+
+    let old_fee_bps = state.fee_bps;
+    state.fee_bps = fee_bps;
+
+    emit!(FeeBpsUpdated { old_fee_bps, new_fee_bps: fee_bps });
+    msg!("Protocol fee set to {} bps", fee_bps);
+    Ok(())
+}
+
+pub fn set_claim_tip_bps(state: &mut LcState, tip_bps: u16) -> Result<()> {
+    require!(tip_bps <= LcState::MAX_CLAIM_TIP_BPS, ZkError::InvalidFee);
+
+    let old_tip_bps = state.claim_tip_bps;
+    state.claim_tip_bps = tip_bps;
+
+    emit!(ClaimTipUpdated { old_tip_bps, new_tip_bps: tip_bps });
+    msg!("Permissionless claim tip set to {} bps", tip_bps);
+    Ok(())
+}
+
+pub fn set_governance_delay(state: &mut LcState, delay_secs: i64) -> Result<()> {
+    require!(
+        (0..=LcState::MAX_GOVERNANCE_DELAY_SECS).contains(&delay_secs),
+        ZkError::InvalidGovernanceDelay
+    );
+    state.governance_delay_secs = delay_secs;
+
+    emit!(GovernanceDelayUpdated { delay_secs });
+    msg!("Governance delay set to {}s", delay_secs);
+    Ok(())
+}
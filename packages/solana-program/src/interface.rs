@@ -0,0 +1,117 @@
+// interface.rs
+// What other programs need to compose with the bridge, e.g. to swap wrapped
+// TON on arrival. Depend on this crate with `features = ["interface"]` (no
+// entrypoint) and call `zk_lc::cpi::verify_ton_event` with the accounts from
+// `VerifyTonEventAddresses`, or check a delivered event with `read_event_state`.
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+
+pub use crate::cpi;
+pub use crate::merkle::TxInclusionProof;
+pub use crate::mint::{TREASURY_SEED, WRAPPED_TON_DECIMALS, WRAPPED_TON_MINT_SEED};
+pub use crate::state::{EventPublicInputs, EventState, HeaderChain, LcState, TokenConfig, VerifyingKey, VolumeLimit};
+pub use crate::zk_verifier::ZKProof;
+use crate::zk_verifier::hash_scalar;
+pub use crate::{ClaimTipPaid, NullifierState, TonEventVerified, ID};
+
+pub fn state_address() -> Pubkey {
+    Pubkey::find_program_address(&[LcState::SEED], &ID).0
+}
+
+pub fn wrapped_mint_address() -> Pubkey {
+    Pubkey::find_program_address(&[WRAPPED_TON_MINT_SEED], &ID).0
+}
+
+pub fn event_address(event_id: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[EventState::SEED, &hash_scalar(event_id)], &ID).0
+}
+
+pub fn nullifier_address(nullifier: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[NullifierState::SEED, &hash_scalar(nullifier)], &ID).0
+}
+
+/// Every account `verify_ton_event` / `claim_private_ton_event` takes for one event
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifyTonEventAddresses {
+    pub state: Pubkey,
+    pub header_chain: Pubkey,
+    pub verifying_key: Pubkey,
+    pub token_config: Pubkey,
+    pub global_volume: Pubkey,
+    pub token_volume: Pubkey,
+    pub event_account: Pubkey,
+    pub nullifier_account: Pubkey,
+    pub wrapped_mint: Pubkey,
+    pub treasury: Pubkey,
+    pub recipient: Pubkey,
+    pub recipient_token_account: Pubkey,
+}
+
+impl VerifyTonEventAddresses {
+    pub fn derive(public_inputs: &EventPublicInputs) -> Self {
+        let wrapped_mint = wrapped_mint_address();
+        let find = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &ID).0;
+
+        Self {
+            state: state_address(),
+            header_chain: find(&[HeaderChain::SEED]),
+            verifying_key: find(&[VerifyingKey::SEED, &public_inputs.vk_version.to_le_bytes()]),
+            token_config: find(&[TokenConfig::SEED, &public_inputs.token_id]),
+            global_volume: find(&[VolumeLimit::SEED, VolumeLimit::GLOBAL]),
+            token_volume: find(&[VolumeLimit::SEED, &public_inputs.token_id]),
+            event_account: event_address(&public_inputs.event_id),
+            nullifier_account: nullifier_address(&public_inputs.nullifier),
+            wrapped_mint,
+            treasury: find(&[TREASURY_SEED]),
+            recipient: public_inputs.recipient_solana,
+            recipient_token_account: get_associated_token_address(&public_inputs.recipient_solana, &wrapped_mint),
+        }
+    }
+
+    /// Instruction accounts for a client-built transaction. `header_chain`
+    /// anchors the proof to a verified header; `claimer_token_account` collects
+    /// the claim tip when the program has one set.
+    pub fn accounts(
+        &self,
+        payer: Pubkey,
+        use_header_chain: bool,
+        claimer_token_account: Option<Pubkey>,
+    ) -> crate::accounts::VerifyTonEvent {
+        crate::accounts::VerifyTonEvent {
+            state: self.state,
+            header_chain: use_header_chain.then_some(self.header_chain),
+            verifying_key: self.verifying_key,
+            token_config: self.token_config,
+            global_volume: self.global_volume,
+            token_volume: self.token_volume,
+            event_account: self.event_account,
+            nullifier_account: self.nullifier_account,
+            wrapped_mint: self.wrapped_mint,
+            treasury: self.treasury,
+            recipient: self.recipient,
+            recipient_token_account: self.recipient_token_account,
+            claimer_token_account,
+            payer,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+    }
+}
+
+/// Read an event record, checking it is the bridge's PDA for `event_id`
+pub fn read_event_state(account: &AccountInfo, event_id: &[u8; 32]) -> Result<EventState> {
+    require_keys_eq!(account.key(), event_address(event_id), ErrorCode::ConstraintSeeds);
+    require_keys_eq!(*account.owner, ID, ErrorCode::AccountOwnedByWrongProgram);
+    EventState::try_deserialize(&mut &account.try_borrow_data()?[..])
+}
+
+/// Whether the bridge has minted the TON event `event_id`. Reads false for a
+/// record never created or already reclaimed with `close_event`.
+pub fn is_event_consumed(account: &AccountInfo, event_id: &[u8; 32]) -> Result<bool> {
+    require_keys_eq!(account.key(), event_address(event_id), ErrorCode::ConstraintSeeds);
+    if account.data_is_empty() {
+        return Ok(false);
+    }
+    Ok(read_event_state(account, event_id)?.consumed)
+}
@@ -3,8 +3,18 @@ use anchor_lang::prelude::*;
 mod state;
 mod verify;
 mod zk_verifier;
+mod pda;
+mod merkle;
+mod mint;
+mod governance;
+pub mod interface;
 use state::*;
 use zk_verifier::{ZkError, ZKProof};
+use merkle::TxInclusionProof;
+use governance::{GovernanceAction, PendingAction};
+use mint::{TREASURY_SEED, WRAPPED_TON_DECIMALS, WRAPPED_TON_MINT_SEED};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
 
 declare_id!("8zcmz77ahioCSGX7QnmFL51a1A3qBY1nw5az7R11KF9o");
 
@@ -24,11 +34,600 @@ pub mod zk_lc {
         s.vk_id = vk_id;
         s.ton_state_root = initial_ton_root;
         s.relayer = relayer;
+        s.previous_vk_id = vk_id;
+        s.previous_vk_expires_at = 0;
+        s.fee_bps = 0;
+        s.nullifier_mode = NULLIFIER_MODE_PDA;
+        s.pending_admin = Pubkey::default();
+        s.root_approval_threshold = 0;
+        s.paused = false;
+        s.governance_delay_secs = 0;
+        s.next_action_id = 0;
+        s.event_close_age_secs = 0;
+        s.version = LcState::CURRENT_VERSION;
+        s.events_verified = 0;
+        s.total_amount_bridged = 0;
+        s.header_chain_only = false;
+        s.bump = ctx.bumps.state;
+        s.mint_bump = Pubkey::find_program_address(&[WRAPPED_TON_MINT_SEED], ctx.program_id).1;
+        s.treasury_bump = Pubkey::find_program_address(&[TREASURY_SEED], ctx.program_id).1;
+        s.claim_tip_bps = 0;
+        s.poseidon_from_vk_id = 0;
+        s.deposit_tree_root = [0u8; 32];
 
-        let vk = &mut ctx.accounts.verifying_key;
-        vk.vk_id = vk_id;
-        vk.data = vec![]; // Would load actual verification key
-        
+        // Key bytes are uploaded afterwards via upload_verifying_key / append_vk_chunk
+        ctx.accounts.verifying_key.load_init()?.vk_id = vk_id;
+
+        emit!(StateInitialized {
+            admin: s.admin,
+            vk_id,
+            ton_state_root: initial_ton_root,
+            relayer,
+        });
+        Ok(())
+    }
+
+    /// Grow a state account created by an older program version to the current
+    /// `LcState::SIZE` and stamp it with `CURRENT_VERSION`. Fields added since
+    /// then start zeroed; the admin pays any extra rent.
+    pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+        let state_info = ctx.accounts.state.to_account_info();
+
+        // Older layouts can't be deserialized as LcState, so check the prefix by hand
+        {
+            let data = state_info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 + 32 && &data[..8] == LcState::DISCRIMINATOR,
+                ZkError::InvalidStateAccount
+            );
+            let admin = Pubkey::try_from(&data[8..40]).map_err(|_| ZkError::InvalidStateAccount)?;
+            require_keys_eq!(admin, ctx.accounts.admin.key(), ZkError::UnauthorizedAdmin);
+        }
+
+        let new_len = 8 + LcState::SIZE;
+        let old_len = state_info.data_len();
+        if old_len < new_len {
+            let top_up = Rent::get()?
+                .minimum_balance(new_len)
+                .saturating_sub(state_info.lamports());
+            if top_up > 0 {
+                anchor_lang::system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::Transfer {
+                            from: ctx.accounts.admin.to_account_info(),
+                            to: state_info.clone(),
+                        },
+                    ),
+                    top_up,
+                )?;
+            }
+            state_info.realloc(new_len, true)?;
+        }
+
+        let mut state = LcState::try_deserialize(&mut &state_info.try_borrow_data()?[..])?;
+        let from_version = state.version;
+        require!(
+            old_len < new_len || from_version < LcState::CURRENT_VERSION,
+            ZkError::StateAlreadyMigrated
+        );
+        state.version = LcState::CURRENT_VERSION;
+        state.bump = ctx.bumps.state;
+        state.mint_bump = Pubkey::find_program_address(&[WRAPPED_TON_MINT_SEED], ctx.program_id).1;
+        state.treasury_bump = Pubkey::find_program_address(&[TREASURY_SEED], ctx.program_id).1;
+        pda::write_account(&state_info, &state)?;
+
+        emit!(StateMigrated {
+            from_version,
+            to_version: LcState::CURRENT_VERSION,
+            old_len: old_len as u32,
+            new_len: new_len as u32,
+        });
+        msg!("State migrated v{} -> v{} ({} -> {} bytes)", from_version, LcState::CURRENT_VERSION, old_len, new_len);
+        Ok(())
+    }
+
+    /// Create the wrapped-TON mint; minting is only possible through verified TON events
+    pub fn init_wrapped_mint(ctx: Context<InitWrappedMint>) -> Result<()> {
+        emit!(WrappedMintInitialized { mint: ctx.accounts.wrapped_mint.key() });
+        msg!("Wrapped TON mint created: {}", ctx.accounts.wrapped_mint.key());
+        Ok(())
+    }
+
+    /// Emergency stop: rejects event verification and root updates until unpaused
+    pub fn pause(ctx: Context<SetPaused>) -> Result<()> {
+        ctx.accounts.state.paused = true;
+
+        emit!(BridgePausedChanged { paused: true, admin: ctx.accounts.admin.key() });
+        msg!("⛔ Bridge paused");
+        Ok(())
+    }
+
+    pub fn unpause(ctx: Context<SetPaused>) -> Result<()> {
+        ctx.accounts.state.paused = false;
+
+        emit!(BridgePausedChanged { paused: false, admin: ctx.accounts.admin.key() });
+        msg!("Bridge unpaused");
+        Ok(())
+    }
+
+    /// Freeze a single token's event verification without pausing the bridge
+    pub fn pause_token(ctx: Context<SetTokenPaused>, token_id: [u8; 32]) -> Result<()> {
+        ctx.accounts.token_config.paused = true;
+
+        emit!(TokenPausedChanged { token_id, paused: true, admin: ctx.accounts.admin.key() });
+        msg!("⛔ Token {:?} paused", token_id);
+        Ok(())
+    }
+
+    pub fn unpause_token(ctx: Context<SetTokenPaused>, token_id: [u8; 32]) -> Result<()> {
+        ctx.accounts.token_config.paused = false;
+
+        emit!(TokenPausedChanged { token_id, paused: false, admin: ctx.accounts.admin.key() });
+        msg!("Token {:?} unpaused", token_id);
+        Ok(())
+    }
+
+    /// First step of an admin handover; proposing the default key cancels a pending one
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        state.pending_admin = new_admin;
+
+        emit!(AdminProposed {
+            current_admin: state.admin,
+            proposed_admin: new_admin,
+        });
+        msg!("Admin handover proposed to {}", new_admin);
+        Ok(())
+    }
+
+    /// Second step: the proposed admin signs to take over
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        require!(state.pending_admin != Pubkey::default(), ZkError::NotPendingAdmin);
+        require_keys_eq!(
+            ctx.accounts.new_admin.key(),
+            state.pending_admin,
+            ZkError::NotPendingAdmin
+        );
+
+        let previous_admin = state.admin;
+        state.admin = state.pending_admin;
+        state.pending_admin = Pubkey::default();
+
+        emit!(AdminTransferred {
+            previous_admin,
+            new_admin: state.admin,
+        });
+        msg!("Admin transferred {} -> {}", previous_admin, state.admin);
+        Ok(())
+    }
+
+    /// Create the wrapped-TON treasury token account that collects protocol fees
+    pub fn init_treasury(ctx: Context<InitTreasury>) -> Result<()> {
+        emit!(TreasuryInitialized { treasury: ctx.accounts.treasury.key() });
+        msg!("Treasury created: {}", ctx.accounts.treasury.key());
+        Ok(())
+    }
+
+    /// Set the protocol fee taken from each bridged amount
+    pub fn set_fee_bps(ctx: Context<SetFeeBps>, fee_bps: u16) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        require!(state.governance_delay_secs == 0, ZkError::TimelockRequired);
+        governance::set_fee_bps(state, fee_bps)
+    }
+
+    /// Set the tip minted to whoever claims a deposit with their own token
+    /// account, so anyone can complete deposits while the relayer is down
+    pub fn set_claim_tip_bps(ctx: Context<SetFeeBps>, tip_bps: u16) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        require!(state.governance_delay_secs == 0, ZkError::TimelockRequired);
+        governance::set_claim_tip_bps(state, tip_bps)
+    }
+
+    /// Enable the governance timelock. Once non-zero, the delay itself can only
+    /// be changed through a timelocked action.
+    pub fn set_governance_delay(ctx: Context<SetFeeBps>, delay_secs: i64) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        require!(state.governance_delay_secs == 0, ZkError::TimelockRequired);
+        governance::set_governance_delay(state, delay_secs)
+    }
+
+    /// Set the bridge-wide 24-hour volume cap (0 removes it)
+    pub fn set_global_volume_cap(ctx: Context<SetGlobalVolumeCap>, cap: u64) -> Result<()> {
+        ctx.accounts.volume_limit.cap = cap;
+
+        emit!(VolumeCapUpdated { token_id: None, cap });
+        msg!("Global 24h volume cap set to {}", cap);
+        Ok(())
+    }
+
+    /// Set the 24-hour volume cap for one token (0 removes it)
+    pub fn set_token_volume_cap(ctx: Context<SetTokenVolumeCap>, token_id: [u8; 32], cap: u64) -> Result<()> {
+        ctx.accounts.volume_limit.cap = cap;
+
+        emit!(VolumeCapUpdated { token_id: Some(token_id), cap });
+        msg!("24h volume cap for token {:?} set to {}", token_id, cap);
+        Ok(())
+    }
+
+    /// Create or update the bridge settings for a TON token
+    pub fn configure_token(
+        ctx: Context<ConfigureToken>,
+        token_id: [u8; 32],
+        mint: Pubkey,
+        min_amount: u64,
+        max_amount: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        require!(max_amount == 0 || min_amount <= max_amount, ZkError::InvalidTokenBounds);
+
+        let config = &mut ctx.accounts.token_config;
+        config.token_id = token_id;
+        config.mint = mint;
+        config.min_amount = min_amount;
+        config.max_amount = max_amount;
+        config.enabled = enabled;
+
+        emit!(TokenConfigured {
+            token_id,
+            mint,
+            min_amount,
+            max_amount,
+            enabled,
+        });
+        msg!("Token {:?} configured: mint {}, enabled {}", token_id, mint, enabled);
+        Ok(())
+    }
+
+    pub fn set_event_close_age(ctx: Context<SetEventCloseAge>, age_secs: i64) -> Result<()> {
+        require!(age_secs >= 0, ZkError::InvalidCloseAge);
+        ctx.accounts.state.event_close_age_secs = age_secs;
+
+        emit!(EventCloseAgeUpdated { age_secs });
+        msg!("Consumed events can be closed after {}s", age_secs);
+        Ok(())
+    }
+
+    /// Close a consumed `EventState` older than `event_close_age_secs`, refunding
+    /// rent to its original payer. The nullifier PDA is kept or created first;
+    /// both nullifier modes check it, so the event stays unreplayable.
+    pub fn close_event(ctx: Context<CloseEvent>, event_id: [u8; 32]) -> Result<()> {
+        let state = &ctx.accounts.state;
+        let event_account = &ctx.accounts.event_account;
+        require!(event_account.consumed, ZkError::EventNotConsumed);
+
+        let authority = ctx.accounts.authority.key();
+        require!(
+            authority == event_account.payer || authority == state.admin,
+            ZkError::UnauthorizedClose
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now.saturating_sub(event_account.consumed_at) >= state.event_close_age_secs,
+            ZkError::EventTooRecent
+        );
+
+        let nullifier_account = &mut ctx.accounts.nullifier_account;
+        nullifier_account.consumed = true;
+        nullifier_account.nullifier = event_account.nullifier;
+        nullifier_account.ton_tx_hash = event_account.ton_tx_hash;
+
+        emit!(EventClosed {
+            event_id,
+            payer: event_account.payer,
+            closed_by: authority,
+        });
+        msg!("Event account closed, rent refunded to {}", event_account.payer);
+        Ok(())
+    }
+
+    /// Publish a critical change; it can be executed after the governance delay
+    pub fn propose_action(ctx: Context<ProposeAction>, action: GovernanceAction) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        let now = Clock::get()?.unix_timestamp;
+
+        let pending = &mut ctx.accounts.pending_action;
+        pending.id = state.next_action_id;
+        pending.action = action.clone();
+        pending.proposed_at = now;
+        pending.executable_at = now.saturating_add(state.governance_delay_secs);
+        pending.executed = false;
+        pending.cancelled = false;
+        state.next_action_id += 1;
+
+        emit!(GovernanceActionProposed {
+            id: pending.id,
+            action,
+            executable_at: pending.executable_at,
+        });
+        msg!("Governance action {} proposed, executable at {}", pending.id, pending.executable_at);
+        Ok(())
+    }
+
+    /// Apply a proposed action once its delay has elapsed
+    pub fn execute_action(ctx: Context<ExecuteAction>, action_id: u64) -> Result<()> {
+        let pending = &mut ctx.accounts.pending_action;
+        require!(!pending.executed && !pending.cancelled, ZkError::ActionNotPending);
+        require!(
+            Clock::get()?.unix_timestamp >= pending.executable_at,
+            ZkError::TimelockNotElapsed
+        );
+
+        let state = &mut ctx.accounts.state;
+        match pending.action.clone() {
+            GovernanceAction::RotateVerifyingKey { new_vk_id, deprecation_window_secs } => {
+                let new_vk = ctx
+                    .accounts
+                    .new_verifying_key
+                    .as_ref()
+                    .ok_or(ZkError::MissingActionAccount)?
+                    .load()?;
+                governance::rotate_verifying_key(state, &new_vk, new_vk_id, deprecation_window_secs)?;
+            }
+            GovernanceAction::AddRelayer { relayer } => {
+                let set = ctx.accounts.relayer_set.as_mut().ok_or(ZkError::MissingActionAccount)?;
+                governance::add_relayer(set, relayer)?;
+            }
+            GovernanceAction::RemoveRelayer { relayer } => {
+                let set = ctx.accounts.relayer_set.as_mut().ok_or(ZkError::MissingActionAccount)?;
+                governance::remove_relayer(state, set, relayer)?;
+            }
+            GovernanceAction::SetFeeBps { fee_bps } => governance::set_fee_bps(state, fee_bps)?,
+            GovernanceAction::SetGovernanceDelay { delay_secs } => {
+                governance::set_governance_delay(state, delay_secs)?
+            }
+            GovernanceAction::SetClaimTipBps { tip_bps } => governance::set_claim_tip_bps(state, tip_bps)?,
+        }
+        pending.executed = true;
+
+        emit!(GovernanceActionExecuted { id: action_id });
+        msg!("Governance action {} executed", action_id);
+        Ok(())
+    }
+
+    pub fn cancel_action(ctx: Context<CancelAction>, action_id: u64) -> Result<()> {
+        let pending = &mut ctx.accounts.pending_action;
+        require!(!pending.executed && !pending.cancelled, ZkError::ActionNotPending);
+        pending.cancelled = true;
+
+        emit!(GovernanceActionCancelled { id: action_id });
+        msg!("Governance action {} cancelled", action_id);
+        Ok(())
+    }
+
+    /// Choose how consumed events are recorded: per-event PDAs or paged bitmaps.
+    /// Moving to bitmaps is one-way: the compact path checks the nullifier PDAs
+    /// left by earlier events, but events it consumes only have a bit, which the
+    /// PDA paths never read.
+    pub fn set_nullifier_mode(ctx: Context<SetNullifierMode>, mode: u8) -> Result<()> {
+        require!(
+            mode == NULLIFIER_MODE_PDA || mode == NULLIFIER_MODE_BITMAP,
+            ZkError::InvalidNullifierMode
+        );
+        let state = &mut ctx.accounts.state;
+        require!(
+            state.nullifier_mode != NULLIFIER_MODE_BITMAP || mode == NULLIFIER_MODE_BITMAP,
+            ZkError::NullifierModeIrreversible
+        );
+        state.nullifier_mode = mode;
+
+        emit!(NullifierModeUpdated { mode });
+        msg!("Nullifier mode set to {}", mode);
+        Ok(())
+    }
+
+    /// Move collected fees out of the treasury
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        require!(amount > 0, ZkError::InvalidAmount);
+        require!(amount <= ctx.accounts.treasury.amount, ZkError::InvalidAmount);
+
+        let signer: &[&[&[u8]]] = &[&[LcState::SEED, &[ctx.bumps.state]]];
+        anchor_spl::token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::Transfer {
+                    from: ctx.accounts.treasury.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.state.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        emit!(FeesWithdrawn {
+            admin: ctx.accounts.admin.key(),
+            destination: ctx.accounts.destination.key(),
+            amount,
+        });
+        msg!("Withdrew {} in fees to {}", amount, ctx.accounts.destination.key());
+        Ok(())
+    }
+
+    /// Burn wrapped TON to be paid out to `ton_recipient` on TON
+    pub fn request_withdrawal(
+        ctx: Context<RequestWithdrawal>,
+        nonce: u64,
+        amount: u64,
+        ton_recipient: [u8; 32],
+    ) -> Result<()> {
+        require!(!ctx.accounts.state.paused, ZkError::BridgePaused);
+        require!(amount > 0, ZkError::InvalidAmount);
+
+        anchor_spl::token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::Burn {
+                    mint: ctx.accounts.wrapped_mint.to_account_info(),
+                    from: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let withdrawal = &mut ctx.accounts.withdrawal;
+        withdrawal.owner = ctx.accounts.owner.key();
+        withdrawal.nonce = nonce;
+        withdrawal.amount = amount;
+        withdrawal.ton_recipient = ton_recipient;
+        withdrawal.requested_at = Clock::get()?.unix_timestamp;
+        withdrawal.status = Withdrawal::STATUS_PENDING;
+        withdrawal.ton_tx_hash = [0u8; 32];
+
+        emit!(WithdrawalRequested {
+            owner: withdrawal.owner,
+            nonce,
+            amount,
+            ton_recipient,
+            refundable_at: withdrawal.refundable_at(),
+        });
+        msg!("Withdrawal {} of {} requested by {}", nonce, amount, withdrawal.owner);
+        Ok(())
+    }
+
+    /// Relayer attests the TON payout of a pending withdrawal
+    pub fn finalize_withdrawal(ctx: Context<FinalizeWithdrawal>, ton_tx_hash: [u8; 32]) -> Result<()> {
+        let state = &ctx.accounts.state;
+        let signer = ctx.accounts.relayer.key();
+        require!(
+            signer == state.relayer ||
+            ctx.accounts.relayer_set.as_ref().is_some_and(|set| set.contains(&signer)),
+            ZkError::UnauthorizedRelayer
+        );
+
+        let withdrawal = &mut ctx.accounts.withdrawal;
+        require!(withdrawal.status == Withdrawal::STATUS_PENDING, ZkError::WithdrawalNotPending);
+        // Past the deadline the owner may already be reclaiming; the payout must not race it
+        require!(
+            Clock::get()?.unix_timestamp < withdrawal.refundable_at(),
+            ZkError::WithdrawalExpired
+        );
+        withdrawal.status = Withdrawal::STATUS_FINALIZED;
+        withdrawal.ton_tx_hash = ton_tx_hash;
+
+        emit!(WithdrawalFinalized {
+            owner: withdrawal.owner,
+            nonce: withdrawal.nonce,
+            ton_tx_hash,
+            relayer: signer,
+        });
+        msg!("Withdrawal {} of {} finalized", withdrawal.nonce, withdrawal.owner);
+        Ok(())
+    }
+
+    /// Re-mint an unfinalized withdrawal to its owner once the timeout has passed
+    pub fn refund_withdrawal(ctx: Context<RefundWithdrawal>) -> Result<()> {
+        let withdrawal = &mut ctx.accounts.withdrawal;
+        require!(withdrawal.status == Withdrawal::STATUS_PENDING, ZkError::WithdrawalNotPending);
+        require!(
+            Clock::get()?.unix_timestamp >= withdrawal.refundable_at(),
+            ZkError::WithdrawalNotRefundable
+        );
+        withdrawal.status = Withdrawal::STATUS_REFUNDED;
+
+        mint::mint_wrapped(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.wrapped_mint.to_account_info(),
+            &ctx.accounts.owner_token_account.to_account_info(),
+            &ctx.accounts.state.to_account_info(),
+            ctx.bumps.state,
+            withdrawal.amount,
+        )?;
+
+        emit!(WithdrawalRefunded {
+            owner: withdrawal.owner,
+            nonce: withdrawal.nonce,
+            amount: withdrawal.amount,
+        });
+        msg!("Withdrawal {} of {} refunded", withdrawal.nonce, withdrawal.owner);
+        Ok(())
+    }
+
+    /// Create an empty verifying key account for a new circuit version
+    pub fn create_verifying_key(ctx: Context<CreateVerifyingKey>, vk_id: u32) -> Result<()> {
+        ctx.accounts.verifying_key.load_init()?.vk_id = vk_id;
+
+        emit!(VerifyingKeyUpdated { vk_id, len: 0, total_len: 0, finalized: false });
+        msg!("Verifying key {} created", vk_id);
+        Ok(())
+    }
+
+    /// Make a finalized key current; the old key stays valid for `deprecation_window_secs`
+    pub fn rotate_verifying_key(
+        ctx: Context<RotateVerifyingKey>,
+        new_vk_id: u32,
+        deprecation_window_secs: i64,
+    ) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        require!(state.governance_delay_secs == 0, ZkError::TimelockRequired);
+        governance::rotate_verifying_key(
+            state,
+            &ctx.accounts.new_verifying_key.load()?,
+            new_vk_id,
+            deprecation_window_secs,
+        )
+    }
+
+    /// Start (or restart) uploading a verifying key, sizing the account for `total_len` bytes
+    pub fn upload_verifying_key(
+        ctx: Context<UploadVerifyingKey>,
+        vk_id: u32,
+        total_len: u32,
+        first_chunk: Vec<u8>,
+    ) -> Result<()> {
+        let mut vk = ctx.accounts.verifying_key.load_mut()?;
+        require!(!vk.is_finalized(), ZkError::VerifyingKeyFinalized);
+        require!(vk.vk_id == vk_id, ZkError::InvalidVerifyingKey);
+        require!(total_len as usize <= VerifyingKey::MAX_LEN, ZkError::VerifyingKeyTooLarge);
+
+        vk.total_len = total_len;
+        vk.len = 0;
+        vk.append(&first_chunk)?;
+
+        emit!(VerifyingKeyUpdated { vk_id, len: vk.len, total_len, finalized: false });
+        msg!("Verifying key {} upload started: {}/{} bytes", vk_id, vk.len, total_len);
+        Ok(())
+    }
+
+    /// Append the next chunk; `offset` must equal the bytes already written
+    pub fn append_vk_chunk(
+        ctx: Context<AppendVkChunk>,
+        vk_id: u32,
+        offset: u32,
+        chunk: Vec<u8>,
+    ) -> Result<()> {
+        let mut vk = ctx.accounts.verifying_key.load_mut()?;
+        require!(!vk.is_finalized(), ZkError::VerifyingKeyFinalized);
+        require!(offset == vk.len, ZkError::InvalidVkChunk);
+
+        vk.append(&chunk)?;
+
+        emit!(VerifyingKeyUpdated {
+            vk_id,
+            len: vk.len,
+            total_len: vk.total_len,
+            finalized: false,
+        });
+        msg!("Verifying key {} upload: {}/{} bytes", vk_id, vk.len, vk.total_len);
+        Ok(())
+    }
+
+    /// Lock a fully uploaded verifying key so proofs can be verified against it
+    pub fn finalize_verifying_key(ctx: Context<AppendVkChunk>, vk_id: u32) -> Result<()> {
+        let mut vk = ctx.accounts.verifying_key.load_mut()?;
+        require!(!vk.is_finalized(), ZkError::VerifyingKeyFinalized);
+        require!(
+            vk.total_len > 0 && vk.len == vk.total_len,
+            ZkError::VerifyingKeyIncomplete
+        );
+
+        vk.finalized = 1;
+
+        emit!(VerifyingKeyUpdated { vk_id, len: vk.total_len, total_len: vk.total_len, finalized: true });
+        msg!("Verifying key {} finalized ({} bytes)", vk_id, vk.total_len);
         Ok(())
     }
 
@@ -37,109 +636,1727 @@ pub mod zk_lc {
         new_ton_root: [u8; 32],
     ) -> Result<()> {
         let state = &mut ctx.accounts.state;
+        let signer = ctx.accounts.signer.key();
+        require!(!state.paused, ZkError::BridgePaused);
+        require!(state.root_approval_threshold <= 1, ZkError::RootApprovalRequired);
         
-        // Only admin or relayer can update TON root
+        // Only admin, the legacy relayer or a relayer-set member can update TON root
         require!(
-            ctx.accounts.signer.key() == state.admin || 
-            ctx.accounts.signer.key() == state.relayer,
+            signer == state.admin ||
+            signer == state.relayer ||
+            ctx.accounts.relayer_set.as_ref().is_some_and(|set| set.contains(&signer)),
             ZkError::UnauthorizedRelayer
         );
         
+        let previous_root = state.ton_state_root;
         state.ton_state_root = new_ton_root;
-        
+
+        emit!(RootUpdated {
+            previous_root,
+            new_root: new_ton_root,
+            last_verified_slot: state.last_verified_slot,
+            updated_by: signer,
+        });
         msg!("TON state root updated to: {:?}", new_ton_root);
         Ok(())
     }
 
-    pub fn verify_update(ctx: Context<VerifyUpdate>, new_slot: u64, proof: [u8; 32]) -> Result<()> {
-        let s = &mut ctx.accounts.state;
-        verify::verify_mock(&proof, s.last_verified_slot, new_slot)?;
-        s.last_verified_slot = new_slot;
+    /// Commit the root of the bridge's append-only TON deposit tree, the only
+    /// anchor `verify_ton_event_compact` accepts. A new root must extend the
+    /// previous tree, as bitmap bits are keyed by leaf position within it.
+    pub fn update_deposit_tree_root(ctx: Context<UpdateTonRoot>, new_root: [u8; 32]) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        let signer = ctx.accounts.signer.key();
+        require!(!state.paused, ZkError::BridgePaused);
+        require!(state.root_approval_threshold <= 1, ZkError::RootApprovalRequired);
+        require!(
+            signer == state.admin ||
+            signer == state.relayer ||
+            ctx.accounts.relayer_set.as_ref().is_some_and(|set| set.contains(&signer)),
+            ZkError::UnauthorizedRelayer
+        );
+
+        let previous_root = state.deposit_tree_root;
+        state.deposit_tree_root = new_root;
+
+        emit!(DepositTreeRootUpdated {
+            previous_root,
+            new_root,
+            updated_by: signer,
+        });
+        msg!("Deposit tree root updated to: {:?}", new_root);
         Ok(())
     }
 
-    // Enhanced TON Event Verification with ZK Proofs
-    pub fn verify_ton_event(
-        ctx: Context<VerifyTonEvent>,
-        proof: ZKProof,  // Use structured proof instead of raw bytes
-        public_inputs: EventPublicInputs,
-    ) -> Result<()> {
-        let state = &ctx.accounts.state;
-        
-        // Verify the ZK proof
-        zk_verifier::ZKVerifier::verify_ton_event_proof(
-            &proof,
-            &public_inputs,
-            &state.ton_state_root,
-            &ctx.accounts.verifying_key.data,
-        )?;
-        
-        // Check if event was already consumed via nullifier
-        let nullifier_account = &mut ctx.accounts.nullifier_account;
-        require!(!nullifier_account.consumed, ZkError::EventAlreadyConsumed);
-        
-        // Mark nullifier as consumed
-        nullifier_account.consumed = true;
-        nullifier_account.nullifier = public_inputs.nullifier;
-        nullifier_account.ton_tx_hash = public_inputs.ton_tx_hash;
-        
-        // Create event account
-        let event_account = &mut ctx.accounts.event_account;
-        event_account.consumed = true;
-        event_account.event_id = public_inputs.event_id;
-        event_account.recipient = public_inputs.recipient_solana;
-        event_account.amount = public_inputs.amount_in_ton;
-        event_account.ton_tx_hash = public_inputs.ton_tx_hash;
-        event_account.ton_sender = public_inputs.ton_sender;
-
-        // Emit event for indexers
-        emit!(TonEventVerified {
-            event_id: public_inputs.event_id,
-            recipient: public_inputs.recipient_solana,
-            amount: public_inputs.amount_in_ton,
-            ton_tx_hash: public_inputs.ton_tx_hash,
-            ton_sender: public_inputs.ton_sender,
+    /// Require `threshold` relayer-set approvals per TON root (0 or 1 restores
+    /// single-signer update_ton_root)
+    pub fn set_root_approval_threshold(ctx: Context<SetRootApprovalThreshold>, threshold: u8) -> Result<()> {
+        require!(
+            threshold as usize <= ctx.accounts.relayer_set.relayers.len(),
+            ZkError::InvalidApprovalThreshold
+        );
+        ctx.accounts.state.root_approval_threshold = threshold;
+
+        emit!(RootApprovalThresholdUpdated { threshold });
+        msg!("Root approval threshold set to {}", threshold);
+        Ok(())
+    }
+
+    /// Approve `new_ton_root` as a relayer-set member; the root is committed
+    /// once approvals from current members reach the threshold
+    pub fn approve_ton_root(ctx: Context<ApproveTonRoot>, new_ton_root: [u8; 32]) -> Result<()> {
+        require!(!ctx.accounts.state.paused, ZkError::BridgePaused);
+        let relayer = ctx.accounts.relayer.key();
+        let relayer_set = &ctx.accounts.relayer_set;
+        require!(relayer_set.contains(&relayer), ZkError::UnauthorizedRelayer);
+
+        let pending = &mut ctx.accounts.pending_root;
+        require!(!pending.committed, ZkError::RootAlreadyCommitted);
+        if pending.approvals.is_empty() {
+            pending.root = new_ton_root;
+            pending.created_at = Clock::get()?.unix_timestamp;
+        }
+        require!(!pending.approvals.contains(&relayer), ZkError::RootAlreadyApproved);
+        pending.approvals.push(relayer);
+
+        // Approvals from relayers removed since they approved no longer count
+        let approvals = pending
+            .approvals
+            .iter()
+            .filter(|r| relayer_set.contains(r))
+            .count();
+        emit!(TonRootApproved {
+            root: new_ton_root,
+            relayer,
+            approvals: approvals as u8,
         });
 
-        msg!(
-            "✅ TON event verified: {} TON to {}",
-            public_inputs.amount_in_ton,
-            public_inputs.recipient_solana
+        let state = &mut ctx.accounts.state;
+        if approvals >= state.root_approval_threshold.max(1) as usize {
+            pending.committed = true;
+            let previous_root = state.ton_state_root;
+            state.ton_state_root = new_ton_root;
+
+            emit!(RootUpdated {
+                previous_root,
+                new_root: new_ton_root,
+                last_verified_slot: state.last_verified_slot,
+                updated_by: relayer,
+            });
+            emit!(TonRootCommitted {
+                root: new_ton_root,
+                approvals: approvals as u8,
+            });
+            msg!("TON state root committed with {} approvals: {:?}", approvals, new_ton_root);
+        } else {
+            msg!(
+                "TON root approval {}/{} from {}",
+                approvals,
+                state.root_approval_threshold,
+                relayer
+            );
+        }
+        Ok(())
+    }
+
+    /// Authorize another relayer to update the TON root
+    pub fn add_relayer(ctx: Context<AddRelayer>, relayer: Pubkey) -> Result<()> {
+        require!(ctx.accounts.state.governance_delay_secs == 0, ZkError::TimelockRequired);
+        governance::add_relayer(&mut ctx.accounts.relayer_set, relayer)
+    }
+
+    pub fn remove_relayer(ctx: Context<RemoveRelayer>, relayer: Pubkey) -> Result<()> {
+        require!(ctx.accounts.state.governance_delay_secs == 0, ZkError::TimelockRequired);
+        governance::remove_relayer(&ctx.accounts.state, &mut ctx.accounts.relayer_set, relayer)
+    }
+
+    /// Admin sets the light-client committee and how many of its members must sign updates
+    pub fn set_committee(ctx: Context<SetCommittee>, members: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        require!(
+            !members.is_empty() && members.len() <= Committee::MAX_MEMBERS,
+            ZkError::InvalidCommittee
         );
-        
+        require!(
+            threshold > 0 && threshold as usize <= members.len(),
+            ZkError::InvalidCommittee
+        );
+        for (i, member) in members.iter().enumerate() {
+            require!(!members[..i].contains(member), ZkError::InvalidCommittee);
+        }
+
+        let committee = &mut ctx.accounts.committee;
+        committee.members = members;
+        committee.threshold = threshold;
+
+        emit!(CommitteeUpdated { members: committee.members.clone(), threshold });
+        msg!("Committee set: {} members, threshold {}", committee.members.len(), threshold);
         Ok(())
     }
-}
 
-// Event for indexing
-#[event]
-pub struct TonEventVerified {
-    pub event_id: [u8; 32],
-    pub recipient: Pubkey,
-    pub amount: u64,
-    pub ton_tx_hash: [u8; 32],
-    pub ton_sender: [u8; 32],
+    /// Advance the light client to `new_slot` / `new_ton_root`. The transaction
+    /// must carry ed25519 program instructions with signatures from at least
+    /// `threshold` committee members over `verify::update_message`.
+    pub fn verify_update(ctx: Context<VerifyUpdate>, new_slot: u64, new_ton_root: [u8; 32]) -> Result<()> {
+        let s = &mut ctx.accounts.state;
+        require!(!s.paused, ZkError::BridgePaused);
+        require!(new_slot > s.last_verified_slot, ZkError::SlotGoesBackwards);
+
+        let committee = &ctx.accounts.committee;
+        let message = verify::update_message(ctx.program_id, new_slot, &new_ton_root);
+        let signatures = verify::count_committee_signatures(
+            &ctx.accounts.instructions_sysvar,
+            &committee.members,
+            &message,
+        )?;
+        require!(
+            signatures >= committee.threshold as usize,
+            ZkError::InsufficientCommitteeSignatures
+        );
+
+        let previous_root = s.ton_state_root;
+        s.last_verified_slot = new_slot;
+        s.ton_state_root = new_ton_root;
+
+        emit!(RootUpdated {
+            previous_root,
+            new_root: new_ton_root,
+            last_verified_slot: new_slot,
+            updated_by: verify::COMMITTEE_UPDATER,
+        });
+        msg!("Light client advanced to slot {} ({} committee signatures)", new_slot, signatures);
+        Ok(())
+    }
+
+    /// Append a committee-signed TON block header to the HeaderChain. Like
+    /// verify_update, the signatures come from earlier ed25519 instructions,
+    /// here over `verify::header_message`.
+    pub fn submit_ton_header(
+        ctx: Context<SubmitTonHeader>,
+        seqno: u32,
+        root_hash: [u8; 32],
+        file_hash: [u8; 32],
+        prev_root_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(!ctx.accounts.state.paused, ZkError::BridgePaused);
+
+        let committee = &ctx.accounts.committee;
+        let message = verify::header_message(ctx.program_id, seqno, &root_hash, &file_hash, &prev_root_hash);
+        let signatures = verify::count_committee_signatures(
+            &ctx.accounts.instructions_sysvar,
+            &committee.members,
+            &message,
+        )?;
+        require!(
+            signatures >= committee.threshold as usize,
+            ZkError::InsufficientCommitteeSignatures
+        );
+
+        let header = TonBlockHeader { seqno, root_hash, file_hash };
+        let mut header_chain = match ctx.accounts.header_chain.load_mut() {
+            Ok(header_chain) => header_chain,
+            // First header: the account was just created by init_if_needed
+            Err(_) => ctx.accounts.header_chain.load_init()?,
+        };
+        header_chain.push(header, &prev_root_hash)?;
+
+        emit!(TonHeaderVerified { seqno, root_hash, file_hash, signatures: signatures as u8 });
+        msg!("TON header {} verified ({} committee signatures)", seqno, signatures);
+        Ok(())
+    }
+
+    /// Require event proofs to anchor to verified headers, ignoring pushed roots
+    pub fn set_header_chain_only(ctx: Context<SetHeaderChainOnly>, enabled: bool) -> Result<()> {
+        if enabled {
+            require!(
+                !ctx.accounts.header_chain.load()?.is_empty(),
+                ZkError::AnchorNotInHeaderChain
+            );
+        }
+        ctx.accounts.state.header_chain_only = enabled;
+
+        emit!(HeaderChainOnlyUpdated { enabled });
+        msg!("Header-chain-only anchoring {}", if enabled { "enabled" } else { "disabled" });
+        Ok(())
+    }
+
+    /// Rebuild event ids with Poseidon for keys from `vk_id` on, for circuits
+    /// that hash with Poseidon rather than SHA-256 (0 keeps SHA-256 for all)
+    pub fn set_poseidon_from_vk_id(ctx: Context<SetFeeBps>, vk_id: u32) -> Result<()> {
+        ctx.accounts.state.poseidon_from_vk_id = vk_id;
+
+        emit!(EventHashUpdated { poseidon_from_vk_id: vk_id });
+        msg!("Poseidon event ids from verifying key {}", vk_id);
+        Ok(())
+    }
+
+    /// Verify one recursive proof for a whole tree of events. Nothing is minted
+    /// here; each event is then minted by `claim_aggregated_event`.
+    pub fn verify_aggregate_proof(
+        ctx: Context<VerifyAggregateProof>,
+        proof: ZKProof,
+        aggregate: AggregatePublicInputs,
+    ) -> Result<()> {
+        let state = &ctx.accounts.state;
+        require!(!state.paused, ZkError::BridgePaused);
+        let now = Clock::get()?.unix_timestamp;
+        require!(state.is_vk_allowed(aggregate.vk_version, now), ZkError::VkVersionNotAllowed);
+        let verifying_key = ctx.accounts.verifying_key.load()?;
+        require!(verifying_key.is_finalized(), ZkError::VerifyingKeyNotFinalized);
+
+        let header_chain = ctx.accounts.header_chain.as_ref().map(|chain| chain.load()).transpose()?;
+        let anchor_root = state.anchor_root_for(header_chain.as_deref(), &aggregate.anchor_root)?;
+        zk_verifier::ZKVerifier::verify_aggregate_proof(
+            &proof,
+            &aggregate,
+            &anchor_root,
+            verifying_key.bytes(),
+        )?;
+
+        let root = &mut ctx.accounts.aggregate_root;
+        root.events_root = aggregate.events_root;
+        root.anchor_root = aggregate.anchor_root;
+        root.event_count = aggregate.event_count;
+        root.claimed = 0;
+        root.vk_version = aggregate.vk_version;
+        root.verified_at = now;
+
+        emit!(AggregateProofVerified {
+            events_root: aggregate.events_root,
+            anchor_root: aggregate.anchor_root,
+            event_count: aggregate.event_count,
+        });
+        msg!("✅ Aggregated proof verified: {} events", aggregate.event_count);
+        Ok(())
+    }
+
+    /// Mint one event of a verified aggregated proof, given its path in the events tree
+    pub fn claim_aggregated_event(
+        ctx: Context<ClaimAggregatedEvent>,
+        events_root: [u8; 32],
+        public_inputs: EventPublicInputs,
+        path: TxInclusionProof,
+        inclusion: TxInclusionProof,
+    ) -> Result<()> {
+        let state = &ctx.accounts.state;
+        require!(!state.paused, ZkError::BridgePaused);
+        require!(state.nullifier_mode == NULLIFIER_MODE_PDA, ZkError::NullifierModeMismatch);
+        // Privacy-mode deposits must go through claim_private_ton_event
+        require!(!public_inputs.is_private(), ZkError::RecipientRevealRequired);
+
+        let aggregate = &ctx.accounts.aggregate_root;
+        require!(aggregate.claimed < aggregate.event_count, ZkError::AggregateFullyClaimed);
+        require!(
+            merkle::verify_leaf(&zk_verifier::ZKVerifier::event_commitment(&public_inputs), &path, &events_root),
+            ZkError::NotInAggregate
+        );
+        zk_verifier::ZKVerifier::validate_public_inputs(
+            &public_inputs,
+            &aggregate.anchor_root,
+            state.event_hash_for(public_inputs.vk_version),
+        )?;
+        zk_verifier::ZKVerifier::verify_tx_inclusion(&public_inputs, &inclusion)?;
+
+        let nullifier_account = &mut ctx.accounts.nullifier_account;
+        require!(!nullifier_account.consumed, ZkError::EventAlreadyConsumed);
+        nullifier_account.consumed = true;
+        nullifier_account.nullifier = public_inputs.nullifier;
+        nullifier_account.ton_tx_hash = public_inputs.ton_tx_hash;
+
+        let event_account = &mut ctx.accounts.event_account;
+        require!(!event_account.consumed, ZkError::EventAlreadyConsumed);
+        record_event(event_account, &public_inputs, &inclusion, ctx.accounts.payer.key())?;
+        ctx.accounts.aggregate_root.claimed += 1;
+
+        let mint_accounts = mint::EventMintAccounts {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            mint: ctx.accounts.wrapped_mint.to_account_info(),
+            treasury: ctx.accounts.treasury.to_account_info(),
+            state: ctx.accounts.state.to_account_info(),
+            state_bump: ctx.bumps.state,
+        };
+        let net_amount = mint::settle_event(
+            &mut ctx.accounts.state,
+            &mint_accounts,
+            &ctx.accounts.token_config,
+            &mut ctx.accounts.global_volume,
+            &mut ctx.accounts.token_volume,
+            &ctx.accounts.recipient_token_account.to_account_info(),
+            None,
+            &public_inputs,
+        )?;
+        msg!(
+            "✅ Aggregated TON event claimed: {} TON to {} ({} minted)",
+            public_inputs.amount_in_ton,
+            public_inputs.recipient_solana,
+            net_amount
+        );
+        Ok(())
+    }
+
+    // Enhanced TON Event Verification with ZK Proofs
+    pub fn verify_ton_event(
+        ctx: Context<VerifyTonEvent>,
+        proof: ZKProof,  // Use structured proof instead of raw bytes
+        public_inputs: EventPublicInputs,
+        inclusion: TxInclusionProof,
+    ) -> Result<()> {
+        // Privacy-mode deposits must go through claim_private_ton_event
+        require!(
+            public_inputs.recipient_commitment == [0u8; 32],
+            ZkError::RecipientRevealRequired
+        );
+        process_ton_event(ctx, proof, public_inputs, inclusion)
+    }
+
+    /// `verify_ton_event` for NULLIFIER_MODE_BITMAP: marks the event consumed in
+    /// the bitmap page for `inclusion.leaf_index` instead of creating nullifier
+    /// and event PDAs, so a deposit costs no rent of its own. Proofs must anchor
+    /// to `deposit_tree_root`, where a leaf index names a single deposit.
+    pub fn verify_ton_event_compact(
+        ctx: Context<VerifyTonEventCompact>,
+        proof: ZKProof,
+        public_inputs: EventPublicInputs,
+        inclusion: TxInclusionProof,
+        recipient_salt: [u8; 32],
+    ) -> Result<()> {
+        let state = &ctx.accounts.state;
+        require!(!state.paused, ZkError::BridgePaused);
+        require!(
+            state.nullifier_mode == NULLIFIER_MODE_BITMAP,
+            ZkError::NullifierModeMismatch
+        );
+        if public_inputs.is_private() {
+            zk_verifier::ZKVerifier::check_recipient_reveal(&public_inputs, &recipient_salt)?;
+        }
+
+        verify_event_proofs(
+            state,
+            state.deposit_anchor_root()?,
+            &ctx.accounts.verifying_key.load()?,
+            &proof,
+            &public_inputs,
+            &inclusion,
+        )?;
+
+        // Events consumed before the switch to bitmaps only have a nullifier PDA
+        require!(
+            !nullifier_consumed(&ctx.accounts.nullifier_account)?,
+            ZkError::EventAlreadyConsumed
+        );
+        let bitmap = &mut ctx.accounts.nullifier_bitmap;
+        bitmap.page = NullifierBitmap::page_for(inclusion.leaf_index);
+        require!(!bitmap.is_set(inclusion.leaf_index), ZkError::EventAlreadyConsumed);
+        bitmap.set(inclusion.leaf_index);
+
+        let page = ctx.accounts.nullifier_bitmap.page;
+        let mint_accounts = mint::EventMintAccounts {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            mint: ctx.accounts.wrapped_mint.to_account_info(),
+            treasury: ctx.accounts.treasury.to_account_info(),
+            state: ctx.accounts.state.to_account_info(),
+            state_bump: ctx.accounts.state.bump,
+        };
+        let net_amount = mint::settle_event(
+            &mut ctx.accounts.state,
+            &mint_accounts,
+            &ctx.accounts.token_config,
+            &mut ctx.accounts.global_volume,
+            &mut ctx.accounts.token_volume,
+            &ctx.accounts.recipient_token_account.to_account_info(),
+            None,
+            &public_inputs,
+        )?;
+
+        msg!(
+            "✅ TON event verified: {} TON to {} ({} minted, bitmap page {})",
+            public_inputs.amount_in_ton,
+            public_inputs.recipient_solana,
+            net_amount,
+            page
+        );
+        Ok(())
+    }
+
+    /// Claim a privacy-mode deposit: the TON side only committed to
+    /// `hash(recipient || salt)`, which is checked here before verification
+    pub fn claim_private_ton_event(
+        ctx: Context<VerifyTonEvent>,
+        proof: ZKProof,
+        public_inputs: EventPublicInputs,
+        inclusion: TxInclusionProof,
+        recipient_salt: [u8; 32],
+    ) -> Result<()> {
+        zk_verifier::ZKVerifier::check_recipient_reveal(&public_inputs, &recipient_salt)?;
+        process_ton_event(ctx, proof, public_inputs, inclusion)
+    }
+
+    /// Verify several TON events in one transaction. `remaining_accounts` must
+    /// hold `(event_account, nullifier_account, recipient, recipient_token_account)`
+    /// groups in the same order as `events`; the PDAs are created here and must
+    /// not already exist, the recipient's ATA is created if missing.
+    pub fn verify_ton_event_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyTonEventBatch<'info>>,
+        events: Vec<BatchedEvent>,
+    ) -> Result<()> {
+        require!(
+            !events.is_empty() && events.len() <= MAX_BATCH_EVENTS,
+            ZkError::InvalidBatchSize
+        );
+        require!(
+            ctx.remaining_accounts.len() == events.len() * BATCH_ACCOUNTS_PER_EVENT,
+            ZkError::InvalidBatchAccounts
+        );
+
+        let state = &ctx.accounts.state;
+        require!(!state.paused, ZkError::BridgePaused);
+        require!(state.nullifier_mode == NULLIFIER_MODE_PDA, ZkError::NullifierModeMismatch);
+        let verifying_key = ctx.accounts.verifying_key.load()?;
+        require!(verifying_key.is_finalized(), ZkError::VerifyingKeyNotFinalized);
+        let header_chain = ctx.accounts.header_chain.as_ref().map(|chain| chain.load()).transpose()?;
+        require!(
+            state.is_vk_allowed(verifying_key.vk_id, Clock::get()?.unix_timestamp),
+            ZkError::VkVersionNotAllowed
+        );
+
+        let payer = ctx.accounts.payer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let associated_token_program = ctx.accounts.associated_token_program.to_account_info();
+        let mint_accounts = mint::EventMintAccounts {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            mint: ctx.accounts.wrapped_mint.to_account_info(),
+            treasury: ctx.accounts.treasury.to_account_info(),
+            state: state.to_account_info(),
+            state_bump: state.bump,
+        };
+
+        for (i, event) in events.iter().enumerate() {
+            let public_inputs = &event.public_inputs;
+            require!(
+                public_inputs.vk_version == verifying_key.vk_id,
+                ZkError::VkVersionNotAllowed
+            );
+            if public_inputs.recipient_commitment != [0u8; 32] {
+                zk_verifier::ZKVerifier::check_recipient_reveal(public_inputs, &event.recipient_salt)?;
+            }
+
+            let state = &ctx.accounts.state;
+            let anchor_root =
+                state.anchor_root_for(header_chain.as_deref(), &public_inputs.anchor_root)?;
+            zk_verifier::ZKVerifier::verify_ton_event_proof(
+                &event.proof,
+                public_inputs,
+                &anchor_root,
+                verifying_key.bytes(),
+                state.event_hash_for(public_inputs.vk_version),
+            )?;
+            zk_verifier::ZKVerifier::verify_tx_inclusion(public_inputs, &event.inclusion)?;
+
+            let group = &ctx.remaining_accounts[BATCH_ACCOUNTS_PER_EVENT * i..BATCH_ACCOUNTS_PER_EVENT * (i + 1)];
+            let (event_info, nullifier_info, recipient_info, recipient_ata) =
+                (&group[0], &group[1], &group[2], &group[3]);
+            require_keys_eq!(
+                recipient_info.key(),
+                public_inputs.recipient_solana,
+                ZkError::InvalidRecipient
+            );
+
+            let (event_seed, nullifier_seed) = (public_inputs.event_seed(), public_inputs.nullifier_seed());
+            let (event_pda, event_bump) =
+                Pubkey::find_program_address(&[EventState::SEED, &event_seed], ctx.program_id);
+            let (nullifier_pda, nullifier_bump) =
+                Pubkey::find_program_address(&[NullifierState::SEED, &nullifier_seed], ctx.program_id);
+            require_keys_eq!(event_info.key(), event_pda, ZkError::InvalidBatchAccounts);
+            require_keys_eq!(nullifier_info.key(), nullifier_pda, ZkError::InvalidBatchAccounts);
+
+            // An existing nullifier or event account means this deposit was already bridged
+            require!(nullifier_info.data_is_empty(), ZkError::EventAlreadyConsumed);
+            require!(event_info.data_is_empty(), ZkError::EventAlreadyConsumed);
+
+            pda::create_pda_account(
+                &payer,
+                nullifier_info,
+                &system_program,
+                8 + NullifierState::SIZE,
+                &[NullifierState::SEED, &nullifier_seed, &[nullifier_bump]],
+                ctx.program_id,
+            )?;
+            pda::write_account(nullifier_info, &NullifierState {
+                consumed: true,
+                nullifier: public_inputs.nullifier,
+                ton_tx_hash: public_inputs.ton_tx_hash,
+            })?;
+
+            pda::create_pda_account(
+                &payer,
+                event_info,
+                &system_program,
+                8 + EventState::SIZE,
+                &[EventState::SEED, &event_seed, &[event_bump]],
+                ctx.program_id,
+            )?;
+            pda::write_account(event_info, &EventState {
+                consumed: true,
+                event_id: public_inputs.event_id,
+                recipient: public_inputs.recipient_solana,
+                amount: public_inputs.amount_in_ton,
+                ton_tx_hash: public_inputs.ton_tx_hash,
+                ton_sender: public_inputs.ton_sender,
+                nullifier: public_inputs.nullifier,
+                leaf_index: event.inclusion.leaf_index,
+                payer: payer.key(),
+                consumed_at: Clock::get()?.unix_timestamp,
+            })?;
+
+            mint::ensure_recipient_ata(
+                &payer,
+                recipient_ata,
+                recipient_info,
+                &mint_accounts.mint,
+                &system_program,
+                &mint_accounts.token_program,
+                &associated_token_program,
+            )?;
+            mint::settle_event(
+                &mut ctx.accounts.state,
+                &mint_accounts,
+                &ctx.accounts.token_config,
+                &mut ctx.accounts.global_volume,
+                &mut ctx.accounts.token_volume,
+                recipient_ata,
+                None,
+                public_inputs,
+            )?;
+        }
+
+        msg!("✅ Batch of {} TON events verified", events.len());
+        Ok(())
+    }
+}
+
+// Shared body of verify_ton_event / claim_private_ton_event
+fn process_ton_event(
+    ctx: Context<VerifyTonEvent>,
+    proof: ZKProof,
+    public_inputs: EventPublicInputs,
+    inclusion: TxInclusionProof,
+) -> Result<()> {
+    let state = &ctx.accounts.state;
+    require!(!state.paused, ZkError::BridgePaused);
+    require!(state.nullifier_mode == NULLIFIER_MODE_PDA, ZkError::NullifierModeMismatch);
+
+    let anchor_root = {
+        let header_chain = ctx.accounts.header_chain.as_ref().map(|chain| chain.load()).transpose()?;
+        state.anchor_root_for(header_chain.as_deref(), &public_inputs.anchor_root)?
+    };
+    verify_event_proofs(
+        state,
+        anchor_root,
+        &ctx.accounts.verifying_key.load()?,
+        &proof,
+        &public_inputs,
+        &inclusion,
+    )?;
+    
+    // Both accounts were created by this instruction, so a replay already
+    // failed at account creation
+    let nullifier_account = &mut ctx.accounts.nullifier_account;
+    nullifier_account.consumed = true;
+    nullifier_account.nullifier = public_inputs.nullifier;
+    nullifier_account.ton_tx_hash = public_inputs.ton_tx_hash;
+    
+    let event_account = &mut ctx.accounts.event_account;
+    record_event(event_account, &public_inputs, &inclusion, ctx.accounts.payer.key())?;
+
+    // Mint wrapped TON to the recipient's ATA, protocol fee to the treasury.
+    // A claimer passing their own token account takes the claim tip, if enabled.
+    let mint_accounts = mint::EventMintAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        mint: ctx.accounts.wrapped_mint.to_account_info(),
+        treasury: ctx.accounts.treasury.to_account_info(),
+        state: ctx.accounts.state.to_account_info(),
+        state_bump: ctx.accounts.state.bump,
+    };
+    let claimer = ctx.accounts.claimer_token_account.as_ref().map(|account| mint::Claimer {
+        key: ctx.accounts.payer.key(),
+        token_account: account.to_account_info(),
+    });
+    let net_amount = mint::settle_event(
+        &mut ctx.accounts.state,
+        &mint_accounts,
+        &ctx.accounts.token_config,
+        &mut ctx.accounts.global_volume,
+        &mut ctx.accounts.token_volume,
+        &ctx.accounts.recipient_token_account.to_account_info(),
+        claimer,
+        &public_inputs,
+    )?;
+
+    msg!(
+        "✅ TON event verified: {} TON to {} ({} minted)",
+        public_inputs.amount_in_ton,
+        public_inputs.recipient_solana,
+        net_amount
+    );
+    
+    Ok(())
+}
+
+// Key checks, ZK proof and TON tx inclusion shared by the single-event paths;
+// `anchor_root` is the root the path lets the proof anchor to
+fn verify_event_proofs(
+    state: &LcState,
+    anchor_root: [u8; 32],
+    verifying_key: &VerifyingKey,
+    proof: &ZKProof,
+    public_inputs: &EventPublicInputs,
+    inclusion: &TxInclusionProof,
+) -> Result<()> {
+    require!(
+        state.is_vk_allowed(public_inputs.vk_version, Clock::get()?.unix_timestamp),
+        ZkError::VkVersionNotAllowed
+    );
+    require!(verifying_key.is_finalized(), ZkError::VerifyingKeyNotFinalized);
+
+    // Verify the ZK proof
+    zk_verifier::ZKVerifier::verify_ton_event_proof(
+        proof,
+        public_inputs,
+        &anchor_root,
+        verifying_key.bytes(),
+        state.event_hash_for(public_inputs.vk_version),
+    )?;
+
+    // Defense in depth: the TON tx must also be included under anchor_root
+    zk_verifier::ZKVerifier::verify_tx_inclusion(public_inputs, inclusion)
+}
+
+/// Whether a nullifier record exists and marks its event consumed; an
+/// account the program never created reads as not consumed
+fn nullifier_consumed(account: &AccountInfo) -> Result<bool> {
+    if account.data_is_empty() || *account.owner != ID {
+        return Ok(false);
+    }
+    Ok(NullifierState::try_deserialize(&mut &account.try_borrow_data()?[..])?.consumed)
+}
+
+fn record_event(
+    event_account: &mut EventState,
+    public_inputs: &EventPublicInputs,
+    inclusion: &TxInclusionProof,
+    payer: Pubkey,
+) -> Result<()> {
+    event_account.consumed = true;
+    event_account.event_id = public_inputs.event_id;
+    event_account.recipient = public_inputs.recipient_solana;
+    event_account.amount = public_inputs.amount_in_ton;
+    event_account.ton_tx_hash = public_inputs.ton_tx_hash;
+    event_account.ton_sender = public_inputs.ton_sender;
+    event_account.nullifier = public_inputs.nullifier;
+    event_account.leaf_index = inclusion.leaf_index;
+    event_account.payer = payer;
+    event_account.consumed_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+// Event for indexing
+#[event]
+pub struct StateInitialized {
+    pub admin: Pubkey,
+    pub vk_id: u32,
+    pub ton_state_root: [u8; 32],
+    pub relayer: Pubkey,
+}
+
+#[event]
+pub struct WrappedMintInitialized {
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct TreasuryInitialized {
+    pub treasury: Pubkey,
+}
+
+// Every change of `LcState.deposit_tree_root`
+#[event]
+pub struct DepositTreeRootUpdated {
+    pub previous_root: [u8; 32],
+    pub new_root: [u8; 32],
+    pub updated_by: Pubkey,
+}
+
+// Every change of `LcState.ton_state_root`, whichever path made it
+#[event]
+pub struct RootUpdated {
+    pub previous_root: [u8; 32],
+    pub new_root: [u8; 32],
+    pub last_verified_slot: u64,
+    pub updated_by: Pubkey,  // Signer or committing relayer; verify::COMMITTEE_UPDATER for committee updates
+}
+
+#[event]
+pub struct VkRotated {
+    pub previous_vk_id: u32,
+    pub new_vk_id: u32,
+    pub previous_vk_expires_at: i64,
+}
+
+#[event]
+pub struct VerifyingKeyUpdated {
+    pub vk_id: u32,
+    pub len: u32,
+    pub total_len: u32,
+    pub finalized: bool,
+}
+
+#[event]
+pub struct CommitteeUpdated {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct RootApprovalThresholdUpdated {
+    pub threshold: u8,
+}
+
+#[event]
+pub struct GovernanceDelayUpdated {
+    pub delay_secs: i64,
+}
+
+#[event]
+pub struct EventCloseAgeUpdated {
+    pub age_secs: i64,
+}
+
+#[event]
+pub struct NullifierModeUpdated {
+    pub mode: u8,
+}
+
+#[event]
+pub struct HeaderChainOnlyUpdated {
+    pub enabled: bool,
+}
+
+#[event]
+pub struct EventHashUpdated {
+    pub poseidon_from_vk_id: u32,
+}
+
+#[event]
+pub struct TonEventVerified {
+    pub event_id: [u8; 32],
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub ton_tx_hash: [u8; 32],
+    pub ton_sender: [u8; 32],
+    pub fee: u64,
+    pub sequence: u64,       // LcState.events_verified after this event
+}
+
+#[event]
+pub struct AggregateProofVerified {
+    pub events_root: [u8; 32],
+    pub anchor_root: [u8; 32],
+    pub event_count: u32,
+}
+
+#[event]
+pub struct TonHeaderVerified {
+    pub seqno: u32,
+    pub root_hash: [u8; 32],
+    pub file_hash: [u8; 32],
+    pub signatures: u8,
+}
+
+#[event]
+pub struct TonRootApproved {
+    pub root: [u8; 32],
+    pub relayer: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct TonRootCommitted {
+    pub root: [u8; 32],
+    pub approvals: u8,
+}
+
+#[event]
+pub struct VolumeCapUpdated {
+    pub token_id: Option<[u8; 32]>,
+    pub cap: u64,
+}
+
+#[event]
+pub struct TokenConfigured {
+    pub token_id: [u8; 32],
+    pub mint: Pubkey,
+    pub min_amount: u64,
+    pub max_amount: u64,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct StateMigrated {
+    pub from_version: u8,
+    pub to_version: u8,
+    pub old_len: u32,
+    pub new_len: u32,
+}
+
+#[event]
+pub struct EventClosed {
+    pub event_id: [u8; 32],
+    pub payer: Pubkey,
+    pub closed_by: Pubkey,
+}
+
+#[event]
+pub struct GovernanceActionProposed {
+    pub id: u64,
+    pub action: GovernanceAction,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct GovernanceActionExecuted {
+    pub id: u64,
+}
+
+#[event]
+pub struct GovernanceActionCancelled {
+    pub id: u64,
+}
+
+#[event]
+pub struct RelayerAdded {
+    pub relayer: Pubkey,
+}
+
+#[event]
+pub struct RelayerRemoved {
+    pub relayer: Pubkey,
+}
+
+#[event]
+pub struct BridgePausedChanged {
+    pub paused: bool,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct TokenPausedChanged {
+    pub token_id: [u8; 32],
+    pub paused: bool,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub current_admin: Pubkey,
+    pub proposed_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct FeeBpsUpdated {
+    pub old_fee_bps: u16,
+    pub new_fee_bps: u16,
+}
+
+#[event]
+pub struct ClaimTipUpdated {
+    pub old_tip_bps: u16,
+    pub new_tip_bps: u16,
+}
+
+#[event]
+pub struct ClaimTipPaid {
+    pub event_id: [u8; 32],
+    pub claimer: Pubkey,
+    pub tip: u64,
+}
+
+#[event]
+pub struct WithdrawalRequested {
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub amount: u64,
+    pub ton_recipient: [u8; 32],
+    pub refundable_at: i64,
+}
+
+#[event]
+pub struct WithdrawalFinalized {
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub ton_tx_hash: [u8; 32],
+    pub relayer: Pubkey,
+}
+
+#[event]
+pub struct WithdrawalRefunded {
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub admin: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(vk_id: u32)]
+pub struct InitState<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + LcState::SIZE,
+        seeds = [LcState::SEED],
+        bump
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VerifyingKey::SIZE,
+        seeds = [VerifyingKey::SEED, &vk_id.to_le_bytes()],
+        bump
+    )]
+    pub verifying_key: AccountLoader<'info, VerifyingKey>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitWrappedMint<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [WRAPPED_TON_MINT_SEED],
+        bump,
+        mint::decimals = WRAPPED_TON_DECIMALS,
+        mint::authority = state
+    )]
+    pub wrapped_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(token_id: [u8; 32])]
+pub struct SetTokenPaused<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        mut,
+        seeds = [TokenConfig::SEED, &token_id],
+        bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump
+    )]
+    pub state: Account<'info, LcState>,
+
+    pub new_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitTreasury<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        seeds = [WRAPPED_TON_MINT_SEED],
+        bump
+    )]
+    pub wrapped_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [TREASURY_SEED],
+        bump,
+        token::mint = wrapped_mint,
+        token::authority = state
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeBps<'info> {
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGlobalVolumeCap<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + VolumeLimit::SIZE,
+        seeds = [VolumeLimit::SEED, VolumeLimit::GLOBAL],
+        bump
+    )]
+    pub volume_limit: Account<'info, VolumeLimit>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(token_id: [u8; 32])]
+pub struct SetTokenVolumeCap<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + VolumeLimit::SIZE,
+        seeds = [VolumeLimit::SEED, &token_id],
+        bump
+    )]
+    pub volume_limit: Account<'info, VolumeLimit>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(token_id: [u8; 32])]
+pub struct ConfigureToken<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + TokenConfig::SIZE,
+        seeds = [TokenConfig::SEED, &token_id],
+        bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateState<'info> {
+    /// CHECK: may predate the current LcState layout; discriminator, admin and
+    /// owner are checked in migrate_state / by the constraints below
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump,
+        owner = crate::ID
+    )]
+    pub state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetEventCloseAge<'info> {
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(event_id: [u8; 32])]
+pub struct CloseEvent<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        mut,
+        seeds = [EventState::SEED, &zk_verifier::hash_scalar(&event_id)],
+        bump,
+        close = rent_receiver
+    )]
+    pub event_account: Account<'info, EventState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + NullifierState::SIZE,
+        seeds = [NullifierState::SEED, &zk_verifier::hash_scalar(&event_account.nullifier)],
+        bump
+    )]
+    pub nullifier_account: Account<'info, NullifierState>,
+
+    /// CHECK: receives the refunded rent; must be the account that paid for the event
+    #[account(mut, address = event_account.payer @ ZkError::UnauthorizedClose)]
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAction<'info> {
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PendingAction::SIZE,
+        seeds = [PendingAction::SEED, &state.next_action_id.to_le_bytes()],
+        bump
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(action_id: u64)]
+pub struct ExecuteAction<'info> {
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        mut,
+        seeds = [PendingAction::SEED, &action_id.to_le_bytes()],
+        bump
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+
+    // Only needed by RotateVerifyingKey; matched against the action's vk id
+    pub new_verifying_key: Option<AccountLoader<'info, VerifyingKey>>,
+
+    // Only needed by relayer actions
+    #[account(
+        mut,
+        seeds = [RelayerSet::SEED],
+        bump
+    )]
+    pub relayer_set: Option<Account<'info, RelayerSet>>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(action_id: u64)]
+pub struct CancelAction<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        mut,
+        seeds = [PendingAction::SEED, &action_id.to_le_bytes()],
+        bump
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetNullifierMode<'info> {
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = treasury.mint
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct RequestWithdrawal<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Withdrawal::SIZE,
+        seeds = [Withdrawal::SEED, owner.key().as_ref(), &nonce.to_le_bytes()],
+        bump
+    )]
+    pub withdrawal: Account<'info, Withdrawal>,
+
+    #[account(
+        mut,
+        seeds = [WRAPPED_TON_MINT_SEED],
+        bump
+    )]
+    pub wrapped_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = wrapped_mint,
+        token::authority = owner
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeWithdrawal<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        mut,
+        seeds = [Withdrawal::SEED, withdrawal.owner.as_ref(), &withdrawal.nonce.to_le_bytes()],
+        bump
+    )]
+    pub withdrawal: Account<'info, Withdrawal>,
+
+    // Optional so deployments without a relayer set keep working
+    #[account(
+        seeds = [RelayerSet::SEED],
+        bump
+    )]
+    pub relayer_set: Option<Account<'info, RelayerSet>>,
+
+    pub relayer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RefundWithdrawal<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        mut,
+        seeds = [Withdrawal::SEED, withdrawal.owner.as_ref(), &withdrawal.nonce.to_le_bytes()],
+        bump,
+        has_one = owner
+    )]
+    pub withdrawal: Account<'info, Withdrawal>,
+
+    #[account(
+        mut,
+        seeds = [WRAPPED_TON_MINT_SEED],
+        bump
+    )]
+    pub wrapped_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = wrapped_mint,
+        token::authority = owner
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(vk_id: u32)]
+pub struct CreateVerifyingKey<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + VerifyingKey::SIZE,
+        seeds = [VerifyingKey::SEED, &vk_id.to_le_bytes()],
+        bump
+    )]
+    pub verifying_key: AccountLoader<'info, VerifyingKey>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_vk_id: u32)]
+pub struct RotateVerifyingKey<'info> {
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        seeds = [VerifyingKey::SEED, &new_vk_id.to_le_bytes()],
+        bump
+    )]
+    pub new_verifying_key: AccountLoader<'info, VerifyingKey>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(vk_id: u32)]
+pub struct UploadVerifyingKey<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        mut,
+        seeds = [VerifyingKey::SEED, &vk_id.to_le_bytes()],
+        bump
+    )]
+    pub verifying_key: AccountLoader<'info, VerifyingKey>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(vk_id: u32)]
+pub struct AppendVkChunk<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        mut,
+        seeds = [VerifyingKey::SEED, &vk_id.to_le_bytes()],
+        bump
+    )]
+    pub verifying_key: AccountLoader<'info, VerifyingKey>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTonRoot<'info> {
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump
+    )]
+    pub state: Account<'info, LcState>,
+
+    // Optional so deployments without a relayer set keep working
+    #[account(
+        seeds = [RelayerSet::SEED],
+        bump
+    )]
+    pub relayer_set: Option<Account<'info, RelayerSet>>,
+    
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRootApprovalThreshold<'info> {
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        seeds = [RelayerSet::SEED],
+        bump
+    )]
+    pub relayer_set: Account<'info, RelayerSet>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_ton_root: [u8; 32])]
+pub struct ApproveTonRoot<'info> {
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        seeds = [RelayerSet::SEED],
+        bump
+    )]
+    pub relayer_set: Account<'info, RelayerSet>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + PendingRoot::SIZE,
+        seeds = [PendingRoot::SEED, &new_ton_root],
+        bump
+    )]
+    pub pending_root: Account<'info, PendingRoot>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddRelayer<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + RelayerSet::SIZE,
+        seeds = [RelayerSet::SEED],
+        bump
+    )]
+    pub relayer_set: Account<'info, RelayerSet>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveRelayer<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        mut,
+        seeds = [RelayerSet::SEED],
+        bump
+    )]
+    pub relayer_set: Account<'info, RelayerSet>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        seeds = [Committee::SEED],
+        bump
+    )]
+    pub committee: Account<'info, Committee>,
+
+    /// CHECK: address constrained to the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
-pub struct InitState<'info> {
+pub struct SubmitTonHeader<'info> {
     #[account(
-        init,
-        payer = payer,
-        space = 8 + LcState::SIZE,
         seeds = [LcState::SEED],
         bump
     )]
     pub state: Account<'info, LcState>,
 
     #[account(
-        init,
+        seeds = [Committee::SEED],
+        bump
+    )]
+    pub committee: Account<'info, Committee>,
+
+    #[account(
+        init_if_needed,
         payer = payer,
-        space = 8 + 4 + 4 + 1024,
-        seeds = [VerifyingKey::SEED, &0u32.to_le_bytes()],
+        space = 8 + HeaderChain::SIZE,
+        seeds = [HeaderChain::SEED],
         bump
     )]
-    pub verifying_key: Account<'info, VerifyingKey>,
+    pub header_chain: AccountLoader<'info, HeaderChain>,
+
+    /// CHECK: address constrained to the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
 
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -147,48 +2364,233 @@ pub struct InitState<'info> {
 }
 
 #[derive(Accounts)]
-pub struct UpdateTonRoot<'info> {
+pub struct SetHeaderChainOnly<'info> {
     #[account(
         mut,
         seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        seeds = [HeaderChain::SEED],
         bump
     )]
+    pub header_chain: AccountLoader<'info, HeaderChain>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCommittee<'info> {
+    #[account(
+        seeds = [LcState::SEED],
+        bump,
+        has_one = admin @ ZkError::UnauthorizedAdmin
+    )]
     pub state: Account<'info, LcState>,
-    
-    pub signer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + Committee::SIZE,
+        seeds = [Committee::SEED],
+        bump
+    )]
+    pub committee: Account<'info, Committee>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
+// Enhanced TON event verification accounts. PDAs are checked against the
+// bumps stored in LcState, and the event and nullifier records must be created
+// by this instruction: a replay fails when their accounts already exist.
 #[derive(Accounts)]
-pub struct VerifyUpdate<'info> {
+#[instruction(proof: ZKProof, public_inputs: EventPublicInputs)]
+pub struct VerifyTonEvent<'info> {
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump = state.bump
+    )]
+    pub state: Account<'info, LcState>,
+
+    // Optional; lets proofs anchor to committee-verified headers
+    #[account(
+        seeds = [HeaderChain::SEED],
+        bump
+    )]
+    pub header_chain: Option<AccountLoader<'info, HeaderChain>>,
+
+    #[account(
+        seeds = [VerifyingKey::SEED, &public_inputs.vk_version.to_le_bytes()],
+        bump,
+        constraint = verifying_key.load()?.vk_id == public_inputs.vk_version @ ZkError::VerifyingKeyMismatch,
+        constraint = state.is_vk_allowed(public_inputs.vk_version, Clock::get()?.unix_timestamp)
+            @ ZkError::VkVersionNotAllowed
+    )]
+    pub verifying_key: AccountLoader<'info, VerifyingKey>,
+
+    #[account(
+        seeds = [TokenConfig::SEED, &public_inputs.token_id],
+        bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [VolumeLimit::SEED, VolumeLimit::GLOBAL],
+        bump
+    )]
+    pub global_volume: Account<'info, VolumeLimit>,
+
     #[account(
         mut,
+        seeds = [VolumeLimit::SEED, &public_inputs.token_id],
+        bump
+    )]
+    pub token_volume: Account<'info, VolumeLimit>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + EventState::SIZE,
+        seeds = [EventState::SEED, &public_inputs.event_seed()],
+        bump
+    )]
+    pub event_account: Account<'info, EventState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + NullifierState::SIZE,
+        seeds = [NullifierState::SEED, &public_inputs.nullifier_seed()],
+        bump
+    )]
+    pub nullifier_account: Account<'info, NullifierState>,
+
+    #[account(
+        mut,
+        seeds = [WRAPPED_TON_MINT_SEED],
+        bump = state.mint_bump
+    )]
+    pub wrapped_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = state.treasury_bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    /// CHECK: only used as the ATA owner; must be the proven recipient
+    #[account(address = public_inputs.recipient_solana @ ZkError::InvalidRecipient)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = wrapped_mint,
+        associated_token::authority = recipient
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    // Optional; the payer's own wrapped-TON account, credited with the claim tip
+    #[account(
+        mut,
+        token::mint = wrapped_mint,
+        token::authority = payer
+    )]
+    pub claimer_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proof: ZKProof, aggregate: AggregatePublicInputs)]
+pub struct VerifyAggregateProof<'info> {
+    #[account(
         seeds = [LcState::SEED],
         bump
     )]
     pub state: Account<'info, LcState>,
+
+    // Optional; lets proofs anchor to committee-verified headers
+    #[account(
+        seeds = [HeaderChain::SEED],
+        bump
+    )]
+    pub header_chain: Option<AccountLoader<'info, HeaderChain>>,
+
+    #[account(
+        seeds = [VerifyingKey::SEED, &aggregate.vk_version.to_le_bytes()],
+        bump
+    )]
+    pub verifying_key: AccountLoader<'info, VerifyingKey>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AggregateRoot::SIZE,
+        seeds = [AggregateRoot::SEED, &aggregate.events_root],
+        bump
+    )]
+    pub aggregate_root: Account<'info, AggregateRoot>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
-// Enhanced TON event verification accounts - Remove Bumps derive
 #[derive(Accounts)]
-#[instruction(proof: ZKProof, public_inputs: EventPublicInputs)]
-pub struct VerifyTonEvent<'info> {
+#[instruction(events_root: [u8; 32], public_inputs: EventPublicInputs)]
+pub struct ClaimAggregatedEvent<'info> {
     #[account(
+        mut,
         seeds = [LcState::SEED],
         bump
     )]
     pub state: Account<'info, LcState>,
 
     #[account(
-        seeds = [VerifyingKey::SEED, &0u32.to_le_bytes()],
+        mut,
+        seeds = [AggregateRoot::SEED, &events_root],
+        bump
+    )]
+    pub aggregate_root: Account<'info, AggregateRoot>,
+
+    #[account(
+        seeds = [TokenConfig::SEED, &public_inputs.token_id],
+        bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [VolumeLimit::SEED, VolumeLimit::GLOBAL],
+        bump
+    )]
+    pub global_volume: Account<'info, VolumeLimit>,
+
+    #[account(
+        mut,
+        seeds = [VolumeLimit::SEED, &public_inputs.token_id],
         bump
     )]
-    pub verifying_key: Account<'info, VerifyingKey>,
+    pub token_volume: Account<'info, VolumeLimit>,
 
     #[account(
         init_if_needed,
         payer = payer,
         space = 8 + EventState::SIZE,
-        seeds = [EventState::SEED, &public_inputs.event_id],
+        seeds = [EventState::SEED, &public_inputs.event_seed()],
         bump
     )]
     pub event_account: Account<'info, EventState>,
@@ -197,13 +2599,205 @@ pub struct VerifyTonEvent<'info> {
         init_if_needed,
         payer = payer,
         space = 8 + NullifierState::SIZE,
-        seeds = [NullifierState::SEED, &public_inputs.nullifier],
+        seeds = [NullifierState::SEED, &public_inputs.nullifier_seed()],
         bump
     )]
     pub nullifier_account: Account<'info, NullifierState>,
 
+    #[account(
+        mut,
+        seeds = [WRAPPED_TON_MINT_SEED],
+        bump
+    )]
+    pub wrapped_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    /// CHECK: only used as the ATA owner; must be the proven recipient
+    #[account(address = public_inputs.recipient_solana @ ZkError::InvalidRecipient)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = wrapped_mint,
+        associated_token::authority = recipient
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proof: ZKProof, public_inputs: EventPublicInputs, inclusion: TxInclusionProof)]
+pub struct VerifyTonEventCompact<'info> {
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump = state.bump
+    )]
+    pub state: Account<'info, LcState>,
+
+    #[account(
+        seeds = [VerifyingKey::SEED, &public_inputs.vk_version.to_le_bytes()],
+        bump
+    )]
+    pub verifying_key: AccountLoader<'info, VerifyingKey>,
+
+    #[account(
+        seeds = [TokenConfig::SEED, &public_inputs.token_id],
+        bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [VolumeLimit::SEED, VolumeLimit::GLOBAL],
+        bump
+    )]
+    pub global_volume: Account<'info, VolumeLimit>,
+
+    #[account(
+        mut,
+        seeds = [VolumeLimit::SEED, &public_inputs.token_id],
+        bump
+    )]
+    pub token_volume: Account<'info, VolumeLimit>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NullifierBitmap::SIZE,
+        seeds = [
+            NullifierBitmap::SEED,
+            &NullifierBitmap::page_for(inclusion.leaf_index).to_le_bytes()
+        ],
+        bump
+    )]
+    pub nullifier_bitmap: Account<'info, NullifierBitmap>,
+
+    /// CHECK: the nullifier record of an event consumed in NULLIFIER_MODE_PDA;
+    /// read only to refuse replaying it here
+    #[account(
+        seeds = [NullifierState::SEED, &public_inputs.nullifier_seed()],
+        bump
+    )]
+    pub nullifier_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [WRAPPED_TON_MINT_SEED],
+        bump = state.mint_bump
+    )]
+    pub wrapped_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = state.treasury_bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    /// CHECK: only used as the ATA owner; must be the proven recipient
+    #[account(address = public_inputs.recipient_solana @ ZkError::InvalidRecipient)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = wrapped_mint,
+        associated_token::authority = recipient
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(events: Vec<BatchedEvent>)]
+pub struct VerifyTonEventBatch<'info> {
+    #[account(
+        mut,
+        seeds = [LcState::SEED],
+        bump = state.bump
+    )]
+    pub state: Account<'info, LcState>,
+
+    // Optional; lets proofs anchor to committee-verified headers
+    #[account(
+        seeds = [HeaderChain::SEED],
+        bump
+    )]
+    pub header_chain: Option<AccountLoader<'info, HeaderChain>>,
+
+    // All events in a batch must be proven against the same key
+    #[account(
+        seeds = [
+            VerifyingKey::SEED,
+            &events.first().map(|e| e.public_inputs.vk_version).unwrap_or_default().to_le_bytes()
+        ],
+        bump
+    )]
+    pub verifying_key: AccountLoader<'info, VerifyingKey>,
+
+    // All events in a batch must be for the same token
+    #[account(
+        seeds = [
+            TokenConfig::SEED,
+            &events.first().map(|e| e.public_inputs.token_id).unwrap_or_default()
+        ],
+        bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [VolumeLimit::SEED, VolumeLimit::GLOBAL],
+        bump
+    )]
+    pub global_volume: Account<'info, VolumeLimit>,
+
+    #[account(
+        mut,
+        seeds = [
+            VolumeLimit::SEED,
+            &events.first().map(|e| e.public_inputs.token_id).unwrap_or_default()
+        ],
+        bump
+    )]
+    pub token_volume: Account<'info, VolumeLimit>,
+
+    #[account(
+        mut,
+        seeds = [WRAPPED_TON_MINT_SEED],
+        bump = state.mint_bump
+    )]
+    pub wrapped_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = state.treasury_bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
     #[account(mut)]
     pub payer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
@@ -0,0 +1,52 @@
+// merkle.rs
+use anchor_lang::prelude::*;
+use solana_program::hash::hashv;
+
+/// Deepest tree accepted for TON transaction inclusion paths
+pub const MAX_MERKLE_DEPTH: usize = 32;
+
+const LEAF_PREFIX: &[u8] = &[0x00];
+const NODE_PREFIX: &[u8] = &[0x01];
+
+/// Sibling path proving a TON transaction hash is a leaf under `anchor_root`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct TxInclusionProof {
+    pub leaf_index: u64,
+    pub siblings: Vec<[u8; 32]>,
+}
+
+pub fn hash_leaf(ton_tx_hash: &[u8; 32]) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, ton_tx_hash]).to_bytes()
+}
+
+pub fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+/// Recompute the root from `ton_tx_hash` and its sibling path. Bit `i` of
+/// `leaf_index` selects whether the running hash is the right (1) or left (0)
+/// child at level `i`.
+pub fn verify_inclusion(ton_tx_hash: &[u8; 32], proof: &TxInclusionProof, root: &[u8; 32]) -> bool {
+    verify_leaf(ton_tx_hash, proof, root)
+}
+
+/// Same path check for any 32-byte leaf value, e.g. an aggregated proof's event commitments
+pub fn verify_leaf(leaf: &[u8; 32], proof: &TxInclusionProof, root: &[u8; 32]) -> bool {
+    if proof.siblings.len() > MAX_MERKLE_DEPTH {
+        return false;
+    }
+    if proof.siblings.len() < 64 && proof.leaf_index >> proof.siblings.len() != 0 {
+        return false;
+    }
+
+    let mut node = hash_leaf(leaf);
+    for (level, sibling) in proof.siblings.iter().enumerate() {
+        node = if (proof.leaf_index >> level) & 1 == 1 {
+            hash_node(sibling, &node)
+        } else {
+            hash_node(&node, sibling)
+        };
+    }
+
+    node == *root
+}
@@ -0,0 +1,226 @@
+// mint.rs
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, get_associated_token_address, Create};
+use anchor_spl::token::{self, MintTo};
+
+use crate::state::{EventPublicInputs, LcState, TokenConfig, VolumeLimit};
+use crate::zk_verifier::ZkError;
+use crate::{ClaimTipPaid, TonEventVerified};
+
+/// Seed of the wrapped-TON mint PDA; its mint authority is the `LcState` PDA
+pub const WRAPPED_TON_MINT_SEED: &[u8] = b"wrapped_ton";
+/// Seed of the wrapped-TON token account collecting protocol fees
+pub const TREASURY_SEED: &[u8] = b"treasury";
+/// Same precision as `amount_in_ton` (nanotons)
+pub const WRAPPED_TON_DECIMALS: u8 = 9;
+
+const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Split a bridged amount into `(net, fee)` for the given fee in basis points
+pub fn split_fee(amount: u64, fee_bps: u16) -> Result<(u64, u64)> {
+    let fee = (amount as u128 * fee_bps as u128 / BPS_DENOMINATOR) as u64;
+    let net = amount.checked_sub(fee).ok_or(ZkError::InvalidFee)?;
+    Ok((net, fee))
+}
+
+/// Split using the protocol fee in `LcState`; the proof's `fee_bps` is the most
+/// the depositor agreed to pay
+pub fn split_protocol_fee(amount: u64, state_fee_bps: u16, max_fee_bps: u16) -> Result<(u64, u64)> {
+    require!(state_fee_bps <= max_fee_bps, ZkError::InvalidFee);
+    split_fee(amount, state_fee_bps)
+}
+
+/// Accounts a verified event's amount is minted with
+pub struct EventMintAccounts<'info> {
+    pub token_program: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub treasury: AccountInfo<'info>,
+    pub state: AccountInfo<'info>,
+    pub state_bump: u8,
+}
+
+/// A third party completing someone else's deposit, paid the claim tip
+pub struct Claimer<'info> {
+    pub key: Pubkey,
+    pub token_account: AccountInfo<'info>,
+}
+
+/// Settle a verified event the caller has already marked consumed: check it
+/// against its token config and the 24-hour volume caps, mint it (net to the
+/// recipient, fee to the treasury, the claim tip to `claimer` when tips are
+/// enabled), count it in `LcState` and emit `TonEventVerified`. Every
+/// verification path mints through here, so they share one set of fee, tip
+/// and volume rules. Returns the net amount minted to the recipient.
+#[allow(clippy::too_many_arguments)]
+pub fn settle_event<'info>(
+    state: &mut LcState,
+    accounts: &EventMintAccounts<'info>,
+    token_config: &TokenConfig,
+    global_volume: &mut VolumeLimit,
+    token_volume: &mut VolumeLimit,
+    recipient_token_account: &AccountInfo<'info>,
+    claimer: Option<Claimer<'info>>,
+    public_inputs: &EventPublicInputs,
+) -> Result<u64> {
+    let amount = public_inputs.amount_in_ton;
+    token_config.check_event(&public_inputs.token_id, accounts.mint.key, amount)?;
+    let now = Clock::get()?.unix_timestamp;
+    global_volume.record(amount, now)?;
+    token_volume.record(amount, now)?;
+
+    let (net, fee) = match claimer {
+        Some(claimer) if state.claim_tip_bps > 0 => {
+            let (net, fee, tip) = mint_event_amount_with_tip(
+                &accounts.token_program,
+                &accounts.mint,
+                recipient_token_account,
+                &accounts.treasury,
+                &claimer.token_account,
+                &accounts.state,
+                accounts.state_bump,
+                amount,
+                state.fee_bps,
+                state.claim_tip_bps,
+                public_inputs.fee_bps,
+            )?;
+            emit!(ClaimTipPaid {
+                event_id: public_inputs.event_id,
+                claimer: claimer.key,
+                tip,
+            });
+            (net, fee)
+        }
+        _ => mint_event_amount(
+            &accounts.token_program,
+            &accounts.mint,
+            recipient_token_account,
+            &accounts.treasury,
+            &accounts.state,
+            accounts.state_bump,
+            amount,
+            state.fee_bps,
+            public_inputs.fee_bps,
+        )?,
+    };
+    let sequence = state.record_verified(amount);
+
+    emit!(TonEventVerified {
+        event_id: public_inputs.event_id,
+        recipient: public_inputs.recipient_solana,
+        amount,
+        ton_tx_hash: public_inputs.ton_tx_hash,
+        ton_sender: public_inputs.ton_sender,
+        fee,
+        sequence,
+    });
+    Ok(net)
+}
+
+/// Mint a verified event's amount: net to the recipient, protocol fee to the
+/// treasury. Returns `(net, fee)`.
+#[allow(clippy::too_many_arguments)]
+pub fn mint_event_amount<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    recipient_token_account: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    state: &AccountInfo<'info>,
+    state_bump: u8,
+    amount: u64,
+    state_fee_bps: u16,
+    max_fee_bps: u16,
+) -> Result<(u64, u64)> {
+    let (net, fee) = split_protocol_fee(amount, state_fee_bps, max_fee_bps)?;
+    mint_wrapped(token_program, mint, recipient_token_account, state, state_bump, net)?;
+    mint_wrapped(token_program, mint, treasury, state, state_bump, fee)?;
+    Ok((net, fee))
+}
+
+/// `mint_event_amount` for a third-party claim: `tip_bps` of the amount is
+/// carved out of the recipient's share and minted to the claimer. The
+/// protocol fee and tip together must fit the depositor's `max_fee_bps`.
+/// Returns `(net, fee, tip)`.
+#[allow(clippy::too_many_arguments)]
+pub fn mint_event_amount_with_tip<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    recipient_token_account: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    claimer_token_account: &AccountInfo<'info>,
+    state: &AccountInfo<'info>,
+    state_bump: u8,
+    amount: u64,
+    state_fee_bps: u16,
+    tip_bps: u16,
+    max_fee_bps: u16,
+) -> Result<(u64, u64, u64)> {
+    require!(
+        state_fee_bps as u32 + tip_bps as u32 <= max_fee_bps as u32,
+        ZkError::InvalidFee
+    );
+    let (net, fee) = split_fee(amount, state_fee_bps)?;
+    let tip = (amount as u128 * tip_bps as u128 / BPS_DENOMINATOR) as u64;
+    let net = net.checked_sub(tip).ok_or(ZkError::InvalidFee)?;
+    mint_wrapped(token_program, mint, recipient_token_account, state, state_bump, net)?;
+    mint_wrapped(token_program, mint, treasury, state, state_bump, fee)?;
+    mint_wrapped(token_program, mint, claimer_token_account, state, state_bump, tip)?;
+    Ok((net, fee, tip))
+}
+
+/// Mint `amount` wrapped TON to `to`, signing as the `LcState` PDA
+pub fn mint_wrapped<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    state: &AccountInfo<'info>,
+    state_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let signer: &[&[&[u8]]] = &[&[LcState::SEED, &[state_bump]]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            MintTo {
+                mint: mint.clone(),
+                to: to.clone(),
+                authority: state.clone(),
+            },
+            signer,
+        ),
+        amount,
+    )
+}
+
+/// Create `recipient`'s wrapped-TON ATA if it does not exist yet. Used where
+/// accounts come from remaining_accounts and `init_if_needed` cannot apply.
+pub fn ensure_recipient_ata<'info>(
+    payer: &AccountInfo<'info>,
+    ata: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    associated_token_program: &AccountInfo<'info>,
+) -> Result<()> {
+    require_keys_eq!(
+        ata.key(),
+        get_associated_token_address(recipient.key, mint.key),
+        ZkError::InvalidRecipient
+    );
+
+    associated_token::create_idempotent(CpiContext::new(
+        associated_token_program.clone(),
+        Create {
+            payer: payer.clone(),
+            associated_token: ata.clone(),
+            authority: recipient.clone(),
+            mint: mint.clone(),
+            system_program: system_program.clone(),
+            token_program: token_program.clone(),
+        },
+    ))
+}
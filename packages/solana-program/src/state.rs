@@ -1,5 +1,12 @@
 // state.rs
 use anchor_lang::prelude::*;
+use crate::zk_verifier::{hash_scalar, ZkError, ZKProof};
+use crate::merkle::TxInclusionProof;
+
+/// Upper bound on events accepted by `verify_ton_event_batch`
+pub const MAX_BATCH_EVENTS: usize = 8;
+/// remaining_accounts per batched event: event, nullifier, recipient, recipient ATA
+pub const BATCH_ACCOUNTS_PER_EVENT: usize = 4;
 
 #[account]
 pub struct LcState {
@@ -8,21 +15,357 @@ pub struct LcState {
     pub vk_id: u32,
     pub ton_state_root: [u8; 32],  // ADD: Current TON state root
     pub relayer: Pubkey,           // ADD: Authorized relayer for state updates
+    pub previous_vk_id: u32,       // Key being phased out after a rotation
+    pub previous_vk_expires_at: i64, // Unix timestamp; previous key rejected after this
+    pub fee_bps: u16,              // Protocol fee minted to the treasury
+    pub nullifier_mode: u8,        // NULLIFIER_MODE_PDA or NULLIFIER_MODE_BITMAP
+    pub pending_admin: Pubkey,     // Proposed admin awaiting accept_admin; default when none
+    pub root_approval_threshold: u8, // Relayer approvals needed per root; <= 1 allows update_ton_root
+    pub paused: bool,              // Emergency stop for event verification and root updates
+    pub governance_delay_secs: i64, // Timelock on critical changes; 0 allows direct admin calls
+    pub next_action_id: u64,       // Id of the next proposed governance action
+    pub event_close_age_secs: i64, // Minimum age before a consumed EventState can be closed
+    pub version: u8,               // Layout version; bumped by migrate_state
+    pub events_verified: u64,      // Monotonic count of verified TON events; also their sequence numbers
+    pub total_amount_bridged: u128, // Sum of verified event amounts, before fees
+    pub header_chain_only: bool,   // Accept only anchor roots from verified HeaderChain headers
+    pub bump: u8,                  // Canonical bumps, so hot paths skip find_program_address
+    pub mint_bump: u8,
+    pub treasury_bump: u8,
+    pub claim_tip_bps: u16,        // Share of the amount minted to a third-party claimer; 0 disables
+    pub poseidon_from_vk_id: u32,  // Keys with this id or newer rebuild event ids with Poseidon; 0 = never
+    pub deposit_tree_root: [u8; 32], // Root of the append-only deposit tree compact claims anchor to; zero until set
 }
 
 impl LcState {
     pub const SEED: &'static [u8] = b"lc_state";
-    pub const SIZE: usize = 32 + 8 + 4 + 32 + 32 + 8; // Updated size
+    pub const SIZE: usize = 32 + 8 + 4 + 32 + 32 + 4 + 8 + 2 + 1 + 32 + 1 + 1 + 8 + 8 + 8 + 8 + 1 + 8 + 16 + 1 + 3 + 2 + 4 + 32; // Updated size
+    /// Layout written by this program. New fields are appended so accounts
+    /// from older layouts read them as zero after `migrate_state` reallocs.
+    pub const CURRENT_VERSION: u8 = 7;
+    pub const MAX_FEE_BPS: u16 = 1000; // Same 10% cap the verifier applies to public inputs
+    /// The tip comes out of the recipient's share, so it stays small
+    pub const MAX_CLAIM_TIP_BPS: u16 = 100;
+    pub const MAX_GOVERNANCE_DELAY_SECS: i64 = 30 * 24 * 60 * 60;
+
+    /// Root a proof's `anchor_root` must match: the claimed root itself when it
+    /// is a verified header in `header_chain`, otherwise the pushed TON root
+    pub fn anchor_root_for(&self, header_chain: Option<&HeaderChain>, claimed: &[u8; 32]) -> Result<[u8; 32]> {
+        if header_chain.is_some_and(|chain| chain.contains_root(claimed)) {
+            return Ok(*claimed);
+        }
+        require!(!self.header_chain_only, ZkError::AnchorNotInHeaderChain);
+        Ok(self.ton_state_root)
+    }
+
+    /// Root a `verify_ton_event_compact` proof must anchor to. Bitmap bits are
+    /// keyed by leaf position, which only identifies a deposit within the one
+    /// append-only deposit tree.
+    pub fn deposit_anchor_root(&self) -> Result<[u8; 32]> {
+        require!(self.deposit_tree_root != [0u8; 32], ZkError::DepositTreeRootUnset);
+        Ok(self.deposit_tree_root)
+    }
+
+    /// Count a verified event; returns its sequence number (1-based)
+    pub fn record_verified(&mut self, amount: u64) -> u64 {
+        self.events_verified += 1;
+        self.total_amount_bridged += amount as u128;
+        self.events_verified
+    }
+
+    /// How `event_id` is rebuilt for proofs against key `vk_id`, matching the
+    /// hash its circuit uses
+    pub fn event_hash_for(&self, vk_id: u32) -> u8 {
+        if self.poseidon_from_vk_id != 0 && vk_id >= self.poseidon_from_vk_id {
+            EVENT_HASH_POSEIDON
+        } else {
+            EVENT_HASH_SHA256
+        }
+    }
+
+    /// Current key, or the previous key while its deprecation window is open
+    pub fn is_vk_allowed(&self, vk_id: u32, now: i64) -> bool {
+        vk_id == self.vk_id || (vk_id == self.previous_vk_id && now < self.previous_vk_expires_at)
+    }
+}
+
+/// `event_id` = SHA-256 over the tagged, little-endian event components
+pub const EVENT_HASH_SHA256: u8 = 0;
+/// `event_id` = Poseidon (BN254, x^5) over the components' field elements
+pub const EVENT_HASH_POSEIDON: u8 = 1;
+
+/// One `NullifierState` PDA per consumed event (verify_ton_event / batch)
+pub const NULLIFIER_MODE_PDA: u8 = 0;
+/// Bits in paged `NullifierBitmap` accounts (verify_ton_event_compact)
+pub const NULLIFIER_MODE_BITMAP: u8 = 1;
+
+// Consumed-event bitmap for one page of leaf positions in the bridge's TON
+// deposit tree (`LcState.deposit_tree_root`). The tree is append-only, so a
+// deposit keeps its `leaf_index` under every later root and pages are seeded by
+// page number alone. Proofs against any other root never reach the bitmap.
+#[account]
+pub struct NullifierBitmap {
+    pub page: u64,
+    pub bits: [u8; NullifierBitmap::PAGE_BYTES],
+}
+
+impl NullifierBitmap {
+    pub const SEED: &'static [u8] = b"nullifier_bitmap";
+    pub const PAGE_BYTES: usize = 1024;
+    pub const BITS_PER_PAGE: u64 = (Self::PAGE_BYTES * 8) as u64;
+    pub const SIZE: usize = 8 + Self::PAGE_BYTES;
+
+    pub fn page_for(leaf_index: u64) -> u64 {
+        leaf_index / Self::BITS_PER_PAGE
+    }
+
+    fn position(leaf_index: u64) -> (usize, u8) {
+        let bit = leaf_index % Self::BITS_PER_PAGE;
+        ((bit / 8) as usize, 1u8 << (bit % 8))
+    }
+
+    pub fn is_set(&self, leaf_index: u64) -> bool {
+        let (byte, mask) = Self::position(leaf_index);
+        self.bits[byte] & mask != 0
+    }
+
+    pub fn set(&mut self, leaf_index: u64) {
+        let (byte, mask) = Self::position(leaf_index);
+        self.bits[byte] |= mask;
+    }
+}
+
+// Relayers authorized to call update_ton_root, in addition to `LcState.relayer`
+#[account]
+pub struct RelayerSet {
+    pub relayers: Vec<Pubkey>,
+}
+
+impl RelayerSet {
+    pub const SEED: &'static [u8] = b"relayer_set";
+    pub const MAX_RELAYERS: usize = 16;
+    pub const SIZE: usize = 4 + 32 * Self::MAX_RELAYERS;
+
+    pub fn contains(&self, relayer: &Pubkey) -> bool {
+        self.relayers.contains(relayer)
+    }
+}
+
+// Wrapped TON burned on Solana, awaiting payout on TON. Keyed by owner and a
+// caller-chosen nonce so requests need no global counter.
+#[account]
+pub struct Withdrawal {
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub amount: u64,
+    pub ton_recipient: [u8; 32], // Raw TON address (account id) to pay out to
+    pub requested_at: i64,
+    pub status: u8,              // STATUS_*
+    pub ton_tx_hash: [u8; 32],   // Payout transaction, once finalized
+}
+
+impl Withdrawal {
+    pub const SEED: &'static [u8] = b"withdrawal";
+    pub const SIZE: usize = 32 + 8 + 8 + 32 + 8 + 1 + 32;
+    /// After this long without finalization the owner may reclaim the burned
+    /// amount; relayers must not pay out a withdrawal past this deadline.
+    pub const REFUND_TIMEOUT_SECS: i64 = 24 * 60 * 60;
+
+    pub const STATUS_PENDING: u8 = 0;
+    pub const STATUS_FINALIZED: u8 = 1;
+    pub const STATUS_REFUNDED: u8 = 2;
+
+    pub fn refundable_at(&self) -> i64 {
+        self.requested_at.saturating_add(Self::REFUND_TIMEOUT_SECS)
+    }
+}
+
+// Bridge settings for one TON token, keyed by `EventPublicInputs.token_id`
+#[account]
+pub struct TokenConfig {
+    pub token_id: [u8; 32],
+    pub mint: Pubkey,       // Solana mint events for this token are minted from
+    pub min_amount: u64,
+    pub max_amount: u64,    // 0 = no upper bound
+    pub enabled: bool,
+    pub paused: bool,       // Temporary freeze by the admin, independent of `enabled`
+}
+
+impl TokenConfig {
+    pub const SEED: &'static [u8] = b"token_config";
+    pub const SIZE: usize = 32 + 32 + 8 + 8 + 1 + 1;
+
+    /// Reject events for disabled or paused tokens, the wrong mint, or out-of-range amounts
+    pub fn check_event(&self, token_id: &[u8; 32], mint: &Pubkey, amount: u64) -> Result<()> {
+        require!(self.token_id == *token_id, ZkError::TokenConfigMismatch);
+        require!(self.enabled, ZkError::TokenDisabled);
+        require!(!self.paused, ZkError::TokenPaused);
+        require_keys_eq!(self.mint, *mint, ZkError::TokenMintMismatch);
+        require!(
+            amount >= self.min_amount && (self.max_amount == 0 || amount <= self.max_amount),
+            ZkError::AmountOutOfBounds
+        );
+        Ok(())
+    }
+}
+
+// Bridged volume in the current 24-hour epoch, globally or for one token
+#[account]
+pub struct VolumeLimit {
+    pub cap: u64,           // Max amount per epoch; 0 = uncapped
+    pub epoch: i64,         // unix_timestamp / EPOCH_SECS of the window `volume` counts
+    pub volume: u64,
+}
+
+impl VolumeLimit {
+    pub const SEED: &'static [u8] = b"volume_limit";
+    /// Second seed of the bridge-wide limiter; per-token limiters use the token id
+    pub const GLOBAL: &'static [u8] = b"global";
+    pub const EPOCH_SECS: i64 = 24 * 60 * 60;
+    pub const SIZE: usize = 8 + 8 + 8;
+
+    /// Count `amount` against the current epoch, rolling over to a new epoch as needed
+    pub fn record(&mut self, amount: u64, now: i64) -> Result<()> {
+        let epoch = now.div_euclid(Self::EPOCH_SECS);
+        if epoch != self.epoch {
+            self.epoch = epoch;
+            self.volume = 0;
+        }
+
+        let volume = self.volume.checked_add(amount).ok_or(ZkError::VolumeCapExceeded)?;
+        require!(self.cap == 0 || volume <= self.cap, ZkError::VolumeCapExceeded);
+        self.volume = volume;
+        Ok(())
+    }
+}
+
+// Relayer approvals collected for a proposed TON root
+#[account]
+pub struct PendingRoot {
+    pub root: [u8; 32],
+    pub approvals: Vec<Pubkey>,
+    pub committed: bool,
+    pub created_at: i64,
+}
+
+impl PendingRoot {
+    pub const SEED: &'static [u8] = b"pending_root";
+    pub const SIZE: usize = 32 + 4 + 32 * RelayerSet::MAX_RELAYERS + 1 + 8;
+}
+
+// A TON masterchain block header, as signed by the committee
+#[zero_copy]
+#[derive(Default, Debug, PartialEq)]
+pub struct TonBlockHeader {
+    pub seqno: u32,
+    pub root_hash: [u8; 32],
+    pub file_hash: [u8; 32],
 }
 
+impl TonBlockHeader {
+    pub const SIZE: usize = 4 + 32 + 32;
+}
+
+const HEADER_CHAIN_CAPACITY: usize = 32;
+
+// Ring buffer of the most recent committee-verified TON block headers; event
+// proofs may anchor to any of their root hashes. Zero-copy with a fixed
+// layout so verify instructions scan it in place instead of deserializing it.
+#[account(zero_copy)]
+pub struct HeaderChain {
+    pub headers: [TonBlockHeader; HEADER_CHAIN_CAPACITY],
+    pub latest: TonBlockHeader,
+    pub len: u8,            // Slots of `headers` filled so far
+    pub next: u8,           // Slot the next header overwrites once `headers` is full
+    pub _padding: [u8; 2],
+}
+
+impl HeaderChain {
+    pub const SEED: &'static [u8] = b"header_chain";
+    pub const MAX_HEADERS: usize = HEADER_CHAIN_CAPACITY;
+    pub const SIZE: usize = TonBlockHeader::SIZE * Self::MAX_HEADERS + TonBlockHeader::SIZE + 1 + 1 + 2;
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains_root(&self, root_hash: &[u8; 32]) -> bool {
+        self.headers[..self.len as usize].iter().any(|header| header.root_hash == *root_hash)
+    }
+
+    /// Append a header; it must advance the chain and, when it directly follows
+    /// the latest header, link to it through `prev_root_hash`
+    pub fn push(&mut self, header: TonBlockHeader, prev_root_hash: &[u8; 32]) -> Result<()> {
+        if !self.is_empty() {
+            require!(header.seqno > self.latest.seqno, ZkError::SlotGoesBackwards);
+            if header.seqno == self.latest.seqno + 1 {
+                require!(*prev_root_hash == self.latest.root_hash, ZkError::HeaderLinkMismatch);
+            }
+        }
+
+        if (self.len as usize) < Self::MAX_HEADERS {
+            self.headers[self.len as usize] = header;
+            self.len += 1;
+        } else {
+            self.headers[self.next as usize] = header;
+            self.next = ((self.next as usize + 1) % Self::MAX_HEADERS) as u8;
+        }
+        self.latest = header;
+        Ok(())
+    }
+}
+
+// TON validator/relayer committee whose signatures advance the light client
 #[account]
+pub struct Committee {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+impl Committee {
+    pub const SEED: &'static [u8] = b"committee";
+    pub const MAX_MEMBERS: usize = 16;
+    pub const SIZE: usize = 4 + 32 * Self::MAX_MEMBERS + 1;
+}
+
+const VK_MAX_LEN: usize = 4096;
+
+// Groth16 verifying key, uploaded in chunks. Zero-copy with a fixed-size
+// buffer so verify instructions read the key bytes in place rather than
+// deserializing them on every call.
+#[account(zero_copy)]
 pub struct VerifyingKey {
     pub vk_id: u32,
-    pub data: Vec<u8>,
+    pub total_len: u32,   // Expected length of `data` once fully uploaded
+    pub len: u32,         // Bytes of `data` uploaded so far
+    pub finalized: u8,    // Locked (1); proofs may only be verified once set
+    pub _padding: [u8; 3],
+    pub data: [u8; VK_MAX_LEN],
 }
 
 impl VerifyingKey {
     pub const SEED: &'static [u8] = b"vk";
+    pub const MAX_LEN: usize = VK_MAX_LEN;
+    pub const SIZE: usize = 4 + 4 + 4 + 1 + 3 + Self::MAX_LEN;
+
+    pub fn is_finalized(&self) -> bool {
+        self.finalized != 0
+    }
+
+    /// The uploaded key
+    pub fn bytes(&self) -> &[u8] {
+        &self.data[..self.len as usize]
+    }
+
+    /// Write `chunk` at the current end of the key, up to `total_len`
+    pub fn append(&mut self, chunk: &[u8]) -> Result<()> {
+        let start = self.len as usize;
+        let end = start + chunk.len();
+        require!(end <= self.total_len as usize, ZkError::InvalidVkChunk);
+        self.data[start..end].copy_from_slice(chunk);
+        self.len = end as u32;
+        Ok(())
+    }
 }
 
 // Event state to prevent double-spending
@@ -34,11 +377,42 @@ pub struct EventState {
     pub amount: u64,
     pub ton_tx_hash: [u8; 32],     // ADD: TON transaction hash
     pub ton_sender: [u8; 32],      // ADD: TON sender address
+    pub nullifier: [u8; 32],       // Kept so close_event can pin the nullifier record
+    pub leaf_index: u64,           // Position in the anchor root's TON tx tree
+    pub payer: Pubkey,             // Rent is refunded here on close_event
+    pub consumed_at: i64,
 }
 
 impl EventState {
     pub const SEED: &'static [u8] = b"event";
-    pub const SIZE: usize = 1 + 32 + 32 + 8 + 32 + 32 + 8; // Updated size
+    pub const SIZE: usize = 1 + 32 + 32 + 8 + 32 + 32 + 8 + 32 + 8 + 32 + 8; // Updated size
+}
+
+// Public inputs of a recursive proof covering many events: every leaf of the
+// events tree is `ZKVerifier::event_commitment` of a valid event under `anchor_root`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AggregatePublicInputs {
+    pub domain: [u8; 32],
+    pub anchor_root: [u8; 32],
+    pub events_root: [u8; 32],
+    pub event_count: u32,
+    pub vk_version: u32,  // Key of the aggregation circuit
+}
+
+// A verified aggregated proof; its events are minted one claim at a time
+#[account]
+pub struct AggregateRoot {
+    pub events_root: [u8; 32],
+    pub anchor_root: [u8; 32],
+    pub event_count: u32,
+    pub claimed: u32,
+    pub vk_version: u32,
+    pub verified_at: i64,
+}
+
+impl AggregateRoot {
+    pub const SEED: &'static [u8] = b"aggregate_root";
+    pub const SIZE: usize = 32 + 32 + 4 + 4 + 4 + 8;
 }
 
 // Enhanced public inputs for TON event verification
@@ -55,4 +429,40 @@ pub struct EventPublicInputs {
     pub ton_tx_hash: [u8; 32],     // ADD: TON transaction hash
     pub ton_sender: [u8; 32],      // ADD: TON sender address
     pub nullifier: [u8; 32],       // ADD: Double-spend protection
-}
\ No newline at end of file
+    pub recipient_commitment: [u8; 32], // Privacy mode: hash(recipient || salt); zero otherwise
+}
+
+impl EventPublicInputs {
+    pub fn is_private(&self) -> bool {
+        self.recipient_commitment != [0u8; 32]
+    }
+
+    /// Seed of the event's `EventState`: `event_id` as the proof binds it
+    pub fn event_seed(&self) -> [u8; 32] {
+        hash_scalar(&self.event_id)
+    }
+
+    /// Seed of the event's `NullifierState`: `nullifier` as the proof binds it
+    pub fn nullifier_seed(&self) -> [u8; 32] {
+        hash_scalar(&self.nullifier)
+    }
+
+    /// What the event commits to for the recipient: the commitment in privacy
+    /// mode, the plain recipient otherwise
+    pub fn recipient_binding(&self) -> [u8; 32] {
+        if self.is_private() {
+            self.recipient_commitment
+        } else {
+            self.recipient_solana.to_bytes()
+        }
+    }
+}
+
+// One proof and its public inputs within a batch verification
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BatchedEvent {
+    pub proof: ZKProof,
+    pub public_inputs: EventPublicInputs,
+    pub inclusion: TxInclusionProof,
+    pub recipient_salt: [u8; 32], // Only checked for privacy-mode events
+}
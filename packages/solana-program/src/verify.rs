@@ -1,9 +1,115 @@
 // verify.rs
 use anchor_lang::prelude::*;
+use solana_program::ed25519_program;
+use solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
 use crate::zk_verifier::ZkError;
 
-pub fn verify_mock(proof: &[u8; 32], old_slot: u64, new_slot: u64) -> Result<()> {
-    require!(new_slot > old_slot, ZkError::SlotGoesBackwards);
-    require!(proof.iter().all(|b| *b == 0xAA), ZkError::BadProof);
-    Ok(())
-}
\ No newline at end of file
+/// Ed25519 program: offsets struct size per signature
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const SIGNATURE_OFFSETS_START: usize = 2;
+const PUBKEY_SIZE: usize = 32;
+const SIGNATURE_SIZE: usize = 64;
+
+/// `RootUpdated.updated_by` for roots advanced by committee signatures rather than a single signer
+pub const COMMITTEE_UPDATER: Pubkey = Pubkey::new_from_array([0xff; 32]);
+
+/// Message committee members sign to advance the light client
+pub fn update_message(program_id: &Pubkey, new_slot: u64, new_ton_root: &[u8; 32]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(13 + 32 + 8 + 32);
+    message.extend_from_slice(b"TON_LC_UPDATE");
+    message.extend_from_slice(program_id.as_ref());
+    message.extend_from_slice(&new_slot.to_le_bytes());
+    message.extend_from_slice(new_ton_root);
+    message
+}
+
+/// Message committee members sign to append a TON block header to the HeaderChain
+pub fn header_message(
+    program_id: &Pubkey,
+    seqno: u32,
+    root_hash: &[u8; 32],
+    file_hash: &[u8; 32],
+    prev_root_hash: &[u8; 32],
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(13 + 32 + 4 + 32 * 3);
+    message.extend_from_slice(b"TON_LC_HEADER");
+    message.extend_from_slice(program_id.as_ref());
+    message.extend_from_slice(&seqno.to_le_bytes());
+    message.extend_from_slice(root_hash);
+    message.extend_from_slice(file_hash);
+    message.extend_from_slice(prev_root_hash);
+    message
+}
+
+/// Count distinct committee members whose signature over `message` was
+/// checked by an ed25519 program instruction earlier in this transaction.
+/// Only self-contained ed25519 instructions (all offsets pointing into the
+/// instruction's own data) are accepted.
+pub fn count_committee_signatures(
+    instructions_sysvar: &AccountInfo,
+    committee: &[Pubkey],
+    message: &[u8],
+) -> Result<usize> {
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    let mut signed = vec![false; committee.len()];
+
+    for index in 0..current_index {
+        let ix = load_instruction_at_checked(index, instructions_sysvar)?;
+        if ix.program_id != ed25519_program::ID {
+            continue;
+        }
+
+        for signer in ed25519_signers(&ix.data, message)? {
+            if let Some(pos) = committee.iter().position(|member| *member == signer) {
+                signed[pos] = true;
+            }
+        }
+    }
+
+    Ok(signed.iter().filter(|s| **s).count())
+}
+
+/// Public keys of signatures in an ed25519 instruction that cover exactly `message`
+fn ed25519_signers(data: &[u8], message: &[u8]) -> Result<Vec<Pubkey>> {
+    require!(data.len() >= SIGNATURE_OFFSETS_START, ZkError::InvalidSignatureInstruction);
+    let count = data[0] as usize;
+    let mut signers = Vec::with_capacity(count);
+
+    for i in 0..count {
+        let start = SIGNATURE_OFFSETS_START + i * SIGNATURE_OFFSETS_SIZE;
+        let offsets = data
+            .get(start..start + SIGNATURE_OFFSETS_SIZE)
+            .ok_or(ZkError::InvalidSignatureInstruction)?;
+        let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+
+        let signature_ix = read_u16(2);
+        let pubkey_offset = read_u16(4) as usize;
+        let pubkey_ix = read_u16(6);
+        let message_offset = read_u16(8) as usize;
+        let message_size = read_u16(10) as usize;
+        let message_ix = read_u16(12);
+
+        // Data referenced from other instructions could be swapped out; require it inline
+        require!(
+            signature_ix == u16::MAX && pubkey_ix == u16::MAX && message_ix == u16::MAX,
+            ZkError::InvalidSignatureInstruction
+        );
+        require!(
+            read_u16(0) as usize + SIGNATURE_SIZE <= data.len(),
+            ZkError::InvalidSignatureInstruction
+        );
+
+        let pubkey = data
+            .get(pubkey_offset..pubkey_offset + PUBKEY_SIZE)
+            .ok_or(ZkError::InvalidSignatureInstruction)?;
+        let signed_message = data
+            .get(message_offset..message_offset + message_size)
+            .ok_or(ZkError::InvalidSignatureInstruction)?;
+
+        if signed_message == message {
+            signers.push(Pubkey::try_from(pubkey).map_err(|_| ZkError::InvalidSignatureInstruction)?);
+        }
+    }
+
+    Ok(signers)
+}
@@ -1,6 +1,25 @@
 // zk_verifier.rs
 use anchor_lang::prelude::*;
-use crate::state::EventPublicInputs;
+use crate::state::{AggregatePublicInputs, EventPublicInputs, EVENT_HASH_POSEIDON};
+use solana_program::poseidon::{self, Endianness, Parameters};
+#[cfg(feature = "production")]
+use solana_program::alt_bn128::prelude::{
+    alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing,
+};
+
+/// BN254 base field modulus (big-endian), used to negate G1 points
+#[cfg(feature = "production")]
+const BN254_FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+/// Number of field elements produced by `ZKVerifier::public_input_scalars`
+pub const EVENT_PUBLIC_INPUT_COUNT: usize = 11;
+/// Number of field elements produced by `ZKVerifier::aggregate_input_scalars`
+pub const AGGREGATE_PUBLIC_INPUT_COUNT: usize = 5;
+/// Most events one aggregated proof may cover (a depth-16 events tree)
+pub const MAX_AGGREGATED_EVENTS: u32 = 1 << 16;
 
 /// ZK Proof structure compatible with Groth16
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -12,13 +31,30 @@ pub struct ZKProof {
 
 pub struct ZKVerifier;
 
+/// 32-byte hashes have their top three bits cleared so they are always below
+/// the scalar field modulus. A proof cannot tell apart byte strings that only
+/// differ in those bits, so accounts keyed by such a hash are seeded with this.
+pub fn hash_scalar(bytes: &[u8; 32]) -> [u8; 32] {
+    let mut scalar = *bytes;
+    scalar[0] &= 0x1f;
+    scalar
+}
+
+/// Integers are big-endian and left-padded
+fn int_scalar(value: u64) -> [u8; 32] {
+    let mut scalar = [0u8; 32];
+    scalar[24..].copy_from_slice(&value.to_be_bytes());
+    scalar
+}
+
 impl ZKVerifier {
     /// Verify a TON event inclusion proof with proper validation
     pub fn verify_ton_event_proof(
         proof: &ZKProof,
         public_inputs: &EventPublicInputs,
         current_ton_root: &[u8; 32],
-        _verification_key: &[u8],
+        verification_key: &[u8],
+        event_hash: u8,
     ) -> Result<()> {
         // Development mode - mock verification
         #[cfg(not(feature = "production"))]
@@ -26,7 +62,10 @@ impl ZKVerifier {
             msg!("⚠️  MOCK ZK VERIFICATION - Performing validation checks");
             
             // Validate public inputs match expected structure
-            Self::validate_public_inputs(public_inputs, current_ton_root)?;
+            Self::validate_public_inputs(public_inputs, current_ton_root, event_hash)?;
+
+            // The key must still be one the real verifier would accept for these inputs
+            Groth16VerifyingKey::parse(verification_key)?.check_input_count(EVENT_PUBLIC_INPUT_COUNT)?;
             
             // Mock proof verification (replace with real Groth16 in production)
             Self::mock_verify_proof(proof, public_inputs)?;
@@ -38,16 +77,51 @@ impl ZKVerifier {
         // Production verification
         #[cfg(feature = "production")]
         {
-            msg!("🚨 REAL ZK VERIFICATION REQUIRED");
-            Self::real_groth16_verification(proof, public_inputs, verification_key)?;
+            Self::validate_public_inputs(public_inputs, current_ton_root, event_hash)?;
+            Self::real_groth16_verification(proof, &Self::public_input_scalars(public_inputs), verification_key)?;
+            msg!("✅ Groth16 verification passed");
+            Ok(())
+        }
+    }
+
+    /// Verify a recursive proof attesting that every event committed to by
+    /// `aggregate.events_root` is valid under `aggregate.anchor_root`
+    pub fn verify_aggregate_proof(
+        proof: &ZKProof,
+        aggregate: &AggregatePublicInputs,
+        current_ton_root: &[u8; 32],
+        verification_key: &[u8],
+    ) -> Result<()> {
+        require!(aggregate.anchor_root == *current_ton_root, ZkError::InvalidAnchorRoot);
+        require!(
+            aggregate.event_count > 0 && aggregate.event_count <= MAX_AGGREGATED_EVENTS,
+            ZkError::InvalidAggregate
+        );
+
+        #[cfg(not(feature = "production"))]
+        {
+            msg!("⚠️  MOCK ZK VERIFICATION - aggregated proof");
+            Groth16VerifyingKey::parse(verification_key)?.check_input_count(AGGREGATE_PUBLIC_INPUT_COUNT)?;
+            let proof_valid = !proof.a.iter().all(|&b| b == 0) &&
+                             !proof.b.iter().all(|&b| b == 0) &&
+                             !proof.c.iter().all(|&b| b == 0);
+            require!(proof_valid, ZkError::BadProof);
+            return Ok(());
+        }
+
+        #[cfg(feature = "production")]
+        {
+            Self::real_groth16_verification(proof, &Self::aggregate_input_scalars(aggregate), verification_key)?;
+            msg!("✅ Aggregated Groth16 verification passed");
             Ok(())
         }
     }
 
     /// Validate public inputs for consistency
-    fn validate_public_inputs(
+    pub fn validate_public_inputs(
         public_inputs: &EventPublicInputs,
         current_ton_root: &[u8; 32],
+        event_hash: u8,
     ) -> Result<()> {
         // Check TON state root matches
         require!(
@@ -66,11 +140,12 @@ impl ZKVerifier {
         let computed_event_id = Self::hash_event_components(
             &public_inputs.token_id,
             public_inputs.amount_in_ton,
-            &public_inputs.recipient_solana,
+            &public_inputs.recipient_binding(),
             public_inputs.fee_bps,
             public_inputs.vk_version,
             &public_inputs.domain,
-        );
+            event_hash,
+        )?;
         
         require!(
             public_inputs.event_id == computed_event_id,
@@ -100,49 +175,194 @@ impl ZKVerifier {
         Ok(())
     }
 
-    /// Real Groth16 verification (placeholder for production)
+    /// Groth16 verification over BN254 using the alt_bn128 syscalls.
+    ///
+    /// Checks `e(-A, B) · e(alpha, beta) · e(vk_x, gamma) · e(C, delta) == 1`
+    /// where `vk_x = IC[0] + Σ x_i · IC[i+1]`. All points use the syscall's
+    /// big-endian encoding.
     #[cfg(feature = "production")]
     fn real_groth16_verification(
-        _proof: &ZKProof,
-        _public_inputs: &EventPublicInputs,
-        __verification_key: &[u8],
+        proof: &ZKProof,
+        scalars: &[[u8; 32]],
+        verification_key: &[u8],
     ) -> Result<()> {
-        // This would integrate with a real BN254 verifier
-        // For example, using solana-zk or similar crate
-        
-        // TODO: Implement actual Groth16 verification
-        // let valid = groth16_verify(
-        //     verification_key,
-        //     public_inputs,
-        //     proof
-        // );
-        
-        // require!(valid, ZkError::BadProof);
-        
-        Err(ZkError::ProductionVerificationNotImplemented.into())
+        let vk = Groth16VerifyingKey::parse(verification_key)?;
+        vk.check_input_count(scalars.len())?;
+
+        // vk_x = IC[0] + sum(x_i * IC[i+1])
+        let mut vk_x = vk.ic[0].to_vec();
+        for (scalar, ic) in scalars.iter().zip(vk.ic[1..].iter()) {
+            let mut mul_input = [0u8; 96];
+            mul_input[..64].copy_from_slice(ic);
+            mul_input[64..].copy_from_slice(scalar);
+            let term = alt_bn128_multiplication(&mul_input)
+                .map_err(|_| error!(ZkError::Bn128OperationFailed))?;
+
+            let mut add_input = [0u8; 128];
+            add_input[..64].copy_from_slice(&vk_x);
+            add_input[64..].copy_from_slice(&term);
+            vk_x = alt_bn128_addition(&add_input)
+                .map_err(|_| error!(ZkError::Bn128OperationFailed))?;
+        }
+
+        let neg_a = Self::negate_g1(&proof.a);
+
+        let mut pairing_input = Vec::with_capacity(4 * 192);
+        pairing_input.extend_from_slice(&neg_a);
+        pairing_input.extend_from_slice(&proof.b);
+        pairing_input.extend_from_slice(vk.alpha_g1);
+        pairing_input.extend_from_slice(vk.beta_g2);
+        pairing_input.extend_from_slice(&vk_x);
+        pairing_input.extend_from_slice(vk.gamma_g2);
+        pairing_input.extend_from_slice(&proof.c);
+        pairing_input.extend_from_slice(vk.delta_g2);
+
+        let result = alt_bn128_pairing(&pairing_input)
+            .map_err(|_| error!(ZkError::Bn128OperationFailed))?;
+
+        let mut expected = [0u8; 32];
+        expected[31] = 1;
+        require!(result.as_slice() == expected, ZkError::BadProof);
+
+        Ok(())
+    }
+
+    /// Negate a G1 point: (x, y) -> (x, p - y). The point at infinity is its own negation.
+    #[cfg(feature = "production")]
+    fn negate_g1(point: &[u8; 64]) -> [u8; 64] {
+        let mut negated = *point;
+        if point.iter().all(|&b| b == 0) {
+            return negated;
+        }
+
+        let mut borrow = 0i16;
+        for i in (0..32).rev() {
+            let diff = BN254_FIELD_MODULUS[i] as i16 - point[32 + i] as i16 - borrow;
+            if diff < 0 {
+                negated[32 + i] = (diff + 256) as u8;
+                borrow = 1;
+            } else {
+                negated[32 + i] = diff as u8;
+                borrow = 0;
+            }
+        }
+        negated
+    }
+
+    /// Canonical field-element encoding of the public inputs, in circuit order.
+    /// 32-byte hashes have their top three bits cleared so they are always
+    /// below the scalar field modulus; integers are big-endian and left-padded.
+    pub fn public_input_scalars(public_inputs: &EventPublicInputs) -> [[u8; 32]; EVENT_PUBLIC_INPUT_COUNT] {
+        [
+            hash_scalar(&public_inputs.domain),
+            hash_scalar(&public_inputs.anchor_root),
+            hash_scalar(&public_inputs.event_id),
+            hash_scalar(&public_inputs.token_id),
+            int_scalar(public_inputs.amount_in_ton),
+            hash_scalar(&public_inputs.recipient_binding()),
+            int_scalar(public_inputs.fee_bps as u64),
+            int_scalar(public_inputs.vk_version as u64),
+            hash_scalar(&public_inputs.ton_tx_hash),
+            hash_scalar(&public_inputs.ton_sender),
+            hash_scalar(&public_inputs.nullifier),
+        ]
+    }
+
+    /// Field-element encoding of an aggregated proof's public inputs, in circuit order
+    pub fn aggregate_input_scalars(aggregate: &AggregatePublicInputs) -> [[u8; 32]; AGGREGATE_PUBLIC_INPUT_COUNT] {
+        [
+            hash_scalar(&aggregate.domain),
+            hash_scalar(&aggregate.anchor_root),
+            hash_scalar(&aggregate.events_root),
+            int_scalar(aggregate.event_count as u64),
+            int_scalar(aggregate.vk_version as u64),
+        ]
+    }
+
+    /// Leaf an event contributes to an aggregated proof's events tree
+    pub fn event_commitment(public_inputs: &EventPublicInputs) -> [u8; 32] {
+        solana_program::hash::hashv(&[
+            b"TON_EVENT_COMMITMENT",
+            &public_inputs.event_id,
+            &public_inputs.nullifier,
+            &public_inputs.ton_tx_hash,
+            &public_inputs.ton_sender,
+        ])
+        .to_bytes()
     }
 
     /// Hash event components to reconstruct event_id (must match circuit)
     pub fn hash_event_components(
         token_id: &[u8; 32],
         amount_in_ton: u64,
-        recipient_solana: &Pubkey,
+        recipient_binding: &[u8; 32],
         fee_bps: u16,
         vk_version: u32,
         domain: &[u8; 32],
-    ) -> [u8; 32] {
+        event_hash: u8,
+    ) -> Result<[u8; 32]> {
+        if event_hash == EVENT_HASH_POSEIDON {
+            // Same field encoding as the public inputs, so circuits can recompute it
+            let hash = poseidon::hashv(
+                Parameters::Bn254X5,
+                Endianness::BigEndian,
+                &[
+                    &hash_scalar(token_id),
+                    &int_scalar(amount_in_ton),
+                    &hash_scalar(recipient_binding),
+                    &int_scalar(fee_bps as u64),
+                    &int_scalar(vk_version as u64),
+                    &hash_scalar(domain),
+                ],
+            )
+            .map_err(|_| error!(ZkError::PoseidonFailed))?;
+            return Ok(hash.to_bytes());
+        }
+
         // Use the standard hash function directly
         let mut preimage = Vec::new();
         preimage.extend_from_slice(b"TON_EVENT");
         preimage.extend_from_slice(token_id);
         preimage.extend_from_slice(&amount_in_ton.to_le_bytes());
-        preimage.extend_from_slice(recipient_solana.as_ref());
+        preimage.extend_from_slice(recipient_binding);
         preimage.extend_from_slice(&fee_bps.to_le_bytes());
         preimage.extend_from_slice(&vk_version.to_le_bytes());
         preimage.extend_from_slice(domain);
         
         let hash = solana_program::hash::hashv(&[&preimage]);
-        hash.to_bytes()
+        Ok(hash.to_bytes())
+    }
+
+    /// Check `ton_tx_hash` is a leaf of the tree committed to by `anchor_root`
+    pub fn verify_tx_inclusion(
+        public_inputs: &EventPublicInputs,
+        inclusion: &crate::merkle::TxInclusionProof,
+    ) -> Result<()> {
+        require!(
+            crate::merkle::verify_inclusion(
+                &public_inputs.ton_tx_hash,
+                inclusion,
+                &public_inputs.anchor_root,
+            ),
+            ZkError::InvalidInclusionProof
+        );
+        Ok(())
+    }
+
+    /// Commitment to a recipient used by privacy-mode deposits
+    pub fn recipient_commitment(recipient: &Pubkey, salt: &[u8; 32]) -> [u8; 32] {
+        solana_program::hash::hashv(&[b"RECIPIENT", recipient.as_ref(), salt]).to_bytes()
+    }
+
+    /// Check the revealed recipient and salt open the event's recipient commitment
+    pub fn check_recipient_reveal(public_inputs: &EventPublicInputs, salt: &[u8; 32]) -> Result<()> {
+        require!(public_inputs.is_private(), ZkError::RecipientCommitmentMismatch);
+        require!(
+            Self::recipient_commitment(&public_inputs.recipient_solana, salt)
+                == public_inputs.recipient_commitment,
+            ZkError::RecipientCommitmentMismatch
+        );
+        Ok(())
     }
 
     /// Generate nullifier from TON tx hash and sender (prevents double spending)
@@ -181,4 +401,167 @@ pub enum ZkError {
     ProductionVerificationNotImplemented,
     #[msg("unauthorized relayer")]
     UnauthorizedRelayer,
+    #[msg("verifying key is malformed")]
+    InvalidVerifyingKey,
+    #[msg("public input count does not match verifying key")]
+    PublicInputCountMismatch,
+    #[msg("alt_bn128 operation failed")]
+    Bn128OperationFailed,
+    #[msg("signer is not the admin")]
+    UnauthorizedAdmin,
+    #[msg("verifying key is finalized and cannot be modified")]
+    VerifyingKeyFinalized,
+    #[msg("verifying key has not been finalized")]
+    VerifyingKeyNotFinalized,
+    #[msg("verifying key chunk is out of order or exceeds the declared length")]
+    InvalidVkChunk,
+    #[msg("verifying key upload is incomplete")]
+    VerifyingKeyIncomplete,
+    #[msg("vk_version is not current or within its deprecation window")]
+    VkVersionNotAllowed,
+    #[msg("deprecation window must not be negative")]
+    InvalidDeprecationWindow,
+    #[msg("batch is empty or exceeds the maximum size")]
+    InvalidBatchSize,
+    #[msg("batch accounts do not match the expected event/nullifier PDAs")]
+    InvalidBatchAccounts,
+    #[msg("privacy-mode event requires a recipient reveal")]
+    RecipientRevealRequired,
+    #[msg("revealed recipient does not match the commitment")]
+    RecipientCommitmentMismatch,
+    #[msg("TON transaction is not included under the anchor root")]
+    InvalidInclusionProof,
+    #[msg("committee must have 1..=16 unique members and a threshold within range")]
+    InvalidCommittee,
+    #[msg("not enough committee signatures over the update")]
+    InsufficientCommitteeSignatures,
+    #[msg("malformed ed25519 signature instruction")]
+    InvalidSignatureInstruction,
+    #[msg("recipient account does not match the proven recipient")]
+    InvalidRecipient,
+    #[msg("instruction does not match the configured nullifier mode")]
+    NullifierModeMismatch,
+    #[msg("unknown nullifier mode")]
+    InvalidNullifierMode,
+    #[msg("signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("relayer set is full")]
+    RelayerSetFull,
+    #[msg("relayer already in the set")]
+    RelayerAlreadyExists,
+    #[msg("relayer not in the set")]
+    RelayerNotFound,
+    #[msg("approval threshold must be between 1 and the relayer set size")]
+    InvalidApprovalThreshold,
+    #[msg("root updates require threshold approval via approve_ton_root")]
+    RootApprovalRequired,
+    #[msg("relayer already approved this root")]
+    RootAlreadyApproved,
+    #[msg("root already committed")]
+    RootAlreadyCommitted,
+    #[msg("bridge is paused")]
+    BridgePaused,
+    #[msg("change must go through propose_action / execute_action")]
+    TimelockRequired,
+    #[msg("governance delay out of range")]
+    InvalidGovernanceDelay,
+    #[msg("governance action is not executable yet")]
+    TimelockNotElapsed,
+    #[msg("governance action already executed or cancelled")]
+    ActionNotPending,
+    #[msg("account required by this governance action is missing")]
+    MissingActionAccount,
+    #[msg("event account is too recent to close")]
+    EventTooRecent,
+    #[msg("only the event payer or admin can close it")]
+    UnauthorizedClose,
+    #[msg("event account has not been consumed")]
+    EventNotConsumed,
+    #[msg("event close age must not be negative")]
+    InvalidCloseAge,
+    #[msg("state account is not an LcState")]
+    InvalidStateAccount,
+    #[msg("state account is already at the current version")]
+    StateAlreadyMigrated,
+    #[msg("token config does not match the event's token id")]
+    TokenConfigMismatch,
+    #[msg("bridging is disabled for this token")]
+    TokenDisabled,
+    #[msg("mint does not match the token config")]
+    TokenMintMismatch,
+    #[msg("amount outside the token's configured bounds")]
+    AmountOutOfBounds,
+    #[msg("min_amount exceeds max_amount")]
+    InvalidTokenBounds,
+    #[msg("24-hour bridged volume cap exceeded")]
+    VolumeCapExceeded,
+    #[msg("Token is paused")]
+    TokenPaused,
+    #[msg("Withdrawal is not pending")]
+    WithdrawalNotPending,
+    #[msg("Withdrawal refund timeout has not elapsed")]
+    WithdrawalNotRefundable,
+    #[msg("Withdrawal finalization deadline has passed")]
+    WithdrawalExpired,
+    #[msg("Anchor root is not a verified header in the header chain")]
+    AnchorNotInHeaderChain,
+    #[msg("Header does not link to the latest verified header")]
+    HeaderLinkMismatch,
+    #[msg("Invalid aggregated proof inputs")]
+    InvalidAggregate,
+    #[msg("Event is not included under the aggregated events root")]
+    NotInAggregate,
+    #[msg("Every event of the aggregated proof has been claimed")]
+    AggregateFullyClaimed,
+    #[msg("Verifying key exceeds the account's fixed capacity")]
+    VerifyingKeyTooLarge,
+    #[msg("Verifying key account does not hold the key named in the public inputs")]
+    VerifyingKeyMismatch,
+    #[msg("Poseidon hash syscall failed")]
+    PoseidonFailed,
+    #[msg("Bitmap nullifier mode cannot be switched back to PDAs")]
+    NullifierModeIrreversible,
+    #[msg("Deposit tree root has not been set")]
+    DepositTreeRootUnset,
+}
+
+/// Borrowed view over a serialized Groth16 verifying key:
+/// `alpha_g1 (64) | beta_g2 (128) | gamma_g2 (128) | delta_g2 (128) | ic_len (u32 LE) | ic (64 each)`.
+/// Mock verification only checks its shape, so the points go unread there.
+#[cfg_attr(not(feature = "production"), allow(dead_code))]
+struct Groth16VerifyingKey<'a> {
+    alpha_g1: &'a [u8],
+    beta_g2: &'a [u8],
+    gamma_g2: &'a [u8],
+    delta_g2: &'a [u8],
+    ic: Vec<&'a [u8]>,
+}
+
+impl<'a> Groth16VerifyingKey<'a> {
+    const HEADER_LEN: usize = 64 + 128 * 3 + 4;
+
+    fn parse(data: &'a [u8]) -> Result<Self> {
+        require!(data.len() >= Self::HEADER_LEN, ZkError::InvalidVerifyingKey);
+
+        let ic_len = u32::from_le_bytes(data[448..452].try_into().unwrap()) as usize;
+        require!(ic_len > 0, ZkError::InvalidVerifyingKey);
+        require!(
+            data.len() == Self::HEADER_LEN + ic_len * 64,
+            ZkError::InvalidVerifyingKey
+        );
+
+        Ok(Self {
+            alpha_g1: &data[0..64],
+            beta_g2: &data[64..192],
+            gamma_g2: &data[192..320],
+            delta_g2: &data[320..448],
+            ic: data[Self::HEADER_LEN..].chunks_exact(64).collect(),
+        })
+    }
+
+    /// The key has one IC point per public input, plus the constant term
+    fn check_input_count(&self, input_count: usize) -> Result<()> {
+        require!(self.ic.len() == input_count + 1, ZkError::PublicInputCountMismatch);
+        Ok(())
+    }
 }
\ No newline at end of file
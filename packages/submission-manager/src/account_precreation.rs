@@ -0,0 +1,38 @@
+use crate::solana_client::{SolanaClient, EVENT_ACCOUNT_SPACE, NULLIFIER_ACCOUNT_SPACE};
+use crate::types::Batch;
+use solana_sdk::pubkey::Pubkey;
+
+/// Event and nullifier PDAs the verification transaction for `batch` will
+/// create, for deposits whose ids are known
+pub fn batch_targets(client: &SolanaClient, batch: &Batch) -> Vec<(Pubkey, usize)> {
+    let mut targets = Vec::with_capacity(batch.deposits.len() * 2);
+    for deposit in &batch.deposits {
+        if let Some(event_id) = deposit.event_id.as_deref().and_then(decode_32) {
+            targets.push((client.event_pda(&event_id), EVENT_ACCOUNT_SPACE));
+        }
+        if let Some(nullifier) = deposit.nullifier.as_deref().and_then(decode_32) {
+            targets.push((client.nullifier_pda(&nullifier), NULLIFIER_ACCOUNT_SPACE));
+        }
+    }
+    targets
+}
+
+/// Pre-fund the batch's PDAs to rent exemption in separate transactions, so
+/// the verification transaction only allocates them. Returns the number of
+/// funding transactions sent.
+pub async fn precreate_batch_accounts(client: &SolanaClient, batch: &Batch) -> crate::Result<usize> {
+    let targets = batch_targets(client, batch);
+    if targets.is_empty() {
+        return Ok(0);
+    }
+
+    let signatures = client.prefund_accounts(&targets).await?;
+    for signature in &signatures {
+        log::info!("🏗️ Pre-funded batch accounts: {}", signature);
+    }
+    Ok(signatures.len())
+}
+
+fn decode_32(value: &str) -> Option<[u8; 32]> {
+    hex::decode(value.trim_start_matches("0x")).ok()?.try_into().ok()
+}
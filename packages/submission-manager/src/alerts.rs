@@ -0,0 +1,85 @@
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+
+pub const ALERT_BREAKER_OPEN: &str = "breaker_open";
+pub const ALERT_DEAD_LETTER: &str = "dead_letter_non_empty";
+pub const ALERT_INVARIANT_VIOLATED: &str = "invariant_violated";
+pub const ALERT_FEE_PAYER_LOW: &str = "fee_payer_low";
+pub const ALERT_ROOT_STALE: &str = "root_stale";
+pub const ALERT_SLA_BREACH: &str = "sla_breach";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    Warning,
+    Critical,
+}
+
+/// One internal alert condition that currently holds
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    pub name: &'static str,
+    pub severity: AlertSeverity,
+    pub message: String,
+    pub since: Option<i64>, // Unix timestamp the condition started, when known
+}
+
+/// A condition that could not be evaluated, e.g. because the RPC was down
+#[derive(Debug, Clone, Serialize)]
+pub struct UncheckedAlert {
+    pub name: &'static str,
+    pub error: String,
+}
+
+/// Answer of `GET /api/alerts`: an empty `firing` list means all clear
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertReport {
+    pub firing: Vec<Alert>,
+    pub unchecked: Vec<UncheckedAlert>,
+    pub checked_at: i64,
+}
+
+impl AlertReport {
+    pub fn new() -> Self {
+        Self {
+            firing: Vec::new(),
+            unchecked: Vec::new(),
+            checked_at: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    pub fn fire(&mut self, name: &'static str, severity: AlertSeverity, message: String, since: Option<i64>) {
+        self.firing.push(Alert { name, severity, message, since });
+    }
+
+    pub fn unchecked(&mut self, name: &'static str, error: impl ToString) {
+        self.unchecked.push(UncheckedAlert { name, error: error.to_string() });
+    }
+}
+
+impl Default for AlertReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Remembers when the on-chain TON root last changed, so staleness can be
+/// judged from periodic reads of the root alone
+#[derive(Clone, Default)]
+pub struct RootWatch {
+    last: Arc<Mutex<Option<([u8; 32], i64)>>>,
+}
+
+impl RootWatch {
+    /// Record a read of the root at `now`; returns when it last changed
+    pub fn observe(&self, root: [u8; 32], now: i64) -> i64 {
+        let mut last = self.last.lock().unwrap();
+        match *last {
+            Some((seen, changed_at)) if seen == root => changed_at,
+            _ => {
+                *last = Some((root, now));
+                now
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use crate::database::ReceiptRecord;
+use crate::proof::Proof;
+use crate::types::Deposit;
+use crate::{OrchestratorError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Inputs the proof was generated from, kept so an auditor can rebuild the witness
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WitnessRefs {
+    pub ton_tx_hash: String,
+    pub event_id: Option<String>,
+    pub nullifier: Option<String>,
+    pub recipient_commitment: Option<String>,
+}
+
+/// Everything needed to audit one finalized deposit after it leaves the hot DB
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedDeposit {
+    pub deposit: Deposit,
+    pub proof: Option<Proof>,
+    pub witness: WitnessRefs,
+    pub receipt: ReceiptRecord,
+    pub archived_at: i64,
+}
+
+impl ArchivedDeposit {
+    pub fn new(deposit: Deposit, proof: Option<Proof>, receipt: ReceiptRecord) -> Self {
+        let witness = WitnessRefs {
+            ton_tx_hash: deposit.ton_tx_hash.clone(),
+            event_id: deposit.event_id.clone(),
+            nullifier: deposit.nullifier.clone(),
+            recipient_commitment: deposit.recipient_commitment.clone(),
+        };
+        Self {
+            deposit,
+            proof,
+            witness,
+            receipt,
+            archived_at: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+/// Where an archived deposit can be fetched from
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ArchiveLocation {
+    pub deposit_id: String,
+    pub content_hash: String,     // Hex SHA-256 of the archived JSON; also its file name
+    pub ipfs_cid: Option<String>, // Set when the IPFS backend is configured
+    pub archived_at: i64,
+}
+
+/// Content-addressed cold storage for finalized deposits. Records are written
+/// to `{dir}/{sha256}.json` and, when an IPFS API is configured, also added
+/// (and pinned) there.
+#[derive(Clone)]
+pub struct ProofArchive {
+    dir: PathBuf,
+    ipfs_api_url: Option<String>,
+    client: reqwest::Client,
+}
+
+impl ProofArchive {
+    /// `None` when archival is disabled (empty `dir`)
+    pub fn new(dir: &str, ipfs_api_url: &str) -> Option<Self> {
+        if dir.is_empty() {
+            return None;
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        Some(Self {
+            dir: PathBuf::from(dir),
+            ipfs_api_url: (!ipfs_api_url.is_empty()).then(|| ipfs_api_url.trim_end_matches('/').to_string()),
+            client,
+        })
+    }
+
+    pub async fn store(&self, record: &ArchivedDeposit) -> Result<ArchiveLocation> {
+        let bytes = serde_json::to_vec(record)?;
+        let content_hash = hex::encode(solana_sdk::hash::hash(&bytes).to_bytes());
+
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| OrchestratorError::ArchiveError(format!("{}: {}", self.dir.display(), e)))?;
+        let path = self.path(&content_hash);
+        tokio::fs::write(&path, &bytes)
+            .await
+            .map_err(|e| OrchestratorError::ArchiveError(format!("{}: {}", path.display(), e)))?;
+
+        let ipfs_cid = match &self.ipfs_api_url {
+            Some(api_url) => Some(self.add_to_ipfs(api_url, &content_hash, bytes).await?),
+            None => None,
+        };
+
+        Ok(ArchiveLocation {
+            deposit_id: record.deposit.deposit_id.clone(),
+            content_hash,
+            ipfs_cid,
+            archived_at: record.archived_at,
+        })
+    }
+
+    /// Read an archived record back, rejecting it if its content no longer matches the hash
+    pub async fn load(&self, content_hash: &str) -> Result<ArchivedDeposit> {
+        let path = self.path(content_hash);
+        let bytes = tokio::fs::read(&path)
+            .await
+            .map_err(|e| OrchestratorError::ArchiveError(format!("{}: {}", path.display(), e)))?;
+
+        if hex::encode(solana_sdk::hash::hash(&bytes).to_bytes()) != content_hash {
+            return Err(OrchestratorError::ArchiveError(format!(
+                "{} does not match its content hash",
+                path.display()
+            )));
+        }
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    fn path(&self, content_hash: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", content_hash))
+    }
+
+    /// `POST /api/v0/add` of the Kubo HTTP API; returns the CID
+    async fn add_to_ipfs(&self, api_url: &str, name: &str, bytes: Vec<u8>) -> Result<String> {
+        // Hand-built multipart body: reqwest is compiled without its multipart feature
+        let boundary = format!("zkbridge-{}", name);
+        let mut body = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}.json\"\r\nContent-Type: application/json\r\n\r\n",
+            boundary, name
+        )
+        .into_bytes();
+        body.extend_from_slice(&bytes);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+        let response: serde_json::Value = self.client
+            .post(&format!("{}/api/v0/add", api_url))
+            .query(&[("pin", "true"), ("cid-version", "1")])
+            .header("Content-Type", format!("multipart/form-data; boundary={}", boundary))
+            .body(body)
+            .send()
+            .await
+            .map_err(OrchestratorError::NetworkError)?
+            .error_for_status()
+            .map_err(OrchestratorError::NetworkError)?
+            .json()
+            .await
+            .map_err(OrchestratorError::NetworkError)?;
+
+        response["Hash"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| OrchestratorError::ArchiveError(format!("IPFS add returned no hash: {}", response)))
+    }
+}
@@ -0,0 +1,43 @@
+use serde::Serialize;
+use std::sync::{Arc, RwLock};
+
+pub const CODE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Key figures reported to external watchtowers
+#[derive(Debug, Clone, Serialize)]
+pub struct AttestationSnapshot {
+    pub last_batch_signature: Option<String>,
+    pub queue_depth: usize,
+    pub last_ton_root: Option<String>,
+    pub code_version: &'static str,
+    pub timestamp: i64,
+}
+
+/// Snapshot plus an ed25519 signature by the manager's Solana key over the
+/// exact JSON bytes of `snapshot`
+#[derive(Debug, Clone, Serialize)]
+pub struct SignedAttestation {
+    pub snapshot: AttestationSnapshot,
+    pub signer: String,
+    pub signature: String,
+}
+
+/// Figures recorded by the processing loop; shared across manager clones
+#[derive(Debug, Clone, Default)]
+pub struct AttestationTracker {
+    last_batch_signature: Arc<RwLock<Option<String>>>,
+}
+
+impl AttestationTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_batch_signature(&self, signature: &str) {
+        *self.last_batch_signature.write().unwrap() = Some(signature.to_string());
+    }
+
+    pub fn last_batch_signature(&self) -> Option<String> {
+        self.last_batch_signature.read().unwrap().clone()
+    }
+}
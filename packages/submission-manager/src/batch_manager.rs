@@ -1,57 +1,233 @@
-use crate::{Deposit, Result};
+use crate::{Deposit, OrchestratorError, Result};
 use crate::types::Batch;
+use crate::gas_optimizer::COMPUTE_UNITS_PER_DEPOSIT;
+use crate::program_instructions::{TxInclusionProof, ZKProof, BATCH_ACCOUNTS_PER_EVENT};
+use crate::proof::Proof;
+use crate::sealing_policy::SealingPolicy;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
 use std::time::Duration;
-use chrono::Utc;
+use uuid::Uuid;
+
+/// `verify_ton_event_batch` v0 transaction bytes before its first event:
+/// signature, version and header, the payer, program and compute budget
+/// program as static keys, blockhash, the compute budget instructions, the
+/// instruction's 12 fixed account indexes and data prefix, and the lookup
+/// table section loading the 11 fixed accounts besides the payer
+const BATCH_TRANSACTION_OVERHEAD_BYTES: usize =
+    (1 + 64) + (1 + 3) + (1 + 3 * 32) + 32 + (1 + 8 + 12) + (1 + 1 + 12 + 2 + 8 + 4) + (1 + 32 + 2 + 11);
+
+/// `EventPublicInputs` as Borsh lays them out in the instruction data
+const EVENT_PUBLIC_INPUTS_BYTES: usize = 302;
+
+/// Bytes one event adds to a `verify_ton_event_batch` v0 transaction: its
+/// event, nullifier, recipient and token account, which the lookup table
+/// cannot hold, as static keys and account indexes, and its proof, public
+/// inputs, inclusion path and recipient salt as instruction data
+pub fn estimated_deposit_bytes(proof: &Proof) -> usize {
+    let proof_bytes = borsh::to_vec(&ZKProof::from(proof)).map_or(256, |bytes| bytes.len());
+    let inclusion_bytes = borsh::to_vec(&TxInclusionProof::from(&proof.inclusion)).map_or(12, |bytes| bytes.len());
+    BATCH_ACCOUNTS_PER_EVENT * (32 + 1) + proof_bytes + EVENT_PUBLIC_INPUTS_BYTES + inclusion_bytes + 32
+}
+
+/// Estimated size of the `verify_ton_event_batch` transaction carrying `proofs`
+pub fn estimated_batch_bytes(proofs: &[Proof]) -> usize {
+    BATCH_TRANSACTION_OVERHEAD_BYTES + proofs.iter().map(estimated_deposit_bytes).sum::<usize>()
+}
+
+/// Bounds for sizing batches by the proving backlog instead of a fixed size
+#[derive(Debug, Clone)]
+pub struct BatchSizing {
+    pub min_size: usize,
+    pub max_size: usize,
+    pub max_bytes: usize, // Of the one transaction the batch is submitted in
+    pub max_compute_units: u64,
+}
+
+impl BatchSizing {
+    /// Deposits to collect before sealing: one per deposit waiting to be
+    /// proven, so a deep backlog fills batches and a shallow one seals small
+    /// batches promptly. Every deposit write-locks the program state, so a
+    /// batch never carries more compute than one block allows on it.
+    pub fn target_size(&self, queue_depth: usize) -> usize {
+        let by_compute = (self.max_compute_units / COMPUTE_UNITS_PER_DEPOSIT).max(1) as usize;
+        queue_depth.clamp(self.min_size, self.max_size).min(by_compute)
+    }
+
+    /// Whether `batch` reached its target, or a next deposit as large as its
+    /// last would take its transaction past `max_bytes`
+    pub fn is_full(&self, batch: &Batch, queue_depth: usize) -> bool {
+        let next = batch.proofs.last().map_or(0, estimated_deposit_bytes);
+        batch.deposits.len() >= self.target_size(queue_depth)
+            || estimated_batch_bytes(&batch.proofs) + next > self.max_bytes
+    }
+}
+
+/// Deposits sharing a key go through the same on-chain route with the same
+/// token, so they can share a batch: native TON or the jetton master, and
+/// whether the recipient is revealed or committed to (privacy mode). Priority
+/// deposits never share a batch with bulk traffic.
+pub fn batch_key(deposit: &Deposit) -> String {
+    let lane = if deposit.priority { "priority" } else { "bulk" };
+    let route = if deposit.recipient_commitment.is_some() { "private" } else { "public" };
+    format!("{}:{}:{}", lane, route, deposit.jetton_master.as_deref().unwrap_or("native"))
+}
+
+/// Sealing limits of the priority lane, which replace the sealing policy and
+/// sizing for batches of priority deposits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriorityLane {
+    pub max_size: usize,
+    pub max_age: Duration,
+}
+
+impl PriorityLane {
+    fn is_due(&self, batch: &Batch, now: DateTime<Utc>) -> bool {
+        let age = (now - batch.created_at).to_std().unwrap_or_default();
+        batch.deposits.len() >= self.max_size || age >= self.max_age
+    }
+}
+
+struct OpenBatch {
+    id: String, // Key of the batch in the `batches` table
+    batch: Batch,
+}
 
 pub struct BatchManager {
-    batch_size: usize,
-    current_batch: Option<Batch>,
+    policy: Arc<dyn SealingPolicy>,
+    open: BTreeMap<String, OpenBatch>, // By `batch_key`
+    sizing: Option<BatchSizing>,
+    priority_lane: Option<PriorityLane>, // None batches priority deposits like bulk ones
+    queue_depth: usize, // Deposits waiting to be proven, as last reported
 }
 
 impl BatchManager {
-    pub fn new(batch_size: usize) -> Self {
+    pub fn new(policy: Arc<dyn SealingPolicy>) -> Self {
         Self {
-            batch_size,
-            current_batch: None,
+            policy,
+            open: BTreeMap::new(),
+            sizing: None,
+            priority_lane: None,
+            queue_depth: 0,
         }
     }
 
-    pub async fn add_to_batch(&mut self, deposit: Deposit, proof: String) -> Result<Option<Batch>> {
-        if self.current_batch.is_none() {
-            self.current_batch = Some(Batch {
+    /// Seal batches of priority deposits under `lane`'s limits
+    pub fn with_priority_lane(mut self, lane: PriorityLane) -> Self {
+        self.priority_lane = Some(lane);
+        self
+    }
+
+    /// Seal batches at a size driven by the proving backlog, within `sizing`
+    pub fn with_sizing(mut self, sizing: BatchSizing) -> Self {
+        self.sizing = Some(sizing);
+        self
+    }
+
+    /// Record how many deposits wait to be proven; returns the batch size now targeted
+    pub fn set_queue_depth(&mut self, queue_depth: usize) -> Option<usize> {
+        self.queue_depth = queue_depth;
+        self.sizing.as_ref().map(|sizing| sizing.target_size(queue_depth))
+    }
+
+    fn should_seal(&self, batch: &Batch) -> bool {
+        let priority = batch.deposits.first().is_some_and(|deposit| deposit.priority);
+        if let (true, Some(lane)) = (priority, &self.priority_lane) {
+            return lane.is_due(batch, Utc::now());
+        }
+        self.policy.should_seal(batch, Utc::now())
+            || self.sizing.as_ref().is_some_and(|sizing| sizing.is_full(batch, self.queue_depth))
+    }
+
+    /// Id and creation time (Unix millis) of the open batch for `key`, and the
+    /// position the next deposit takes in it; opens a batch if there is none
+    pub fn open_batch(&mut self, key: &str) -> (String, i64, usize) {
+        let open = self.open.entry(key.to_string()).or_insert_with(|| {
+            let batch = Batch {
+                id: Uuid::new_v4(),
                 deposits: Vec::new(),
                 proofs: Vec::new(),
                 created_at: Utc::now(),
                 retry_count: 0, // Initialize retry_count
-            });
+            };
+            OpenBatch { id: batch.id.to_string(), batch }
+        });
+        (open.id.clone(), open.batch.created_at.timestamp_millis(), open.batch.deposits.len())
+    }
+
+    /// Resume filling a batch persisted by a previous run
+    pub fn restore(&mut self, batch_id: String, batch: Batch) {
+        let Some(first) = batch.deposits.first() else {
+            return;
+        };
+        self.open.insert(batch_key(first), OpenBatch { id: batch_id, batch });
+    }
+
+    /// Refuse a deposit an open batch already holds, by id or by TON
+    /// transaction and nonce, so it cannot be submitted twice
+    pub fn check_not_batched(&self, deposit: &Deposit) -> Result<()> {
+        let existing = self.open.values().flat_map(|open| &open.batch.deposits).find(|batched| {
+            batched.deposit_id == deposit.deposit_id
+                || (batched.ton_tx_hash == deposit.ton_tx_hash && batched.nonce == deposit.nonce)
+        });
+        match existing {
+            Some(batched) => Err(OrchestratorError::DuplicateDeposit {
+                deposit_id: deposit.deposit_id.clone(),
+                existing_id: batched.deposit_id.clone(),
+            }),
+            None => Ok(()),
         }
+    }
 
-        if let Some(batch) = &mut self.current_batch {
-            batch.deposits.push(deposit);
-            batch.proofs.push(proof);
+    /// Add to the open batch of the deposit's key; returns that batch if it is now sealed
+    pub async fn add_to_batch(&mut self, deposit: Deposit, proof: Proof) -> Result<Option<Batch>> {
+        self.check_not_batched(&deposit)?;
+        let key = batch_key(&deposit);
+        self.open_batch(&key);
 
-            if batch.deposits.len() >= self.batch_size {
-                let completed_batch = self.current_batch.take();
-                return Ok(completed_batch);
-            }
+        if let Some(open) = self.open.get_mut(&key) {
+            open.batch.deposits.push(deposit);
+            open.batch.proofs.push(proof);
+        }
+        if self.open.get(&key).is_some_and(|open| self.should_seal(&open.batch)) {
+            return Ok(self.open.remove(&key).map(|open| open.batch));
         }
 
         Ok(None)
     }
 
-    pub async fn finalize_batch(&mut self) -> Result<Option<Batch>> {
-        Ok(self.current_batch.take())
+    /// Seal every open batch
+    pub async fn finalize_batches(&mut self) -> Result<Vec<Batch>> {
+        Ok(std::mem::take(&mut self.open).into_values().map(|open| open.batch).collect())
     }
 
-    // ADD THIS METHOD
-    pub async fn finalize_if_stale(&mut self, max_age: Duration) -> Result<Option<Batch>> {
-        if let Some(batch) = &self.current_batch {
-            let batch_age = Utc::now() - batch.created_at;
-            if batch_age > chrono::Duration::from_std(max_age).unwrap() {
-                log::info!("Batch is stale (age: {}s), finalizing", batch_age.num_seconds());
-                return Ok(self.current_batch.take());
+    /// Seal the open batches the policy says are due (e.g. they aged out)
+    pub async fn finalize_if_due(&mut self) -> Result<Vec<Batch>> {
+        let due: Vec<String> = self
+            .open
+            .iter()
+            .filter(|(_, open)| self.should_seal(&open.batch))
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        let mut sealed = Vec::with_capacity(due.len());
+        for key in due {
+            if let Some(open) = self.open.remove(&key) {
+                let batch_age = Utc::now() - open.batch.created_at;
+                log::info!(
+                    "Batch {} due under '{}' policy (age: {}s), finalizing",
+                    key,
+                    self.policy.name(),
+                    batch_age.num_seconds()
+                );
+                sealed.push(open.batch);
             }
         }
-        Ok(None)
+        Ok(sealed)
     }
-}
\ No newline at end of file
+
+    pub fn policy(&self) -> Arc<dyn SealingPolicy> {
+        self.policy.clone()
+    }
+}
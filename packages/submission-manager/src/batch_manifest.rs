@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::hash::hashv;
+use crate::program_instructions::EventPublicInputs;
+use crate::types::Batch;
+use crate::Result;
+
+// Domain separation of `merkle.rs`, so the program can check paths under the root
+pub(crate) const LEAF_PREFIX: &[u8] = &[0x00];
+pub(crate) const NODE_PREFIX: &[u8] = &[0x01];
+
+/// `ZKVerifier::event_commitment`: the leaf an event contributes to an events tree
+pub fn event_commitment(public_inputs: &EventPublicInputs) -> [u8; 32] {
+    hashv(&[
+        b"TON_EVENT_COMMITMENT",
+        &public_inputs.event_id,
+        &public_inputs.nullifier,
+        &public_inputs.ton_tx_hash,
+        &public_inputs.ton_sender,
+    ])
+    .to_bytes()
+}
+
+/// Root over `leaves` hashed as `merkle::hash_leaf`. A level with an odd node
+/// count pairs its last node with itself, so every leaf has a sibling at each
+/// level and its path checks with `merkle::verify_leaf`.
+pub fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    let mut level: Vec<[u8; 32]> = leaves.iter().map(|leaf| hashv(&[LEAF_PREFIX, leaf]).to_bytes()).collect();
+    if level.is_empty() {
+        return [0u8; 32];
+    }
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let right = pair.get(1).unwrap_or(&pair[0]);
+                hashv(&[NODE_PREFIX, &pair[0], right]).to_bytes()
+            })
+            .collect();
+    }
+    level[0]
+}
+
+/// What a sealed batch commits to: its deposits in order and the events tree
+/// root over their commitments
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BatchManifest {
+    pub batch_id: String,
+    pub content_id: String,
+    pub merkle_root: String,
+    pub deposit_ids: Vec<String>,
+    pub event_commitments: Vec<String>,
+    pub created_at: i64,
+}
+
+impl BatchManifest {
+    pub fn for_batch(batch: &Batch) -> Result<Self> {
+        let mut leaves = Vec::with_capacity(batch.deposits.len());
+        for (deposit, proof) in batch.deposits.iter().zip(&batch.proofs) {
+            // The commitment does not cover the anchor root, which may still change on re-proving
+            leaves.push(event_commitment(&EventPublicInputs::for_deposit(deposit, proof, [0u8; 32])?));
+        }
+
+        Ok(Self {
+            batch_id: batch.id.to_string(),
+            content_id: batch.content_id(),
+            merkle_root: hex::encode(merkle_root(&leaves)),
+            deposit_ids: batch.deposits.iter().map(|deposit| deposit.deposit_id.clone()).collect(),
+            event_commitments: leaves.iter().map(hex::encode).collect(),
+            created_at: batch.created_at.timestamp(),
+        })
+    }
+}
+
+/// Manifest plus an ed25519 signature by the manager's Solana key over the
+/// exact JSON bytes of `manifest`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedBatchManifest {
+    pub manifest: BatchManifest,
+    pub signer: String,
+    pub signature: String,
+}
@@ -0,0 +1,70 @@
+use crate::{OrchestratorError, Result};
+use std::time::Duration;
+use tonic::codec::ProstCodec;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+
+/// Bytes per streamed message, well under tonic's 4 MiB decoding limit
+const CHUNK_SIZE: usize = 1024 * 1024;
+const PROVE_PATH: &str = "/circuit.CircuitService/Prove";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Piece of a streamed proof request or response; mirrors `proto/circuit_service.proto`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Chunk {
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: Vec<u8>,
+}
+
+/// Circuit services reached over gRPC are listed as `grpc://host:port`, or
+/// `grpcs://host:port` behind TLS
+pub fn is_grpc_url(url: &str) -> bool {
+    url.starts_with("grpc://") || url.starts_with("grpcs://")
+}
+
+/// gRPC client of one circuit service
+pub struct CircuitGrpcClient {
+    grpc: tonic::client::Grpc<Channel>,
+}
+
+impl CircuitGrpcClient {
+    pub async fn connect(url: &str) -> Result<Self> {
+        let (endpoint, tls) = match url.strip_prefix("grpcs://") {
+            Some(rest) => (format!("https://{}", rest), true),
+            None => (format!("http://{}", url.trim_start_matches("grpc://")), false),
+        };
+        let mut endpoint = Endpoint::from_shared(endpoint).map_err(grpc_error)?.connect_timeout(CONNECT_TIMEOUT);
+        if tls {
+            endpoint = endpoint.tls_config(ClientTlsConfig::new().with_native_roots()).map_err(grpc_error)?;
+        }
+        let channel = endpoint.connect().await.map_err(grpc_error)?;
+        Ok(Self { grpc: tonic::client::Grpc::new(channel) })
+    }
+
+    /// Stream `request` up and reassemble the response streamed back; both are
+    /// the JSON documents of the HTTP transport. Gives up after `timeout`.
+    pub async fn prove(&mut self, request: &serde_json::Value, timeout: Duration) -> Result<serde_json::Value> {
+        let body = serde_json::to_vec(request)?;
+        let chunks: Vec<Chunk> = body.chunks(CHUNK_SIZE).map(|data| Chunk { data: data.to_vec() }).collect();
+        let mut request = tonic::Request::new(futures_util::stream::iter(chunks));
+        request.set_timeout(timeout);
+
+        self.grpc.ready().await.map_err(grpc_error)?;
+        let mut response = self
+            .grpc
+            .streaming(request, PathAndQuery::from_static(PROVE_PATH), ProstCodec::<Chunk, Chunk>::default())
+            .await
+            .map_err(grpc_error)?
+            .into_inner();
+
+        let mut proof = Vec::new();
+        while let Some(chunk) = response.message().await.map_err(grpc_error)? {
+            proof.extend_from_slice(&chunk.data);
+        }
+        Ok(serde_json::from_slice(&proof)?)
+    }
+}
+
+fn grpc_error(error: impl std::fmt::Display) -> OrchestratorError {
+    OrchestratorError::GrpcError(error.to_string())
+}
@@ -1,8 +1,15 @@
 use serde::{Deserialize, Serialize};
-use sqlx::{sqlite::SqlitePoolOptions, SqlitePool};
-use std::time::{SystemTime, UNIX_EPOCH};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
+use sqlx::SqlitePool;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::jetton_metadata::JettonMetadata;
+use crate::price_oracle::RateSnapshot;
+use crate::notifications::NotificationTarget;
+use crate::archive::ArchiveLocation;
+use crate::db_migration::{DepositMirror, MigrationPhase, ParityReport};
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
 pub struct DepositRecord {
     pub deposit_id: String,
     pub ton_tx_hash: String,
@@ -13,18 +20,191 @@ pub struct DepositRecord {
     pub error_message: Option<String>,
     pub created_at: i64,
     pub updated_at: i64,
+    pub jetton_master: Option<String>,
+    pub jetton_symbol: Option<String>,
+    pub jetton_name: Option<String>,
+    pub jetton_decimals: Option<i64>,
+    pub accepted_ton_usd: Option<f64>,
+    pub accepted_sol_usd: Option<f64>,
+    pub completed_ton_usd: Option<f64>,
+    pub completed_sol_usd: Option<f64>,
+    pub status_detail: Option<String>, // ProgramFailure code when the program rejected the deposit
+    pub nullifier: Option<String>,     // Hex, as computed by the TON indexer
+    pub nonce: Option<String>,         // Indexer nonce; with ton_tx_hash, identifies one TON deposit
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RefundRecord {
+    pub deposit_id: String,
+    pub ton_destination: String,
+    pub amount: String,
+    pub reason: String,
+    pub status: String,
+    pub attempts: i64,
+    pub refund_tx_hash: Option<String>,
+    pub last_error: Option<String>,
+    pub next_attempt_at: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ReceiptRecord {
+    pub deposit_id: String,
+    pub solana_signature: String,
+    pub slot: Option<i64>,
+    pub event_pda: Option<String>,
+    pub merkle_path: Option<String>, // JSON array of hex sibling hashes, when batch anchoring is used
+    pub created_at: i64,
+}
+
+// Submission attempts per batch, used to avoid paying for the same batch twice
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BatchSubmissionRecord {
+    pub batch_id: String,
+    pub status: String, // submitting | submitted
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+// Transaction a deposit of a submitted batch landed in
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DepositSignatureRecord {
+    pub batch_id: String,
+    pub deposit_id: String,
+    pub solana_signature: String,
+    pub created_at: i64,
+}
+
+// A landed transaction awaiting finality, with the deposits it carries
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TrackedSignatureRecord {
+    pub signature: String,
+    pub batch_id: String,
+    pub batch: String,      // JSON of the deposits and proofs the transaction carried
+    pub submitted_at: i64,
+    pub last_seen_at: i64,  // Last time the cluster reported the signature
+}
+
+// A circuit service's asynchronous proof job, kept so a restart resumes polling it
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ProofJobRecord {
+    pub deposit_id: String,
+    pub service_url: String,
+    pub job_id: String,
+    pub created_at: i64,
+}
+
+// A generated proof, keyed by a hash of the inputs the circuit service was asked to prove
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CachedProofRecord {
+    pub inputs_hash: String,
+    pub deposit_id: String,
+    pub proof: String, // JSON Proof
+    pub created_at: i64,
+}
+
+// A batch of proven deposits; `open` while still filling, so a restart can rebuild it
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BatchRecord {
+    pub batch_id: String,
+    pub status: String, // open | sealed
+    pub created_at: i64, // Unix millis, the batch's `created_at`
+    pub sealed_at: Option<i64>,
+}
+
+// Signed commitment to a sealed batch's contents, kept for reconciliation and audit
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BatchManifestRecord {
+    pub batch_id: String,
+    pub merkle_root: String, // Hex events tree root
+    pub manifest: String,    // JSON `BatchManifest`, the exact bytes signed
+    pub signer: String,
+    pub signature: String,
+    pub created_at: i64,
+}
+
+// Hash of every accepted ingestion request, for replay protection and abuse investigations
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RequestLogRecord {
+    pub request_hash: String,
+    pub api_key: String,
+    pub deposit_id: String,
+    pub request_id: String,
+    pub received_at: i64,
+}
+
+// Which manager instance may dequeue and submit batches, see handover
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SubmitterLeaseRecord {
+    pub owner: Option<String>,
+    pub owner_version: Option<String>,
+    pub expires_at: i64,
+    pub handover_to: Option<String>, // Instance waiting to take over from the owner
+    pub updated_at: i64,
+}
+
+// Single-row notice for the public status page, see status_page
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StatusNoticeRecord {
+    pub kind: String, // incident | maintenance
+    pub message: String,
+    pub starts_at: i64,
+    pub ends_at: Option<i64>,
+    pub updated_at: i64,
+}
+
+// Work passed between split-role processes, see roles
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct HandoffRecord {
+    pub id: i64,
+    pub stage: String,   // prove | verified | submit
+    pub payload: String, // JSON Deposit for prove, ProvedDeposit for verified, Batch for submit
+    pub claimed_by: Option<String>,
+    pub claimed_at: Option<i64>,
+    pub created_at: i64,
+}
+
+/// Connection pragmas. WAL lets readers run alongside the single writer, and
+/// the busy timeout makes concurrent writers from the HTTP handlers and the
+/// batch loop queue up instead of failing with `database is locked`.
+#[derive(Debug, Clone)]
+pub struct DatabaseOptions {
+    pub journal_mode: String,
+    pub busy_timeout_ms: u64,
+    pub synchronous: String,
+}
+
+impl Default for DatabaseOptions {
+    fn default() -> Self {
+        Self {
+            journal_mode: "wal".to_string(),
+            busy_timeout_ms: 5000,
+            synchronous: "normal".to_string(), // Safe with WAL; only the last commits can be lost on power failure
+        }
+    }
 }
 
 #[derive(Clone)] 
 pub struct DatabaseService {
     pool: SqlitePool,
+    // Postgres copy of `deposits` while migrating off SQLite, see db_migration
+    mirror: Option<DepositMirror>,
 }
 
 impl DatabaseService {
     pub async fn new(db_url: &str) -> Result<Self, sqlx::Error> {
+        Self::with_options(db_url, &DatabaseOptions::default()).await
+    }
+
+    pub async fn with_options(db_url: &str, options: &DatabaseOptions) -> Result<Self, sqlx::Error> {
+        let connect_options = SqliteConnectOptions::from_str(db_url)?
+            .journal_mode(SqliteJournalMode::from_str(&options.journal_mode)?)
+            .synchronous(SqliteSynchronous::from_str(&options.synchronous)?)
+            .busy_timeout(Duration::from_millis(options.busy_timeout_ms));
         let pool = SqlitePoolOptions::new()
             .max_connections(5)
-            .connect(db_url)
+            .connect_with(connect_options)
             .await?;
 
         // Create table
@@ -46,82 +226,1559 @@ impl DatabaseService {
         .execute(&pool)
         .await?;
 
-        Ok(Self { pool })
-    }
+        // Columns added after the initial schema
+        for column in [
+            "jetton_master TEXT",
+            "jetton_symbol TEXT",
+            "jetton_name TEXT",
+            "jetton_decimals INTEGER",
+            "accepted_ton_usd REAL",
+            "accepted_sol_usd REAL",
+            "completed_ton_usd REAL",
+            "completed_sol_usd REAL",
+            "status_detail TEXT",
+            "nullifier TEXT",
+            "batch_id TEXT",
+            "batch_position INTEGER",
+            "batch_entry TEXT", // JSON deposit and proof while its batch is open
+            "nonce TEXT",
+        ] {
+            Self::add_column_if_missing(&pool, "deposits", column).await?;
+        }
 
-    pub async fn store_deposit(&self, mut deposit: DepositRecord) -> Result<(), sqlx::Error> {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_deposits_nullifier ON deposits (nullifier)")
+            .execute(&pool)
+            .await?;
 
-        deposit.created_at = now;
-        deposit.updated_at = now;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_deposits_ton_tx_hash ON deposits (ton_tx_hash)")
+            .execute(&pool)
+            .await?;
+
+        // Rows stored before nonces were recorded stay out of the constraint
+        sqlx::query(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_deposits_ton_tx_nonce ON deposits (ton_tx_hash, nonce) \
+             WHERE nonce IS NOT NULL",
+        )
+        .execute(&pool)
+        .await?;
 
+        // Proof-of-completion receipts for bridged deposits
         sqlx::query(
             r#"
-            INSERT OR REPLACE INTO deposits 
-            (deposit_id, ton_tx_hash, sender_address, recipient_solana, amount, status, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            CREATE TABLE IF NOT EXISTS receipts (
+                deposit_id TEXT PRIMARY KEY,
+                solana_signature TEXT NOT NULL,
+                slot INTEGER,
+                event_pda TEXT,
+                merkle_path TEXT,
+                created_at INTEGER NOT NULL
+            )
             "#,
         )
-        .bind(&deposit.deposit_id)
-        .bind(&deposit.ton_tx_hash)
-        .bind(&deposit.sender_address)
-        .bind(&deposit.recipient_solana)
-        .bind(&deposit.amount)
-        .bind(&deposit.status)
-        .bind(deposit.created_at)
-        .bind(deposit.updated_at)
-        .execute(&self.pool)
+        .execute(&pool)
         .await?;
 
-        Ok(())
-    }
+        // Refund tasks for deposits that can never be bridged
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS refunds (
+                deposit_id TEXT PRIMARY KEY,
+                ton_destination TEXT NOT NULL,
+                amount TEXT NOT NULL,
+                reason TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'pending',
+                attempts INTEGER NOT NULL DEFAULT 0,
+                refund_tx_hash TEXT,
+                last_error TEXT,
+                next_attempt_at INTEGER NOT NULL,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
 
-    pub async fn get_pending_deposits(&self) -> Result<Vec<DepositRecord>, sqlx::Error> {
-        let deposits = sqlx::query_as::<_, DepositRecord>(
-            "SELECT * FROM deposits WHERE status = 'pending' ORDER BY created_at ASC",
+        // Batch submission idempotency records
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS batch_submissions (
+                batch_id TEXT PRIMARY KEY,
+                status TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )
+            "#,
         )
-        .fetch_all(&self.pool)
+        .execute(&pool)
         .await?;
 
-        Ok(deposits)
-    }
+        // Transaction each landed deposit of a submitted batch went out in
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS deposit_signatures (
+                batch_id TEXT NOT NULL,
+                deposit_id TEXT NOT NULL,
+                solana_signature TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                PRIMARY KEY (batch_id, deposit_id)
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
 
-    pub async fn update_deposit_status(
-        &self,
-        deposit_id: &str,
-        status: &str,
-        error_message: Option<&str>,
-    ) -> Result<(), sqlx::Error> {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_deposit_signatures_deposit ON deposit_signatures (deposit_id)")
+            .execute(&pool)
+            .await?;
 
+        // Accepted ingestion requests
         sqlx::query(
-            "UPDATE deposits SET status = ?, error_message = ?, updated_at = ? WHERE deposit_id = ?",
+            r#"
+            CREATE TABLE IF NOT EXISTS request_log (
+                request_hash TEXT PRIMARY KEY,
+                api_key TEXT NOT NULL,
+                deposit_id TEXT NOT NULL,
+                request_id TEXT NOT NULL,
+                received_at INTEGER NOT NULL
+            )
+            "#,
         )
-        .bind(status)
-        .bind(error_message)
-        .bind(now)
-        .bind(deposit_id)
-        .execute(&self.pool)
+        .execute(&pool)
         .await?;
 
-        Ok(())
-    }
-
-    pub async fn get_queue_stats(&self) -> Result<(usize, usize), sqlx::Error> {
-        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM deposits")
-            .fetch_one(&self.pool)
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_request_log_api_key ON request_log (api_key, received_at)")
+            .execute(&pool)
             .await?;
 
-        let completed: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM deposits WHERE status = 'completed'")
-            .fetch_one(&self.pool)
+        // User notification targets per deposit or per API key
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS notification_targets (
+                scope TEXT NOT NULL,
+                scope_id TEXT NOT NULL,
+                channel TEXT NOT NULL,
+                address TEXT NOT NULL,
+                PRIMARY KEY (scope, scope_id, channel, address)
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        // Single-row copy of the submission queue, see queue_snapshot
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS queue_snapshot (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                version INTEGER NOT NULL,
+                payload TEXT NOT NULL,
+                updated_at INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        // Finalized deposits moved to cold storage, see archive
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS archives (
+                deposit_id TEXT PRIMARY KEY,
+                content_hash TEXT NOT NULL,
+                ipfs_cid TEXT,
+                archived_at INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        // Single-row submitter lease, see handover
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS submitter_lease (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                owner TEXT,
+                owner_version TEXT,
+                expires_at INTEGER NOT NULL DEFAULT 0,
+                handover_to TEXT,
+                updated_at INTEGER NOT NULL DEFAULT 0
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        // Single-row phase of the move to Postgres, see db_migration
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS db_migration (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                phase TEXT NOT NULL,
+                updated_at INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        // Single-row status page notice, see status_page
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS status_notice (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                kind TEXT NOT NULL,
+                message TEXT NOT NULL,
+                starts_at INTEGER NOT NULL,
+                ends_at INTEGER,
+                updated_at INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        // Deposits and batches handed between split-role processes, see roles
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS pipeline_handoff (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                stage TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                claimed_by TEXT,
+                claimed_at INTEGER,
+                created_at INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_pipeline_handoff_stage ON pipeline_handoff(stage, id)")
+            .execute(&pool)
             .await?;
 
-        Ok((total.0 as usize, completed.0 as usize))
+        // Landed transactions whose deposits are confirming until the slot is finalized
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS tracked_signatures (
+                signature TEXT PRIMARY KEY,
+                batch_id TEXT NOT NULL,
+                batch TEXT NOT NULL,
+                submitted_at INTEGER NOT NULL,
+                last_seen_at INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        // Proofs already generated, so the same inputs are never proven twice
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS proofs (
+                inputs_hash TEXT PRIMARY KEY,
+                deposit_id TEXT NOT NULL,
+                proof TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        // Proof jobs still running on a circuit service
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS proof_jobs (
+                deposit_id TEXT NOT NULL,
+                service_url TEXT NOT NULL,
+                job_id TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                PRIMARY KEY (deposit_id, service_url)
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        // Batches deposits were proven into; members carry `deposits.batch_id`
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS batches (
+                batch_id TEXT PRIMARY KEY,
+                status TEXT NOT NULL DEFAULT 'open',
+                created_at INTEGER NOT NULL,
+                sealed_at INTEGER
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS batch_manifests (
+                batch_id TEXT PRIMARY KEY,
+                merkle_root TEXT NOT NULL,
+                manifest TEXT NOT NULL,
+                signer TEXT NOT NULL,
+                signature TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        // Batches carved out of another, e.g. the deposits of a partially landed batch
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS batch_splits (
+                child_id TEXT PRIMARY KEY,
+                parent_id TEXT NOT NULL,
+                reason TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        Ok(Self { pool, mirror: None })
+    }
+
+    /// Connect the Postgres database `deposits` is being migrated to. Must run
+    /// before the service is cloned; the migration phase decides its use.
+    pub async fn attach_mirror(&mut self, url: &str) -> Result<(), sqlx::Error> {
+        self.mirror = Some(DepositMirror::connect(url).await?);
+        Ok(())
+    }
+
+    pub fn has_mirror(&self) -> bool {
+        self.mirror.is_some()
+    }
+
+    /// Stored phase; always `Off` without an attached mirror
+    pub async fn migration_phase(&self) -> Result<MigrationPhase, sqlx::Error> {
+        if self.mirror.is_none() {
+            return Ok(MigrationPhase::Off);
+        }
+        let phase: Option<String> = sqlx::query_scalar("SELECT phase FROM db_migration WHERE id = 1")
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(phase.and_then(|phase| phase.parse().ok()).unwrap_or(MigrationPhase::Off))
+    }
+
+    pub async fn set_migration_phase(&self, phase: MigrationPhase) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        sqlx::query(
+            r#"
+            INSERT INTO db_migration (id, phase, updated_at) VALUES (1, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET phase = excluded.phase, updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(phase.as_str())
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Copy a deposit row to the mirror after a SQLite write. Outside cutover a
+    /// failed copy only logs; the parity check repairs it later.
+    async fn mirror_deposit(&self, deposit_id: &str) -> Result<(), sqlx::Error> {
+        let Some(mirror) = &self.mirror else {
+            return Ok(());
+        };
+        let phase = self.migration_phase().await?;
+        if phase == MigrationPhase::Off {
+            return Ok(());
+        }
+        let Some(row) = self.get_primary_deposit(deposit_id).await? else {
+            return Ok(());
+        };
+
+        match mirror.upsert(&row).await {
+            Err(e) if phase == MigrationPhase::CutOver => Err(e),
+            Err(e) => {
+                log::warn!("Mirroring deposit {} to Postgres failed, left to parity check: {}", deposit_id, e);
+                Ok(())
+            }
+            Ok(()) => Ok(()),
+        }
+    }
+
+    /// Compare `deposits` in SQLite and Postgres and, with `repair`, copy rows
+    /// that are missing or differ from SQLite. None without a mirror.
+    pub async fn check_deposit_parity(&self, repair: bool) -> Result<Option<ParityReport>, sqlx::Error> {
+        let Some(mirror) = &self.mirror else {
+            return Ok(None);
+        };
+
+        let primary = sqlx::query_as::<_, DepositRecord>("SELECT * FROM deposits")
+            .fetch_all(&self.pool)
+            .await?;
+        let mut copies = mirror.all_deposits().await?;
+        let mut report = ParityReport {
+            primary_rows: primary.len(),
+            mirror_rows: copies.len(),
+            checked_at: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64,
+            ..ParityReport::default()
+        };
+
+        for row in primary {
+            match copies.remove(&row.deposit_id) {
+                Some(copy) if copy == row => continue,
+                Some(_) => report.mismatched += 1,
+                None => report.missing += 1,
+            }
+            if repair {
+                mirror.upsert(&row).await?;
+                report.repaired += 1;
+            }
+        }
+        report.extra = copies.len();
+
+        Ok(Some(report))
+    }
+
+    pub async fn push_handoff(&self, stage: &str, payload: &str) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        sqlx::query("INSERT INTO pipeline_handoff (stage, payload, created_at) VALUES (?, ?, ?)")
+            .bind(stage)
+            .bind(payload)
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Claim up to `limit` unclaimed items of `stage`, oldest first. Items
+    /// claimed more than `reclaim_after_secs` ago are assumed abandoned by a
+    /// crashed process and handed out again.
+    pub async fn claim_handoffs(
+        &self,
+        stage: &str,
+        claimer: &str,
+        limit: i64,
+        reclaim_after_secs: u64,
+    ) -> Result<Vec<HandoffRecord>, sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            r#"
+            UPDATE pipeline_handoff SET claimed_by = ?, claimed_at = ?
+            WHERE id IN (
+                SELECT id FROM pipeline_handoff
+                WHERE stage = ? AND (claimed_by IS NULL OR claimed_at < ?)
+                ORDER BY id ASC LIMIT ?
+            )
+            "#,
+        )
+        .bind(claimer)
+        .bind(now)
+        .bind(stage)
+        .bind(now - reclaim_after_secs as i64)
+        .bind(limit)
+        .execute(&mut *tx)
+        .await?;
+
+        let claimed = sqlx::query_as::<_, HandoffRecord>(
+            "SELECT * FROM pipeline_handoff WHERE stage = ? AND claimed_by = ? AND claimed_at = ? ORDER BY id ASC",
+        )
+        .bind(stage)
+        .bind(claimer)
+        .bind(now)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(claimed)
+    }
+
+    /// Drop a handoff item once the claiming stage has taken it over
+    pub async fn complete_handoff(&self, id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM pipeline_handoff WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Unclaimed `stage` item carrying the deposit `deposit_id`, if any
+    pub async fn find_deposit_handoff(&self, stage: &str, deposit_id: &str) -> Result<Option<HandoffRecord>, sqlx::Error> {
+        sqlx::query_as::<_, HandoffRecord>(
+            r#"
+            SELECT * FROM pipeline_handoff
+            WHERE stage = ? AND claimed_by IS NULL AND json_extract(payload, '$.deposit_id') = ?
+            ORDER BY id ASC LIMIT 1
+            "#,
+        )
+        .bind(stage)
+        .bind(deposit_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Move an item to another stage with a new payload, as long as no process
+    /// has claimed it meanwhile. Returns whether it was moved.
+    pub async fn restage_handoff(&self, id: i64, stage: &str, payload: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE pipeline_handoff SET stage = ?, payload = ? WHERE id = ? AND claimed_by IS NULL",
+        )
+        .bind(stage)
+        .bind(payload)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Unclaimed items per stage, for the split-role backlog gauges
+    pub async fn count_handoffs(&self, stage: &str) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM pipeline_handoff WHERE stage = ?")
+            .bind(stage)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Take or renew the submitter lease. Succeeds when `instance_id` already
+    /// owns it, or when it has expired and no other instance is waiting for a
+    /// handover (a handover request nobody acted on for a full TTL is ignored).
+    /// Returns whether `instance_id` holds the lease afterwards.
+    pub async fn acquire_submitter_lease(&self, instance_id: &str, version: &str, ttl_secs: u64) -> Result<bool, sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        sqlx::query("INSERT OR IGNORE INTO submitter_lease (id, expires_at, updated_at) VALUES (1, 0, ?)")
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE submitter_lease
+            SET owner = ?1, owner_version = ?2, expires_at = ?3, updated_at = ?4,
+                handover_to = CASE WHEN handover_to = ?1 THEN NULL ELSE handover_to END
+            WHERE id = 1
+              AND (owner = ?1
+                   OR ((owner IS NULL OR expires_at < ?4)
+                       AND (handover_to IS NULL OR handover_to = ?1 OR updated_at < ?5)))
+            "#,
+        )
+        .bind(instance_id)
+        .bind(version)
+        .bind(now + ttl_secs as i64)
+        .bind(now)
+        .bind(now - ttl_secs as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Ask the current owner to drain and hand the lease to `instance_id`.
+    /// Returns false when `instance_id` already owns it.
+    pub async fn request_lease_handover(&self, instance_id: &str) -> Result<bool, sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let result = sqlx::query(
+            "UPDATE submitter_lease SET handover_to = ?, updated_at = ? WHERE id = 1 AND owner IS NOT NULL AND owner != ?",
+        )
+        .bind(instance_id)
+        .bind(now)
+        .bind(instance_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Release a drained lease straight to the waiting instance, which picks it
+    /// up on its next renewal. No-op unless `instance_id` still owns it.
+    pub async fn release_submitter_lease(&self, instance_id: &str) -> Result<bool, sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let result = sqlx::query(
+            "UPDATE submitter_lease SET owner = NULL, owner_version = NULL, expires_at = 0, updated_at = ? WHERE id = 1 AND owner = ?",
+        )
+        .bind(now)
+        .bind(instance_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn get_submitter_lease(&self) -> Result<Option<SubmitterLeaseRecord>, sqlx::Error> {
+        sqlx::query_as::<_, SubmitterLeaseRecord>(
+            "SELECT owner, owner_version, expires_at, handover_to, updated_at FROM submitter_lease WHERE id = 1",
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    pub async fn save_queue_snapshot(&self, version: u32, payload: &str) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        sqlx::query(
+            r#"
+            INSERT INTO queue_snapshot (id, version, payload, updated_at) VALUES (1, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET version = excluded.version, payload = excluded.payload,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(version as i64)
+        .bind(payload)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn load_queue_snapshot(&self) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT payload FROM queue_snapshot WHERE id = 1")
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// `ALTER TABLE ... ADD COLUMN` for databases created before `column_def` existed
+    async fn add_column_if_missing(pool: &SqlitePool, table: &str, column_def: &str) -> Result<(), sqlx::Error> {
+        let column = column_def.split_whitespace().next().unwrap_or(column_def);
+        let exists: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pragma_table_info(?) WHERE name = ?")
+            .bind(table)
+            .bind(column)
+            .fetch_one(pool)
+            .await?;
+
+        if exists == 0 {
+            sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {}", table, column_def))
+                .execute(pool)
+                .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn store_deposit(&self, mut deposit: DepositRecord) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        deposit.created_at = now;
+        deposit.updated_at = now;
+
+        sqlx::query(
+            r#"
+            INSERT INTO deposits
+            (deposit_id, ton_tx_hash, sender_address, recipient_solana, amount, status, created_at, updated_at,
+             jetton_master, jetton_symbol, jetton_name, jetton_decimals, nullifier, nonce)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&deposit.deposit_id)
+        .bind(&deposit.ton_tx_hash)
+        .bind(&deposit.sender_address)
+        .bind(&deposit.recipient_solana)
+        .bind(&deposit.amount)
+        .bind(&deposit.status)
+        .bind(deposit.created_at)
+        .bind(deposit.updated_at)
+        .bind(&deposit.jetton_master)
+        .bind(&deposit.jetton_symbol)
+        .bind(&deposit.jetton_name)
+        .bind(deposit.jetton_decimals)
+        .bind(&deposit.nullifier)
+        .bind(&deposit.nonce)
+        .execute(&self.pool)
+        .await?;
+
+        self.mirror_deposit(&deposit.deposit_id).await
+    }
+
+    /// The already stored deposit that `deposit_id`, or the TON transaction
+    /// `ton_tx_hash` with `nonce`, would duplicate
+    pub async fn find_duplicate_deposit(
+        &self,
+        deposit_id: &str,
+        ton_tx_hash: &str,
+        nonce: &str,
+    ) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT deposit_id FROM deposits WHERE deposit_id = ? OR (ton_tx_hash = ? AND nonce = ?) LIMIT 1",
+        )
+        .bind(deposit_id)
+        .bind(ton_tx_hash)
+        .bind(nonce)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    pub async fn get_pending_deposits(&self) -> Result<Vec<DepositRecord>, sqlx::Error> {
+        let deposits = sqlx::query_as::<_, DepositRecord>(
+            "SELECT * FROM deposits WHERE status = 'pending' ORDER BY created_at ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(deposits)
+    }
+
+    pub async fn set_deposit_token(&self, deposit_id: &str, token: &JettonMetadata) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE deposits SET jetton_master = ?, jetton_symbol = ?, jetton_name = ?, jetton_decimals = ? WHERE deposit_id = ?",
+        )
+        .bind(&token.master)
+        .bind(&token.symbol)
+        .bind(&token.name)
+        .bind(token.decimals as i64)
+        .bind(deposit_id)
+        .execute(&self.pool)
+        .await?;
+
+        self.mirror_deposit(deposit_id).await
+    }
+
+    /// Record reference rates at acceptance (`completed = false`) or completion
+    pub async fn set_deposit_rates(&self, deposit_id: &str, completed: bool, rates: &RateSnapshot) -> Result<(), sqlx::Error> {
+        let query = if completed {
+            "UPDATE deposits SET completed_ton_usd = ?, completed_sol_usd = ? WHERE deposit_id = ?"
+        } else {
+            "UPDATE deposits SET accepted_ton_usd = ?, accepted_sol_usd = ? WHERE deposit_id = ?"
+        };
+
+        sqlx::query(query)
+            .bind(rates.ton_usd)
+            .bind(rates.sol_usd)
+            .bind(deposit_id)
+            .execute(&self.pool)
+            .await?;
+
+        self.mirror_deposit(deposit_id).await
+    }
+
+    /// Deposits created in `[from, to)` (unix seconds), oldest first
+    pub async fn get_deposits_between(&self, from: i64, to: i64) -> Result<Vec<DepositRecord>, sqlx::Error> {
+        if let Some(mirror) = self.cut_over_mirror().await? {
+            return mirror.get_deposits_between(from, to).await;
+        }
+        sqlx::query_as::<_, DepositRecord>(
+            "SELECT * FROM deposits WHERE created_at >= ? AND created_at < ? ORDER BY created_at ASC",
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn get_deposit(&self, deposit_id: &str) -> Result<Option<DepositRecord>, sqlx::Error> {
+        if let Some(mirror) = self.cut_over_mirror().await? {
+            return mirror.get_deposit(deposit_id).await;
+        }
+        self.get_primary_deposit(deposit_id).await
+    }
+
+    /// The mirror, once deposit reads have been cut over to it
+    async fn cut_over_mirror(&self) -> Result<Option<&DepositMirror>, sqlx::Error> {
+        if self.migration_phase().await? == MigrationPhase::CutOver {
+            return Ok(self.mirror.as_ref());
+        }
+        Ok(None)
+    }
+
+    async fn get_primary_deposit(&self, deposit_id: &str) -> Result<Option<DepositRecord>, sqlx::Error> {
+        let deposit = sqlx::query_as::<_, DepositRecord>("SELECT * FROM deposits WHERE deposit_id = ?")
+            .bind(deposit_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(deposit)
+    }
+
+    pub async fn get_deposits_by_ton_tx_hash(&self, ton_tx_hash: &str) -> Result<Vec<DepositRecord>, sqlx::Error> {
+        if let Some(mirror) = self.cut_over_mirror().await? {
+            return mirror.get_deposits_by_ton_tx_hash(ton_tx_hash).await;
+        }
+        let deposits = sqlx::query_as::<_, DepositRecord>(
+            "SELECT * FROM deposits WHERE ton_tx_hash = ? ORDER BY created_at ASC",
+        )
+        .bind(ton_tx_hash)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(deposits)
+    }
+
+    pub async fn update_deposit_status(
+        &self,
+        deposit_id: &str,
+        status: &str,
+        error_message: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        sqlx::query(
+            "UPDATE deposits SET status = ?, error_message = ?, updated_at = ? WHERE deposit_id = ?",
+        )
+        .bind(status)
+        .bind(error_message)
+        .bind(now)
+        .bind(deposit_id)
+        .execute(&self.pool)
+        .await?;
+
+        self.mirror_deposit(deposit_id).await
+    }
+
+    /// Record (or clear) why the program rejected a deposit, without touching its status
+    pub async fn set_deposit_status_detail(&self, deposit_id: &str, detail: Option<&str>) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE deposits SET status_detail = ? WHERE deposit_id = ?")
+            .bind(detail)
+            .bind(deposit_id)
+            .execute(&self.pool)
+            .await?;
+
+        self.mirror_deposit(deposit_id).await
+    }
+
+    pub async fn get_queue_stats(&self) -> Result<(usize, usize), sqlx::Error> {
+        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM deposits")
+            .fetch_one(&self.pool)
+            .await?;
+
+        let completed: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM deposits WHERE status = 'completed'")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok((total.0 as usize, completed.0 as usize))
+    }
+
+    /// Completed deposit count and their summed amount, for reconciliation
+    /// against the program's event counters
+    pub async fn get_completed_totals(&self) -> Result<(u64, u128), sqlx::Error> {
+        let (count, amount): (i64, Option<i64>) = sqlx::query_as(
+            "SELECT COUNT(*), SUM(CAST(amount AS INTEGER)) FROM deposits WHERE status = 'completed'",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok((count as u64, amount.unwrap_or(0) as u128))
+    }
+
+    /// Deposits that failed terminally and need an operator (the dead-letter set)
+    pub async fn count_dead_letter_deposits(&self) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM deposits WHERE status IN ('failed', 'refund_failed')")
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Deposits with a completion receipt that are nonetheless failed or
+    /// refunded, plus completed deposits that also have a refund task
+    pub async fn find_completed_dead_letters(&self) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT d.deposit_id FROM deposits d JOIN receipts r ON r.deposit_id = d.deposit_id
+            WHERE d.status IN ('failed', 'refund_failed', 'refund_pending', 'refunded')
+            UNION
+            SELECT d.deposit_id FROM deposits d JOIN refunds f ON f.deposit_id = d.deposit_id
+            WHERE d.status = 'completed'
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Nullifiers recorded on more than one deposit, with how many
+    pub async fn find_duplicate_nullifiers(&self) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT LOWER(nullifier), COUNT(*) FROM deposits WHERE nullifier IS NOT NULL GROUP BY LOWER(nullifier) HAVING COUNT(*) > 1",
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Creation time of the oldest deposit still waiting to be bridged
+    pub async fn get_oldest_open_deposit_at(&self) -> Result<Option<i64>, sqlx::Error> {
+        sqlx::query_scalar("SELECT MIN(created_at) FROM deposits WHERE status IN ('pending', 'delayed')")
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Mean time from acceptance to completion of deposits completed since `since`
+    pub async fn average_bridging_secs(&self, since: i64) -> Result<Option<f64>, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT AVG(updated_at - created_at) FROM deposits WHERE status = 'completed' AND updated_at >= ?",
+        )
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn set_status_notice(
+        &self,
+        kind: &str,
+        message: &str,
+        starts_at: i64,
+        ends_at: Option<i64>,
+    ) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        sqlx::query(
+            r#"
+            INSERT INTO status_notice (id, kind, message, starts_at, ends_at, updated_at)
+            VALUES (1, ?, ?, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                kind = excluded.kind, message = excluded.message, starts_at = excluded.starts_at,
+                ends_at = excluded.ends_at, updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(kind)
+        .bind(message)
+        .bind(starts_at)
+        .bind(ends_at)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn clear_status_notice(&self) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM status_notice WHERE id = 1")
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_status_notice(&self) -> Result<Option<StatusNoticeRecord>, sqlx::Error> {
+        sqlx::query_as::<_, StatusNoticeRecord>("SELECT kind, message, starts_at, ends_at, updated_at FROM status_notice WHERE id = 1")
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    pub async fn create_refund_task(
+        &self,
+        deposit_id: &str,
+        ton_destination: &str,
+        amount: &str,
+        reason: &str,
+    ) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO refunds
+            (deposit_id, ton_destination, amount, reason, status, attempts, next_attempt_at, created_at, updated_at)
+            VALUES (?, ?, ?, ?, 'pending', 0, ?, ?, ?)
+            "#,
+        )
+        .bind(deposit_id)
+        .bind(ton_destination)
+        .bind(amount)
+        .bind(reason)
+        .bind(now)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        self.update_deposit_status(deposit_id, "refund_pending", Some(reason)).await
+    }
+
+    pub async fn get_due_refunds(&self) -> Result<Vec<RefundRecord>, sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let refunds = sqlx::query_as::<_, RefundRecord>(
+            "SELECT * FROM refunds WHERE status = 'pending' AND next_attempt_at <= ? ORDER BY created_at ASC",
+        )
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(refunds)
+    }
+
+    pub async fn record_refund_attempt(
+        &self,
+        deposit_id: &str,
+        status: &str,
+        error_message: &str,
+        next_attempt_at: i64,
+    ) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        sqlx::query(
+            "UPDATE refunds SET status = ?, attempts = attempts + 1, last_error = ?, next_attempt_at = ?, updated_at = ? WHERE deposit_id = ?",
+        )
+        .bind(status)
+        .bind(error_message)
+        .bind(next_attempt_at)
+        .bind(now)
+        .bind(deposit_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn complete_refund(&self, deposit_id: &str, refund_tx_hash: &str) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        sqlx::query(
+            "UPDATE refunds SET status = 'completed', attempts = attempts + 1, refund_tx_hash = ?, last_error = NULL, updated_at = ? WHERE deposit_id = ?",
+        )
+        .bind(refund_tx_hash)
+        .bind(now)
+        .bind(deposit_id)
+        .execute(&self.pool)
+        .await?;
+
+        self.update_deposit_status(deposit_id, "refunded", None).await
+    }
+
+    /// Fold the WAL back into the main database file so it can't grow unbounded
+    pub async fn checkpoint_wal(&self) -> Result<(), sqlx::Error> {
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Rebuild the database file, reclaiming pages freed by deleted rows
+    pub async fn vacuum(&self) -> Result<(), sqlx::Error> {
+        sqlx::query("VACUUM")
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn database_size_bytes(&self) -> Result<i64, sqlx::Error> {
+        let page_count: (i64,) = sqlx::query_as("PRAGMA page_count")
+            .fetch_one(&self.pool)
+            .await?;
+
+        let page_size: (i64,) = sqlx::query_as("PRAGMA page_size")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(page_count.0 * page_size.0)
+    }
+
+    pub async fn store_receipt(&self, mut receipt: ReceiptRecord) -> Result<(), sqlx::Error> {
+        receipt.created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO receipts
+            (deposit_id, solana_signature, slot, event_pda, merkle_path, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&receipt.deposit_id)
+        .bind(&receipt.solana_signature)
+        .bind(receipt.slot)
+        .bind(&receipt.event_pda)
+        .bind(&receipt.merkle_path)
+        .bind(receipt.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Note a submission attempt before sending, keeping any signature already recorded
+    pub async fn begin_batch_submission(&self, batch_id: &str) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        sqlx::query(
+            r#"
+            INSERT INTO batch_submissions (batch_id, status, created_at, updated_at)
+            VALUES (?, 'submitting', ?, ?)
+            ON CONFLICT(batch_id) DO UPDATE SET updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(batch_id)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Store the transaction each landed deposit of the batch went out in, as
+    /// `(deposit_id, signature)` pairs
+    pub async fn record_batch_signatures(&self, batch_id: &str, landed: &[(String, String)]) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let mut tx = self.pool.begin().await?;
+        for (deposit_id, signature) in landed {
+            sqlx::query(
+                r#"
+                INSERT OR REPLACE INTO deposit_signatures (batch_id, deposit_id, solana_signature, created_at)
+                VALUES (?, ?, ?, ?)
+                "#,
+            )
+            .bind(batch_id)
+            .bind(deposit_id)
+            .bind(signature)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query("UPDATE batch_submissions SET status = 'submitted', updated_at = ? WHERE batch_id = ?")
+            .bind(now)
+            .bind(batch_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Every transaction a deposit went out in, whichever batch carried it
+    pub async fn get_deposit_signatures(&self, deposit_id: &str) -> Result<Vec<DepositSignatureRecord>, sqlx::Error> {
+        sqlx::query_as::<_, DepositSignatureRecord>(
+            "SELECT * FROM deposit_signatures WHERE deposit_id = ? ORDER BY created_at ASC",
+        )
+        .bind(deposit_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn get_batch_submission(&self, batch_id: &str) -> Result<Option<BatchSubmissionRecord>, sqlx::Error> {
+        sqlx::query_as::<_, BatchSubmissionRecord>("SELECT * FROM batch_submissions WHERE batch_id = ?")
+            .bind(batch_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Track a landed transaction until it is finalized
+    pub async fn track_signature(&self, signature: &str, batch_id: &str, batch: &str) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO tracked_signatures (signature, batch_id, batch, submitted_at, last_seen_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(signature)
+        .bind(batch_id)
+        .bind(batch)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_tracked_signatures(&self) -> Result<Vec<TrackedSignatureRecord>, sqlx::Error> {
+        sqlx::query_as::<_, TrackedSignatureRecord>("SELECT * FROM tracked_signatures ORDER BY submitted_at ASC")
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Note the cluster still knows `signature`
+    pub async fn touch_tracked_signature(&self, signature: &str) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        sqlx::query("UPDATE tracked_signatures SET last_seen_at = ? WHERE signature = ?")
+            .bind(now)
+            .bind(signature)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn untrack_signature(&self, signature: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM tracked_signatures WHERE signature = ?")
+            .bind(signature)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn cache_proof(&self, inputs_hash: &str, deposit_id: &str, proof: &str) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO proofs (inputs_hash, deposit_id, proof, created_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(inputs_hash)
+        .bind(deposit_id)
+        .bind(proof)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_cached_proof(&self, inputs_hash: &str) -> Result<Option<CachedProofRecord>, sqlx::Error> {
+        sqlx::query_as::<_, CachedProofRecord>("SELECT * FROM proofs WHERE inputs_hash = ?")
+            .bind(inputs_hash)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    pub async fn save_proof_job(&self, deposit_id: &str, service_url: &str, job_id: &str) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO proof_jobs (deposit_id, service_url, job_id, created_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(deposit_id)
+        .bind(service_url)
+        .bind(job_id)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_proof_job(&self, deposit_id: &str, service_url: &str) -> Result<Option<ProofJobRecord>, sqlx::Error> {
+        sqlx::query_as::<_, ProofJobRecord>("SELECT * FROM proof_jobs WHERE deposit_id = ? AND service_url = ?")
+            .bind(deposit_id)
+            .bind(service_url)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    pub async fn delete_proof_job(&self, deposit_id: &str, service_url: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM proof_jobs WHERE deposit_id = ? AND service_url = ?")
+            .bind(deposit_id)
+            .bind(service_url)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Record `deposit_id` with its proof (`entry`) as member `position` of the
+    /// open batch `batch_id`, creating the batch on its first member
+    pub async fn add_batch_member(
+        &self,
+        batch_id: &str,
+        created_at: i64,
+        deposit_id: &str,
+        position: usize,
+        entry: &str,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("INSERT OR IGNORE INTO batches (batch_id, status, created_at) VALUES (?, 'open', ?)")
+            .bind(batch_id)
+            .bind(created_at)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("UPDATE deposits SET batch_id = ?, batch_position = ?, batch_entry = ? WHERE deposit_id = ?")
+            .bind(batch_id)
+            .bind(position as i64)
+            .bind(entry)
+            .bind(deposit_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await
+    }
+
+    /// Mark the batch holding `deposit_id` sealed; its members keep their `batch_id`
+    pub async fn seal_batch_of(&self, deposit_id: &str) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let mut tx = self.pool.begin().await?;
+        let batch_id: Option<String> = sqlx::query_scalar("SELECT batch_id FROM deposits WHERE deposit_id = ?")
+            .bind(deposit_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .flatten();
+        if let Some(batch_id) = batch_id {
+            sqlx::query("UPDATE batches SET status = 'sealed', sealed_at = ? WHERE batch_id = ? AND status = 'open'")
+                .bind(now)
+                .bind(&batch_id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("UPDATE deposits SET batch_entry = NULL WHERE batch_id = ?")
+                .bind(&batch_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Batches still open, e.g. when the previous run crashed while filling them
+    pub async fn get_open_batches(&self) -> Result<Vec<BatchRecord>, sqlx::Error> {
+        sqlx::query_as::<_, BatchRecord>("SELECT * FROM batches WHERE status = 'open' ORDER BY created_at")
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Entries of the open batch's members still in flight, in batch order
+    pub async fn get_open_batch_entries(&self, batch_id: &str) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT batch_entry FROM deposits
+            WHERE batch_id = ? AND batch_entry IS NOT NULL AND status NOT IN ('completed', 'failed')
+            ORDER BY batch_position
+            "#,
+        )
+        .bind(batch_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Keep the first manifest stored for a batch: it is the commitment later
+    /// reconciliation checks against, even if the batch is retried or split
+    pub async fn store_batch_manifest(&self, record: &BatchManifestRecord) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO batch_manifests (batch_id, merkle_root, manifest, signer, signature, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT(batch_id) DO NOTHING
+            "#,
+        )
+        .bind(&record.batch_id)
+        .bind(&record.merkle_root)
+        .bind(&record.manifest)
+        .bind(&record.signer)
+        .bind(&record.signature)
+        .bind(record.created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn record_batch_split(&self, parent_id: &str, child_ids: &[String], reason: &str) -> Result<(), sqlx::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let mut tx = self.pool.begin().await?;
+        for child_id in child_ids {
+            sqlx::query("INSERT OR IGNORE INTO batch_splits (child_id, parent_id, reason, created_at) VALUES (?, ?, ?, ?)")
+                .bind(child_id)
+                .bind(parent_id)
+                .bind(reason)
+                .bind(now)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await
+    }
+
+    pub async fn get_batch_manifest(&self, batch_id: &str) -> Result<Option<BatchManifestRecord>, sqlx::Error> {
+        sqlx::query_as::<_, BatchManifestRecord>("SELECT * FROM batch_manifests WHERE batch_id = ?")
+            .bind(batch_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Log an accepted request; returns the existing entry instead if this exact request was seen before
+    pub async fn log_request(&self, mut record: RequestLogRecord) -> Result<Option<RequestLogRecord>, sqlx::Error> {
+        record.received_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO request_log (request_hash, api_key, deposit_id, request_id, received_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&record.request_hash)
+        .bind(&record.api_key)
+        .bind(&record.deposit_id)
+        .bind(&record.request_id)
+        .bind(record.received_at)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+
+        if inserted > 0 {
+            return Ok(None);
+        }
+
+        sqlx::query_as::<_, RequestLogRecord>("SELECT * FROM request_log WHERE request_hash = ?")
+            .bind(&record.request_hash)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// API key that submitted a deposit, from the request log
+    pub async fn get_request_api_key(&self, deposit_id: &str) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT api_key FROM request_log WHERE deposit_id = ? ORDER BY received_at ASC LIMIT 1")
+            .bind(deposit_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Replace the notification targets of a deposit or API key
+    pub async fn set_notification_targets(
+        &self,
+        scope: &str,
+        scope_id: &str,
+        targets: &[NotificationTarget],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM notification_targets WHERE scope = ? AND scope_id = ?")
+            .bind(scope)
+            .bind(scope_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for target in targets {
+            sqlx::query(
+                "INSERT OR IGNORE INTO notification_targets (scope, scope_id, channel, address) VALUES (?, ?, ?, ?)",
+            )
+            .bind(scope)
+            .bind(scope_id)
+            .bind(&target.channel)
+            .bind(&target.address)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await
+    }
+
+    pub async fn get_notification_targets(&self, scope: &str, scope_id: &str) -> Result<Vec<NotificationTarget>, sqlx::Error> {
+        sqlx::query_as::<_, NotificationTarget>(
+            "SELECT channel, address FROM notification_targets WHERE scope = ? AND scope_id = ?",
+        )
+        .bind(scope)
+        .bind(scope_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn get_request_log(&self, api_key: Option<&str>, limit: i64) -> Result<Vec<RequestLogRecord>, sqlx::Error> {
+        match api_key {
+            Some(api_key) => {
+                sqlx::query_as::<_, RequestLogRecord>(
+                    "SELECT * FROM request_log WHERE api_key = ? ORDER BY received_at DESC LIMIT ?",
+                )
+                .bind(api_key)
+                .bind(limit)
+                .fetch_all(&self.pool)
+                .await
+            }
+            None => {
+                sqlx::query_as::<_, RequestLogRecord>("SELECT * FROM request_log ORDER BY received_at DESC LIMIT ?")
+                    .bind(limit)
+                    .fetch_all(&self.pool)
+                    .await
+            }
+        }
+    }
+
+    /// Record where a deposit was archived and drop its hot receipt row
+    pub async fn record_archive(&self, location: &ArchiveLocation) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "INSERT OR REPLACE INTO archives (deposit_id, content_hash, ipfs_cid, archived_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(&location.deposit_id)
+        .bind(&location.content_hash)
+        .bind(&location.ipfs_cid)
+        .bind(location.archived_at)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM receipts WHERE deposit_id = ?")
+            .bind(&location.deposit_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await
+    }
+
+    pub async fn get_archive_location(&self, deposit_id: &str) -> Result<Option<ArchiveLocation>, sqlx::Error> {
+        sqlx::query_as::<_, ArchiveLocation>("SELECT * FROM archives WHERE deposit_id = ?")
+            .bind(deposit_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    pub async fn get_receipt(&self, deposit_id: &str) -> Result<Option<ReceiptRecord>, sqlx::Error> {
+        sqlx::query_as::<_, ReceiptRecord>("SELECT * FROM receipts WHERE deposit_id = ?")
+            .bind(deposit_id)
+            .fetch_optional(&self.pool)
+            .await
     }
 }
\ No newline at end of file
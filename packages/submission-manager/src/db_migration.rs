@@ -0,0 +1,202 @@
+use crate::database::DepositRecord;
+use serde::Serialize;
+use sqlx::postgres::PgPoolOptions;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Where a live move of the `deposits` table from SQLite to Postgres stands.
+/// SQLite takes every write first in all phases, so rolling back never loses
+/// data; the phase decides what happens to the Postgres copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationPhase {
+    /// SQLite only
+    Off,
+    /// Every deposit write is copied to Postgres; copy failures are logged
+    /// and left to the parity check
+    DualWrite,
+    /// Deposit reads are served from Postgres and copy failures fail the write
+    CutOver,
+}
+
+impl MigrationPhase {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MigrationPhase::Off => "off",
+            MigrationPhase::DualWrite => "dual_write",
+            MigrationPhase::CutOver => "cut_over",
+        }
+    }
+}
+
+impl FromStr for MigrationPhase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(MigrationPhase::Off),
+            "dual_write" => Ok(MigrationPhase::DualWrite),
+            "cut_over" => Ok(MigrationPhase::CutOver),
+            other => Err(format!("unknown migration phase '{}'", other)),
+        }
+    }
+}
+
+impl fmt::Display for MigrationPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Outcome of comparing the SQLite and Postgres `deposits` tables
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ParityReport {
+    pub primary_rows: usize,
+    pub mirror_rows: usize,
+    pub missing: usize,    // In SQLite, not in Postgres
+    pub mismatched: usize, // Present in both with different contents
+    pub extra: usize,      // In Postgres only
+    pub repaired: usize,
+    pub checked_at: i64,
+}
+
+impl ParityReport {
+    /// Every difference found was repaired: safe to cut over
+    pub fn is_clean(&self) -> bool {
+        self.missing + self.mismatched == self.repaired && self.extra == 0
+    }
+}
+
+/// The Postgres copy of the `deposits` table
+#[derive(Clone)]
+pub struct DepositMirror {
+    pool: PgPool,
+}
+
+impl DepositMirror {
+    pub async fn connect(url: &str) -> Result<Self, sqlx::Error> {
+        let pool = PgPoolOptions::new().max_connections(5).connect(url).await?;
+
+        // Same columns as the SQLite table so rows map onto DepositRecord
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS deposits (
+                deposit_id TEXT PRIMARY KEY,
+                ton_tx_hash TEXT NOT NULL,
+                sender_address TEXT NOT NULL,
+                recipient_solana TEXT NOT NULL,
+                amount TEXT NOT NULL,
+                status TEXT NOT NULL,
+                error_message TEXT,
+                created_at BIGINT NOT NULL,
+                updated_at BIGINT NOT NULL,
+                jetton_master TEXT,
+                jetton_symbol TEXT,
+                jetton_name TEXT,
+                jetton_decimals BIGINT,
+                accepted_ton_usd DOUBLE PRECISION,
+                accepted_sol_usd DOUBLE PRECISION,
+                completed_ton_usd DOUBLE PRECISION,
+                completed_sol_usd DOUBLE PRECISION,
+                status_detail TEXT,
+                nullifier TEXT,
+                nonce TEXT
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        for column in ["status_detail TEXT", "nullifier TEXT", "nonce TEXT"] {
+            sqlx::query(&format!("ALTER TABLE deposits ADD COLUMN IF NOT EXISTS {}", column))
+                .execute(&pool)
+                .await?;
+        }
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_deposits_ton_tx_hash ON deposits(ton_tx_hash)")
+            .execute(&pool)
+            .await?;
+
+        Ok(Self { pool })
+    }
+
+    /// Write the full row as SQLite holds it
+    pub async fn upsert(&self, deposit: &DepositRecord) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO deposits
+            (deposit_id, ton_tx_hash, sender_address, recipient_solana, amount, status, error_message,
+             created_at, updated_at, jetton_master, jetton_symbol, jetton_name, jetton_decimals,
+             accepted_ton_usd, accepted_sol_usd, completed_ton_usd, completed_sol_usd, status_detail, nullifier,
+             nonce)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
+            ON CONFLICT (deposit_id) DO UPDATE SET
+                ton_tx_hash = EXCLUDED.ton_tx_hash, sender_address = EXCLUDED.sender_address,
+                recipient_solana = EXCLUDED.recipient_solana, amount = EXCLUDED.amount,
+                status = EXCLUDED.status, error_message = EXCLUDED.error_message,
+                created_at = EXCLUDED.created_at, updated_at = EXCLUDED.updated_at,
+                jetton_master = EXCLUDED.jetton_master, jetton_symbol = EXCLUDED.jetton_symbol,
+                jetton_name = EXCLUDED.jetton_name, jetton_decimals = EXCLUDED.jetton_decimals,
+                accepted_ton_usd = EXCLUDED.accepted_ton_usd, accepted_sol_usd = EXCLUDED.accepted_sol_usd,
+                completed_ton_usd = EXCLUDED.completed_ton_usd, completed_sol_usd = EXCLUDED.completed_sol_usd,
+                status_detail = EXCLUDED.status_detail, nullifier = EXCLUDED.nullifier, nonce = EXCLUDED.nonce
+            "#,
+        )
+        .bind(&deposit.deposit_id)
+        .bind(&deposit.ton_tx_hash)
+        .bind(&deposit.sender_address)
+        .bind(&deposit.recipient_solana)
+        .bind(&deposit.amount)
+        .bind(&deposit.status)
+        .bind(&deposit.error_message)
+        .bind(deposit.created_at)
+        .bind(deposit.updated_at)
+        .bind(&deposit.jetton_master)
+        .bind(&deposit.jetton_symbol)
+        .bind(&deposit.jetton_name)
+        .bind(deposit.jetton_decimals)
+        .bind(deposit.accepted_ton_usd)
+        .bind(deposit.accepted_sol_usd)
+        .bind(deposit.completed_ton_usd)
+        .bind(deposit.completed_sol_usd)
+        .bind(&deposit.status_detail)
+        .bind(&deposit.nullifier)
+        .bind(&deposit.nonce)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_deposit(&self, deposit_id: &str) -> Result<Option<DepositRecord>, sqlx::Error> {
+        sqlx::query_as::<_, DepositRecord>("SELECT * FROM deposits WHERE deposit_id = $1")
+            .bind(deposit_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    pub async fn get_deposits_by_ton_tx_hash(&self, ton_tx_hash: &str) -> Result<Vec<DepositRecord>, sqlx::Error> {
+        sqlx::query_as::<_, DepositRecord>("SELECT * FROM deposits WHERE ton_tx_hash = $1 ORDER BY created_at ASC")
+            .bind(ton_tx_hash)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    pub async fn get_deposits_between(&self, from: i64, to: i64) -> Result<Vec<DepositRecord>, sqlx::Error> {
+        sqlx::query_as::<_, DepositRecord>(
+            "SELECT * FROM deposits WHERE created_at >= $1 AND created_at < $2 ORDER BY created_at ASC",
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Every row by deposit id, for parity checks
+    pub async fn all_deposits(&self) -> Result<HashMap<String, DepositRecord>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, DepositRecord>("SELECT * FROM deposits")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.into_iter().map(|row| (row.deposit_id.clone(), row)).collect())
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{OrchestratorError, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// Deployment target selected with `--env` (or `BRIDGE_ENV`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Environment {
+    Devnet,
+    Testnet,
+    Mainnet,
+}
+
+/// Defaults bundled for a deployment target; env vars still override each field
+#[derive(Debug, Clone)]
+pub struct EnvironmentPreset {
+    pub environment: Environment,
+    pub solana_rpc_url: &'static str,
+    pub solana_program_id: &'static str,
+    pub commitment: &'static str,
+    pub batch_size: usize,
+    pub max_retries: u32,
+    pub max_deposit_amount: u64,
+    pub mock_proving: bool,
+}
+
+impl Environment {
+    /// Resolve the environment from `--env <name>` / `--env=<name>`, falling back to `BRIDGE_ENV`
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Self> {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if let Some(name) = arg.strip_prefix("--env=") {
+                return name.parse();
+            }
+            if arg == "--env" {
+                let name = args.next().ok_or_else(|| {
+                    OrchestratorError::ConfigurationError("--env requires a value".to_string())
+                })?;
+                return name.parse();
+            }
+        }
+
+        std::env::var("BRIDGE_ENV")
+            .unwrap_or_else(|_| "devnet".to_string())
+            .parse()
+    }
+
+    pub fn preset(&self) -> EnvironmentPreset {
+        match self {
+            Environment::Devnet => EnvironmentPreset {
+                environment: *self,
+                solana_rpc_url: "https://api.devnet.solana.com",
+                solana_program_id: "8zcmz77ahioCSGX7QnmFL51a1A3qBY1nw5az7R11KF9o",
+                commitment: "confirmed",
+                batch_size: 5,
+                max_retries: 3,
+                max_deposit_amount: 1_000_000_000_000,
+                mock_proving: true,
+            },
+            Environment::Testnet => EnvironmentPreset {
+                environment: *self,
+                solana_rpc_url: "https://api.testnet.solana.com",
+                solana_program_id: "8zcmz77ahioCSGX7QnmFL51a1A3qBY1nw5az7R11KF9o",
+                commitment: "confirmed",
+                batch_size: 5,
+                max_retries: 5,
+                max_deposit_amount: 1_000_000_000_000,
+                mock_proving: false,
+            },
+            Environment::Mainnet => EnvironmentPreset {
+                environment: *self,
+                solana_rpc_url: "https://api.mainnet-beta.solana.com",
+                solana_program_id: "8zcmz77ahioCSGX7QnmFL51a1A3qBY1nw5az7R11KF9o",
+                commitment: "finalized",
+                batch_size: 10,
+                max_retries: 5,
+                max_deposit_amount: 100_000_000_000,
+                mock_proving: false,
+            },
+        }
+    }
+}
+
+impl FromStr for Environment {
+    type Err = OrchestratorError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "devnet" => Ok(Environment::Devnet),
+            "testnet" => Ok(Environment::Testnet),
+            "mainnet" | "mainnet-beta" => Ok(Environment::Mainnet),
+            other => Err(OrchestratorError::ConfigurationError(format!(
+                "Unknown environment '{}', expected devnet, testnet or mainnet",
+                other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Environment::Devnet => write!(f, "devnet"),
+            Environment::Testnet => write!(f, "testnet"),
+            Environment::Mainnet => write!(f, "mainnet"),
+        }
+    }
+}
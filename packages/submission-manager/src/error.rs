@@ -31,6 +31,97 @@ pub enum OrchestratorError {
     
     #[error("Batch processing failed: {reason}")]
     BatchProcessingFailed { reason: String },
+    
+    #[error("Invalid proof: {0}")]
+    InvalidProof(String),
+    
+    #[error("Refund failed for deposit {deposit_id}: {reason}")]
+    RefundFailed { deposit_id: String, reason: String },
+
+    #[error("Queue snapshot v{version} needs reader v{min_reader_version} or newer")]
+    UnsupportedSnapshotVersion { version: u32, min_reader_version: u32 },
+
+    #[error("Archive error: {0}")]
+    ArchiveError(String),
+
+    #[error("Database migration error: {0}")]
+    MigrationError(String),
+
+    #[error("Rejected by the bridge program: {}", .0.detail_code())]
+    ProgramRejected(crate::program_errors::ProgramFailure),
+
+    #[error("Transaction simulation failed: {error}")]
+    SimulationFailed { error: String, logs: Vec<String> },
+
+    #[error("Websocket subscription error: {0}")]
+    SubscriptionError(String),
+
+    #[error("Insufficient funds: {0}")]
+    InsufficientFunds(String),
+
+    #[error("Proof job {job_id} not done after {secs}s")]
+    ProofTimeout { job_id: String, secs: u64 },
+
+    #[error("gRPC error: {0}")]
+    GrpcError(String),
+
+    #[error("Invalid deposit: {0}")]
+    InvalidDeposit(String),
+
+    #[error("Proof for deposit {0} is anchored to a TON root the program no longer accepts")]
+    ProofExpired(String),
+
+    #[error("Transaction exceeds Solana limits: {0}")]
+    ResourceLimitExceeded(String),
+
+    #[error("Deposit {deposit_id} duplicates deposit {existing_id}")]
+    DuplicateDeposit { deposit_id: String, existing_id: String },
+}
+
+impl OrchestratorError {
+    /// Stable machine-readable code surfaced in API error responses
+    pub fn code(&self) -> &'static str {
+        match self {
+            OrchestratorError::NetworkError(_) => "network_error",
+            OrchestratorError::SerializationError(_) => "serialization_error",
+            OrchestratorError::DatabaseError(_) => "database_error",
+            OrchestratorError::SolanaError(_) => "solana_error",
+            OrchestratorError::MetricsError(_) => "metrics_error",
+            OrchestratorError::ConfigurationError(_) => "configuration_error",
+            OrchestratorError::InsufficientSignatures { .. } => "insufficient_signatures",
+            OrchestratorError::MaxRetriesExceeded { .. } => "max_retries_exceeded",
+            OrchestratorError::SystemUnhealthy { .. } => "system_unhealthy",
+            OrchestratorError::BatchProcessingFailed { .. } => "batch_processing_failed",
+            OrchestratorError::InvalidProof(_) => "invalid_proof",
+            OrchestratorError::RefundFailed { .. } => "refund_failed",
+            OrchestratorError::UnsupportedSnapshotVersion { .. } => "unsupported_snapshot_version",
+            OrchestratorError::ArchiveError(_) => "archive_error",
+            OrchestratorError::MigrationError(_) => "migration_error",
+            OrchestratorError::ProgramRejected(_) => "program_rejected",
+            OrchestratorError::SimulationFailed { .. } => "simulation_failed",
+            OrchestratorError::SubscriptionError(_) => "subscription_error",
+            OrchestratorError::InsufficientFunds(_) => "insufficient_funds",
+            OrchestratorError::ProofTimeout { .. } => "proof_timeout",
+            OrchestratorError::GrpcError(_) => "grpc_error",
+            OrchestratorError::ProofExpired(_) => "proof_expired",
+            OrchestratorError::InvalidDeposit(_) => "invalid_deposit",
+            OrchestratorError::DuplicateDeposit { .. } => "duplicate_deposit",
+            OrchestratorError::ResourceLimitExceeded(_) => "resource_limit_exceeded",
+        }
+    }
+
+    /// Failures that no amount of retrying the same submission will fix
+    pub fn is_permanent(&self) -> bool {
+        match self {
+            OrchestratorError::ProgramRejected(failure) => failure.is_permanent(),
+            OrchestratorError::InvalidProof(_) => true,
+            OrchestratorError::InvalidDeposit(_) => true,
+            OrchestratorError::DuplicateDeposit { .. } => true,
+            // Retrying the same transaction cannot shrink it; batches are split instead
+            OrchestratorError::ResourceLimitExceeded(_) => true,
+            _ => false,
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, OrchestratorError>;
\ No newline at end of file
@@ -0,0 +1,45 @@
+use crate::types::Batch;
+use tokio::sync::broadcast;
+
+const EVENT_BUS_CAPACITY: usize = 1024;
+
+/// Typed events passed between pipeline stages
+#[derive(Debug, Clone)]
+pub enum PipelineEvent {
+    DepositAccepted { deposit_id: String },
+    ProofReady { deposit_id: String },
+    BatchSealed(Batch),
+    BatchSubmitted { signature: String, deposit_ids: Vec<String> },
+    BatchConfirmed { signature: String, deposit_ids: Vec<String> },
+    BatchFailed { deposit_ids: Vec<String>, reason: String },
+    SpendGuardTripped { reason: String },
+}
+
+/// In-process broadcast bus; every stage publishes here and any number of
+/// consumers (the submitter, webhooks, indexers) can subscribe independently
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<PipelineEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn publish(&self, event: PipelineEvent) {
+        // No subscribers is fine: the event simply has no consumer yet
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<PipelineEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,144 @@
+use crate::database::{DatabaseService, ReceiptRecord};
+use crate::event_bus::{EventBus, PipelineEvent};
+use crate::metrics::BridgeMetrics;
+use crate::program_instructions::EVENT_SEED;
+use crate::public_inputs::hash_scalar;
+use crate::program_events::{self, ProgramEvent, TonEventVerified};
+use crate::{OrchestratorError, Result};
+use futures_util::StreamExt;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use std::time::Duration;
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Follows the program's finalized `TonEventVerified` events over a websocket
+/// and completes deposits the database does not know landed, e.g. because the
+/// manager crashed between sending a transaction and recording it
+pub struct EventListener {
+    ws_url: String,
+    program_id: Pubkey,
+    database: DatabaseService,
+    event_bus: EventBus,
+    metrics: BridgeMetrics,
+}
+
+impl EventListener {
+    pub fn new(
+        ws_url: String,
+        program_id: Pubkey,
+        database: DatabaseService,
+        event_bus: EventBus,
+        metrics: BridgeMetrics,
+    ) -> Self {
+        Self { ws_url, program_id, database, event_bus, metrics }
+    }
+
+    pub async fn run(self) {
+        loop {
+            if let Err(e) = self.listen().await {
+                log::warn!("Program event subscription on {} ended: {}", self.ws_url, e);
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    async fn listen(&self) -> Result<()> {
+        let client = PubsubClient::new(&self.ws_url).await.map_err(subscription_error)?;
+        let (mut notifications, unsubscribe) = client
+            .logs_subscribe(
+                RpcTransactionLogsFilter::Mentions(vec![self.program_id.to_string()]),
+                RpcTransactionLogsConfig { commitment: Some(CommitmentConfig::finalized()) },
+            )
+            .await
+            .map_err(subscription_error)?;
+        log::info!("👂 Listening for program events on {}", self.ws_url);
+
+        while let Some(notification) = notifications.next().await {
+            let logs = notification.value;
+            if logs.err.is_some() {
+                continue;
+            }
+            for event in program_events::decode_logs(&logs.logs) {
+                if let ProgramEvent::TonEventVerified(verified) = event {
+                    if let Err(e) = self.reconcile(&logs.signature, notification.context.slot, &verified).await {
+                        log::warn!("Could not reconcile event {}: {}", hex::encode(verified.event_id), e);
+                    }
+                }
+            }
+        }
+
+        unsubscribe().await;
+        Err(OrchestratorError::SubscriptionError("closed by the server".to_string()))
+    }
+
+    /// Complete the deposit behind `verified` unless the pipeline already
+    /// recorded its transaction
+    async fn reconcile(&self, signature: &str, slot: u64, verified: &TonEventVerified) -> Result<()> {
+        let ton_tx_hash = hex::encode(verified.ton_tx_hash);
+        let mut candidates = self.database.get_deposits_by_ton_tx_hash(&ton_tx_hash).await?;
+        if candidates.is_empty() {
+            candidates = self.database.get_deposits_by_ton_tx_hash(&format!("0x{}", ton_tx_hash)).await?;
+        }
+
+        let recipient = verified.recipient.to_string();
+        let amount = verified.amount.to_string();
+        let Some(deposit) = candidates
+            .into_iter()
+            .find(|deposit| deposit.recipient_solana == recipient && deposit.amount == amount)
+        else {
+            log::warn!("⚠️ {} verified TON tx {} for no known deposit", signature, ton_tx_hash);
+            return Ok(());
+        };
+
+        // Recorded by the pipeline: the finality loop completes confirming deposits
+        if matches!(deposit.status.as_str(), "completed" | "confirming") {
+            return Ok(());
+        }
+
+        log::warn!(
+            "🔁 Deposit {} landed in {} while recorded as {}, marking completed",
+            deposit.deposit_id,
+            signature,
+            deposit.status
+        );
+        self.database.update_deposit_status(&deposit.deposit_id, "completed", None).await?;
+        self.database.set_deposit_status_detail(&deposit.deposit_id, None).await?;
+        self.database
+            .store_receipt(ReceiptRecord {
+                deposit_id: deposit.deposit_id.clone(),
+                solana_signature: signature.to_string(),
+                slot: Some(slot as i64),
+                event_pda: Some(
+                    Pubkey::find_program_address(&[EVENT_SEED, &hash_scalar(&verified.event_id)], &self.program_id).0.to_string(),
+                ),
+                merkle_path: None,
+                created_at: 0,
+            })
+            .await?;
+        self.metrics.deposits_reconciled.inc();
+
+        self.event_bus.publish(PipelineEvent::BatchConfirmed {
+            signature: signature.to_string(),
+            deposit_ids: vec![deposit.deposit_id],
+        });
+        Ok(())
+    }
+}
+
+fn subscription_error(error: impl std::fmt::Display) -> OrchestratorError {
+    OrchestratorError::SubscriptionError(error.to_string())
+}
+
+/// Websocket endpoint of an RPC URL: same host, `ws(s)` scheme
+pub fn websocket_url(rpc_url: &str) -> String {
+    if let Some(rest) = rpc_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = rpc_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        rpc_url.to_string()
+    }
+}
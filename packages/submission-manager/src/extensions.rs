@@ -0,0 +1,101 @@
+use crate::event_bus::PipelineEvent;
+use crate::notifications::Notifier;
+use crate::proof::Proof;
+use crate::proof_orchestrator::ProofOrchestrator;
+use crate::prover_selection::ProverHealth;
+use crate::queue_manager::QueueManager;
+use crate::types::{Batch, Deposit, QueueStats};
+use crate::Result;
+use std::future::Future;
+use std::pin::Pin;
+use tokio::sync::broadcast;
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Produces the proof for a single deposit. The default is
+/// `ProofOrchestrator`, which calls the configured circuit services.
+pub trait Prover: Send + Sync {
+    fn generate_proof<'a>(&'a self, deposit: &'a Deposit) -> BoxFuture<'a, Result<Proof>>;
+
+    /// Health of the services behind the prover, if it has any
+    fn health(&self) -> Vec<ProverHealth> {
+        Vec::new()
+    }
+
+    /// TON root the program holds now, for new proofs to anchor to
+    fn set_anchor_root(&self, _root: [u8; 32]) {}
+
+    /// Refresh whatever load data the prover schedules on; run periodically
+    fn refresh_status<'a>(&'a self) -> BoxFuture<'a, ()> {
+        Box::pin(async {})
+    }
+}
+
+impl Prover for ProofOrchestrator {
+    fn generate_proof<'a>(&'a self, deposit: &'a Deposit) -> BoxFuture<'a, Result<Proof>> {
+        Box::pin(ProofOrchestrator::generate_proof(self, deposit))
+    }
+
+    fn health(&self) -> Vec<ProverHealth> {
+        self.prover_health()
+    }
+
+    fn set_anchor_root(&self, root: [u8; 32]) {
+        ProofOrchestrator::set_anchor_root(self, root)
+    }
+
+    fn refresh_status<'a>(&'a self) -> BoxFuture<'a, ()> {
+        Box::pin(self.refresh_prover_status())
+    }
+}
+
+/// FIFO of sealed batches awaiting submission. The default is the in-memory
+/// `QueueManager`; it is persisted through queue snapshots either way.
+pub trait BatchQueue: Send {
+    fn enqueue(&mut self, batch: Batch);
+    fn dequeue(&mut self) -> Option<Batch>;
+    /// Deposit count of the batch `dequeue` would return
+    fn peek_len(&self) -> Option<usize>;
+    fn batches(&self) -> Vec<Batch>;
+    /// Put batches from a snapshot ahead of anything queued since startup
+    fn restore(&mut self, batches: Vec<Batch>);
+    fn stats(&self) -> QueueStats;
+}
+
+impl BatchQueue for QueueManager {
+    fn enqueue(&mut self, batch: Batch) {
+        self.push(batch);
+    }
+
+    fn dequeue(&mut self) -> Option<Batch> {
+        self.pop()
+    }
+
+    fn peek_len(&self) -> Option<usize> {
+        self.peek_batch().map(|batch| batch.deposits.len())
+    }
+
+    fn batches(&self) -> Vec<Batch> {
+        QueueManager::batches(self).to_vec()
+    }
+
+    fn restore(&mut self, batches: Vec<Batch>) {
+        QueueManager::restore(self, batches);
+    }
+
+    fn stats(&self) -> QueueStats {
+        self.queue_stats()
+    }
+}
+
+/// Consumes pipeline events to tell users about their transfers. Every
+/// registered sink gets its own event bus subscription.
+pub trait NotificationSink: Send + Sync {
+    fn run(&self, events: broadcast::Receiver<PipelineEvent>) -> BoxFuture<'static, ()>;
+}
+
+impl NotificationSink for Notifier {
+    fn run(&self, events: broadcast::Receiver<PipelineEvent>) -> BoxFuture<'static, ()> {
+        Box::pin(Notifier::run(self.clone(), events))
+    }
+}
@@ -1,10 +1,83 @@
-// If you have existing GasOptimizer code, use that instead
-// Otherwise, here's a minimal placeholder:
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// Number of landed batches kept for the recent-actuals average
+const RECENT_ACTUALS_WINDOW: usize = 50;
+
+/// Base fee charged per transaction signature
+pub const BASE_FEE_LAMPORTS: u64 = 5_000;
+/// Compute units budgeted per `verify_ton_event` instruction
+pub const COMPUTE_UNITS_PER_DEPOSIT: u64 = 200_000;
+/// Percentile of recent prioritization fees bid, to land ahead of most traffic
+const PRIORITY_FEE_PERCENTILE: usize = 75;
+
+/// Tracks current fee conditions shared by everything that prices a submission
 #[derive(Clone)]
-pub struct GasOptimizer;
+pub struct GasOptimizer {
+    priority_fee_micro_lamports: Arc<AtomicU64>,
+    max_priority_fee_micro_lamports: u64, // 0 = uncapped
+    recent_actuals: Arc<RwLock<VecDeque<(u64, usize)>>>, // (fee lamports, deposits)
+}
 
 impl GasOptimizer {
-    pub fn new(_gas_update_interval: u64) -> Self {
-        Self
+    pub fn new(max_priority_fee_micro_lamports: u64) -> Self {
+        Self {
+            priority_fee_micro_lamports: Arc::new(AtomicU64::new(0)),
+            max_priority_fee_micro_lamports,
+            recent_actuals: Arc::new(RwLock::new(VecDeque::new())),
+        }
+    }
+
+    /// Current compute-unit price in micro-lamports
+    pub fn priority_fee(&self) -> u64 {
+        self.priority_fee_micro_lamports.load(Ordering::Relaxed)
+    }
+
+    pub fn set_priority_fee(&self, micro_lamports: u64) {
+        self.priority_fee_micro_lamports.store(micro_lamports, Ordering::Relaxed);
+    }
+
+    /// Set the price from prioritization fees recently paid for the program's
+    /// accounts, capped so congestion can't run away with the fee budget
+    pub fn observe_recent_fees(&self, mut fees: Vec<u64>) -> u64 {
+        if fees.is_empty() {
+            return self.priority_fee();
+        }
+        fees.sort_unstable();
+        let mut fee = fees[(fees.len() - 1) * PRIORITY_FEE_PERCENTILE / 100];
+        if self.max_priority_fee_micro_lamports > 0 {
+            fee = fee.min(self.max_priority_fee_micro_lamports);
+        }
+        self.set_priority_fee(fee);
+        fee
+    }
+
+    /// Estimated lamports to submit one transaction carrying `deposits` deposits
+    pub fn estimate_transaction_cost(&self, deposits: usize) -> u64 {
+        let compute_units = COMPUTE_UNITS_PER_DEPOSIT * deposits as u64;
+        BASE_FEE_LAMPORTS + compute_units * self.priority_fee() / 1_000_000
+    }
+
+    /// Record the fee actually paid for a landed batch
+    pub fn record_actual_cost(&self, fee_lamports: u64, deposits: usize) {
+        let mut actuals = self.recent_actuals.write().unwrap();
+        actuals.push_back((fee_lamports, deposits));
+        while actuals.len() > RECENT_ACTUALS_WINDOW {
+            actuals.pop_front();
+        }
+    }
+
+    /// Average fee per deposit over recently landed batches
+    pub fn recent_cost_per_deposit(&self) -> Option<u64> {
+        let actuals = self.recent_actuals.read().unwrap();
+        let (fees, deposits) = actuals
+            .iter()
+            .fold((0u64, 0usize), |(f, d), (fee, n)| (f + fee, d + n));
+        if deposits == 0 {
+            None
+        } else {
+            Some(fees / deposits as u64)
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,151 @@
+use crate::database::{DatabaseService, SubmitterLeaseRecord};
+use crate::Result;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+/// Where this instance stands in the submitter lease
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaseState {
+    /// Another instance owns the lease; serve the API but submit nothing
+    Standby,
+    /// This instance dequeues and submits batches
+    Owner,
+    /// A newer instance asked to take over; stop sealing and hand the queue over
+    Draining,
+    /// Lease released to the new owner; this instance is on its way out
+    HandedOver,
+}
+
+/// Snapshot reported by the admin API
+#[derive(Debug, Clone, Serialize)]
+pub struct HandoverStatus {
+    pub instance_id: String,
+    pub version: String,
+    pub state: LeaseState,
+    pub lease: Option<SubmitterLeaseRecord>,
+}
+
+struct Shared {
+    state: Mutex<LeaseState>,
+    // Set once the lease owner restored the queue snapshot and may overwrite it
+    snapshot_writable: AtomicBool,
+    drained: Notify,
+}
+
+/// Coordinates zero-downtime deploys between two manager versions. Only the
+/// holder of the DB submitter lease dequeues batches. A new instance starts in
+/// standby and asks the owner to hand over; the owner stops accepting
+/// deposits, lets its in-flight submission finish, seals its open batch into
+/// the persisted queue and releases the lease, which the new instance then
+/// picks up together with the queue.
+#[derive(Clone)]
+pub struct HandoverCoordinator {
+    database: DatabaseService,
+    instance_id: String,
+    version: String,
+    ttl_secs: u64,
+    request_handover: bool,
+    shared: Arc<Shared>,
+}
+
+impl HandoverCoordinator {
+    pub fn new(database: DatabaseService, ttl_secs: u64, request_handover: bool) -> Self {
+        let instance_id = format!("{}-{:x}", std::process::id(), chrono::Utc::now().timestamp_millis());
+        Self {
+            database,
+            instance_id,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            ttl_secs,
+            request_handover,
+            shared: Arc::new(Shared {
+                state: Mutex::new(LeaseState::Standby),
+                snapshot_writable: AtomicBool::new(false),
+                drained: Notify::new(),
+            }),
+        }
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    pub fn state(&self) -> LeaseState {
+        *self.shared.state.lock().unwrap()
+    }
+
+    /// New deposits are refused once the handover has started
+    pub fn is_draining(&self) -> bool {
+        matches!(self.state(), LeaseState::Draining | LeaseState::HandedOver)
+    }
+
+    /// Renew or acquire the lease and work out what the submitter may do this
+    /// tick. Called once per submitter tick, which must stay well inside the TTL.
+    pub async fn tick(&self) -> Result<LeaseState> {
+        let state = self.state();
+        if matches!(state, LeaseState::Draining | LeaseState::HandedOver) {
+            return Ok(state);
+        }
+
+        let next = if self.database.acquire_submitter_lease(&self.instance_id, &self.version, self.ttl_secs).await? {
+            let lease = self.database.get_submitter_lease().await?;
+            match lease.and_then(|lease| lease.handover_to) {
+                Some(successor) if successor != self.instance_id => {
+                    log::info!("🔁 Instance {} requested the submitter lease, draining", successor);
+                    LeaseState::Draining
+                }
+                _ => LeaseState::Owner,
+            }
+        } else {
+            if state == LeaseState::Owner {
+                log::warn!("Submitter lease lost to another instance, no longer submitting");
+            }
+            if self.request_handover {
+                // Re-asserted every tick so the request does not go stale
+                self.database.request_lease_handover(&self.instance_id).await?;
+            }
+            LeaseState::Standby
+        };
+
+        if next != state {
+            log::info!("Submitter lease for {} (v{}): {:?} -> {:?}", self.instance_id, self.version, state, next);
+            *self.shared.state.lock().unwrap() = next;
+            if next == LeaseState::Draining {
+                self.shared.drained.notify_one();
+            }
+        }
+        Ok(next)
+    }
+
+    pub fn set_snapshot_writable(&self, writable: bool) {
+        self.shared.snapshot_writable.store(writable, Ordering::SeqCst);
+    }
+
+    pub fn snapshot_writable(&self) -> bool {
+        self.shared.snapshot_writable.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once this instance has started draining for a successor
+    pub async fn draining(&self) {
+        self.shared.drained.notified().await
+    }
+
+    /// Give the lease to the waiting successor; call after the queue is persisted
+    pub async fn release(&self) -> Result<()> {
+        self.database.release_submitter_lease(&self.instance_id).await?;
+        *self.shared.state.lock().unwrap() = LeaseState::HandedOver;
+        log::info!("🤝 Submitter lease released by {}", self.instance_id);
+        Ok(())
+    }
+
+    pub async fn status(&self) -> Result<HandoverStatus> {
+        Ok(HandoverStatus {
+            instance_id: self.instance_id.clone(),
+            version: self.version.clone(),
+            state: self.state(),
+            lease: self.database.get_submitter_lease().await?,
+        })
+    }
+}
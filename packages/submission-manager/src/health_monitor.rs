@@ -1,6 +1,13 @@
+use crate::prover_selection::ProverHealth;
 use crate::types::SystemHealth;
 use crate::Result;
 
+/// The program's monotonic `LcState` counters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventCounters {
+    pub events_verified: u64,
+    pub total_amount_bridged: u128,
+}
 
 #[derive(Clone)]
 pub struct HealthMonitor;
@@ -10,17 +17,45 @@ impl HealthMonitor {
         Self
     }
 
-    pub async fn get_system_health(&self) -> Result<SystemHealth> {
+    /// `provers`: the circuit services; proving works while any is not backing off
+    pub async fn get_system_health(&self, provers: &[ProverHealth]) -> Result<SystemHealth> {
+        for prover in provers.iter().filter(|prover| !prover.healthy) {
+            log::warn!(
+                "⚠️ Circuit service {} backing off after {} failures",
+                prover.url,
+                prover.consecutive_failures
+            );
+        }
+
         Ok(SystemHealth {
             ton_rpc: true,
             solana_rpc: true,
             database: true,
-            validators: true,
+            validators: provers.is_empty() || provers.iter().any(|prover| prover.healthy),
             queue_size: true,
             last_batch_time: true,
         })
     }
 
+    /// Compare locally completed deposits with the on-chain counters. Returns
+    /// how many more events the database claims than the program verified;
+    /// positive drift means deposits were marked completed without landing.
+    /// Counters start at zero on migration, so negative drift is expected on
+    /// older deployments and only positive drift is reported.
+    pub fn reconcile_event_counters(&self, local: EventCounters, onchain: EventCounters) -> i64 {
+        let drift = local.events_verified as i64 - onchain.events_verified as i64;
+        if drift > 0 || (drift == 0 && local.total_amount_bridged != onchain.total_amount_bridged) {
+            log::warn!(
+                "⚠️ Event counter drift: database has {} completed ({} bridged), program has {} verified ({} bridged)",
+                local.events_verified,
+                local.total_amount_bridged,
+                onchain.events_verified,
+                onchain.total_amount_bridged
+            );
+        }
+        drift
+    }
+
     pub fn is_system_healthy(&self, health: &SystemHealth) -> bool {
         health.ton_rpc && health.solana_rpc && health.database && 
         health.validators && health.queue_size && health.last_batch_time
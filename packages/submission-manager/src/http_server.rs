@@ -1,11 +1,91 @@
 use warp::Filter;
+use warp::http::StatusCode;
 use std::convert::Infallible;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use serde::{Deserialize, Serialize};
-use crate::SubmissionManager;
-use crate::types::Deposit;
+use crate::{NoticeKind, Proof, SubmissionManager};
+use crate::status_page::STATUS_CACHE_SECS;
+use crate::program_errors::ProgramFailure;
+use crate::types::{Deposit, RequestAdmission, ValidationIssue};
+use crate::database::{DepositRecord, ReceiptRecord};
+use crate::OrchestratorError;
+use crate::notifications::{self, NotificationTarget};
 use prometheus::{TextEncoder, Encoder};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Version of the response DTOs. Fields are only ever added within a version;
+/// removals or type changes require bumping it.
+pub const API_VERSION: &str = "1";
+
+static REQUEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Standard envelope wrapping every `/api` response
+#[derive(Debug, Serialize)]
+pub struct ApiResponse<T: Serialize> {
+    pub data: Option<T>,
+    pub error: Option<ApiError>,
+    pub meta: ResponseMeta,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiError {
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResponseMeta {
+    pub request_id: String,
+    pub version: &'static str,
+}
+
+type EnvelopeReply = warp::reply::WithStatus<warp::reply::Json>;
+
+fn respond<T: Serialize>(request_id: String, status: StatusCode, data: Option<T>, error: Option<ApiError>) -> EnvelopeReply {
+    let body = ApiResponse {
+        data,
+        error,
+        meta: ResponseMeta {
+            request_id,
+            version: API_VERSION,
+        },
+    };
+    warp::reply::with_status(warp::reply::json(&body), status)
+}
+
+fn ok_reply<T: Serialize>(request_id: String, data: T) -> EnvelopeReply {
+    respond(request_id, StatusCode::OK, Some(data), None)
+}
+
+fn error_reply(request_id: String, status: StatusCode, code: &str, message: String) -> EnvelopeReply {
+    respond::<()>(
+        request_id,
+        status,
+        None,
+        Some(ApiError {
+            code: code.to_string(),
+            message,
+        }),
+    )
+}
+
+fn orchestrator_error_reply(request_id: String, error: &OrchestratorError) -> EnvelopeReply {
+    error_reply(request_id, StatusCode::INTERNAL_SERVER_ERROR, error.code(), error.to_string())
+}
+
+/// Use the caller's `x-request-id` when present so logs can be correlated end to end
+fn with_request_id() -> impl Filter<Extract = (String,), Error = warp::Rejection> + Clone {
+    warp::header::optional::<String>("x-request-id").map(|id: Option<String>| {
+        id.unwrap_or_else(|| {
+            format!(
+                "{:x}-{:x}",
+                chrono::Utc::now().timestamp_millis(),
+                REQUEST_COUNTER.fetch_add(1, Ordering::Relaxed)
+            )
+        })
+    })
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DepositRequest {
@@ -17,6 +97,119 @@ pub struct DepositRequest {
     pub fee_est: String,
     pub nonce: String,
     pub created_at: u64,
+    #[serde(default)]
+    pub recipient_commitment: Option<String>,
+    #[serde(default)]
+    pub recipient_salt: Option<String>,
+    #[serde(default)]
+    pub event_id: Option<String>,
+    #[serde(default)]
+    pub nullifier: Option<String>,
+    #[serde(default)]
+    pub jetton_master: Option<String>,
+    #[serde(default)]
+    pub priority: bool,
+    #[serde(default)]
+    pub notify: Vec<NotificationTarget>,
+}
+
+impl DepositRequest {
+    /// Split into the pipeline deposit and its notification targets
+    pub fn into_deposit(self) -> (Deposit, Vec<NotificationTarget>) {
+        let deposit = Deposit {
+            deposit_id: self.deposit_id,
+            ton_tx_hash: self.ton_tx_hash,
+            sender_address: self.sender_address,
+            recipient_solana: self.recipient_solana,
+            amount: self.amount,
+            fee_est: self.fee_est,
+            nonce: self.nonce,
+            created_at: self.created_at,
+            recipient_commitment: self.recipient_commitment,
+            recipient_salt: self.recipient_salt,
+            event_id: self.event_id,
+            nullifier: self.nullifier,
+            jetton_master: self.jetton_master,
+            priority: self.priority,
+        };
+        (deposit, self.notify)
+    }
+}
+
+/// Body of `PUT /api/admin/status-notice`; `starts_at` defaults to now
+#[derive(Debug, Deserialize)]
+pub struct StatusNoticeRequest {
+    pub kind: NoticeKind,
+    pub message: String,
+    #[serde(default)]
+    pub starts_at: Option<i64>,
+    #[serde(default)]
+    pub ends_at: Option<i64>,
+}
+
+/// Body of `POST /api/proofs`; the proof carries its public inputs
+#[derive(Debug, Deserialize)]
+pub struct ExternalProofRequest {
+    pub deposit_id: String,
+    pub proof: Proof,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DepositValidationResponse {
+    pub valid: bool,
+    pub errors: Vec<ValidationIssue>,
+    /// Large-transfer hold the deposit would get, if any
+    pub delay_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DepositAcceptedResponse {
+    pub deposit_id: String,
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RequestLogQuery {
+    pub api_key: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    pub from: i64,
+    pub to: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EstimateQuery {
+    pub deposits: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CancelDepositRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DepositStatusResponse {
+    pub deposit_id: String,
+    pub status: String,
+    pub error_message: Option<String>,
+    pub delay_remaining_secs: Option<u64>,
+    pub status_detail: Option<StatusDetail>, // Why the Solana program rejected the deposit
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusDetail {
+    pub code: String,
+    pub message: &'static str,
+}
+
+impl StatusDetail {
+    fn from_code(code: String) -> Option<Self> {
+        let failure = ProgramFailure::from_detail_code(&code)?;
+        Some(Self { code, message: failure.message() })
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -26,49 +219,577 @@ pub struct QueueStatsResponse {
     pub completed: usize,
 }
 
-pub async fn start_http_server(manager: Arc<Mutex<SubmissionManager>>) {
-    // Health check endpoint
-    let health = warp::path!("health")
-        .map(|| warp::reply::json(&serde_json::json!({"status": "healthy"})));
+#[derive(Debug, Serialize)]
+pub struct DepositResponse {
+    pub deposit_id: String,
+    pub ton_tx_hash: String,
+    pub sender_address: String,
+    pub recipient_solana: String,
+    pub amount: String,
+    pub status: String,
+    pub error_message: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub token: Option<TokenResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TokenResponse {
+    pub jetton_master: String,
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+    pub decimals: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReceiptResponse {
+    pub deposit_id: String,
+    pub solana_signature: String,
+    pub slot: Option<i64>,
+    pub event_pda: Option<String>,
+    pub merkle_path: Option<Vec<String>>,
+    pub created_at: i64,
+}
+
+impl From<ReceiptRecord> for ReceiptResponse {
+    fn from(record: ReceiptRecord) -> Self {
+        Self {
+            deposit_id: record.deposit_id,
+            solana_signature: record.solana_signature,
+            slot: record.slot,
+            event_pda: record.event_pda,
+            merkle_path: record.merkle_path.and_then(|path| serde_json::from_str(&path).ok()),
+            created_at: record.created_at,
+        }
+    }
+}
+
+impl From<DepositRecord> for DepositResponse {
+    fn from(record: DepositRecord) -> Self {
+        Self {
+            deposit_id: record.deposit_id,
+            ton_tx_hash: record.ton_tx_hash,
+            sender_address: record.sender_address,
+            recipient_solana: record.recipient_solana,
+            amount: record.amount,
+            status: record.status,
+            error_message: record.error_message,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+            token: record.jetton_master.map(|jetton_master| TokenResponse {
+                jetton_master,
+                symbol: record.jetton_symbol,
+                name: record.jetton_name,
+                decimals: record.jetton_decimals,
+            }),
+        }
+    }
+}
+
+fn deposits_csv(records: &[DepositRecord]) -> String {
+    fn rate(value: Option<f64>) -> String {
+        value.map(|v| v.to_string()).unwrap_or_default()
+    }
+
+    let mut csv = String::from(
+        "deposit_id,ton_tx_hash,recipient_solana,amount,jetton_symbol,status,created_at,updated_at,\
+         accepted_ton_usd,accepted_sol_usd,completed_ton_usd,completed_sol_usd\n",
+    );
+    for r in records {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{}\n",
+            r.deposit_id,
+            r.ton_tx_hash,
+            r.recipient_solana,
+            r.amount,
+            r.jetton_symbol.as_deref().unwrap_or("TON"),
+            r.status,
+            r.created_at,
+            r.updated_at,
+            rate(r.accepted_ton_usd),
+            rate(r.accepted_sol_usd),
+            rate(r.completed_ton_usd),
+            rate(r.completed_sol_usd),
+        ));
+    }
+    csv
+}
+
+/// Serve the HTTP API until `shutdown` resolves, then drain in-flight requests
+pub async fn start_http_server<F>(manager: Arc<Mutex<SubmissionManager>>, shutdown: F)
+where
+    F: std::future::Future<Output = ()> + Send + 'static,
+{
+    // Health check endpoint, with the Solana RPC endpoint in use and circuit service backoff
+    let health = {
+        let manager = manager.clone();
+        warp::path!("health").and_then(move || {
+            let manager = manager.clone();
+            async move {
+                let (endpoints, provers) = {
+                    let manager = manager.lock().await;
+                    (manager.rpc_endpoints(), manager.prover_health())
+                };
+                let proving = provers.is_empty() || provers.iter().any(|prover| prover.healthy);
+                let status = if endpoints.iter().any(|endpoint| endpoint.healthy) && proving {
+                    "healthy"
+                } else {
+                    "degraded"
+                };
+                Ok::<_, Infallible>(warp::reply::json(&serde_json::json!({
+                    "status": status,
+                    "solana_rpc": {
+                        "active": endpoints.iter().find(|endpoint| endpoint.active).map(|endpoint| &endpoint.url),
+                        "endpoints": endpoints,
+                    },
+                    "circuit_services": provers,
+                })))
+            }
+        })
+    };
 
     // Add deposit endpoint
     let add_deposit = {
         let manager = manager.clone();
         warp::path!("api" / "deposits")
+            .and(warp::post())
+            .and(with_request_id())
+            .and(warp::header::optional::<String>("x-api-key"))
+            .and(warp::body::bytes())
+            .and_then(move |request_id: String, api_key: Option<String>, body: warp::hyper::body::Bytes| {
+                let manager = manager.clone();
+                async move {
+                    let deposit: DepositRequest = match serde_json::from_slice(&body) {
+                        Ok(deposit) => deposit,
+                        Err(e) => {
+                            return Ok::<_, Infallible>(error_reply(
+                                request_id,
+                                StatusCode::BAD_REQUEST,
+                                "invalid_request",
+                                e.to_string(),
+                            ));
+                        }
+                    };
+                    let deposit_id = deposit.deposit_id.clone();
+                    let api_key = api_key.unwrap_or_else(|| "anonymous".to_string());
+
+                    let admission = {
+                        let mgr = manager.lock().await;
+                        if !mgr.role().runs_ingest() {
+                            return Ok(error_reply(
+                                request_id,
+                                StatusCode::SERVICE_UNAVAILABLE,
+                                "not_ingesting",
+                                format!("this manager runs the {} role and does not accept deposits", mgr.role()),
+                            ));
+                        }
+                        if mgr.is_draining() {
+                            return Ok(error_reply(
+                                request_id,
+                                StatusCode::SERVICE_UNAVAILABLE,
+                                "draining",
+                                "manager is handing over to a new instance, retry shortly".to_string(),
+                            ));
+                        }
+                        mgr.admit_request(&body, &api_key, &deposit_id, &request_id).await
+                    };
+                    match admission {
+                        Ok(RequestAdmission::New) => {
+                            let duplicate = manager
+                                .lock()
+                                .await
+                                .check_duplicate_deposit(&deposit_id, &deposit.ton_tx_hash, &deposit.nonce)
+                                .await;
+                            match duplicate {
+                                Ok(()) => {}
+                                Err(e @ OrchestratorError::DuplicateDeposit { .. }) => {
+                                    return Ok(error_reply(request_id, StatusCode::CONFLICT, e.code(), e.to_string()));
+                                }
+                                Err(e) => return Ok(orchestrator_error_reply(request_id, &e)),
+                            }
+                        }
+                        Ok(RequestAdmission::Duplicate) => {
+                            return Ok(ok_reply(request_id, DepositAcceptedResponse {
+                                deposit_id,
+                                status: "processing".to_string(),
+                            }));
+                        }
+                        Ok(RequestAdmission::Replay) => {
+                            return Ok(error_reply(
+                                request_id,
+                                StatusCode::CONFLICT,
+                                "replayed_request",
+                                format!("request for deposit {} was already accepted", deposit_id),
+                            ));
+                        }
+                        Err(e) => {
+                            log::error!("❌ Failed to log request for deposit {}: {}", deposit_id, e);
+                            return Ok(orchestrator_error_reply(request_id, &e));
+                        }
+                    }
+
+                    // Use tokio::spawn to handle async operations
+                    tokio::spawn(async move {
+                        let mut mgr = manager.lock().await;
+                        let (internal_deposit, notify) = deposit.into_deposit();
+
+                        if !notify.is_empty() {
+                            if let Err(e) = mgr
+                                .set_notification_targets(notifications::SCOPE_DEPOSIT, &internal_deposit.deposit_id, &notify)
+                                .await
+                            {
+                                log::error!("❌ Failed to store notification targets for {}: {}", internal_deposit.deposit_id, e);
+                            }
+                        }
+
+                        let deposit_id = internal_deposit.deposit_id.clone();
+                        match mgr.add_deposit(internal_deposit).await {
+                            Ok(()) => log::info!("✅ Deposit {} queued successfully", deposit_id),
+                            Err(e) => log::error!("❌ Failed to queue deposit {}: {}", deposit_id, e),
+                        }
+                    });
+
+                    // Return immediate response - processing happens in background
+                    Ok(ok_reply(request_id, DepositAcceptedResponse {
+                        deposit_id,
+                        status: "processing".to_string(),
+                    }))
+                }
+            })
+    };
+
+    // Pre-flight a deposit: every ingestion check, nothing stored or queued
+    let validate_deposit = {
+        let manager = manager.clone();
+        warp::path!("api" / "deposits" / "validate")
             .and(warp::post())
             .and(warp::body::json())
-            .map(move |deposit: DepositRequest| {
+            .and(with_request_id())
+            .and_then(move |deposit: DepositRequest, request_id: String| {
                 let manager = manager.clone();
-                
-                // Use tokio::spawn to handle async operations
-                tokio::spawn(async move {
-                    let mut mgr = manager.lock().await;
-                    
-                    let internal_deposit = Deposit {
-                        deposit_id: deposit.deposit_id.clone(),
-                        ton_tx_hash: deposit.ton_tx_hash,
-                        sender_address: deposit.sender_address,
-                        recipient_solana: deposit.recipient_solana,
-                        amount: deposit.amount,
-                        fee_est: deposit.fee_est,
-                        nonce: deposit.nonce,
-                        created_at: deposit.created_at,
+                async move {
+                    let (deposit, _) = deposit.into_deposit();
+                    let mgr = manager.lock().await;
+                    let errors = match mgr.preflight_deposit(&deposit).await {
+                        Ok(errors) => errors,
+                        Err(e) => {
+                            log::error!("❌ Failed to validate deposit {}: {}", deposit.deposit_id, e);
+                            return Ok::<_, Infallible>(orchestrator_error_reply(request_id, &e));
+                        }
+                    };
+
+                    let response = DepositValidationResponse {
+                        valid: errors.is_empty(),
+                        delay_secs: mgr.preflight_delay_secs(&deposit),
+                        errors,
+                    };
+                    // Same error code the deposit would be rejected with, plus every issue in `data`
+                    let reply = match response.errors.first() {
+                        None => ok_reply(request_id, response),
+                        Some(first) => {
+                            let error = ApiError {
+                                code: first.code.to_string(),
+                                message: first.message.clone(),
+                            };
+                            respond(request_id, StatusCode::UNPROCESSABLE_ENTITY, Some(response), Some(error))
+                        }
                     };
+                    Ok::<_, Infallible>(reply)
+                }
+            })
+    };
 
-                    match mgr.add_deposit(internal_deposit).await {
-                        Ok(()) => {
-                            log::info!("✅ Deposit {} queued successfully", deposit.deposit_id);
-                            warp::reply::json(&serde_json::json!({"status": "queued"}))
+    // Proof generated by a user or partner prover for a deposit waiting on the circuit services
+    let submit_proof = {
+        let manager = manager.clone();
+        warp::path!("api" / "proofs")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_request_id())
+            .and_then(move |request: ExternalProofRequest, request_id: String| {
+                let manager = manager.clone();
+                async move {
+                    let mgr = manager.lock().await;
+                    let deposit_id = request.deposit_id;
+                    let reply = match mgr.submit_external_proof(&deposit_id, request.proof).await {
+                        Ok(true) => ok_reply(request_id, DepositAcceptedResponse {
+                            deposit_id,
+                            status: "proved".to_string(),
+                        }),
+                        Ok(false) => error_reply(
+                            request_id,
+                            StatusCode::CONFLICT,
+                            "deposit_not_awaiting_proof",
+                            format!("deposit {} is not waiting for a prover", deposit_id),
+                        ),
+                        Err(e @ OrchestratorError::InvalidProof(_)) => {
+                            error_reply(request_id, StatusCode::UNPROCESSABLE_ENTITY, e.code(), e.to_string())
+                        }
+                        Err(e @ OrchestratorError::ConfigurationError(_)) => {
+                            error_reply(request_id, StatusCode::SERVICE_UNAVAILABLE, e.code(), e.to_string())
                         }
                         Err(e) => {
-                            log::error!("❌ Failed to queue deposit {}: {}", deposit.deposit_id, e);
-                            warp::reply::json(&serde_json::json!({"error": e.to_string()}))
+                            log::error!("❌ Failed to accept proof for deposit {}: {}", deposit_id, e);
+                            orchestrator_error_reply(request_id, &e)
                         }
+                    };
+                    Ok::<_, Infallible>(reply)
+                }
+            })
+    };
+
+    // Default notification targets for every deposit of an API key (admin key required)
+    let api_key_notifications = {
+        let manager = manager.clone();
+        warp::path!("api" / "admin" / "notifications" / String)
+            .and(warp::put())
+            .and(warp::header::optional::<String>("x-admin-key"))
+            .and(warp::body::json())
+            .and(with_request_id())
+            .and_then(move |api_key: String, admin_key: Option<String>, targets: Vec<NotificationTarget>, request_id: String| {
+                let manager = manager.clone();
+                async move {
+                    let mgr = manager.lock().await;
+                    if !admin_key.is_some_and(|key| mgr.is_admin_key(&key)) {
+                        return Ok::<_, Infallible>(error_reply(
+                            request_id,
+                            StatusCode::FORBIDDEN,
+                            "forbidden",
+                            "admin key required".to_string(),
+                        ));
+                    }
+
+                    let reply = match mgr.set_notification_targets(notifications::SCOPE_API_KEY, &api_key, &targets).await {
+                        Ok(()) => ok_reply(request_id, targets),
+                        Err(e) => {
+                            log::error!("❌ Failed to store notification targets for {}: {}", api_key, e);
+                            orchestrator_error_reply(request_id, &e)
+                        }
+                    };
+                    Ok::<_, Infallible>(reply)
+                }
+            })
+    };
+
+    // Accepted-request log for abuse investigations (admin key required)
+    let request_log = {
+        let manager = manager.clone();
+        warp::path!("api" / "admin" / "request-log")
+            .and(warp::get())
+            .and(warp::header::optional::<String>("x-admin-key"))
+            .and(warp::query::<RequestLogQuery>())
+            .and(with_request_id())
+            .and_then(move |admin_key: Option<String>, query: RequestLogQuery, request_id: String| {
+                let manager = manager.clone();
+                async move {
+                    let mgr = manager.lock().await;
+                    if !admin_key.is_some_and(|key| mgr.is_admin_key(&key)) {
+                        return Ok::<_, Infallible>(error_reply(
+                            request_id,
+                            StatusCode::FORBIDDEN,
+                            "forbidden",
+                            "admin key required".to_string(),
+                        ));
+                    }
+
+                    let limit = query.limit.unwrap_or(100).clamp(1, 1000);
+                    let reply = match mgr.get_request_log(query.api_key.as_deref(), limit).await {
+                        Ok(entries) => ok_reply(request_id, entries),
+                        Err(e) => {
+                            log::error!("❌ Failed to read request log: {}", e);
+                            orchestrator_error_reply(request_id, &e)
+                        }
+                    };
+                    Ok::<_, Infallible>(reply)
+                }
+            })
+    };
+
+    // Fee spend guard state; POST clears a tripped guard (admin key required)
+    let spend_guard = {
+        let manager = manager.clone();
+        warp::path!("api" / "admin" / "spend-guard")
+            .and(warp::get().map(|| false).or(warp::post().map(|| true)).unify())
+            .and(warp::header::optional::<String>("x-admin-key"))
+            .and(with_request_id())
+            .and_then(move |reset: bool, admin_key: Option<String>, request_id: String| {
+                let manager = manager.clone();
+                async move {
+                    let mgr = manager.lock().await;
+                    if !admin_key.is_some_and(|key| mgr.is_admin_key(&key)) {
+                        return Ok::<_, Infallible>(error_reply(
+                            request_id,
+                            StatusCode::FORBIDDEN,
+                            "forbidden",
+                            "admin key required".to_string(),
+                        ));
                     }
-                });
-                
-                // Return immediate response - processing happens in background
-                warp::reply::json(&serde_json::json!({"status": "processing"}))
+
+                    let status = if reset { mgr.reset_spend_guard() } else { mgr.spend_guard_status() };
+                    Ok::<_, Infallible>(ok_reply(request_id, status))
+                }
+            })
+    };
+
+    // Public status page feed: coarse state, bridging time and operator notice
+    let status_json = {
+        let manager = manager.clone();
+        warp::path!("status.json")
+            .and(warp::get())
+            .and_then(move || {
+                let manager = manager.clone();
+                async move {
+                    let mgr = manager.lock().await;
+                    let reply: Box<dyn warp::Reply> = match mgr.service_status().await {
+                        Ok(status) => Box::new(warp::reply::with_header(
+                            warp::reply::json(&status),
+                            "cache-control",
+                            format!("public, max-age={}", STATUS_CACHE_SECS),
+                        )),
+                        Err(e) => {
+                            log::error!("❌ Failed to compute service status: {}", e);
+                            Box::new(warp::reply::with_status(
+                                warp::reply::json(&serde_json::json!({ "status": "unknown" })),
+                                StatusCode::SERVICE_UNAVAILABLE,
+                            ))
+                        }
+                    };
+                    Ok::<_, Infallible>(reply)
+                }
+            })
+    };
+
+    // Set or clear the status page notice (admin key required)
+    let status_notice = {
+        let manager = manager.clone();
+        warp::path!("api" / "admin" / "status-notice")
+            .and(
+                warp::put()
+                    .and(warp::body::json())
+                    .map(Some)
+                    .or(warp::delete().map(|| None))
+                    .unify(),
+            )
+            .and(warp::header::optional::<String>("x-admin-key"))
+            .and(with_request_id())
+            .and_then(move |request: Option<StatusNoticeRequest>, admin_key: Option<String>, request_id: String| {
+                let manager = manager.clone();
+                async move {
+                    let mgr = manager.lock().await;
+                    if !admin_key.is_some_and(|key| mgr.is_admin_key(&key)) {
+                        return Ok::<_, Infallible>(error_reply(
+                            request_id,
+                            StatusCode::FORBIDDEN,
+                            "forbidden",
+                            "admin key required".to_string(),
+                        ));
+                    }
+
+                    let result = match request {
+                        Some(notice) => {
+                            mgr.set_status_notice(notice.kind, &notice.message, notice.starts_at, notice.ends_at).await
+                        }
+                        None => mgr.clear_status_notice().await,
+                    };
+                    let reply = match result {
+                        Ok(()) => match mgr.service_status().await {
+                            Ok(status) => ok_reply(request_id, status),
+                            Err(e) => orchestrator_error_reply(request_id, &e),
+                        },
+                        Err(e) => {
+                            log::error!("❌ Failed to update status notice: {}", e);
+                            orchestrator_error_reply(request_id, &e)
+                        }
+                    };
+                    Ok::<_, Infallible>(reply)
+                }
+            })
+    };
+
+    // Deposit migration to Postgres: status and phase changes (admin key required)
+    let db_migration = {
+        let manager = manager.clone();
+        warp::path!("api" / "admin" / "db-migration")
+            .and(warp::get())
+            .map(|| None::<String>)
+            .or(warp::path!("api" / "admin" / "db-migration" / String).and(warp::post()).map(Some))
+            .unify()
+            .and(warp::header::optional::<String>("x-admin-key"))
+            .and(with_request_id())
+            .and_then(move |command: Option<String>, admin_key: Option<String>, request_id: String| {
+                let manager = manager.clone();
+                async move {
+                    let mgr = manager.lock().await;
+                    if !admin_key.is_some_and(|key| mgr.is_admin_key(&key)) {
+                        return Ok::<_, Infallible>(error_reply(
+                            request_id,
+                            StatusCode::FORBIDDEN,
+                            "forbidden",
+                            "admin key required".to_string(),
+                        ));
+                    }
+
+                    if let Some(command) = command {
+                        if let Err(e) = mgr.db_migration_command(&command).await {
+                            log::error!("❌ Database migration {} failed: {}", command, e);
+                            let reply = match e {
+                                OrchestratorError::MigrationError(message) => {
+                                    error_reply(request_id, StatusCode::CONFLICT, "migration_error", message)
+                                }
+                                e => orchestrator_error_reply(request_id, &e),
+                            };
+                            return Ok::<_, Infallible>(reply);
+                        }
+                    }
+                    let reply = match mgr.db_migration_status().await {
+                        Ok(status) => ok_reply(request_id, status),
+                        Err(e) => orchestrator_error_reply(request_id, &e),
+                    };
+                    Ok::<_, Infallible>(reply)
+                }
+            })
+    };
+
+    // Submitter lease and handover state of this instance (admin key required)
+    let handover = {
+        let manager = manager.clone();
+        warp::path!("api" / "admin" / "handover")
+            .and(warp::get())
+            .and(warp::header::optional::<String>("x-admin-key"))
+            .and(with_request_id())
+            .and_then(move |admin_key: Option<String>, request_id: String| {
+                let manager = manager.clone();
+                async move {
+                    let mgr = manager.lock().await;
+                    if !admin_key.is_some_and(|key| mgr.is_admin_key(&key)) {
+                        return Ok::<_, Infallible>(error_reply(
+                            request_id,
+                            StatusCode::FORBIDDEN,
+                            "forbidden",
+                            "admin key required".to_string(),
+                        ));
+                    }
+
+                    match mgr.handover_status().await {
+                        Ok(status) => Ok::<_, Infallible>(ok_reply(request_id, status)),
+                        Err(e) => Ok(orchestrator_error_reply(request_id, &e)),
+                    }
+                }
+            })
+    };
+
+    // Firing internal alert conditions, as JSON for simple monitors
+    let alerts = {
+        let manager = manager.clone();
+        warp::path!("api" / "alerts")
+            .and(warp::get())
+            .and(with_request_id())
+            .and_then(move |request_id: String| {
+                let manager = manager.clone();
+                async move {
+                    let mgr = manager.lock().await;
+                    Ok::<_, Infallible>(ok_reply(request_id, mgr.alerts().await))
+                }
             })
     };
 
@@ -77,7 +798,8 @@ pub async fn start_http_server(manager: Arc<Mutex<SubmissionManager>>) {
         let manager = manager.clone();
         warp::path!("api" / "queue-stats")
             .and(warp::get())
-            .and_then(move || {
+            .and(with_request_id())
+            .and_then(move |request_id: String| {
                 let manager = manager.clone();
                 async move {
                     let mgr = manager.lock().await;
@@ -87,7 +809,235 @@ pub async fn start_http_server(manager: Arc<Mutex<SubmissionManager>>) {
                         total: stats.total,
                         completed: stats.completed,
                     };
-                    Ok::<_, Infallible>(warp::reply::json(&response))
+                    Ok::<_, Infallible>(ok_reply(request_id, response))
+                }
+            })
+    };
+
+    // Deposit lookup by TON transaction hash
+    let deposits_by_ton_tx = {
+        let manager = manager.clone();
+        warp::path!("api" / "deposits" / "by-ton-tx" / String)
+            .and(warp::get())
+            .and(with_request_id())
+            .and_then(move |ton_tx_hash: String, request_id: String| {
+                let manager = manager.clone();
+                async move {
+                    let mgr = manager.lock().await;
+                    let reply = match mgr.find_deposits_by_ton_tx(&ton_tx_hash).await {
+                        Ok(deposits) if deposits.is_empty() => error_reply(
+                            request_id,
+                            StatusCode::NOT_FOUND,
+                            "deposit_not_found",
+                            format!("no deposit found for TON tx {}", ton_tx_hash),
+                        ),
+                        Ok(deposits) => ok_reply(
+                            request_id,
+                            deposits.into_iter().map(DepositResponse::from).collect::<Vec<_>>(),
+                        ),
+                        Err(e) => {
+                            log::error!("❌ Failed to look up TON tx {}: {}", ton_tx_hash, e);
+                            orchestrator_error_reply(request_id, &e)
+                        }
+                    };
+                    Ok::<_, Infallible>(reply)
+                }
+            })
+    };
+
+    // Proof-of-completion receipt for a bridged deposit
+    let deposit_receipt = {
+        let manager = manager.clone();
+        warp::path!("api" / "deposits" / String / "receipt")
+            .and(warp::get())
+            .and(with_request_id())
+            .and_then(move |deposit_id: String, request_id: String| {
+                let manager = manager.clone();
+                async move {
+                    let mgr = manager.lock().await;
+                    let reply = match mgr.get_receipt(&deposit_id).await {
+                        Ok(Some(receipt)) => ok_reply(request_id, ReceiptResponse::from(receipt)),
+                        Ok(None) => error_reply(
+                            request_id,
+                            StatusCode::NOT_FOUND,
+                            "receipt_not_found",
+                            format!("no receipt for deposit {}", deposit_id),
+                        ),
+                        Err(e) => {
+                            log::error!("❌ Failed to load receipt for {}: {}", deposit_id, e);
+                            orchestrator_error_reply(request_id, &e)
+                        }
+                    };
+                    Ok::<_, Infallible>(reply)
+                }
+            })
+    };
+
+    // Deposit status, including any remaining large-transfer delay
+    let deposit_status = {
+        let manager = manager.clone();
+        warp::path!("api" / "deposits" / String / "status")
+            .and(warp::get())
+            .and(with_request_id())
+            .and_then(move |deposit_id: String, request_id: String| {
+                let manager = manager.clone();
+                async move {
+                    let mgr = manager.lock().await;
+                    let reply = match mgr.get_deposit(&deposit_id).await {
+                        Ok(Some(record)) => ok_reply(request_id, DepositStatusResponse {
+                            delay_remaining_secs: mgr.delay_remaining_secs(&deposit_id),
+                            deposit_id: record.deposit_id,
+                            status: record.status,
+                            error_message: record.error_message,
+                            status_detail: record.status_detail.and_then(StatusDetail::from_code),
+                        }),
+                        Ok(None) => error_reply(
+                            request_id,
+                            StatusCode::NOT_FOUND,
+                            "deposit_not_found",
+                            format!("no deposit {}", deposit_id),
+                        ),
+                        Err(e) => {
+                            log::error!("❌ Failed to load deposit {}: {}", deposit_id, e);
+                            orchestrator_error_reply(request_id, &e)
+                        }
+                    };
+                    Ok::<_, Infallible>(reply)
+                }
+            })
+    };
+
+    // Cancel a large deposit during its delay window (operator or risk checks)
+    let cancel_deposit = {
+        let manager = manager.clone();
+        warp::path!("api" / "deposits" / String / "cancel")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_request_id())
+            .and_then(move |deposit_id: String, request: CancelDepositRequest, request_id: String| {
+                let manager = manager.clone();
+                async move {
+                    let mgr = manager.lock().await;
+                    let reply = match mgr.cancel_delayed_deposit(&deposit_id, &request.reason).await {
+                        Ok(true) => ok_reply(request_id, serde_json::json!({ "deposit_id": deposit_id, "cancelled": true })),
+                        Ok(false) => error_reply(
+                            request_id,
+                            StatusCode::CONFLICT,
+                            "deposit_not_delayed",
+                            format!("deposit {} is not inside a delay window", deposit_id),
+                        ),
+                        Err(e) => {
+                            log::error!("❌ Failed to cancel deposit {}: {}", deposit_id, e);
+                            orchestrator_error_reply(request_id, &e)
+                        }
+                    };
+                    Ok::<_, Infallible>(reply)
+                }
+            })
+    };
+
+    // CSV export of deposits with fiat reference rates, for finance reporting
+    let export_deposits = {
+        let manager = manager.clone();
+        warp::path!("api" / "export" / "deposits")
+            .and(warp::get())
+            .and(warp::query::<ExportQuery>())
+            .and_then(move |query: ExportQuery| {
+                let manager = manager.clone();
+                async move {
+                    let mgr = manager.lock().await;
+                    let (status, body) = match mgr.export_deposits(query.from, query.to).await {
+                        Ok(records) => (StatusCode::OK, deposits_csv(&records)),
+                        Err(e) => {
+                            log::error!("❌ Failed to export deposits: {}", e);
+                            (StatusCode::INTERNAL_SERVER_ERROR, format!("error: {}\n", e))
+                        }
+                    };
+                    Ok::<_, Infallible>(warp::reply::with_status(
+                        warp::reply::with_header(body, "content-type", "text/csv"),
+                        status,
+                    ))
+                }
+            })
+    };
+
+    // SOL-denominated cost estimate for bridging N deposits
+    let estimate = {
+        let manager = manager.clone();
+        warp::path!("api" / "estimate")
+            .and(warp::get())
+            .and(warp::query::<EstimateQuery>())
+            .and(with_request_id())
+            .and_then(move |query: EstimateQuery, request_id: String| {
+                let manager = manager.clone();
+                async move {
+                    if query.deposits == 0 || query.deposits > 10_000 {
+                        return Ok::<_, Infallible>(error_reply(
+                            request_id,
+                            StatusCode::BAD_REQUEST,
+                            "invalid_deposit_count",
+                            "deposits must be between 1 and 10000".to_string(),
+                        ));
+                    }
+
+                    let mgr = manager.lock().await;
+                    let reply = match mgr.estimate_cost(query.deposits).await {
+                        Ok(estimate) => ok_reply(request_id, estimate),
+                        Err(e) => {
+                            log::error!("❌ Failed to estimate cost: {}", e);
+                            orchestrator_error_reply(request_id, &e)
+                        }
+                    };
+                    Ok::<_, Infallible>(reply)
+                }
+            })
+    };
+
+    // Signed state snapshot for external watchtowers
+    let attestation = {
+        let manager = manager.clone();
+        warp::path!("api" / "attestation")
+            .and(warp::get())
+            .and(with_request_id())
+            .and_then(move |request_id: String| {
+                let manager = manager.clone();
+                async move {
+                    let mgr = manager.lock().await;
+                    let reply = match mgr.attestation().await {
+                        Ok(attestation) => ok_reply(request_id, attestation),
+                        Err(e) => {
+                            log::error!("❌ Failed to build attestation: {}", e);
+                            orchestrator_error_reply(request_id, &e)
+                        }
+                    };
+                    Ok::<_, Infallible>(reply)
+                }
+            })
+    };
+
+    let batch_manifest = {
+        let manager = manager.clone();
+        warp::path!("api" / "batches" / String / "manifest")
+            .and(warp::get())
+            .and(with_request_id())
+            .and_then(move |batch_id: String, request_id: String| {
+                let manager = manager.clone();
+                async move {
+                    let mgr = manager.lock().await;
+                    let reply = match mgr.batch_manifest(&batch_id).await {
+                        Ok(Some(manifest)) => ok_reply(request_id, manifest),
+                        Ok(None) => error_reply(
+                            request_id,
+                            StatusCode::NOT_FOUND,
+                            "batch_not_found",
+                            format!("no manifest stored for batch {}", batch_id),
+                        ),
+                        Err(e) => {
+                            log::error!("❌ Failed to load manifest of batch {}: {}", batch_id, e);
+                            orchestrator_error_reply(request_id, &e)
+                        }
+                    };
+                    Ok::<_, Infallible>(reply)
                 }
             })
     };
@@ -115,10 +1065,29 @@ pub async fn start_http_server(manager: Arc<Mutex<SubmissionManager>>) {
 
     let routes = health
         .or(add_deposit)
+        .or(validate_deposit)
+        .or(submit_proof)
         .or(queue_stats)
+        .or(deposits_by_ton_tx)
+        .or(deposit_receipt)
+        .or(deposit_status)
+        .or(cancel_deposit)
+        .or(estimate)
+        .or(export_deposits)
+        .or(attestation)
+        .or(batch_manifest)
+        .or(request_log)
+        .or(spend_guard)
+        .or(handover)
+        .or(alerts)
+        .or(status_json)
+        .or(status_notice)
+        .or(db_migration)
+        .or(api_key_notifications)
         .or(metrics_endpoint)
         .with(warp::cors().allow_any_origin());
 
-    log::info!("🌐 Starting HTTP server on :3000");
-    warp::serve(routes).run(([0, 0, 0, 0], 3000)).await;
+    let (addr, server) = warp::serve(routes).bind_with_graceful_shutdown(([0, 0, 0, 0], 3000), shutdown);
+    log::info!("🌐 Starting HTTP server on {}", addr);
+    server.await;
 }
\ No newline at end of file
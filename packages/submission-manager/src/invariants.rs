@@ -0,0 +1,105 @@
+use crate::alerts::UncheckedAlert;
+use crate::database::DatabaseService;
+use crate::SolanaClient;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+
+/// Deposits marked completed must not add up to more than the program minted
+pub const INVARIANT_MINTED_TOTAL: &str = "minted_total";
+/// A deposit that landed on Solana must not also be failed or refunded
+pub const INVARIANT_COMPLETED_DEAD_LETTER: &str = "completed_dead_letter";
+/// Each TON nullifier belongs to one deposit
+pub const INVARIANT_DUPLICATE_NULLIFIER: &str = "duplicate_nullifier";
+
+/// A cross-cutting truth about the bridge that does not hold
+#[derive(Debug, Clone, Serialize)]
+pub struct InvariantViolation {
+    pub invariant: &'static str,
+    pub message: String,
+}
+
+/// Result of one audit; any violation points at a bug, not an operational condition
+#[derive(Debug, Clone, Serialize)]
+pub struct InvariantAudit {
+    pub violations: Vec<InvariantViolation>,
+    pub unchecked: Vec<UncheckedAlert>,
+    pub checked_at: i64,
+}
+
+impl InvariantAudit {
+    pub async fn run(database: &DatabaseService, solana_client: &SolanaClient) -> Self {
+        let mut audit = Self {
+            violations: Vec::new(),
+            unchecked: Vec::new(),
+            checked_at: chrono::Utc::now().timestamp(),
+        };
+
+        // Program counters start at zero on migration, so only a database
+        // total above the on-chain one is a violation
+        match (database.get_completed_totals().await, solana_client.fetch_event_counters().await) {
+            (Ok((_, completed)), Ok(onchain)) if completed > onchain.total_amount_bridged => audit.violate(
+                INVARIANT_MINTED_TOTAL,
+                format!(
+                    "completed deposits sum to {} but the program minted {}",
+                    completed, onchain.total_amount_bridged
+                ),
+            ),
+            (Ok(_), Ok(_)) => {}
+            (Err(e), _) => audit.unchecked(INVARIANT_MINTED_TOTAL, e),
+            (_, Err(e)) => audit.unchecked(INVARIANT_MINTED_TOTAL, e),
+        }
+
+        match database.find_completed_dead_letters().await {
+            Ok(ids) if !ids.is_empty() => audit.violate(
+                INVARIANT_COMPLETED_DEAD_LETTER,
+                format!("{} deposits landed but are failed or refunded: {}", ids.len(), ids.join(", ")),
+            ),
+            Ok(_) => {}
+            Err(e) => audit.unchecked(INVARIANT_COMPLETED_DEAD_LETTER, e),
+        }
+
+        match database.find_duplicate_nullifiers().await {
+            Ok(duplicates) if !duplicates.is_empty() => audit.violate(
+                INVARIANT_DUPLICATE_NULLIFIER,
+                format!(
+                    "{} nullifiers are shared by several deposits: {}",
+                    duplicates.len(),
+                    duplicates
+                        .iter()
+                        .map(|(nullifier, count)| format!("{} ({}x)", nullifier, count))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ),
+            Ok(_) => {}
+            Err(e) => audit.unchecked(INVARIANT_DUPLICATE_NULLIFIER, e),
+        }
+
+        audit
+    }
+
+    fn violate(&mut self, invariant: &'static str, message: String) {
+        log::error!("🚨 Invariant {} violated: {}", invariant, message);
+        self.violations.push(InvariantViolation { invariant, message });
+    }
+
+    fn unchecked(&mut self, invariant: &'static str, error: impl ToString) {
+        self.unchecked.push(UncheckedAlert { name: invariant, error: error.to_string() });
+    }
+}
+
+/// Latest audit, shared by every clone so `GET /api/alerts` can report it
+#[derive(Clone, Default)]
+pub struct LastAudit {
+    last: Arc<Mutex<Option<InvariantAudit>>>,
+}
+
+impl LastAudit {
+    pub fn get(&self) -> Option<InvariantAudit> {
+        self.last.lock().unwrap().clone()
+    }
+
+    pub fn put(&self, audit: InvariantAudit) {
+        *self.last.lock().unwrap() = Some(audit);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{OrchestratorError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Display metadata of a TON jetton master
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JettonMetadata {
+    pub master: String,
+    pub symbol: String,
+    pub name: String,
+    pub decimals: u8,
+}
+
+/// Jettons the bridge accepts, loaded from a JSON array of `JettonMetadata`.
+/// An empty registry accepts any jetton whose metadata resolves.
+#[derive(Debug, Clone, Default)]
+pub struct TokenRegistry {
+    tokens: HashMap<String, JettonMetadata>,
+}
+
+impl TokenRegistry {
+    pub fn load(path: &str) -> Result<Self> {
+        if path.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            OrchestratorError::ConfigurationError(format!("Cannot read token registry {}: {}", path, e))
+        })?;
+        let tokens: Vec<JettonMetadata> = serde_json::from_str(&contents)?;
+
+        Ok(Self {
+            tokens: tokens.into_iter().map(|t| (t.master.clone(), t)).collect(),
+        })
+    }
+
+    pub fn get(&self, master: &str) -> Option<&JettonMetadata> {
+        self.tokens.get(master)
+    }
+
+    pub fn check_supported(&self, master: &str) -> std::result::Result<(), String> {
+        if self.tokens.is_empty() || self.tokens.contains_key(master) {
+            Ok(())
+        } else {
+            Err(format!("Jetton {} is not supported", master))
+        }
+    }
+
+    /// Check resolved on-chain metadata against the registry entry
+    pub fn validate(&self, metadata: &JettonMetadata) -> std::result::Result<(), String> {
+        self.check_supported(&metadata.master)?;
+        let Some(expected) = self.get(&metadata.master) else {
+            return Ok(());
+        };
+        if expected.symbol != metadata.symbol || expected.decimals != metadata.decimals {
+            return Err(format!(
+                "Jetton {} metadata mismatch: registry {} ({} decimals), on-chain {} ({} decimals)",
+                metadata.master, expected.symbol, expected.decimals, metadata.symbol, metadata.decimals
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Resolves jetton master metadata through a toncenter-compatible TON API and
+/// caches it for `ttl`
+#[derive(Clone)]
+pub struct JettonMetadataService {
+    api_url: String,
+    client: reqwest::Client,
+    ttl: Duration,
+    cache: Arc<RwLock<HashMap<String, (JettonMetadata, Instant)>>>,
+}
+
+impl JettonMetadataService {
+    pub fn new(api_url: &str, ttl_secs: u64) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap();
+
+        Self {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            client,
+            ttl: Duration::from_secs(ttl_secs),
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub async fn resolve(&self, master: &str) -> Result<JettonMetadata> {
+        if let Some((metadata, fetched_at)) = self.cache.read().unwrap().get(master) {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(metadata.clone());
+            }
+        }
+
+        let metadata = self.fetch(master).await?;
+        self.cache
+            .write()
+            .unwrap()
+            .insert(master.to_string(), (metadata.clone(), Instant::now()));
+        Ok(metadata)
+    }
+
+    async fn fetch(&self, master: &str) -> Result<JettonMetadata> {
+        let body: serde_json::Value = self.client
+            .get(&format!("{}/api/v3/jetton/masters", self.api_url))
+            .query(&[("address", master), ("limit", "1")])
+            .send()
+            .await
+            .map_err(OrchestratorError::NetworkError)?
+            .error_for_status()
+            .map_err(OrchestratorError::NetworkError)?
+            .json()
+            .await
+            .map_err(OrchestratorError::NetworkError)?;
+
+        let content = &body["jetton_masters"][0]["jetton_content"];
+        let missing = |field: &str| {
+            OrchestratorError::ConfigurationError(format!("Jetton {} metadata has no {}", master, field))
+        };
+
+        Ok(JettonMetadata {
+            master: master.to_string(),
+            symbol: content["symbol"].as_str().ok_or_else(|| missing("symbol"))?.to_string(),
+            name: content["name"].as_str().unwrap_or_default().to_string(),
+            // TEP-64: decimals is a string and defaults to 9
+            decimals: match &content["decimals"] {
+                serde_json::Value::Null => 9,
+                value => value
+                    .as_str()
+                    .and_then(|d| d.parse().ok())
+                    .or_else(|| value.as_u64().and_then(|d| u8::try_from(d).ok()))
+                    .ok_or_else(|| missing("valid decimals"))?,
+            },
+        })
+    }
+}
@@ -0,0 +1,94 @@
+use base64::Engine;
+use serde::Deserialize;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::VersionedTransaction;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use crate::{OrchestratorError, Result};
+
+/// Most transactions the block engine accepts in one bundle
+pub const MAX_BUNDLE_TRANSACTIONS: usize = 5;
+
+/// Accounts the block engine takes tips on; spreading tips over them avoids
+/// write-lock contention between bundles
+const TIP_ACCOUNTS: [Pubkey; 8] = [
+    solana_sdk::pubkey!("96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"),
+    solana_sdk::pubkey!("HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe"),
+    solana_sdk::pubkey!("Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY"),
+    solana_sdk::pubkey!("ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49"),
+    solana_sdk::pubkey!("DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh"),
+    solana_sdk::pubkey!("ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt"),
+    solana_sdk::pubkey!("DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL"),
+    solana_sdk::pubkey!("3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT"),
+];
+
+#[derive(Deserialize)]
+struct JsonRpcResponse<T> {
+    result: Option<T>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+/// Jito block engine: sends transactions as atomic bundles that pay a tip to
+/// the validator, which lands them ahead of the congested public queue
+#[derive(Clone)]
+pub struct JitoClient {
+    bundles_url: String,
+    tip_lamports: u64,
+    http: reqwest::Client,
+    next_tip_account: Arc<AtomicUsize>,
+}
+
+impl JitoClient {
+    pub fn new(block_engine_url: &str, tip_lamports: u64) -> Self {
+        Self {
+            bundles_url: format!("{}/api/v1/bundles", block_engine_url.trim_end_matches('/')),
+            tip_lamports,
+            http: reqwest::Client::new(),
+            next_tip_account: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Transfer of the configured tip from `payer`; belongs in the bundle's last transaction
+    pub fn tip_instruction(&self, payer: &Pubkey) -> Instruction {
+        let index = self.next_tip_account.fetch_add(1, Ordering::Relaxed) % TIP_ACCOUNTS.len();
+        solana_sdk::system_instruction::transfer(payer, &TIP_ACCOUNTS[index], self.tip_lamports)
+    }
+
+    /// Submit signed `transactions` as one bundle; returns the bundle id
+    pub async fn send_bundle(&self, transactions: &[VersionedTransaction]) -> Result<String> {
+        let encoded = transactions
+            .iter()
+            .map(|transaction| {
+                bincode::serialize(transaction)
+                    .map(|bytes| base64::engine::general_purpose::STANDARD.encode(bytes))
+                    .map_err(|e| OrchestratorError::BatchProcessingFailed { reason: format!("cannot encode bundle: {}", e) })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendBundle",
+            "params": [encoded, {"encoding": "base64"}],
+        });
+        let response: JsonRpcResponse<String> =
+            self.http.post(&self.bundles_url).json(&request).send().await?.json().await?;
+
+        match (response.result, response.error) {
+            (Some(bundle_id), _) => Ok(bundle_id),
+            (None, Some(error)) => Err(OrchestratorError::BatchProcessingFailed {
+                reason: format!("block engine rejected bundle ({}): {}", error.code, error.message),
+            }),
+            (None, None) => Err(OrchestratorError::BatchProcessingFailed {
+                reason: "block engine returned neither a bundle id nor an error".to_string(),
+            }),
+        }
+    }
+}
@@ -10,84 +10,436 @@ pub mod http_server;
 pub mod database;
 pub mod solana_client;
 pub mod metrics;
+pub mod refund_engine;
+pub mod environment;
+pub mod proof;
+pub mod rate_limiter;
+pub mod attestation;
+pub mod privacy;
+pub mod event_bus;
+pub mod sealing_policy;
+pub mod transfer_delay;
+pub mod account_precreation;
+pub mod jetton_metadata;
+pub mod price_oracle;
+pub mod notifications;
+pub mod queue_snapshot;
+pub mod prover_selection;
+pub mod root_relay;
+pub mod extensions;
+pub mod spend_guard;
+pub mod archive;
+pub mod program_events;
+pub mod program_instructions;
+pub mod program_errors;
+pub mod invariants;
+pub mod relayer_keypair;
+pub mod handover;
+pub mod roles;
+pub mod alerts;
+pub mod proof_verifier;
+pub mod status_page;
+pub mod db_migration;
+pub mod rpc_pool;
+pub mod program_accounts;
+pub mod event_listener;
+pub mod jito;
+pub mod public_inputs;
+pub mod circuit_grpc;
+pub mod ton_transactions;
+pub mod batch_manifest;
 
-pub use batch_manager::BatchManager;
+pub use batch_manager::{batch_key, BatchManager, BatchSizing, PriorityLane};
 pub use proof_orchestrator::ProofOrchestrator;
 pub use gas_optimizer::GasOptimizer;
-pub use health_monitor::HealthMonitor;
+pub use health_monitor::{EventCounters, HealthMonitor};
 pub use retry_engine::RetryEngine;
 pub use queue_manager::QueueManager;
-pub use types::{OrchestratorConfig, Deposit, SystemHealth, QueueStats, Batch};
+pub use types::{OrchestratorConfig, Deposit, SystemHealth, QueueStats, Batch, CostEstimate, RequestAdmission, ValidationIssue};
 pub use error::{OrchestratorError, Result};
 pub use database::DatabaseService;
-pub use solana_client::SolanaClient;
+pub use solana_client::{DepositSubmission, PreparedDeposit, RpcTimeouts, SignatureFinality, SolanaClient};
 pub use metrics::BridgeMetrics;
+pub use refund_engine::RefundEngine;
+pub use environment::{Environment, EnvironmentPreset};
+pub use proof::Proof;
+pub use proof_verifier::ProofVerifier;
+pub use rate_limiter::SubmissionRateLimiter;
+pub use spend_guard::{SpendGuard, SpendGuardStatus};
+pub use archive::{ArchiveLocation, ArchivedDeposit, ProofArchive};
+pub use handover::{HandoverCoordinator, HandoverStatus, LeaseState};
+pub use roles::ManagerRole;
+pub use alerts::{Alert, AlertReport, AlertSeverity};
+pub use status_page::{NoticeKind, ServiceState, ServiceStatus, StatusNotice};
+pub use program_events::ProgramEvent;
+pub use attestation::{AttestationTracker, SignedAttestation};
+pub use event_bus::{EventBus, PipelineEvent};
+pub use sealing_policy::SealingPolicy;
+pub use transfer_delay::LargeTransferDelay;
+pub use notifications::{NotificationTarget, Notifier};
+pub use jetton_metadata::{JettonMetadata, JettonMetadataService, TokenRegistry};
+pub use extensions::{BatchQueue, NotificationSink, Prover};
+pub use batch_manifest::{BatchManifest, SignedBatchManifest};
 
+use futures_util::StreamExt;
+use public_inputs::proof_anchor;
+use ton_transactions::TonTransactionValidator;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::time::{interval, Duration};
 use std::time::Instant;
+use std::str::FromStr;
 use prometheus::Registry;
+use program_errors::ProgramFailure;
 
 pub struct SubmissionManager {
-    batch_manager: BatchManager,
-    proof_orchestrator: ProofOrchestrator,
+    batch_manager: Arc<Mutex<BatchManager>>, // The open batch, shared with the proving workers
+    prove_wakeup: Arc<tokio::sync::Notify>,  // Deposits were handed to the proving workers
+    prover: Arc<dyn Prover>,
     gas_optimizer: GasOptimizer,
     health_monitor: HealthMonitor,
     retry_engine: RetryEngine,
-    queue_manager: QueueManager,
+    // Shared by every clone so the HTTP API sees the submitter's queue
+    queue: Arc<std::sync::Mutex<dyn BatchQueue>>,
     database: DatabaseService,
     solana_client: SolanaClient, 
+    refund_engine: RefundEngine,
+    rate_limiter: SubmissionRateLimiter,
+    // Shared so the admin API can inspect and reset the submitter's guard
+    spend_guard: Arc<std::sync::Mutex<SpendGuard>>,
+    attestation: AttestationTracker,
+    event_bus: EventBus,
+    transfer_delay: LargeTransferDelay,
+    jetton_metadata: JettonMetadataService,
+    token_registry: TokenRegistry,
+    price_oracle: Option<price_oracle::PriceOracle>,
+    notifiers: Vec<Arc<dyn NotificationSink>>,
+    archive: Option<ProofArchive>,
+    // Shared so the HTTP API refuses deposits once the submitter starts draining
+    handover: HandoverCoordinator,
+    // Shared with the health loop, which samples the TON root periodically
+    root_watch: alerts::RootWatch,
+    // Checks proofs submitted through POST /api/proofs
+    proof_verifier: ProofVerifier,
+    // Shared so every HTTP request reuses the last computed public status
+    status_cache: status_page::StatusCache,
+    last_audit: invariants::LastAudit,
     config: OrchestratorConfig,
     metrics: BridgeMetrics,
     registry: Registry,
     is_running: bool,
+    // Background tasks spawned by start(), aborted by stop()
+    tasks: Vec<tokio::task::JoinHandle<()>>,
 }
 
-impl SubmissionManager {
-    pub async fn new(config: OrchestratorConfig) -> Result<Self> {
+/// Per-deposit outcome of a submitted batch, see `split_batch_results`
+#[derive(Default)]
+struct BatchResults {
+    landed: Vec<(String, Batch)>, // By transaction signature
+    already_bridged: Vec<Deposit>,
+    failed: Vec<(Deposit, String)>,
+    retry: Option<Batch>,
+}
+
+/// Builds a `SubmissionManager` for embedding in another service. Extension
+/// points left unset fall back to the built-in circuit-service prover,
+/// in-memory queue and Telegram/email notifier.
+pub struct SubmissionManagerBuilder {
+    config: OrchestratorConfig,
+    database_url: Option<String>,
+    prover: Option<Arc<dyn Prover>>,
+    queue: Option<Arc<std::sync::Mutex<dyn BatchQueue>>>,
+    notifiers: Vec<Arc<dyn NotificationSink>>,
+}
+
+impl SubmissionManagerBuilder {
+    pub fn new(config: OrchestratorConfig) -> Self {
+        Self {
+            config,
+            database_url: None,
+            prover: None,
+            queue: None,
+            notifiers: Vec::new(),
+        }
+    }
+
+    /// SQLite URL; defaults to `DATABASE_URL` or `sqlite:submission_manager.db`
+    pub fn database_url(mut self, url: impl Into<String>) -> Self {
+        self.database_url = Some(url.into());
+        self
+    }
+
+    pub fn prover(mut self, prover: impl Prover + 'static) -> Self {
+        self.prover = Some(Arc::new(prover));
+        self
+    }
+
+    pub fn queue(mut self, queue: impl BatchQueue + 'static) -> Self {
+        self.queue = Some(Arc::new(std::sync::Mutex::new(queue)));
+        self
+    }
+
+    /// Add a sink next to the built-in notifier (which only runs when configured)
+    pub fn notifier(mut self, notifier: impl NotificationSink + 'static) -> Self {
+        self.notifiers.push(Arc::new(notifier));
+        self
+    }
+
+    pub async fn build(self) -> Result<SubmissionManager> {
+        let SubmissionManagerBuilder { config, database_url, prover, queue, mut notifiers } = self;
+        config.validate()?;
+
         // Initialize database
-        let db_url = std::env::var("DATABASE_URL")
-            .unwrap_or_else(|_| "sqlite:submission_manager.db".to_string());
-        let database = DatabaseService::new(&db_url).await?;
+        let db_url = database_url.unwrap_or_else(|| {
+            std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:submission_manager.db".to_string())
+        });
+        let mut database = DatabaseService::with_options(&db_url, &database::DatabaseOptions {
+            journal_mode: config.db_journal_mode.clone(),
+            busy_timeout_ms: config.db_busy_timeout_ms,
+            synchronous: config.db_synchronous.clone(),
+        })
+        .await?;
+        if !config.db_mirror_url.is_empty() {
+            database.attach_mirror(&config.db_mirror_url).await?;
+            log::info!("🪞 Postgres deposit mirror attached (phase {})", database.migration_phase().await?);
+        }
 
         // Initialize Solana client - USE CONFIG, NOT ENV VARS
+        let gas_optimizer = GasOptimizer::new(config.max_priority_fee_micro_lamports);
+        let (keypair, keypair_source) = relayer_keypair::load(
+            config.relayer_private_key.as_deref(),
+            config.relayer_keypair_path.as_deref(),
+            config.solana_keypair.as_deref(),
+            config.mock_proving,
+        )?;
         let solana_client = SolanaClient::new(
-            &config.solana_rpc_url,
+            &config.solana_rpc_urls,
             &config.solana_program_id,
-            &config.solana_bridge_account,
-            Some(config.verification_key.as_str()),
-        )?;
+            &config.solana_commitment,
+            keypair,
+            RpcTimeouts {
+                request: Duration::from_secs(config.solana_rpc_timeout_secs),
+                confirmation: Duration::from_secs(config.solana_confirm_timeout_secs),
+            },
+        )?
+        .with_gas_optimizer(gas_optimizer.clone());
+        let solana_client = if config.jito_block_engine_url.is_empty() {
+            solana_client
+        } else {
+            log::info!(
+                "📦 Sending batches as Jito bundles via {} ({} lamport tip)",
+                config.jito_block_engine_url,
+                config.jito_tip_lamports
+            );
+            solana_client.with_jito(jito::JitoClient::new(&config.jito_block_engine_url, config.jito_tip_lamports))
+        };
+        let solana_client = match &config.solana_nonce_account {
+            Some(nonce_account) => {
+                let solana_client = solana_client.with_nonce_account(nonce_account)?;
+                solana_client.check_nonce_account().await?;
+                log::info!("🔐 Signing transactions against durable nonce {}", nonce_account);
+                solana_client
+            }
+            None => solana_client,
+        };
+        let solana_client = if config.solana_use_lookup_table {
+            let solana_client = solana_client.with_lookup_table(config.solana_lookup_table.as_deref()).await?;
+            if let Some(table) = solana_client.lookup_table() {
+                log::info!("📇 Sending v0 transactions through lookup table {}", table);
+            }
+            solana_client
+        } else {
+            solana_client
+        };
+        match solana_client.fetch_relayer_balance().await {
+            Ok(0) => log::warn!(
+                "⚠️  Relayer {} (from {}) holds no lamports and cannot pay for submissions",
+                solana_client.relayer_pubkey(),
+                keypair_source
+            ),
+            Ok(balance) => log::info!(
+                "🔑 Submitting as relayer {} (from {}), balance {} lamports",
+                solana_client.relayer_pubkey(),
+                keypair_source,
+                balance
+            ),
+            Err(e) => log::warn!(
+                "🔑 Submitting as relayer {} (from {}), balance unknown: {}",
+                solana_client.relayer_pubkey(),
+                keypair_source,
+                e
+            ),
+        }
 
         // Initialize metrics
         let registry = Registry::new();
         let metrics = BridgeMetrics::new(&registry)?;
 
-        Ok(Self {
-            batch_manager: BatchManager::new(config.batch_size),
-            proof_orchestrator: ProofOrchestrator::new(config.validators.clone(), config.validator_count),
-            gas_optimizer: GasOptimizer::new(config.gas_update_interval),
+        let sealing_policy = sealing_policy::from_config(&config, gas_optimizer.clone())?;
+        log::info!("📐 Batch sealing policy: {}", sealing_policy.name());
+
+        let notifier = Notifier::new(database.clone(), &config);
+        if notifier.is_enabled() {
+            notifiers.insert(0, Arc::new(notifier));
+        }
+
+        let handover = HandoverCoordinator::new(
+            database.clone(),
+            config.submitter_lease_ttl_secs,
+            config.request_handover_on_start,
+        );
+        log::info!("🪪 Manager instance {}", handover.instance_id());
+
+        Ok(SubmissionManager {
+            batch_manager: Arc::new(Mutex::new({
+                let batch_manager = BatchManager::new(sealing_policy).with_priority_lane(PriorityLane {
+                    max_size: config.priority_batch_size,
+                    max_age: Duration::from_secs(config.priority_batch_max_age_secs),
+                });
+                if config.adaptive_batch_sizing {
+                    batch_manager.with_sizing(BatchSizing {
+                        min_size: config.batch_min_size,
+                        max_size: config.batch_max_size,
+                        max_bytes: config.batch_max_bytes,
+                        max_compute_units: config.batch_max_compute_units,
+                    })
+                } else {
+                    batch_manager
+                }
+            })),
+            prove_wakeup: Arc::new(tokio::sync::Notify::new()),
+            prover: prover.unwrap_or_else(|| {
+                let orchestrator =
+                    ProofOrchestrator::new(config.validators.clone(), config.validator_count, config.mock_proving)
+                        .with_database(
+                            database.clone(),
+                            Duration::from_secs(config.proof_job_poll_secs.max(1)),
+                            Duration::from_secs(config.proof_job_timeout_secs),
+                        );
+                Arc::new(if config.validate_deposits_on_ton {
+                    orchestrator.with_ton_validator(TonTransactionValidator::new(&config.ton_api_url))
+                } else {
+                    orchestrator
+                })
+            }),
+            gas_optimizer,
             health_monitor: HealthMonitor::new(config.health_check_interval),
             retry_engine: RetryEngine::new(config.max_retries as usize),
-            queue_manager: QueueManager::new(),
+            queue: queue.unwrap_or_else(|| Arc::new(std::sync::Mutex::new(QueueManager::new()))),
             database,
             solana_client,
+            refund_engine: RefundEngine::new(&config.ton_wallet_url, config.refund_max_attempts),
+            rate_limiter: SubmissionRateLimiter::new(config.max_batches_per_minute, config.max_lamports_per_hour),
+            spend_guard: Arc::new(std::sync::Mutex::new(SpendGuard::new(
+                config.fee_budget_lamports_per_hour,
+                config.fee_budget_lamports_per_day,
+            ))),
+            attestation: AttestationTracker::new(),
+            event_bus: EventBus::new(),
+            transfer_delay: LargeTransferDelay::new(config.large_transfer_threshold, config.large_transfer_delay_secs),
+            jetton_metadata: JettonMetadataService::new(&config.ton_api_url, config.jetton_metadata_ttl_secs),
+            token_registry: TokenRegistry::load(&config.token_registry_path)?,
+            price_oracle: price_oracle::PriceOracle::new(&config.price_oracle_url),
+            notifiers,
+            archive: ProofArchive::new(&config.archive_dir, &config.archive_ipfs_api_url),
+            handover,
+            root_watch: alerts::RootWatch::default(),
+            proof_verifier: ProofVerifier::load(&config.verification_key, config.mock_proving),
+            status_cache: status_page::StatusCache::default(),
+            last_audit: invariants::LastAudit::default(),
             metrics,
             registry,
             config,
             is_running: false,
+            tasks: Vec::new(),
         })
     }
+}
+
+impl SubmissionManager {
+    pub async fn new(config: OrchestratorConfig) -> Result<Self> {
+        Self::builder(config).build().await
+    }
+
+    pub fn builder(config: OrchestratorConfig) -> SubmissionManagerBuilder {
+        SubmissionManagerBuilder::new(config)
+    }
 
     pub async fn start(&mut self) -> Result<()> {
         self.is_running = true;
-        log::info!("🚀 Starting Rust Submission Manager...");
+        log::info!("🚀 Starting Rust Submission Manager ({} role)...", self.config.role);
 
         // Start health monitoring
-        self.start_health_monitoring().await;
+        let task = self.start_health_monitoring().await;
+        self.tasks.push(task);
+
+        // Proving workers pick up accepted deposits, from this process or a separate ingestion tier
+        if self.config.role.runs_prover() {
+            self.restore_open_batches().await?;
+            let task = self.start_proving_worker().await;
+            self.tasks.push(task);
+        }
 
-        // Start batch processing
-        self.start_batch_processing().await;
+        if self.config.role.runs_submitter() {
+            // Start batch processing
+            let task = self.start_batch_processing().await;
+            self.tasks.push(task);
+
+            // Start SQLite maintenance, once per deployment rather than per process
+            let task = self.start_database_maintenance().await;
+            self.tasks.push(task);
+
+            if self.database.has_mirror() {
+                let task = self.start_mirror_parity_check().await;
+                self.tasks.push(task);
+            }
+
+            let task = self.start_priority_fee_updates().await;
+            self.tasks.push(task);
+
+            if self.config.invariant_check_interval_secs > 0 {
+                let task = self.start_invariant_audit().await;
+                self.tasks.push(task);
+            }
+
+            // Catch transactions that landed without the pipeline recording them
+            let ws_url = match self.config.solana_ws_url.as_str() {
+                "" => event_listener::websocket_url(&self.config.solana_rpc_urls[0]),
+                url => url.to_string(),
+            };
+            let listener = event_listener::EventListener::new(
+                ws_url,
+                self.solana_client.program_id(),
+                self.database.clone(),
+                self.event_bus.clone(),
+                self.metrics.clone(),
+            );
+            self.tasks.push(tokio::spawn(listener.run()));
+        }
+
+        // Relay TON roots from the light-client contract
+        if self.config.role.runs_submitter() && !self.config.ton_light_client_address.is_empty() {
+            let relay = root_relay::RootRelay::new(
+                root_relay::LightClientSource::new(
+                    &self.config.ton_api_url,
+                    &self.config.ton_light_client_address,
+                    &self.config.ton_light_client_get_method,
+                ),
+                root_relay::LightClientSource::new(
+                    &self.config.root_listener_api_url,
+                    &self.config.ton_light_client_address,
+                    &self.config.ton_light_client_get_method,
+                ),
+                self.config.root_relay_interval_secs,
+            );
+            self.tasks.push(tokio::spawn(relay.run(self.solana_client.clone(), self.metrics.clone())));
+        }
+
+        // Start user notifications
+        for notifier in &self.notifiers {
+            self.tasks.push(tokio::spawn(notifier.run(self.event_bus.subscribe())));
+        }
 
         log::info!("✅ Rust Submission Manager started successfully");
         Ok(())
@@ -95,10 +447,97 @@ impl SubmissionManager {
 
     pub async fn stop(&mut self) {
         self.is_running = false;
+        for task in self.tasks.drain(..) {
+            task.abort();
+        }
         log::info!("🛑 Rust Submission Manager stopped");
     }
 
-    pub async fn add_deposit(&mut self, deposit: Deposit) -> Result<()> {
+    /// Start the pipeline and HTTP API, serve until `shutdown` resolves or a
+    /// newer instance takes over the submitter lease, then stop all background
+    /// tasks. Batches still queued are kept in the queue snapshot for the next
+    /// run.
+    pub async fn run_until<F>(mut self, shutdown: F) -> Result<()>
+    where
+        F: std::future::Future<Output = ()> + Send + 'static,
+    {
+        self.start().await?;
+        let handover = self.handover.clone();
+        let manager = Arc::new(Mutex::new(self));
+
+        let draining = handover.clone();
+        let shutdown = async move {
+            tokio::select! {
+                _ = shutdown => {}
+                _ = draining.draining() => log::info!("🔁 Handing over to a new manager instance"),
+            }
+        };
+        http_server::start_http_server(manager.clone(), shutdown).await;
+
+        let mut manager = manager.lock().await;
+        if handover.state() == LeaseState::Draining {
+            manager.complete_handover().await?;
+        }
+        manager.stop().await;
+        Ok(())
+    }
+
+    /// Last step of a handover, once the HTTP API has stopped: seal the open
+    /// batch into the queue, persist the queue for the successor and release
+    /// the submitter lease to it.
+    async fn complete_handover(&mut self) -> Result<()> {
+        for batch in self.batch_manager.lock().await.finalize_batches().await? {
+            log::info!("📦 Sealing open batch with {} deposits for the successor", batch.deposits.len());
+            self.metrics.current_batch_size.set(batch.deposits.len() as f64);
+            self.record_sealed(&batch).await?;
+            self.store_batch_manifest(&batch).await?;
+            self.queue.lock().unwrap().enqueue(batch);
+        }
+        if self.handover.snapshot_writable() {
+            self.persist_queue().await;
+        } else {
+            log::warn!("Queue snapshot is not writable by this version, queued batches stay with their pending deposits");
+        }
+        self.handover.release().await
+    }
+
+    pub async fn handover_status(&self) -> Result<HandoverStatus> {
+        self.handover.status().await
+    }
+
+    pub fn role(&self) -> ManagerRole {
+        self.config.role
+    }
+
+    /// Health of each Solana RPC endpoint, the one in use flagged
+    pub fn rpc_endpoints(&self) -> Vec<rpc_pool::EndpointHealth> {
+        self.solana_client.rpc_endpoints()
+    }
+
+    /// Health of each circuit service behind the prover
+    pub fn prover_health(&self) -> Vec<prover_selection::ProverHealth> {
+        self.prover.health()
+    }
+
+    /// True once this instance is draining for a successor and refuses new deposits
+    pub fn is_draining(&self) -> bool {
+        self.handover.is_draining()
+    }
+
+    pub async fn add_deposit(&mut self, mut deposit: Deposit) -> Result<()> {
+        if self.handover.is_draining() {
+            return Err(OrchestratorError::SystemUnhealthy {
+                reason: "draining for handover to a new manager instance".to_string(),
+            });
+        }
+        self.check_duplicate_deposit(&deposit.deposit_id, &deposit.ton_tx_hash, &deposit.nonce)
+            .await?;
+        // Fee tier: paying enough buys the priority lane without asking for it
+        let threshold = self.config.priority_fee_threshold;
+        if threshold > 0 && deposit.fee_est.parse::<u64>().is_ok_and(|fee| fee >= threshold) {
+            deposit.priority = true;
+        }
+
         // Track metrics
         self.metrics.deposits_received.inc();
         
@@ -113,37 +552,477 @@ impl SubmissionManager {
             error_message: None,
             created_at: 0,
             updated_at: 0,
+            jetton_master: deposit.jetton_master.clone(),
+            jetton_symbol: None,
+            jetton_name: None,
+            jetton_decimals: None,
+            accepted_ton_usd: None,
+            accepted_sol_usd: None,
+            completed_ton_usd: None,
+            completed_sol_usd: None,
+            status_detail: None,
+            nullifier: deposit.nullifier.clone(),
+            nonce: Some(deposit.nonce.clone()),
         };
         
         self.database.store_deposit(deposit_record).await?;
+        self.snapshot_rates(std::slice::from_ref(&deposit.deposit_id), false).await;
+        self.event_bus.publish(PipelineEvent::DepositAccepted {
+            deposit_id: deposit.deposit_id.clone(),
+        });
+
+        // Deposits that can never be bridged go straight to the refund path
+        let rejection = match self.validate_deposit(&deposit) {
+            Some(issue) => Some(issue.message),
+            None => self.resolve_deposit_token(&deposit).await?,
+        };
+        if let Some(reason) = rejection {
+            log::warn!("Deposit {} rejected, scheduling refund: {}", deposit.deposit_id, reason);
+            self.database.create_refund_task(
+                &deposit.deposit_id,
+                &deposit.sender_address,
+                &deposit.amount,
+                &reason,
+            ).await?;
+            return Ok(());
+        }
+
+        // Proving workers, here or in a separate prover process, pick the
+        // deposit up from the handoff table, so ingestion never waits on a prover
+        self.database.push_handoff(roles::STAGE_PROVE, &serde_json::to_string(&deposit)?).await?;
+        self.prove_wakeup.notify_one();
+        Ok(())
+    }
 
-        // Generate proof for this individual deposit
-        let proof_start = Instant::now();
-        let proof = match self.proof_orchestrator.generate_proof(&deposit).await {
+    /// Add a deposit the prover was asked about to the open batch, unless
+    /// proving failed or it is a large transfer that has to wait out its delay
+    /// window first
+    async fn batch_generated_proof(
+        &mut self,
+        deposit: Deposit,
+        proof: Result<Proof>,
+        proving_time: Duration,
+    ) -> Result<()> {
+        let proof = match proof {
             Ok(proof) => {
-                self.metrics.proof_generation_time.observe(proof_start.elapsed().as_secs_f64());
+                self.metrics.proof_generation_time.observe(proving_time.as_secs_f64());
                 self.metrics.proofs_generated.inc();
+                self.event_bus.publish(PipelineEvent::ProofReady {
+                    deposit_id: deposit.deposit_id.clone(),
+                });
                 proof
             }
             Err(e) => {
+                if matches!(e, OrchestratorError::InvalidDeposit(_)) {
+                    self.metrics.deposits_rejected_on_ton.inc();
+                }
                 log::error!("Failed to generate proof for deposit {}: {}", deposit.deposit_id, e);
                 self.database.update_deposit_status(&deposit.deposit_id, "failed", Some(&e.to_string())).await?;
                 return Ok(()); // Don't add to batch if proof generation fails
             }
         };
 
-        // Add to batch (deposit + proof)
-        if let Some(batch) = self.batch_manager.add_to_batch(deposit, proof).await? {
+        // Mock proofs only verify in mock mode, where verification is a no-op anyway
+        if self.config.verify_proofs_locally && !self.config.mock_proving {
+            if let Err(e) = self.proof_verifier.verify(&deposit, &proof) {
+                log::error!("Proof for deposit {} failed local verification: {}", deposit.deposit_id, e);
+                self.metrics.proofs_rejected_locally.inc();
+                self.database.update_deposit_status(&deposit.deposit_id, "failed", Some(&e.to_string())).await?;
+                return Ok(());
+            }
+        }
+
+        self.admit_proved_deposit(deposit, proof).await
+    }
+
+    /// Batch a proven deposit, unless it is a large transfer that has to wait
+    /// out its delay window first
+    async fn admit_proved_deposit(&mut self, deposit: Deposit, proof: Proof) -> Result<()> {
+        // Large transfers wait out the risk window before they can be batched
+        if self.transfer_delay.applies_to(&deposit) {
+            log::info!(
+                "⏳ Holding large deposit {} for {}s before submission",
+                deposit.deposit_id,
+                self.config.large_transfer_delay_secs
+            );
+            self.database.update_deposit_status(&deposit.deposit_id, "delayed", None).await?;
+            self.transfer_delay.hold(deposit, proof);
+            return Ok(());
+        }
+
+        self.batch_deposit(deposit, proof).await
+    }
+
+    // Add to batch (deposit + proof)
+    async fn batch_deposit(&mut self, deposit: Deposit, proof: Proof) -> Result<()> {
+        let mut batch_manager = self.batch_manager.lock().await;
+        batch_manager.check_not_batched(&deposit)?;
+        // Persisted first: a crash must not lose a deposit whose proof is done
+        let (batch_id, created_at, position) = batch_manager.open_batch(&batch_key(&deposit));
+        let entry = serde_json::to_string(&roles::ProvedDeposit { deposit: deposit.clone(), proof: proof.clone() })?;
+        self.database
+            .add_batch_member(&batch_id, created_at, &deposit.deposit_id, position, &entry)
+            .await?;
+
+        let sealed = batch_manager.add_to_batch(deposit, proof).await?;
+        drop(batch_manager);
+        if let Some(batch) = sealed {
             log::info!("🎯 Batch completed with {} deposits, adding to queue", batch.deposits.len());
             self.metrics.current_batch_size.set(batch.deposits.len() as f64);
-            self.queue_manager.enqueue_batch(batch).await;
+            self.hand_off_batch(batch).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Pass a sealed batch to the submitter: in-process over the event bus, or
+    /// through the handoff table when the submitter runs as a separate process
+    async fn hand_off_batch(&self, batch: Batch) -> Result<()> {
+        self.record_sealed(&batch).await?;
+        self.store_batch_manifest(&batch).await?;
+        if !self.config.role.runs_submitter() {
+            self.database.push_handoff(roles::STAGE_SUBMIT, &serde_json::to_string(&batch)?).await?;
+        }
+        self.event_bus.publish(PipelineEvent::BatchSealed(batch));
+        Ok(())
+    }
+
+    /// The batch no longer needs rebuilding on startup once it left the batch manager
+    async fn record_sealed(&self, batch: &Batch) -> Result<()> {
+        if let Some(deposit) = batch.deposits.first() {
+            self.database.seal_batch_of(&deposit.deposit_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Sign and store the commitment to a sealed batch's contents
+    async fn store_batch_manifest(&self, batch: &Batch) -> Result<()> {
+        let manifest = BatchManifest::for_batch(batch)?;
+        let message = serde_json::to_vec(&manifest)?;
+        let (signer, signature) = self.solana_client.sign_message(&message);
+        self.database
+            .store_batch_manifest(&database::BatchManifestRecord {
+                batch_id: manifest.batch_id.clone(),
+                merkle_root: manifest.merkle_root.clone(),
+                manifest: String::from_utf8(message).expect("serde_json writes UTF-8"),
+                signer,
+                signature,
+                created_at: manifest.created_at,
+            })
+            .await?;
+        log::info!("🧾 Batch {} manifest stored, events root {}", manifest.batch_id, manifest.merkle_root);
+        Ok(())
+    }
+
+    /// Manifest of batch `batch_id` with its signature, as stored when it was sealed
+    pub async fn batch_manifest(&self, batch_id: &str) -> Result<Option<SignedBatchManifest>> {
+        let Some(record) = self.database.get_batch_manifest(batch_id).await? else {
+            return Ok(None);
+        };
+        Ok(Some(SignedBatchManifest {
+            manifest: serde_json::from_str(&record.manifest)?,
+            signer: record.signer,
+            signature: record.signature,
+        }))
+    }
+
+    /// Rebuild the batches a previous run was filling when it stopped
+    async fn restore_open_batches(&self) -> Result<()> {
+        for record in self.database.get_open_batches().await? {
+            self.restore_open_batch(record).await?;
+        }
+        Ok(())
+    }
+
+    async fn restore_open_batch(&self, record: database::BatchRecord) -> Result<()> {
+        let mut batch = Batch {
+            // Batches opened before ids were UUIDs keep their key; the new id only names the batch
+            id: uuid::Uuid::parse_str(&record.batch_id).unwrap_or_else(|_| uuid::Uuid::new_v4()),
+            deposits: Vec::new(),
+            proofs: Vec::new(),
+            created_at: chrono::DateTime::from_timestamp_millis(record.created_at).unwrap_or_else(chrono::Utc::now),
+            retry_count: 0,
+        };
+        for entry in self.database.get_open_batch_entries(&record.batch_id).await? {
+            let proved: roles::ProvedDeposit = serde_json::from_str(&entry)?;
+            batch.deposits.push(proved.deposit);
+            batch.proofs.push(proved.proof);
+        }
+
+        log::info!("📦 Restored open batch {} with {} deposits", record.batch_id, batch.deposits.len());
+        self.metrics.current_batch_size.set(batch.deposits.len() as f64);
+        self.batch_manager.lock().await.restore(record.batch_id, batch);
+        Ok(())
+    }
+
+    /// Accept a proof generated outside the bridge's circuit services for a
+    /// deposit still waiting for a prover process. A valid proof replaces the
+    /// deposit's prove handoff, so the proving workers batch it without asking
+    /// a circuit service. Returns false when the deposit is not waiting.
+    pub async fn submit_external_proof(&self, deposit_id: &str, proof: Proof) -> Result<bool> {
+        if !self.proof_verifier.is_enabled() {
+            return Err(OrchestratorError::ConfigurationError(
+                "external proofs need a readable verification key".to_string(),
+            ));
+        }
+
+        let Some(handoff) = self.database.find_deposit_handoff(roles::STAGE_PROVE, deposit_id).await? else {
+            return Ok(false);
+        };
+        let deposit: Deposit = serde_json::from_str(&handoff.payload)?;
+
+        if let Err(e) = self.proof_verifier.verify(&deposit, &proof) {
+            self.metrics.external_proofs_rejected.inc();
+            log::warn!("Rejected external proof for deposit {}: {}", deposit_id, e);
+            return Err(e);
+        }
+
+        let payload = serde_json::to_string(&roles::ProvedDeposit { deposit, proof })?;
+        // A prover may have claimed the deposit while the proof was checked
+        if !self.database.restage_handoff(handoff.id, roles::STAGE_VERIFIED, &payload).await? {
+            return Ok(false);
         }
 
+        self.metrics.external_proofs_accepted.inc();
+        self.event_bus.publish(PipelineEvent::ProofReady {
+            deposit_id: deposit_id.to_string(),
+        });
+        log::info!("🧾 External proof accepted for deposit {}", deposit_id);
+        Ok(true)
+    }
+
+    /// Move large deposits whose delay window has elapsed into batching
+    async fn release_delayed_deposits(&mut self) -> Result<()> {
+        for (deposit, proof) in self.transfer_delay.take_released() {
+            log::info!("🔓 Releasing delayed deposit {}", deposit.deposit_id);
+            self.database.update_deposit_status(&deposit.deposit_id, "pending", None).await?;
+            self.batch_deposit(deposit, proof).await?;
+        }
         Ok(())
     }
 
-    async fn start_health_monitoring(&self) {
+    /// Cancel a large deposit still inside its delay window and schedule a refund.
+    /// Returns false if the deposit is not (or no longer) held.
+    pub async fn cancel_delayed_deposit(&self, deposit_id: &str, reason: &str) -> Result<bool> {
+        let Some(deposit) = self.transfer_delay.cancel(deposit_id) else {
+            return Ok(false);
+        };
+
+        log::warn!("🛑 Delayed deposit {} cancelled: {}", deposit_id, reason);
+        self.database.create_refund_task(
+            &deposit.deposit_id,
+            &deposit.sender_address,
+            &deposit.amount,
+            &format!("Cancelled during delay window: {}", reason),
+        ).await?;
+        Ok(true)
+    }
+
+    /// Seconds left in a deposit's large-transfer delay window, if it is held
+    pub fn delay_remaining_secs(&self, deposit_id: &str) -> Option<u64> {
+        self.transfer_delay.remaining(deposit_id).map(|d| d.as_secs())
+    }
+
+    /// Record an ingestion request by the hash of its exact body and classify repeats
+    pub async fn admit_request(
+        &self,
+        body: &[u8],
+        api_key: &str,
+        deposit_id: &str,
+        request_id: &str,
+    ) -> Result<RequestAdmission> {
+        let record = database::RequestLogRecord {
+            request_hash: hex::encode(solana_sdk::hash::hash(body).to_bytes()),
+            api_key: api_key.to_string(),
+            deposit_id: deposit_id.to_string(),
+            request_id: request_id.to_string(),
+            received_at: 0,
+        };
+
+        let Some(previous) = self.database.log_request(record).await? else {
+            return Ok(RequestAdmission::New);
+        };
+
+        let age = chrono::Utc::now().timestamp() - previous.received_at;
+        if age <= self.config.idempotency_window_secs as i64 {
+            Ok(RequestAdmission::Duplicate)
+        } else {
+            log::warn!(
+                "🚫 Replayed request for deposit {} (api key {}, first seen {}s ago)",
+                deposit_id, api_key, age
+            );
+            Ok(RequestAdmission::Replay)
+        }
+    }
+
+    pub fn spend_guard_status(&self) -> SpendGuardStatus {
+        self.spend_guard.lock().unwrap().status()
+    }
+
+    /// Resume submissions after the spend guard tripped
+    pub fn reset_spend_guard(&self) -> SpendGuardStatus {
+        let mut guard = self.spend_guard.lock().unwrap();
+        if let Some(reason) = guard.tripped() {
+            log::warn!("Spend guard reset by operator (was: {})", reason);
+        }
+        guard.reset();
+        self.metrics.spend_guard_tripped.set(0.0);
+        guard.status()
+    }
+
+    pub fn is_admin_key(&self, key: &str) -> bool {
+        !self.config.admin_api_key.is_empty() && key == self.config.admin_api_key
+    }
+
+    pub async fn get_request_log(&self, api_key: Option<&str>, limit: i64) -> Result<Vec<database::RequestLogRecord>> {
+        Ok(self.database.get_request_log(api_key, limit).await?)
+    }
+
+    /// Where to message the user about a deposit (`SCOPE_DEPOSIT`) or about
+    /// every deposit submitted with an API key (`SCOPE_API_KEY`)
+    pub async fn set_notification_targets(
+        &self,
+        scope: &str,
+        scope_id: &str,
+        targets: &[NotificationTarget],
+    ) -> Result<()> {
+        Ok(self.database.set_notification_targets(scope, scope_id, targets).await?)
+    }
+
+    pub async fn get_deposit(&self, deposit_id: &str) -> Result<Option<database::DepositRecord>> {
+        Ok(self.database.get_deposit(deposit_id).await?)
+    }
+
+    /// Resolve and record the deposit's jetton metadata; returns a rejection
+    /// reason if the jetton is unsupported or its metadata does not match the registry
+    async fn resolve_deposit_token(&self, deposit: &Deposit) -> Result<Option<String>> {
+        match self.check_deposit_token(deposit).await {
+            Ok(Some(metadata)) => {
+                self.database.set_deposit_token(&deposit.deposit_id, &metadata).await?;
+                Ok(None)
+            }
+            Ok(None) => Ok(None),
+            Err(reason) => Ok(Some(reason)),
+        }
+    }
+
+    /// Metadata of the deposit's jetton (None for native TON or when it can't
+    /// be resolved), or the reason the jetton can't be bridged
+    async fn check_deposit_token(&self, deposit: &Deposit) -> std::result::Result<Option<JettonMetadata>, String> {
+        let Some(master) = &deposit.jetton_master else {
+            return Ok(None);
+        };
+
+        let metadata = match self.jetton_metadata.resolve(master).await {
+            Ok(metadata) => metadata,
+            // TON API unavailable: fall back to the registry entry if there is one
+            Err(e) => match self.token_registry.get(master) {
+                Some(entry) => {
+                    log::warn!("Jetton {} metadata lookup failed, using registry: {}", master, e);
+                    entry.clone()
+                }
+                None => {
+                    // Without metadata only registry membership can be checked
+                    log::warn!("Jetton {} metadata lookup failed: {}", master, e);
+                    return self.token_registry.check_supported(master).map(|()| None);
+                }
+            },
+        };
+
+        self.token_registry.validate(&metadata)?;
+        Ok(Some(metadata))
+    }
+
+    /// Returns the reason a deposit is terminally invalid, if any
+    fn validate_deposit(&self, deposit: &Deposit) -> Option<ValidationIssue> {
+        if solana_sdk::pubkey::Pubkey::from_str(&deposit.recipient_solana).is_err() {
+            return Some(ValidationIssue::new(
+                "invalid_recipient",
+                format!("Invalid Solana recipient: {}", deposit.recipient_solana),
+            ));
+        }
+
+        if let Some(address) = [&deposit.sender_address, &deposit.recipient_solana]
+            .into_iter()
+            .find(|address| self.config.quarantined_addresses.contains(address))
+        {
+            return Some(ValidationIssue::new(
+                "quarantined_address",
+                format!("Address {} is quarantined", address),
+            ));
+        }
+
+        if let Err(reason) = privacy::check_recipient_reveal(deposit) {
+            return Some(ValidationIssue::new("invalid_recipient_reveal", reason));
+        }
+
+        match deposit.amount.parse::<u64>() {
+            Ok(0) | Err(_) => Some(ValidationIssue::new(
+                "invalid_amount",
+                format!("Invalid amount: {}", deposit.amount),
+            )),
+            Ok(amount) if amount > self.config.max_deposit_amount => Some(ValidationIssue::new(
+                "amount_over_limit",
+                format!("Amount {} exceeds limit {}", amount, self.config.max_deposit_amount),
+            )),
+            Ok(_) => None,
+        }
+    }
+
+    /// Reject a deposit whose id, or TON transaction and nonce, was already accepted
+    pub async fn check_duplicate_deposit(&self, deposit_id: &str, ton_tx_hash: &str, nonce: &str) -> Result<()> {
+        match self.database.find_duplicate_deposit(deposit_id, ton_tx_hash, nonce).await? {
+            Some(existing_id) => Err(OrchestratorError::DuplicateDeposit {
+                deposit_id: deposit_id.to_string(),
+                existing_id,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Run every ingestion check against `deposit` without storing or queuing
+    /// it. An empty result means the deposit would be accepted.
+    pub async fn preflight_deposit(&self, deposit: &Deposit) -> Result<Vec<ValidationIssue>> {
+        let mut issues = Vec::new();
+
+        if self.database.get_deposit(&deposit.deposit_id).await?.is_some() {
+            issues.push(ValidationIssue::new(
+                "duplicate_deposit",
+                format!("Deposit {} was already submitted", deposit.deposit_id),
+            ));
+        }
+        if !self.database.get_deposits_by_ton_tx_hash(&deposit.ton_tx_hash).await?.is_empty() {
+            issues.push(ValidationIssue::new(
+                "duplicate_ton_tx",
+                format!("TON transaction {} was already bridged", deposit.ton_tx_hash),
+            ));
+        }
+
+        if let Some(issue) = self.validate_deposit(deposit) {
+            issues.push(issue);
+        }
+        if let Err(reason) = self.check_deposit_token(deposit).await {
+            issues.push(ValidationIssue::new("unsupported_token", reason));
+        }
+
+        Ok(issues)
+    }
+
+    /// Hold time a valid deposit would get from the large-transfer delay
+    pub fn preflight_delay_secs(&self, deposit: &Deposit) -> Option<u64> {
+        self.transfer_delay
+            .applies_to(deposit)
+            .then_some(self.config.large_transfer_delay_secs)
+    }
+
+    async fn start_health_monitoring(&self) -> tokio::task::JoinHandle<()> {
         let health_monitor = self.health_monitor.clone();
+        let prover = self.prover.clone();
+        let solana_client = self.solana_client.clone();
+        let database = self.database.clone();
+        let metrics = self.metrics.clone();
+        let root_watch = self.root_watch.clone();
+        let min_payer_balance = self.config.min_payer_balance_lamports;
 
         tokio::spawn(async move {
             let mut interval = interval(Duration::from_secs(30));
@@ -151,7 +1030,37 @@ impl SubmissionManager {
             loop {
                 interval.tick().await;
                 
-                match health_monitor.get_system_health().await {
+                prover.refresh_status().await;
+                let provers = prover.health();
+                for service in &provers {
+                    let labels = [service.url.as_str()];
+                    metrics
+                        .circuit_service_up
+                        .with_label_values(&labels)
+                        .set(if service.healthy { 1.0 } else { 0.0 });
+                    metrics
+                        .circuit_service_failures
+                        .with_label_values(&labels)
+                        .set(service.consecutive_failures as f64);
+                    metrics
+                        .circuit_service_proving_ms
+                        .with_label_values(&labels)
+                        .set(service.avg_proving_ms.unwrap_or(0.0));
+                    metrics
+                        .circuit_service_queue_depth
+                        .with_label_values(&labels)
+                        .set(service.queue_depth.unwrap_or(0) as f64);
+                    metrics
+                        .circuit_service_in_flight
+                        .with_label_values(&labels)
+                        .set(service.in_flight as f64);
+                    metrics
+                        .circuit_service_expected_wait_ms
+                        .with_label_values(&labels)
+                        .set(service.expected_wait_ms.unwrap_or(0.0));
+                }
+
+                match health_monitor.get_system_health(&provers).await {
                     Ok(health) => {
                         let health_status = if health_monitor.is_system_healthy(&health) {
                             "✅ Healthy"
@@ -163,62 +1072,598 @@ impl SubmissionManager {
                     }
                     Err(e) => log::error!("Health check failed: {}", e),
                 }
+
+                match solana_client.fetch_ton_state_root().await {
+                    Ok(root) => {
+                        root_watch.observe(root, chrono::Utc::now().timestamp());
+                        prover.set_anchor_root(root);
+                    }
+                    Err(e) => log::warn!("Could not read the TON root: {}", e),
+                }
+
+                // RPC endpoint in use, as measured by the calls above and the pipeline's
+                let endpoints = solana_client.rpc_endpoints();
+                if let Some((index, active)) = endpoints.iter().enumerate().find(|(_, endpoint)| endpoint.active) {
+                    metrics.solana_rpc_active_endpoint.set(index as f64);
+                    metrics.solana_rpc_latency.set(active.avg_latency_ms.unwrap_or(0.0));
+                }
+                metrics.solana_rpc_failovers.set(solana_client.rpc_failovers() as f64);
+
+                // Submissions hold while the payer is short, until this sees it topped up
+                let was_underfunded = solana_client.payer_underfunded();
+                match solana_client.check_payer_balance(min_payer_balance).await {
+                    Ok(balance) => {
+                        metrics.payer_balance_lamports.set(balance as f64);
+                        if was_underfunded {
+                            log::info!("💰 Payer funded again ({} lamports), resuming submissions", balance);
+                        }
+                    }
+                    Err(e @ OrchestratorError::InsufficientFunds(_)) => {
+                        if !was_underfunded {
+                            metrics.insufficient_funds_failures.inc();
+                        }
+                        log::error!("💸 {}, submissions paused", e);
+                    }
+                    Err(e) => log::warn!("Could not read the payer balance: {}", e),
+                }
+
+                // Work waiting between split-role processes, for scaling provers
+                for (stage, gauge) in [
+                    (roles::STAGE_PROVE, &metrics.handoff_prove_backlog),
+                    (roles::STAGE_SUBMIT, &metrics.handoff_submit_backlog),
+                ] {
+                    match database.count_handoffs(stage).await {
+                        Ok(count) => gauge.set(count as f64),
+                        Err(e) => log::warn!("Could not count {} handoffs: {}", stage, e),
+                    }
+                }
+
+                let onchain = match solana_client.fetch_event_counters().await {
+                    Ok(counters) => counters,
+                    Err(e) => {
+                        log::warn!("Could not read on-chain event counters: {}", e);
+                        continue;
+                    }
+                };
+                match database.get_completed_totals().await {
+                    Ok((events_verified, total_amount_bridged)) => {
+                        let local = EventCounters { events_verified, total_amount_bridged };
+                        let drift = health_monitor.reconcile_event_counters(local, onchain);
+                        metrics.onchain_events_verified.set(onchain.events_verified as f64);
+                        metrics.event_counter_drift.set(drift as f64);
+                    }
+                    Err(e) => log::warn!("Could not read completed deposit totals: {}", e),
+                }
             }
-        });
+        })
     }
 
-    async fn start_batch_processing(&self) {
-        log::info!("🔄 Starting batch processing engine...");
-        
-        let mut manager = self.clone();
+    /// Keep the compute-unit price bid on submissions in line with recent fees
+    async fn start_priority_fee_updates(&self) -> tokio::task::JoinHandle<()> {
+        let solana_client = self.solana_client.clone();
+        let gas_optimizer = self.gas_optimizer.clone();
+        let update_interval = Duration::from_millis(self.config.gas_update_interval.max(1000));
 
         tokio::spawn(async move {
-            let mut interval = interval(Duration::from_secs(10)); // Process every 10 seconds
-            
+            let mut interval = interval(update_interval);
+
             loop {
                 interval.tick().await;
-                
-                // Process queued batches
-                if let Err(e) = manager.process_queued_batches().await {
-                    log::error!("Error processing batches: {}", e);
-                }
 
-                // Finalize any partial batch that's been waiting too long
-                if let Err(e) = manager.finalize_stale_batch().await {
-                    log::error!("Error finalizing stale batch: {}", e);
+                match solana_client.fetch_recent_priority_fees().await {
+                    Ok(fees) => {
+                        let fee = gas_optimizer.observe_recent_fees(fees);
+                        log::debug!("⛽ Priority fee set to {} micro-lamports/CU", fee);
+                    }
+                    Err(e) => log::warn!("Could not fetch recent priority fees: {}", e),
                 }
             }
-        });
+        })
     }
 
-    async fn process_queued_batches(&mut self) -> Result<()> {
-        // Get the next batch from queue (FIFO)
-        if let Some(batch) = self.queue_manager.dequeue_batch().await {
-            log::info!("📦 Processing batch with {} deposits", batch.deposits.len());
-            
-            // METRIC: Batch processing started
-            self.metrics.batches_processing.inc();
-            let batch_start_time = Instant::now();
+    /// Periodically audit cross-cutting invariants; violations fire a critical alert
+    async fn start_invariant_audit(&self) -> tokio::task::JoinHandle<()> {
+        let database = self.database.clone();
+        let solana_client = self.solana_client.clone();
+        let metrics = self.metrics.clone();
+        let last_audit = self.last_audit.clone();
+        let audit_interval = Duration::from_secs(self.config.invariant_check_interval_secs);
 
-            // Submit batch to Solana
-            let tx_start = Instant::now();
-            match self.solana_client.submit_batch(&batch).await {
-                Ok(tx_signature) => {
-                    // METRICS: Success
+        tokio::spawn(async move {
+            let mut interval = interval(audit_interval);
+
+            loop {
+                interval.tick().await;
+
+                let audit = invariants::InvariantAudit::run(&database, &solana_client).await;
+                metrics.invariant_violations.set(audit.violations.len() as f64);
+                last_audit.put(audit);
+            }
+        })
+    }
+
+    /// Periodically compare SQLite and the Postgres mirror, repairing drift
+    /// left by failed dual writes
+    async fn start_mirror_parity_check(&self) -> tokio::task::JoinHandle<()> {
+        let database = self.database.clone();
+        let metrics = self.metrics.clone();
+        let check_interval = Duration::from_secs(self.config.db_mirror_parity_interval_secs.max(1));
+
+        tokio::spawn(async move {
+            let mut interval = interval(check_interval);
+
+            loop {
+                interval.tick().await;
+
+                match database.migration_phase().await {
+                    Ok(db_migration::MigrationPhase::Off) => continue,
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::warn!("Could not read the migration phase: {}", e);
+                        continue;
+                    }
+                }
+                match database.check_deposit_parity(true).await {
+                    Ok(Some(report)) => {
+                        metrics.db_mirror_parity_mismatches.set((report.missing + report.mismatched + report.extra) as f64);
+                        if report.repaired > 0 || report.extra > 0 {
+                            log::warn!(
+                                "🪞 Mirror parity: {} missing, {} mismatched, {} extra, {} repaired",
+                                report.missing, report.mismatched, report.extra, report.repaired
+                            );
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => log::error!("Mirror parity check failed: {}", e),
+                }
+            }
+        })
+    }
+
+    async fn start_database_maintenance(&self) -> tokio::task::JoinHandle<()> {
+        let database = self.database.clone();
+        let metrics = self.metrics.clone();
+        let checkpoint_interval = Duration::from_secs(self.config.db_checkpoint_interval_secs.max(1));
+        let vacuum_interval = chrono::Duration::seconds(self.config.db_vacuum_interval_secs as i64);
+        let window = (
+            self.config.db_maintenance_window_start_hour,
+            self.config.db_maintenance_window_end_hour,
+        );
+
+        tokio::spawn(async move {
+            let mut interval = interval(checkpoint_interval);
+            let mut last_vacuum: Option<chrono::DateTime<chrono::Utc>> = None;
+
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = database.checkpoint_wal().await {
+                    log::error!("WAL checkpoint failed: {}", e);
+                }
+
+                let now = chrono::Utc::now();
+                let vacuum_due = last_vacuum.map_or(true, |last| now - last >= vacuum_interval);
+                if vacuum_due && Self::in_maintenance_window(now, window) {
+                    log::info!("🧹 Running scheduled database VACUUM");
+                    match database.vacuum().await {
+                        Ok(()) => {
+                            metrics.database_vacuums.inc();
+                            last_vacuum = Some(now);
+                        }
+                        Err(e) => log::error!("Database VACUUM failed: {}", e),
+                    }
+                }
+
+                match database.database_size_bytes().await {
+                    Ok(size) => metrics.database_size_bytes.set(size as f64),
+                    Err(e) => log::error!("Failed to read database size: {}", e),
+                }
+            }
+        })
+    }
+
+    /// Whether `now` falls in the UTC hour window `[start, end)`, which may wrap midnight
+    fn in_maintenance_window(now: chrono::DateTime<chrono::Utc>, (start, end): (u32, u32)) -> bool {
+        use chrono::Timelike;
+        let hour = now.hour();
+        if start <= end {
+            hour >= start && hour < end
+        } else {
+            hour >= start || hour < end
+        }
+    }
+
+    async fn start_batch_processing(&self) -> tokio::task::JoinHandle<()> {
+        log::info!("🔄 Starting batch processing engine...");
+        
+        let mut manager = self.clone();
+        let mut events = self.event_bus.subscribe();
+
+        tokio::spawn(async move {
+            // Only the submitter lease owner may restore and overwrite the queue snapshot
+            let mut restored = false;
+            let mut snapshot_writable = false;
+
+            let mut interval = interval(Duration::from_secs(manager.config.batch_process_interval_secs));
+            
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    event = events.recv() => {
+                        match event {
+                            // Sealed batches are queued by the submitter stage
+                            Ok(PipelineEvent::BatchSealed(batch)) => {
+                                manager.queue.lock().unwrap().enqueue(batch);
+                                if snapshot_writable {
+                                    manager.persist_queue().await;
+                                }
+                            }
+                            Ok(_) => {}
+                            Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                                log::warn!("Submitter lagged behind the event bus, {} events skipped", skipped);
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                        }
+                        continue;
+                    }
+                }
+
+                // Standby instances leave the queue to the lease owner; a
+                // draining one has already finished its last submission
+                match manager.handover.tick().await {
+                    Ok(LeaseState::Owner) => {}
+                    Ok(_) => continue,
+                    Err(e) => {
+                        log::error!("Could not renew the submitter lease: {}", e);
+                        continue;
+                    }
+                }
+                if !restored {
+                    // Batches sealed but not yet submitted by the previous owner
+                    snapshot_writable = manager.restore_queue().await;
+                    manager.handover.set_snapshot_writable(snapshot_writable);
+                    restored = true;
+                }
+
+                // Batches sealed by separate prover processes
+                if !manager.config.role.runs_prover() {
+                    if let Err(e) = manager.pull_handed_off_batches().await {
+                        log::error!("Error pulling handed-off batches: {}", e);
+                    }
+                }
+                
+                // Process queued batches
+                if let Err(e) = manager.process_queued_batches().await {
+                    log::error!("Error processing batches: {}", e);
+                }
+
+                // Complete deposits whose transactions reached finality
+                if let Err(e) = manager.confirm_tracked_signatures().await {
+                    log::error!("Error confirming landed transactions: {}", e);
+                }
+
+                // Drive refunds for terminally failed deposits
+                if let Err(e) = manager.refund_engine.process_due_refunds(&manager.database).await {
+                    log::error!("Error processing refunds: {}", e);
+                }
+
+                if snapshot_writable {
+                    manager.persist_queue().await;
+                }
+            }
+        })
+    }
+
+    /// Prover role: claim deposits handed off by ingestion, prove up to
+    /// `prover_concurrency` of them at once, batch each as its proof arrives,
+    /// and seal batches for the submitter
+    async fn start_proving_worker(&self) -> tokio::task::JoinHandle<()> {
+        let concurrency = self.config.prover_concurrency.max(1);
+        log::info!("🧮 Starting proving worker ({} concurrent proofs)...", concurrency);
+
+        let mut manager = self.clone();
+
+        tokio::spawn(async move {
+            let claimer = manager.handover.instance_id().to_string();
+            let mut interval = interval(Duration::from_secs(5));
+
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = manager.prove_wakeup.notified() => {}
+                }
+
+                // Adaptive batch sizing follows the backlog of deposits waiting to be proven
+                if manager.config.adaptive_batch_sizing {
+                    match manager.database.count_handoffs(roles::STAGE_PROVE).await {
+                        Ok(depth) => {
+                            if let Some(target) = manager.batch_manager.lock().await.set_queue_depth(depth as usize) {
+                                manager.metrics.batch_target_size.set(target as f64);
+                            }
+                        }
+                        Err(e) => log::warn!("Could not count deposits waiting to be proven: {}", e),
+                    }
+                }
+
+                let claim_limit = manager.config.batch_size.max(concurrency) as i64;
+                match manager
+                    .database
+                    .claim_handoffs(roles::STAGE_PROVE, &claimer, claim_limit, roles::HANDOFF_RECLAIM_SECS)
+                    .await
+                {
+                    Ok(items) => {
+                        let mut deposits = Vec::with_capacity(items.len());
+                        for item in items {
+                            match serde_json::from_str::<Deposit>(&item.payload) {
+                                Ok(deposit) => deposits.push((item.id, deposit)),
+                                Err(e) => {
+                                    log::error!("Dropping undecodable deposit handoff {}: {}", item.id, e);
+                                    if let Err(e) = manager.database.complete_handoff(item.id).await {
+                                        log::error!("Could not complete deposit handoff {}: {}", item.id, e);
+                                    }
+                                }
+                            }
+                        }
+
+                        let prover = manager.prover.clone();
+                        let mut proofs = futures_util::stream::iter(deposits)
+                            .map(|(id, deposit)| {
+                                let prover = prover.clone();
+                                async move {
+                                    let started = Instant::now();
+                                    let proof = prover.generate_proof(&deposit).await;
+                                    (id, deposit, proof, started.elapsed())
+                                }
+                            })
+                            .buffer_unordered(concurrency);
+
+                        while let Some((id, deposit, proof, proving_time)) = proofs.next().await {
+                            if let Err(e) = manager.batch_generated_proof(deposit, proof, proving_time).await {
+                                // Left claimed; handed out again once the claim goes stale
+                                log::error!("Error batching proven deposit handoff {}: {}", id, e);
+                                continue;
+                            }
+                            if let Err(e) = manager.database.complete_handoff(id).await {
+                                log::error!("Could not complete deposit handoff {}: {}", id, e);
+                            }
+                        }
+                    }
+                    Err(e) => log::error!("Error claiming deposits to prove: {}", e),
+                }
+
+                // Deposits proven externally through POST /api/proofs
+                match manager
+                    .database
+                    .claim_handoffs(roles::STAGE_VERIFIED, &claimer, claim_limit, roles::HANDOFF_RECLAIM_SECS)
+                    .await
+                {
+                    Ok(items) => {
+                        for item in items {
+                            match serde_json::from_str::<roles::ProvedDeposit>(&item.payload) {
+                                Ok(proved) => {
+                                    if let Err(e) = manager.admit_proved_deposit(proved.deposit, proved.proof).await {
+                                        log::error!("Error batching externally proven deposit {}: {}", item.id, e);
+                                        continue;
+                                    }
+                                }
+                                Err(e) => log::error!("Dropping undecodable verified handoff {}: {}", item.id, e),
+                            }
+                            if let Err(e) = manager.database.complete_handoff(item.id).await {
+                                log::error!("Could not complete verified handoff {}: {}", item.id, e);
+                            }
+                        }
+                    }
+                    Err(e) => log::error!("Error claiming externally proven deposits: {}", e),
+                }
+
+                if let Err(e) = manager.release_delayed_deposits().await {
+                    log::error!("Error releasing delayed deposits: {}", e);
+                }
+
+                if let Err(e) = manager.finalize_stale_batch().await {
+                    log::error!("Error finalizing stale batch: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Submitter-only role: move batches sealed by prover processes into the
+    /// queue. They are only removed from the handoff table once the queue
+    /// snapshot holding them is written.
+    async fn pull_handed_off_batches(&mut self) -> Result<()> {
+        if !self.handover.snapshot_writable() {
+            return Ok(());
+        }
+
+        let claimer = self.handover.instance_id().to_string();
+        let items = self
+            .database
+            .claim_handoffs(roles::STAGE_SUBMIT, &claimer, 100, roles::HANDOFF_RECLAIM_SECS)
+            .await?;
+        if items.is_empty() {
+            return Ok(());
+        }
+
+        for item in &items {
+            match serde_json::from_str::<Batch>(&item.payload) {
+                Ok(batch) => {
+                    log::info!("📥 Queueing handed-off batch with {} deposits", batch.deposits.len());
+                    self.queue.lock().unwrap().enqueue(batch);
+                }
+                Err(e) => log::error!("Dropping undecodable batch handoff {}: {}", item.id, e),
+            }
+        }
+        self.persist_queue().await;
+
+        for item in items {
+            self.database.complete_handoff(item.id).await?;
+        }
+        Ok(())
+    }
+
+    /// Load the persisted queue. Returns false when the stored snapshot is in a
+    /// format this manager can't read; it is then left untouched for a newer
+    /// manager instead of being overwritten.
+    async fn restore_queue(&mut self) -> bool {
+        let raw = match self.database.load_queue_snapshot().await {
+            Ok(Some(raw)) => raw,
+            Ok(None) => return true,
+            Err(e) => {
+                log::error!("Could not load queue snapshot: {}", e);
+                return false;
+            }
+        };
+
+        match queue_snapshot::decode(&raw) {
+            Ok(batches) => {
+                log::info!("♻️ Restored {} queued batches from snapshot", batches.len());
+                self.queue.lock().unwrap().restore(batches);
+                true
+            }
+            Err(e) => {
+                log::error!("Queue snapshot not restored, leaving it in place: {}", e);
+                false
+            }
+        }
+    }
+
+    async fn persist_queue(&self) {
+        let batches = self.queue.lock().unwrap().batches();
+        let payload = match queue_snapshot::encode(&batches) {
+            Ok(payload) => payload,
+            Err(e) => {
+                log::error!("Could not encode queue snapshot: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = self
+            .database
+            .save_queue_snapshot(queue_snapshot::QUEUE_SNAPSHOT_VERSION, &payload)
+            .await
+        {
+            log::error!("Could not persist queue snapshot: {}", e);
+        }
+    }
+
+    async fn process_queued_batches(&mut self) -> Result<()> {
+        // Hold the queue while the submission rate or fee budget is exhausted
+        let next_len = self.queue.lock().unwrap().peek_len();
+        if let Some(next_len) = next_len {
+            // Submissions would only fail (and burn retries) while the program is paused
+            match self.solana_client.fetch_paused().await {
+                Ok(paused) => {
+                    self.metrics.bridge_paused.set(if paused { 1.0 } else { 0.0 });
+                    if paused {
+                        log::warn!("⛔ Bridge program is paused, holding {} queued deposits", next_len);
+                        return Ok(());
+                    }
+                }
+                Err(e) => log::warn!("Could not read pause state: {}", e),
+            }
+
+            // Every transaction would fail for its fee; the health loop resumes once topped up
+            if self.solana_client.payer_underfunded() {
+                log::warn!("💸 Payer is underfunded, holding {} queued deposits", next_len);
+                return Ok(());
+            }
+
+            let estimated_lamports = next_len as u64 * rate_limiter::LAMPORTS_PER_SIGNATURE;
+            if !self.rate_limiter.allows(estimated_lamports) {
+                log::info!("⏳ Submission rate limit reached, holding {} queued deposits", next_len);
+                self.metrics.rate_limited_checks.inc();
+                return Ok(());
+            }
+
+            // Hard fee budget: once exhausted, nothing is submitted until an operator resets it
+            let (was_tripped, check) = {
+                let mut guard = self.spend_guard.lock().unwrap();
+                (guard.tripped().is_some(), guard.check(estimated_lamports))
+            };
+            if let Err(reason) = check {
+                self.metrics.spend_guard_tripped.set(1.0);
+                if !was_tripped {
+                    log::error!("🛑 Spend guard tripped, submissions halted until reset: {}", reason);
+                    self.event_bus.publish(PipelineEvent::SpendGuardTripped { reason });
+                }
+                return Ok(());
+            }
+        }
+
+        // Get the next batch from queue (FIFO)
+        let next = self.queue.lock().unwrap().dequeue();
+        let next = match next {
+            Some(batch) => match self.drop_completed_deposits(batch).await? {
+                Some(batch) => self.resume_landed_deposits(batch).await?,
+                None => None,
+            },
+            None => None,
+        };
+        if let Some(mut batch) = next {
+            log::info!("📦 Processing batch with {} deposits", batch.deposits.len());
+            let estimated_lamports = batch.deposits.len() as u64 * rate_limiter::LAMPORTS_PER_SIGNATURE;
+            self.rate_limiter.record(estimated_lamports);
+            self.spend_guard.lock().unwrap().record(estimated_lamports);
+            
+            // METRIC: Batch processing started
+            self.metrics.batches_processing.inc();
+            let batch_start_time = Instant::now();
+
+            // Submit batch to Solana
+            let tx_start = Instant::now();
+            let batch_id = batch.content_id();
+            let submission = self.submit_unlanded_batch(&mut batch, &batch_id).await;
+            match submission {
+                Ok(results) => {
+                    // METRICS: Success
                     self.metrics.solana_tx_time.observe(tx_start.elapsed().as_secs_f64());
                     self.metrics.batches_submitted.inc();
-                    self.metrics.deposits_completed.inc_by(batch.deposits.len() as f64);
                     self.metrics.last_successful_batch_time.set(chrono::Utc::now().timestamp() as f64);
-                    
-                    log::info!("✅ Batch successfully submitted to Solana: {}", tx_signature);
-                    
-                    // Update all deposits in this batch to "completed"
-                    for deposit in &batch.deposits {
-                        self.database.update_deposit_status(&deposit.deposit_id, "completed", None).await?;
+
+                    let split = Self::split_batch_results(&batch, &results);
+                    self.record_split_lineage(&batch, &split).await?;
+                    let landed: Vec<(String, String)> = results
+                        .iter()
+                        .filter_map(|result| Some((result.deposit_id.clone(), result.outcome.clone().ok()?)))
+                        .collect();
+                    if !landed.is_empty() {
+                        self.database.record_batch_signatures(&batch_id, &landed).await?;
+                    }
+                    for (tx_signature, part) in &split.landed {
+                        self.track_landed_deposits(&batch_id, part, tx_signature).await?;
+                    }
+
+                    // Deposits that failed while others landed are settled one by
+                    // one; only those a new attempt may get past are rebatched
+                    self.record_program_failures(&results).await;
+                    for deposit in &split.already_bridged {
+                        log::warn!("♻️ Deposit {} was already consumed on-chain, dropped from its batch", deposit.deposit_id);
+                        self.database
+                            .update_deposit_status(&deposit.deposit_id, "failed", Some("already bridged on Solana"))
+                            .await?;
+                    }
+                    for (deposit, reason) in &split.failed {
+                        log::error!("❌ Deposit {} failed on Solana: {}", deposit.deposit_id, reason);
+                        self.database.update_deposit_status(&deposit.deposit_id, "failed", Some(reason)).await?;
+                        self.event_bus.publish(PipelineEvent::BatchFailed {
+                            deposit_ids: vec![deposit.deposit_id.clone()],
+                            reason: reason.clone(),
+                        });
+                    }
+
+                    log::info!(
+                        "🎉 Batch landed: {} deposits confirming on Solana, {} already bridged, {} failed, {} rebatched",
+                        split.landed.iter().map(|(_, part)| part.deposits.len()).sum::<usize>(),
+                        split.already_bridged.len(),
+                        split.failed.len(),
+                        split.retry.as_ref().map_or(0, |retry| retry.deposits.len())
+                    );
+                    if let Some(retry) = split.retry {
+                        self.metrics.deposits_rebatched.inc_by(retry.deposits.len() as f64);
+                        let error = OrchestratorError::BatchProcessingFailed {
+                            reason: format!("{} deposits of batch {} did not land", retry.deposits.len(), batch_id),
+                        };
+                        self.handle_batch_submission_failure(retry, error).await?;
                     }
-                    
-                    // Log batch completion
-                    log::info!("🎉 Batch completed: {} deposits bridged to Solana", batch.deposits.len());
                 }
                 Err(e) => {
                     // METRICS: Submission failure
@@ -228,13 +1673,18 @@ impl SubmissionManager {
                     // METRIC: Failure by type
                     match &e {
                         OrchestratorError::NetworkError(_) => self.metrics.network_failures.inc(),
+                        OrchestratorError::InsufficientFunds(_) => self.metrics.insufficient_funds_failures.inc(),
                         _ => self.metrics.other_failures.inc(),
                     }
                     
                     log::error!("❌ Failed to submit batch to Solana: {}", e);
                     
-                    // Handle retry logic
-                    self.handle_batch_submission_failure(batch, e).await?;
+                    // Too large a batch fails the same way on every retry; smaller ones may fit
+                    if matches!(e, OrchestratorError::ResourceLimitExceeded(_)) && batch.deposits.len() > 1 {
+                        self.split_batch(batch, &e).await?;
+                    } else {
+                        self.handle_batch_submission_failure(batch, e).await?;
+                    }
                 }
             }
             
@@ -249,11 +1699,599 @@ impl SubmissionManager {
         Ok(())
     }
 
+    /// Submit a batch no earlier attempt landed, re-proving any deposit whose
+    /// proof outlived its TON root first
+    async fn submit_unlanded_batch(&self, batch: &mut Batch, batch_id: &str) -> Result<Vec<DepositSubmission>> {
+        self.refresh_expired_proofs(batch).await?;
+        if self.config.precreate_accounts {
+            if let Err(e) = account_precreation::precreate_batch_accounts(&self.solana_client, batch).await {
+                // Not fatal: the verification transaction creates any missing accounts itself
+                log::warn!("Account pre-creation failed for batch {}: {}", batch_id, e);
+            }
+        }
+        self.database.begin_batch_submission(batch_id).await?;
+        let mut results = self.solana_client.submit_batch(batch).await?;
+        self.reclaim_own_landings(batch, &mut results).await?;
+        match results {
+            // Nothing landed: retry the whole batch like any other submission
+            // failure, keeping each program rejection visible meanwhile. When some
+            // deposits failed for good other than by program rejection (e.g. a
+            // transaction over Solana's limits), the caller settles them one by one.
+            results if results.iter().all(|result| result.outcome.is_err()) && !Self::has_mixed_failures(&results) => {
+                self.record_program_failures(&results).await;
+                let reason = results
+                    .iter()
+                    .find_map(|result| result.outcome.clone().err())
+                    .unwrap_or_else(|| "empty batch".to_string());
+                // Rejected for good across the board: surface it so retries are skipped
+                let permanent = results
+                    .iter()
+                    .map(|result| result.failure.filter(|failure| failure.is_permanent()))
+                    .collect::<Option<Vec<_>>>();
+                match permanent.as_deref() {
+                    Some([failure, ..]) => Err(OrchestratorError::ProgramRejected(*failure)),
+                    _ if self.solana_client.payer_underfunded() => {
+                        Err(OrchestratorError::InsufficientFunds(reason))
+                    }
+                    _ => Err(OrchestratorError::BatchProcessingFailed { reason }),
+                }
+            }
+            results => Ok(results),
+        }
+    }
+
+    /// A deposit the program finds already consumed may have been consumed by
+    /// an earlier submission of its own; count it as landed in that transaction
+    /// rather than failed
+    async fn reclaim_own_landings(&self, batch: &Batch, results: &mut [DepositSubmission]) -> Result<()> {
+        let already_bridged = |result: &DepositSubmission| matches!(result.failure, Some(ProgramFailure::AlreadyBridged));
+        if !results.iter().any(already_bridged) {
+            return Ok(());
+        }
+
+        let landed = self.find_landed_signatures(batch).await?;
+        for result in results.iter_mut().filter(|result| already_bridged(result)) {
+            if let Some(signature) = landed.get(&result.deposit_id) {
+                log::info!("Deposit {} was consumed by its own earlier transaction {}", result.deposit_id, signature);
+                result.outcome = Ok(signature.clone());
+                result.failure = None;
+            }
+        }
+        Ok(())
+    }
+
+    /// Re-prove the deposits of `batch` whose proof is anchored to a TON root
+    /// the program no longer accepts, against the newest root it does
+    async fn refresh_expired_proofs(&self, batch: &mut Batch) -> Result<()> {
+        let accepted = self.solana_client.fetch_anchor_roots().await?;
+        let Some(&newest) = accepted.first() else {
+            return Ok(()); // Nothing anchors until the header chain has a root
+        };
+
+        for (deposit, proof) in batch.deposits.iter().zip(batch.proofs.iter_mut()) {
+            if proof_anchor(&proof.public_inputs, &accepted).is_some() {
+                continue;
+            }
+            log::warn!(
+                "⌛ Proof for deposit {} outlived its TON root, re-proving against {}",
+                deposit.deposit_id,
+                hex::encode(newest)
+            );
+            self.prover.set_anchor_root(newest);
+            let fresh = self.prover.generate_proof(deposit).await?;
+            if self.config.verify_proofs_locally && !self.config.mock_proving {
+                self.proof_verifier.verify(deposit, &fresh)?;
+            }
+            *proof = fresh;
+            self.metrics.proofs_reproved_expired.inc();
+        }
+        Ok(())
+    }
+
+    /// Some deposits failed for good, but not all of them by program rejection
+    fn has_mixed_failures(results: &[DepositSubmission]) -> bool {
+        let rejected = results
+            .iter()
+            .filter(|result| result.failure.is_some_and(|failure| failure.is_permanent()))
+            .count();
+        let oversized = results.iter().filter(|result| result.limit_exceeded).count();
+        rejected + oversized > 0 && rejected < results.len()
+    }
+
+    /// Sort a batch's deposits by their outcome: grouped by the transaction
+    /// they landed in, as single batches; already consumed on-chain; failed
+    /// for good; or failed in a way a new batch may get past
+    fn split_batch_results(batch: &Batch, results: &[DepositSubmission]) -> BatchResults {
+        let mut split = BatchResults::default();
+        let mut retry = Batch {
+            id: uuid::Uuid::new_v4(),
+            deposits: Vec::new(),
+            proofs: Vec::new(),
+            created_at: batch.created_at,
+            retry_count: batch.retry_count,
+        };
+
+        for (index, deposit) in batch.deposits.iter().enumerate() {
+            let proof = batch.proofs.get(index).cloned();
+            let (outcome, failure, limit_exceeded) = results
+                .iter()
+                .find(|result| result.deposit_id == deposit.deposit_id)
+                .map(|result| (result.outcome.clone(), result.failure, result.limit_exceeded))
+                .unwrap_or_else(|| (Err("no submission result".to_string()), None, false));
+            let signature = match (outcome, failure) {
+                (Ok(signature), _) => signature,
+                // Over the limit on its own: no smaller batch makes it fit
+                (Err(reason), _) if limit_exceeded => {
+                    split.failed.push((deposit.clone(), reason));
+                    continue;
+                }
+                (Err(_), Some(ProgramFailure::AlreadyBridged)) => {
+                    split.already_bridged.push(deposit.clone());
+                    continue;
+                }
+                (Err(reason), Some(failure)) if failure.is_permanent() => {
+                    split.failed.push((deposit.clone(), reason));
+                    continue;
+                }
+                (Err(_), _) => {
+                    retry.deposits.push(deposit.clone());
+                    retry.proofs.extend(proof);
+                    continue;
+                }
+            };
+
+            let position = match split.landed.iter().position(|(landed_signature, _)| *landed_signature == signature) {
+                Some(position) => position,
+                None => {
+                    split.landed.push((
+                        signature,
+                        Batch {
+                            id: uuid::Uuid::new_v4(),
+                            deposits: Vec::new(),
+                            proofs: Vec::new(),
+                            created_at: batch.created_at,
+                            retry_count: batch.retry_count,
+                        },
+                    ));
+                    split.landed.len() - 1
+                }
+            };
+            let part = &mut split.landed[position].1;
+            part.deposits.push(deposit.clone());
+            part.proofs.extend(proof);
+        }
+
+        // A transaction that carried every deposit is the batch itself
+        if let [(_, part)] = split.landed.as_mut_slice() {
+            if part.deposits.len() == batch.deposits.len() {
+                part.id = batch.id;
+            }
+        }
+        if !retry.deposits.is_empty() {
+            split.retry = Some(retry);
+        }
+        split
+    }
+
+    /// Store manifests for the batches `split` carved out of `batch` and link
+    /// them to it, so each requeued or tracked part is traceable to its origin
+    async fn record_split_lineage(&self, batch: &Batch, split: &BatchResults) -> Result<()> {
+        let parts: Vec<&Batch> = split
+            .landed
+            .iter()
+            .map(|(_, part)| part)
+            .chain(split.retry.as_ref())
+            .filter(|part| part.id != batch.id)
+            .collect();
+        if parts.is_empty() {
+            return Ok(());
+        }
+
+        for part in &parts {
+            self.store_batch_manifest(part).await?;
+        }
+        let child_ids: Vec<String> = parts.iter().map(|part| part.id.to_string()).collect();
+        self.database
+            .record_batch_split(&batch.id.to_string(), &child_ids, "partially landed")
+            .await?;
+        Ok(())
+    }
+
+    /// Requeue `batch` as two halves, each with its own id and manifest, and
+    /// record where they came from
+    async fn split_batch(&mut self, batch: Batch, error: &OrchestratorError) -> Result<()> {
+        let mut first = batch.clone();
+        first.id = uuid::Uuid::new_v4();
+        let mut second = first.clone();
+        second.id = uuid::Uuid::new_v4();
+        let middle = batch.deposits.len() / 2;
+        second.deposits = first.deposits.split_off(middle);
+        second.proofs = first.proofs.split_off(middle.min(first.proofs.len()));
+
+        let child_ids = [first.id.to_string(), second.id.to_string()];
+        self.database
+            .record_batch_split(&batch.id.to_string(), &child_ids, &error.to_string())
+            .await?;
+        for half in [&first, &second] {
+            self.store_batch_manifest(half).await?;
+        }
+
+        log::warn!(
+            "✂️ Batch {} split into {} ({} deposits) and {} ({} deposits): {}",
+            batch.id,
+            first.id,
+            first.deposits.len(),
+            second.id,
+            second.deposits.len(),
+            error
+        );
+        self.metrics.batches_split.inc();
+        let mut queue = self.queue.lock().unwrap();
+        queue.enqueue(first);
+        queue.enqueue(second);
+        Ok(())
+    }
+
+    /// Store the program's rejection reason on each deposit it rejected, for the status API
+    async fn record_program_failures(&self, results: &[DepositSubmission]) {
+        for result in results {
+            let Some(failure) = result.failure else {
+                continue;
+            };
+            if let Err(e) = self.database.set_deposit_status_detail(&result.deposit_id, Some(failure.detail_code())).await {
+                log::error!("Failed to store status detail for deposit {}: {}", result.deposit_id, e);
+            }
+        }
+    }
+
+    /// Remove deposits the event listener already found landed, so they are not
+    /// submitted again and reported as rejected. `None` if nothing is left.
+    async fn drop_completed_deposits(&self, mut batch: Batch) -> Result<Option<Batch>> {
+        let mut index = 0;
+        while index < batch.deposits.len() {
+            let deposit_id = &batch.deposits[index].deposit_id;
+            let completed = self
+                .database
+                .get_deposit(deposit_id)
+                .await?
+                .is_some_and(|record| record.status == "completed");
+            if completed {
+                log::info!("Deposit {} already completed on-chain, dropping it from its batch", deposit_id);
+                batch.deposits.remove(index);
+                if index < batch.proofs.len() {
+                    batch.proofs.remove(index);
+                }
+            } else {
+                index += 1;
+            }
+        }
+        Ok((!batch.deposits.is_empty()).then_some(batch))
+    }
+
+    /// Record a landed transaction and hold its deposits as `confirming` until
+    /// the finality loop sees its slot finalized
+    async fn track_landed_deposits(&self, batch_id: &str, batch: &Batch, tx_signature: &str) -> Result<()> {
+        log::info!("⏳ {} deposits landed on Solana, awaiting finality: {}", batch.deposits.len(), tx_signature);
+        self.database
+            .track_signature(tx_signature, batch_id, &serde_json::to_string(batch)?)
+            .await?;
+
+        let deposit_ids: Vec<String> = batch.deposits.iter().map(|d| d.deposit_id.clone()).collect();
+        for deposit_id in &deposit_ids {
+            self.database.update_deposit_status(deposit_id, "confirming", None).await?;
+        }
+        self.event_bus.publish(PipelineEvent::BatchSubmitted {
+            signature: tx_signature.to_string(),
+            deposit_ids,
+        });
+        Ok(())
+    }
+
+    /// Poll the tracked transactions: complete the deposits of finalized ones,
+    /// and resubmit those that failed or were dropped with their fork
+    async fn confirm_tracked_signatures(&mut self) -> Result<()> {
+        let tracked = self.database.get_tracked_signatures().await?;
+        if tracked.is_empty() {
+            return Ok(());
+        }
+
+        let signatures: Vec<String> = tracked.iter().map(|record| record.signature.clone()).collect();
+        let finality = self.solana_client.get_signature_finality(&signatures).await?;
+        let now = chrono::Utc::now().timestamp();
+
+        for (record, finality) in tracked.into_iter().zip(finality) {
+            let lost = match finality {
+                SignatureFinality::Confirming => {
+                    self.database.touch_tracked_signature(&record.signature).await?;
+                    continue;
+                }
+                SignatureFinality::Unknown
+                    if now - record.last_seen_at < self.config.finality_timeout_secs as i64 =>
+                {
+                    continue;
+                }
+                SignatureFinality::Unknown => "transaction dropped before finality".to_string(),
+                SignatureFinality::Failed(error) => format!("transaction failed: {}", error),
+                SignatureFinality::Finalized => {
+                    let batch: Batch = serde_json::from_str(&record.batch)?;
+                    self.complete_landed_deposits(&batch, &record.signature).await?;
+                    self.database.untrack_signature(&record.signature).await?;
+                    continue;
+                }
+            };
+
+            // Back to the queue: the deposits did not make it on-chain after all
+            log::warn!("⚠️ {} of batch {}: {}", record.signature, record.batch_id, lost);
+            let batch: Batch = serde_json::from_str(&record.batch)?;
+            for deposit in &batch.deposits {
+                self.database.update_deposit_status(&deposit.deposit_id, "pending", None).await?;
+            }
+            self.database.untrack_signature(&record.signature).await?;
+            self.handle_batch_submission_failure(batch, OrchestratorError::BatchProcessingFailed { reason: lost })
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Record receipts, costs and events for deposits that landed in `tx_signature` and mark them completed
+    async fn complete_landed_deposits(&self, batch: &Batch, tx_signature: &str) -> Result<()> {
+        self.metrics.deposits_completed.inc_by(batch.deposits.len() as f64);
+        log::info!("✅ {} deposits finalized on Solana: {}", batch.deposits.len(), tx_signature);
+
+        self.attestation.record_batch_signature(tx_signature);
+        self.store_batch_receipts(batch, tx_signature).await;
+        self.archive_batch(batch);
+        self.record_batch_cost(batch, tx_signature).await;
+        self.check_batch_events(batch, tx_signature).await;
+
+        let deposit_ids: Vec<String> = batch.deposits.iter().map(|d| d.deposit_id.clone()).collect();
+        for deposit in &batch.deposits {
+            self.database.update_deposit_status(&deposit.deposit_id, "completed", None).await?;
+            // Drop any rejection left by an earlier attempt
+            self.database.set_deposit_status_detail(&deposit.deposit_id, None).await?;
+        }
+        self.snapshot_rates(&deposit_ids, true).await;
+
+        self.event_bus.publish(PipelineEvent::BatchConfirmed {
+            signature: tx_signature.to_string(),
+            deposit_ids,
+        });
+        Ok(())
+    }
+
+    /// Hand the deposits an earlier submission already landed to the finality
+    /// loop instead of resubmitting them, e.g. when confirmation was lost to a
+    /// timeout and the batch was re-queued, possibly split or rebatched.
+    /// Returns the deposits left to submit, as a batch of their own; `None` if
+    /// nothing is left.
+    async fn resume_landed_deposits(&self, batch: Batch) -> Result<Option<Batch>> {
+        let batch_id = batch.content_id();
+        let landed = self.find_landed_signatures(&batch).await?;
+        if !batch.deposits.iter().any(|deposit| landed.contains_key(&deposit.deposit_id)) {
+            return Ok(Some(batch));
+        }
+
+        let results: Vec<DepositSubmission> = batch
+            .deposits
+            .iter()
+            .filter_map(|deposit| {
+                Some(DepositSubmission {
+                    deposit_id: deposit.deposit_id.clone(),
+                    outcome: Ok(landed.get(&deposit.deposit_id)?.clone()),
+                    failure: None,
+                    limit_exceeded: false,
+                })
+            })
+            .collect();
+        log::warn!(
+            "♻️ {} of {} deposits of batch {} already landed, not resubmitting them",
+            results.len(),
+            batch.deposits.len(),
+            batch_id
+        );
+
+        // Deposits without a result are the ones left to submit
+        let split = Self::split_batch_results(&batch, &results);
+        self.record_split_lineage(&batch, &split).await?;
+        for (tx_signature, part) in &split.landed {
+            self.track_landed_deposits(&batch_id, part, tx_signature).await?;
+        }
+        Ok(split.retry)
+    }
+
+    /// Signature of the earlier transaction each deposit of `batch` landed in,
+    /// by deposit id, whichever batch carried it then; deposits none of whose
+    /// transactions succeeded are left out
+    async fn find_landed_signatures(&self, batch: &Batch) -> Result<HashMap<String, String>> {
+        let mut succeeded: HashMap<String, bool> = HashMap::new();
+        let mut landed = HashMap::new();
+        for deposit in &batch.deposits {
+            for record in self.database.get_deposit_signatures(&deposit.deposit_id).await? {
+                let ok = match succeeded.get(&record.solana_signature) {
+                    Some(&ok) => ok,
+                    None => {
+                        let ok = self.solana_client.signature_succeeded(&record.solana_signature).await? == Some(true);
+                        succeeded.insert(record.solana_signature.clone(), ok);
+                        ok
+                    }
+                };
+                if ok {
+                    landed.insert(record.deposit_id, record.solana_signature);
+                    break;
+                }
+            }
+        }
+        Ok(landed)
+    }
+
+    /// Store current TON/USD and SOL/USD rates on deposits for fiat reporting.
+    /// Best effort: a failing oracle never blocks bridging.
+    async fn snapshot_rates(&self, deposit_ids: &[String], completed: bool) {
+        let Some(oracle) = &self.price_oracle else {
+            return;
+        };
+
+        let rates = match oracle.fetch_rates().await {
+            Ok(rates) => rates,
+            Err(e) => {
+                log::warn!("Could not fetch reference rates: {}", e);
+                return;
+            }
+        };
+
+        for deposit_id in deposit_ids {
+            if let Err(e) = self.database.set_deposit_rates(deposit_id, completed, &rates).await {
+                log::error!("Failed to store rates for deposit {}: {}", deposit_id, e);
+            }
+        }
+    }
+
+    /// Deposits created in `[from, to)` for reporting exports
+    pub async fn export_deposits(&self, from: i64, to: i64) -> Result<Vec<database::DepositRecord>> {
+        Ok(self.database.get_deposits_between(from, to).await?)
+    }
+
+    /// Estimate the Solana cost of bridging `deposits` deposits at current conditions
+    pub async fn estimate_cost(&self, deposits: usize) -> Result<CostEstimate> {
+        // One verify_ton_event transaction (one signature) per deposit
+        let base_fee_lamports = gas_optimizer::BASE_FEE_LAMPORTS * deposits as u64;
+        let priority_fee_micro_lamports = self.gas_optimizer.priority_fee();
+        let priority_fee_lamports = gas_optimizer::COMPUTE_UNITS_PER_DEPOSIT * deposits as u64
+            * priority_fee_micro_lamports / 1_000_000;
+        let rent_lamports = self.solana_client.rent_per_deposit().await? * deposits as u64;
+
+        Ok(CostEstimate {
+            deposits,
+            base_fee_lamports,
+            priority_fee_lamports,
+            rent_lamports,
+            total_lamports: base_fee_lamports + priority_fee_lamports + rent_lamports,
+            priority_fee_micro_lamports,
+            recent_cost_per_deposit: self.gas_optimizer.recent_cost_per_deposit(),
+        })
+    }
+
+    /// Feed the fee actually paid for a landed batch back into the estimates
+    async fn record_batch_cost(&self, batch: &Batch, tx_signature: &str) {
+        match self.solana_client.get_transaction_fee(tx_signature).await {
+            Ok(Some(fee)) => {
+                self.gas_optimizer.record_actual_cost(fee, batch.deposits.len());
+                // The estimate was charged to the spend guard up front; add any excess
+                let estimated = batch.deposits.len() as u64 * rate_limiter::LAMPORTS_PER_SIGNATURE;
+                self.spend_guard.lock().unwrap().record(fee.saturating_sub(estimated));
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("Could not fetch fee for {}: {}", tx_signature, e),
+        }
+    }
+
+    /// Confirm from the program's own events that every deposit of a landed batch was verified
+    async fn check_batch_events(&self, batch: &Batch, tx_signature: &str) {
+        let events = match self.solana_client.get_transaction_events(tx_signature).await {
+            Ok(Some(events)) => events,
+            Ok(None) => return,
+            Err(e) => {
+                log::warn!("Could not fetch events for {}: {}", tx_signature, e);
+                return;
+            }
+        };
+
+        let verified: Vec<String> = events
+            .iter()
+            .filter_map(|event| match event {
+                ProgramEvent::TonEventVerified(verified) => Some(hex::encode(verified.event_id)),
+                _ => None,
+            })
+            .collect();
+        for deposit in &batch.deposits {
+            let Some(event_id) = deposit.event_id.as_deref() else {
+                continue;
+            };
+            if !verified.iter().any(|id| id.eq_ignore_ascii_case(event_id.trim_start_matches("0x"))) {
+                log::warn!(
+                    "⚠️ {} emitted no TonEventVerified for deposit {} (event {})",
+                    tx_signature,
+                    deposit.deposit_id,
+                    event_id
+                );
+            }
+        }
+    }
+
+    /// Record a proof-of-completion receipt for every deposit in a landed batch
+    async fn store_batch_receipts(&self, batch: &Batch, tx_signature: &str) {
+        let slot = match self.solana_client.get_signature_slot(tx_signature).await {
+            Ok(slot) => slot,
+            Err(e) => {
+                log::warn!("Could not fetch slot for {}: {}", tx_signature, e);
+                None
+            }
+        };
+
+        for deposit in &batch.deposits {
+            let receipt = database::ReceiptRecord {
+                deposit_id: deposit.deposit_id.clone(),
+                solana_signature: tx_signature.to_string(),
+                slot: slot.map(|s| s as i64),
+                event_pda: deposit
+                    .event_id
+                    .as_deref()
+                    .and_then(|id| hex::decode(id.trim_start_matches("0x")).ok())
+                    .and_then(|id| <[u8; 32]>::try_from(id).ok())
+                    .map(|id| self.solana_client.event_pda(&id).to_string()),
+                merkle_path: None,
+                created_at: 0,
+            };
+
+            if let Err(e) = self.database.store_receipt(receipt).await {
+                log::error!("Failed to store receipt for deposit {}: {}", deposit.deposit_id, e);
+            }
+        }
+    }
+
+    /// Move a landed batch's proofs and receipts to cold storage in the background
+    fn archive_batch(&self, batch: &Batch) {
+        let Some(archive) = self.archive.clone() else {
+            return;
+        };
+        let database = self.database.clone();
+        let entries: Vec<(Deposit, Option<Proof>)> = batch
+            .deposits
+            .iter()
+            .cloned()
+            .zip(batch.proofs.iter().cloned().map(Some).chain(std::iter::repeat(None)))
+            .collect();
+
+        tokio::spawn(async move {
+            for (deposit, proof) in entries {
+                let deposit_id = deposit.deposit_id.clone();
+                let receipt = match database.get_receipt(&deposit_id).await {
+                    Ok(Some(receipt)) => receipt,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        log::warn!("Could not load receipt to archive deposit {}: {}", deposit_id, e);
+                        continue;
+                    }
+                };
+
+                let record = ArchivedDeposit::new(deposit, proof, receipt);
+                let stored = match archive.store(&record).await {
+                    Ok(location) => database.record_archive(&location).await.map_err(OrchestratorError::from),
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = stored {
+                    // The hot copy stays in place, so nothing is lost
+                    log::warn!("Failed to archive deposit {}: {}", deposit_id, e);
+                }
+            }
+        });
+    }
+
     async fn handle_batch_submission_failure(&mut self, batch: Batch, error: OrchestratorError) -> Result<()> {
-        log::warn!("Handling batch submission failure, will retry...");
+        log::warn!("Handling batch submission failure: {}", error);
         
-        // Check if we should retry
-        if self.retry_engine.should_retry(batch.retry_count) {
+        // Check if we should retry; permanent rejections go straight to failed
+        if self.retry_engine.should_retry_error(batch.retry_count, &error) {
             // METRIC: Batch retry
              self.metrics.batch_retries.inc();
         
@@ -264,85 +2302,333 @@ impl SubmissionManager {
             let mut retry_batch = batch;
             retry_batch.retry_count = retry_count;
             
-            self.queue_manager.enqueue_batch(retry_batch).await;
+            self.queue.lock().unwrap().enqueue(retry_batch);
         log::info!("🔄 Batch re-queued for retry (attempt {})", retry_count);  // Use stored value
         } else {
-            // METRIC: Max retries exceeded
-            self.metrics.max_retries_exceeded.inc();
-            
-            // Max retries exceeded - mark all deposits as failed
-            log::error!("❌ Max retries exceeded for batch, marking deposits as failed");
+            let reason = if error.is_permanent() {
+                log::error!("❌ Batch rejected permanently, marking deposits as failed without retrying");
+                format!("Permanent failure: {}", error)
+            } else {
+                // METRIC: Max retries exceeded
+                self.metrics.max_retries_exceeded.inc();
+
+                // Max retries exceeded - mark all deposits as failed
+                log::error!("❌ Max retries exceeded for batch, marking deposits as failed");
+                format!("Max retries exceeded: {}", error)
+            };
             
             for deposit in &batch.deposits {
-                self.database.update_deposit_status(
-                    &deposit.deposit_id, 
-                    "failed", 
-                    Some(&format!("Max retries exceeded: {}", error))
-                ).await?;
+                self.database.update_deposit_status(&deposit.deposit_id, "failed", Some(&reason)).await?;
             }
+
+            self.event_bus.publish(PipelineEvent::BatchFailed {
+                deposit_ids: batch.deposits.iter().map(|d| d.deposit_id.clone()).collect(),
+                reason: error.to_string(),
+            });
         }
         
         Ok(())
     }
 
     async fn finalize_stale_batch(&mut self) -> Result<()> {
-        // Check if current batch is getting stale (e.g., waiting more than 2 minutes)
-        if let Some(batch) = self.batch_manager.finalize_if_stale(Duration::from_secs(120)).await? {
+        // Let the sealing policy decide whether the open batch is due (e.g. aged out)
+        for batch in self.batch_manager.lock().await.finalize_if_due().await? {
             log::info!("⏰ Finalizing stale batch with {} deposits", batch.deposits.len());
-            self.queue_manager.enqueue_batch(batch).await;
+            self.hand_off_batch(batch).await?;
         }
         
         Ok(())
     }
 
     pub async fn get_queue_stats(&self) -> QueueStats {
-        let stats = self.queue_manager.get_queue_stats().await;
+        let stats = self.queue.lock().unwrap().stats();
         // Update metrics with current queue size
         self.metrics.queue_size.set(stats.pending as f64);
         stats
     }
 
+    pub async fn find_deposits_by_ton_tx(&self, ton_tx_hash: &str) -> Result<Vec<database::DepositRecord>> {
+        Ok(self.database.get_deposits_by_ton_tx_hash(ton_tx_hash).await?)
+    }
+
+    /// Receipt from the hot DB, or from cold storage once the deposit was archived
+    pub async fn get_receipt(&self, deposit_id: &str) -> Result<Option<database::ReceiptRecord>> {
+        if let Some(receipt) = self.database.get_receipt(deposit_id).await? {
+            return Ok(Some(receipt));
+        }
+        Ok(self.get_archived_deposit(deposit_id).await?.map(|record| record.receipt))
+    }
+
+    pub async fn get_archived_deposit(&self, deposit_id: &str) -> Result<Option<ArchivedDeposit>> {
+        let (Some(archive), Some(location)) = (&self.archive, self.database.get_archive_location(deposit_id).await?) else {
+            return Ok(None);
+        };
+        Ok(Some(archive.load(&location.content_hash).await?))
+    }
+
+    /// Build and sign a snapshot of the manager's self-reported state
+    pub async fn attestation(&self) -> Result<SignedAttestation> {
+        let queue_depth = self.get_queue_stats().await.pending;
+
+        let last_ton_root = match self.solana_client.fetch_ton_state_root().await {
+            Ok(root) => Some(hex::encode(root)),
+            Err(e) => {
+                log::warn!("Could not read TON root for attestation: {}", e);
+                None
+            }
+        };
+
+        let snapshot = attestation::AttestationSnapshot {
+            last_batch_signature: self.attestation.last_batch_signature(),
+            queue_depth,
+            last_ton_root,
+            code_version: attestation::CODE_VERSION,
+            timestamp: chrono::Utc::now().timestamp(),
+        };
+
+        let message = serde_json::to_vec(&snapshot)?;
+        let (signer, signature) = self.solana_client.sign_message(&message);
+
+        Ok(SignedAttestation {
+            snapshot,
+            signer,
+            signature,
+        })
+    }
+
+    /// Internal alert conditions that currently hold, for monitors without a
+    /// Prometheus/Alertmanager stack
+    pub async fn alerts(&self) -> AlertReport {
+        let mut report = AlertReport::new();
+        let now = report.checked_at;
+
+        if let Some(reason) = self.spend_guard.lock().unwrap().tripped() {
+            report.fire(alerts::ALERT_BREAKER_OPEN, AlertSeverity::Critical, format!("spend guard tripped: {}", reason), None);
+        }
+
+        match self.database.count_dead_letter_deposits().await {
+            Ok(0) => {}
+            Ok(count) => report.fire(
+                alerts::ALERT_DEAD_LETTER,
+                AlertSeverity::Warning,
+                format!("{} deposits failed terminally and need an operator", count),
+                None,
+            ),
+            Err(e) => report.unchecked(alerts::ALERT_DEAD_LETTER, e),
+        }
+
+        // From the periodic audit, which runs in the submitter process
+        if let Some(audit) = self.last_audit.get() {
+            for violation in audit.violations {
+                report.fire(
+                    alerts::ALERT_INVARIANT_VIOLATED,
+                    AlertSeverity::Critical,
+                    format!("{}: {}", violation.invariant, violation.message),
+                    Some(audit.checked_at),
+                );
+            }
+            report.unchecked.extend(audit.unchecked);
+        }
+
+        if self.config.alert_fee_payer_min_lamports > 0 {
+            match self.solana_client.fetch_relayer_balance().await {
+                Ok(balance) if balance < self.config.alert_fee_payer_min_lamports => report.fire(
+                    alerts::ALERT_FEE_PAYER_LOW,
+                    AlertSeverity::Critical,
+                    format!(
+                        "relayer {} holds {} lamports, below {}",
+                        self.solana_client.relayer_pubkey(),
+                        balance,
+                        self.config.alert_fee_payer_min_lamports
+                    ),
+                    None,
+                ),
+                Ok(_) => {}
+                Err(e) => report.unchecked(alerts::ALERT_FEE_PAYER_LOW, e),
+            }
+        }
+
+        if self.config.alert_root_stale_secs > 0 {
+            match self.solana_client.fetch_ton_state_root().await {
+                Ok(root) => {
+                    let changed_at = self.root_watch.observe(root, now);
+                    if now - changed_at > self.config.alert_root_stale_secs as i64 {
+                        report.fire(
+                            alerts::ALERT_ROOT_STALE,
+                            AlertSeverity::Warning,
+                            format!("TON root {} unchanged for {}s", hex::encode(root), now - changed_at),
+                            Some(changed_at),
+                        );
+                    }
+                }
+                Err(e) => report.unchecked(alerts::ALERT_ROOT_STALE, e),
+            }
+        }
+
+        if self.config.deposit_sla_secs > 0 {
+            match self.database.get_oldest_open_deposit_at().await {
+                Ok(Some(created_at)) if now - created_at > self.config.deposit_sla_secs as i64 => report.fire(
+                    alerts::ALERT_SLA_BREACH,
+                    AlertSeverity::Warning,
+                    format!("oldest open deposit has waited {}s, SLA is {}s", now - created_at, self.config.deposit_sla_secs),
+                    Some(created_at + self.config.deposit_sla_secs as i64),
+                ),
+                Ok(_) => {}
+                Err(e) => report.unchecked(alerts::ALERT_SLA_BREACH, e),
+            }
+        }
+
+        report
+    }
+
+    /// Coarse public service status, recomputed at most every `STATUS_CACHE_SECS`
+    pub async fn service_status(&self) -> Result<ServiceStatus> {
+        let now = chrono::Utc::now().timestamp();
+        if let Some(status) = self.status_cache.get(now) {
+            return Ok(status);
+        }
+
+        let report = self.alerts().await;
+        let average = self
+            .database
+            .average_bridging_secs(now - status_page::BRIDGING_TIME_WINDOW_SECS)
+            .await?;
+        let notice = self.database.get_status_notice().await?.map(StatusNotice::from);
+
+        let status = ServiceStatus::new(&report, average, notice);
+        self.status_cache.put(status.clone());
+        Ok(status)
+    }
+
+    /// Publish an incident or maintenance notice on the status page, replacing any current one
+    pub async fn set_status_notice(
+        &self,
+        kind: NoticeKind,
+        message: &str,
+        starts_at: Option<i64>,
+        ends_at: Option<i64>,
+    ) -> Result<()> {
+        let starts_at = starts_at.unwrap_or_else(|| chrono::Utc::now().timestamp());
+        self.database.set_status_notice(kind.as_str(), message, starts_at, ends_at).await?;
+        self.status_cache.invalidate();
+        Ok(())
+    }
+
+    pub async fn clear_status_notice(&self) -> Result<()> {
+        self.database.clear_status_notice().await?;
+        self.status_cache.invalidate();
+        Ok(())
+    }
+
+    /// Current phase of the deposit migration to Postgres, with a fresh parity report
+    pub async fn db_migration_status(&self) -> Result<serde_json::Value> {
+        let phase = self.database.migration_phase().await?;
+        let parity = self.database.check_deposit_parity(false).await?;
+        Ok(serde_json::json!({
+            "mirror_attached": self.database.has_mirror(),
+            "phase": phase,
+            "parity": parity,
+        }))
+    }
+
+    /// Move the deposit migration along: `start` dual-writes and backfills,
+    /// `cutover` serves reads from Postgres once parity is clean, `rollback`
+    /// returns reads to SQLite and `stop` ends dual writes
+    pub async fn db_migration_command(&self, command: &str) -> Result<db_migration::MigrationPhase> {
+        use db_migration::MigrationPhase;
+
+        if !self.database.has_mirror() {
+            return Err(OrchestratorError::MigrationError("DB_MIRROR_URL is not configured".to_string()));
+        }
+        let phase = self.database.migration_phase().await?;
+        let next = match (command, phase) {
+            ("start", MigrationPhase::Off) => {
+                self.database.set_migration_phase(MigrationPhase::DualWrite).await?;
+                // Backfill rows written before dual writes began
+                if let Some(report) = self.database.check_deposit_parity(true).await? {
+                    log::info!("🪞 Backfilled {} deposits into the Postgres mirror", report.repaired);
+                }
+                return Ok(MigrationPhase::DualWrite);
+            }
+            ("cutover", MigrationPhase::DualWrite) => {
+                let report = self.database.check_deposit_parity(true).await?.unwrap_or_default();
+                if !report.is_clean() {
+                    return Err(OrchestratorError::MigrationError(format!(
+                        "mirror parity not clean: {} missing, {} mismatched, {} extra, {} repaired",
+                        report.missing, report.mismatched, report.extra, report.repaired
+                    )));
+                }
+                MigrationPhase::CutOver
+            }
+            ("rollback", MigrationPhase::CutOver) => MigrationPhase::DualWrite,
+            ("stop", MigrationPhase::DualWrite) => MigrationPhase::Off,
+            ("start" | "cutover" | "rollback" | "stop", phase) => {
+                return Err(OrchestratorError::MigrationError(format!("cannot {} while {}", command, phase)));
+            }
+            _ => return Err(OrchestratorError::MigrationError(format!("unknown command '{}'", command))),
+        };
+
+        self.database.set_migration_phase(next).await?;
+        log::warn!("🪞 Deposit migration moved from {} to {}", phase, next);
+        Ok(next)
+    }
+
+    /// Subscribe to pipeline events (deposits accepted, proofs ready, batches sealed/submitted/confirmed)
+    pub fn subscribe_events(&self) -> tokio::sync::broadcast::Receiver<PipelineEvent> {
+        self.event_bus.subscribe()
+    }
+
     pub async fn finalize_current_batch(&mut self) -> Result<()> {
-        if let Some(batch) = self.batch_manager.finalize_batch().await? {
-            log::info!("👤 Manually finalizing batch with {} deposits", batch.deposits.len());
-            self.queue_manager.enqueue_batch(batch).await;
-        } else {
+        let batches = self.batch_manager.lock().await.finalize_batches().await?;
+        if batches.is_empty() {
             log::info!("No current batch to finalize");
         }
+        for batch in batches {
+            log::info!("👤 Manually finalizing batch with {} deposits", batch.deposits.len());
+            self.hand_off_batch(batch).await?;
+        }
         Ok(())
     }
 
     pub async fn start_http_server(self) -> Result<()> {
-        let manager = Arc::new(Mutex::new(self));
-        
-        // Start the manager first
-        {
-            let mut mgr = manager.lock().await;
-            mgr.start().await?;
-        }
-        
-        // Then start HTTP server (this will block)
-        http_server::start_http_server(manager).await;
-        
-        Ok(())
+        // Serve until the process exits
+        self.run_until(std::future::pending()).await
     }
 }
 
 impl Clone for SubmissionManager {
     fn clone(&self) -> Self {
         Self {
-            batch_manager: BatchManager::new(self.config.batch_size),
-            proof_orchestrator: self.proof_orchestrator.clone(),
+            batch_manager: self.batch_manager.clone(),
+            prove_wakeup: self.prove_wakeup.clone(),
+            prover: self.prover.clone(),
             gas_optimizer: self.gas_optimizer.clone(),
             health_monitor: self.health_monitor.clone(),
             retry_engine: self.retry_engine.clone(),
-            queue_manager: self.queue_manager.clone(),
+            queue: self.queue.clone(),
             database: self.database.clone(),
             solana_client: self.solana_client.clone(),
+            refund_engine: self.refund_engine.clone(),
+            rate_limiter: self.rate_limiter.clone(),
+            spend_guard: self.spend_guard.clone(),
+            attestation: self.attestation.clone(),
+            event_bus: self.event_bus.clone(),
+            transfer_delay: self.transfer_delay.clone(),
+            jetton_metadata: self.jetton_metadata.clone(),
+            token_registry: self.token_registry.clone(),
+            price_oracle: self.price_oracle.clone(),
+            notifiers: self.notifiers.clone(),
+            archive: self.archive.clone(),
+            handover: self.handover.clone(),
+            root_watch: self.root_watch.clone(),
+            proof_verifier: self.proof_verifier.clone(),
+            status_cache: self.status_cache.clone(),
+            last_audit: self.last_audit.clone(),
             metrics: self.metrics.clone(),
             registry: Registry::new(), // New registry for clone
             config: self.config.clone(),
             is_running: self.is_running,
+            tasks: Vec::new(), // Owned by the instance that started them
         }
     }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use submission_manager::{SubmissionManager, OrchestratorConfig};
+use submission_manager::{SubmissionManager, OrchestratorConfig, Environment};
 use std::error::Error;
 
 #[tokio::main]
@@ -7,16 +7,22 @@ async fn main() -> Result<(), Box<dyn Error>> {
     
     println!("🚀 Starting Rust Submission Manager with Solana ZK Program...");
     
+    // Select the environment preset, then let environment variables override it
+    let environment = Environment::from_args(std::env::args().skip(1))?;
+    let preset = environment.preset();
+    println!("🌍 Using {} environment preset", environment);
+
     // Create configuration from environment variables
     let config = OrchestratorConfig {
+        environment,
         batch_size: std::env::var("BATCH_SIZE")
-            .unwrap_or_else(|_| "5".to_string())
-            .parse()
-            .unwrap_or(5),
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(preset.batch_size),
         max_retries: std::env::var("MAX_RETRIES")
-            .unwrap_or_else(|_| "3".to_string())
-            .parse()
-            .unwrap_or(3),
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(preset.max_retries),
         health_check_interval: std::env::var("HEALTH_CHECK_INTERVAL_MS")
             .unwrap_or_else(|_| "30000".to_string())
             .parse()
@@ -25,19 +31,287 @@ async fn main() -> Result<(), Box<dyn Error>> {
             .unwrap_or_else(|_| "60000".to_string())
             .parse()
             .unwrap_or(60000),
-        validator_count: 2,
-        validators: vec![
-            "http://circuit-service:8080".to_string(),
-        ],
+        // Matching proofs required per deposit; above 1 every service proves every deposit
+        validator_count: std::env::var("CIRCUIT_QUORUM")
+            .unwrap_or_else(|_| "1".to_string())
+            .parse()
+            .unwrap_or(1),
+        // Comma-separated; jobs go to the fastest healthy service. grpc:// and
+        // grpcs:// entries stream over gRPC instead of HTTP
+        validators: std::env::var("CIRCUIT_SERVICE_URLS")
+            .unwrap_or_else(|_| "http://circuit-service:8080".to_string())
+            .split(',')
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+            .collect(),
         // ADD SOLANA CONFIG
-        solana_rpc_url: std::env::var("SOLANA_RPC_URL")
-            .unwrap_or_else(|_| "https://api.devnet.solana.com".to_string()),
+        // Comma-separated; later endpoints take over when the active one fails
+        solana_rpc_urls: std::env::var("SOLANA_RPC_URL")
+            .unwrap_or_else(|_| preset.solana_rpc_url.to_string())
+            .split(',')
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+            .collect(),
         solana_program_id: std::env::var("SOLANA_PROGRAM_ID")
-            .unwrap_or_else(|_| "BridgeProgram11111111111111111111111111111".to_string()),
-        solana_bridge_account: std::env::var("SOLANA_BRIDGE_ACCOUNT")
-            .unwrap_or_else(|_| "BridgeAccount1111111111111111111111111111".to_string()),
+            .unwrap_or_else(|_| preset.solana_program_id.to_string()),
+        solana_commitment: std::env::var("SOLANA_COMMITMENT")
+            .unwrap_or_else(|_| preset.commitment.to_string()),
         verification_key: std::env::var("VERIFICATION_KEY")
             .unwrap_or_else(|_| "path/to/verification_key.json".to_string()),
+        mock_proving: std::env::var("MOCK_PROVING")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(preset.mock_proving),
+        max_deposit_amount: std::env::var("MAX_DEPOSIT_AMOUNT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(preset.max_deposit_amount),
+        ton_wallet_url: std::env::var("TON_WALLET_URL")
+            .unwrap_or_else(|_| "http://ton-wallet:8090".to_string()),
+        refund_max_attempts: std::env::var("REFUND_MAX_ATTEMPTS")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse()
+            .unwrap_or(5),
+        db_checkpoint_interval_secs: std::env::var("DB_CHECKPOINT_INTERVAL_SECS")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse()
+            .unwrap_or(300),
+        db_vacuum_interval_secs: std::env::var("DB_VACUUM_INTERVAL_SECS")
+            .unwrap_or_else(|_| "86400".to_string())
+            .parse()
+            .unwrap_or(86400),
+        db_maintenance_window_start_hour: std::env::var("DB_MAINTENANCE_WINDOW_START_HOUR")
+            .unwrap_or_else(|_| "3".to_string())
+            .parse()
+            .unwrap_or(3),
+        db_maintenance_window_end_hour: std::env::var("DB_MAINTENANCE_WINDOW_END_HOUR")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse()
+            .unwrap_or(5),
+        db_journal_mode: std::env::var("DB_JOURNAL_MODE")
+            .unwrap_or_else(|_| "wal".to_string()),
+        db_busy_timeout_ms: std::env::var("DB_BUSY_TIMEOUT_MS")
+            .unwrap_or_else(|_| "5000".to_string())
+            .parse()
+            .unwrap_or(5000),
+        db_synchronous: std::env::var("DB_SYNCHRONOUS")
+            .unwrap_or_else(|_| "normal".to_string()),
+        db_mirror_url: std::env::var("DB_MIRROR_URL")
+            .unwrap_or_else(|_| "".to_string()),
+        db_mirror_parity_interval_secs: std::env::var("DB_MIRROR_PARITY_INTERVAL_SECS")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse()
+            .unwrap_or(300),
+        max_batches_per_minute: std::env::var("MAX_BATCHES_PER_MINUTE")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse()
+            .unwrap_or(0),
+        max_lamports_per_hour: std::env::var("MAX_LAMPORTS_PER_HOUR")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse()
+            .unwrap_or(0),
+        fee_budget_lamports_per_hour: std::env::var("FEE_BUDGET_LAMPORTS_PER_HOUR")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse()
+            .unwrap_or(0),
+        fee_budget_lamports_per_day: std::env::var("FEE_BUDGET_LAMPORTS_PER_DAY")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse()
+            .unwrap_or(0),
+        sealing_policy: std::env::var("SEALING_POLICY")
+            .unwrap_or_else(|_| "time".to_string()),
+        batch_max_age_secs: std::env::var("BATCH_MAX_AGE_SECS")
+            .unwrap_or_else(|_| "120".to_string())
+            .parse()
+            .unwrap_or(120),
+        batch_process_interval_secs: std::env::var("BATCH_PROCESS_INTERVAL_SECS")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()
+            .unwrap_or(10),
+        seal_min_total_fee: std::env::var("SEAL_MIN_TOTAL_FEE")
+            .unwrap_or_else(|_| "1000000".to_string())
+            .parse()
+            .unwrap_or(1000000),
+        seal_min_total_value: std::env::var("SEAL_MIN_TOTAL_VALUE")
+            .unwrap_or_else(|_| "1000000000000".to_string())
+            .parse()
+            .unwrap_or(1000000000000),
+        seal_target_cost_per_deposit: std::env::var("SEAL_TARGET_COST_PER_DEPOSIT")
+            .unwrap_or_else(|_| "2000".to_string())
+            .parse()
+            .unwrap_or(2000),
+        large_transfer_threshold: std::env::var("LARGE_TRANSFER_THRESHOLD")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse()
+            .unwrap_or(0),
+        large_transfer_delay_secs: std::env::var("LARGE_TRANSFER_DELAY_SECS")
+            .unwrap_or_else(|_| "1800".to_string())
+            .parse()
+            .unwrap_or(1800),
+        idempotency_window_secs: std::env::var("IDEMPOTENCY_WINDOW_SECS")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse()
+            .unwrap_or(300),
+        admin_api_key: std::env::var("ADMIN_API_KEY")
+            .unwrap_or_else(|_| "".to_string()),
+        relayer_private_key: std::env::var("RELAYER_PRIVATE_KEY").ok(),
+        relayer_keypair_path: std::env::var("RELAYER_KEYPAIR_PATH").ok(),
+        solana_keypair: std::env::var("SOLANA_KEYPAIR").ok(),
+        precreate_accounts: std::env::var("PRECREATE_ACCOUNTS")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .unwrap_or(false),
+        ton_api_url: std::env::var("TON_API_URL")
+            .unwrap_or_else(|_| "https://toncenter.com".to_string()),
+        jetton_metadata_ttl_secs: std::env::var("JETTON_METADATA_TTL_SECS")
+            .unwrap_or_else(|_| "3600".to_string())
+            .parse()
+            .unwrap_or(3600),
+        token_registry_path: std::env::var("TOKEN_REGISTRY_PATH")
+            .unwrap_or_else(|_| "".to_string()),
+        price_oracle_url: std::env::var("PRICE_ORACLE_URL")
+            .unwrap_or_else(|_| "".to_string()),
+        telegram_bot_token: std::env::var("TELEGRAM_BOT_TOKEN")
+            .unwrap_or_else(|_| "".to_string()),
+        email_api_url: std::env::var("EMAIL_API_URL")
+            .unwrap_or_else(|_| "".to_string()),
+        email_api_key: std::env::var("EMAIL_API_KEY")
+            .unwrap_or_else(|_| "".to_string()),
+        email_from: std::env::var("EMAIL_FROM")
+            .unwrap_or_else(|_| "bridge@localhost".to_string()),
+        ton_light_client_address: std::env::var("TON_LIGHT_CLIENT_ADDRESS")
+            .unwrap_or_else(|_| "".to_string()),
+        ton_light_client_get_method: std::env::var("TON_LIGHT_CLIENT_GET_METHOD")
+            .unwrap_or_else(|_| "get_state_root".to_string()),
+        root_listener_api_url: std::env::var("ROOT_LISTENER_API_URL")
+            .unwrap_or_else(|_| "".to_string()),
+        root_relay_interval_secs: std::env::var("ROOT_RELAY_INTERVAL_SECS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .unwrap_or(30),
+        quarantined_addresses: std::env::var("QUARANTINED_ADDRESSES")
+            .unwrap_or_else(|_| "".to_string())
+            .split(',')
+            .map(|address| address.trim().to_string())
+            .filter(|address| !address.is_empty())
+            .collect(),
+        archive_dir: std::env::var("ARCHIVE_DIR")
+            .unwrap_or_else(|_| "".to_string()),
+        archive_ipfs_api_url: std::env::var("ARCHIVE_IPFS_API_URL")
+            .unwrap_or_else(|_| "".to_string()),
+        submitter_lease_ttl_secs: std::env::var("SUBMITTER_LEASE_TTL_SECS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .unwrap_or(30),
+        request_handover_on_start: std::env::var("REQUEST_HANDOVER_ON_START")
+            .unwrap_or_else(|_| "true".to_string())
+            .parse()
+            .unwrap_or(true),
+        role: std::env::var("MANAGER_ROLE")
+            .unwrap_or_else(|_| "all".to_string())
+            .parse()?,
+        alert_fee_payer_min_lamports: std::env::var("ALERT_FEE_PAYER_MIN_LAMPORTS")
+            .unwrap_or_else(|_| "100000000".to_string())
+            .parse()
+            .unwrap_or(100000000),
+        alert_root_stale_secs: std::env::var("ALERT_ROOT_STALE_SECS")
+            .unwrap_or_else(|_| "3600".to_string())
+            .parse()
+            .unwrap_or(3600),
+        deposit_sla_secs: std::env::var("DEPOSIT_SLA_SECS")
+            .unwrap_or_else(|_| "900".to_string())
+            .parse()
+            .unwrap_or(900),
+        invariant_check_interval_secs: std::env::var("INVARIANT_CHECK_INTERVAL_SECS")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse()
+            .unwrap_or(300),
+        max_priority_fee_micro_lamports: std::env::var("MAX_PRIORITY_FEE_MICRO_LAMPORTS")
+            .unwrap_or_else(|_| "1000000".to_string())
+            .parse()
+            .unwrap_or(1000000),
+        solana_rpc_timeout_secs: std::env::var("SOLANA_RPC_TIMEOUT_SECS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .unwrap_or(30),
+        solana_confirm_timeout_secs: std::env::var("SOLANA_CONFIRM_TIMEOUT_SECS")
+            .unwrap_or_else(|_| "90".to_string())
+            .parse()
+            .unwrap_or(90),
+        solana_nonce_account: std::env::var("SOLANA_NONCE_ACCOUNT").ok().filter(|account| !account.is_empty()),
+        finality_timeout_secs: std::env::var("FINALITY_TIMEOUT_SECS")
+            .unwrap_or_else(|_| "120".to_string())
+            .parse()
+            .unwrap_or(120),
+        solana_ws_url: std::env::var("SOLANA_WS_URL")
+            .unwrap_or_else(|_| "".to_string()),
+        jito_block_engine_url: std::env::var("JITO_BLOCK_ENGINE_URL")
+            .unwrap_or_else(|_| "".to_string()),
+        jito_tip_lamports: std::env::var("JITO_TIP_LAMPORTS")
+            .unwrap_or_else(|_| "10000".to_string())
+            .parse()
+            .unwrap_or(10000),
+        solana_use_lookup_table: std::env::var("SOLANA_USE_LOOKUP_TABLE")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .unwrap_or(false),
+        solana_lookup_table: std::env::var("SOLANA_LOOKUP_TABLE").ok().filter(|table| !table.is_empty()),
+        min_payer_balance_lamports: std::env::var("MIN_PAYER_BALANCE_LAMPORTS")
+            .unwrap_or_else(|_| "10000000".to_string())
+            .parse()
+            .unwrap_or(10000000),
+        verify_proofs_locally: std::env::var("VERIFY_PROOFS_LOCALLY")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .unwrap_or(false),
+        proof_job_poll_secs: std::env::var("PROOF_JOB_POLL_SECS")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse()
+            .unwrap_or(5),
+        proof_job_timeout_secs: std::env::var("PROOF_JOB_TIMEOUT_SECS")
+            .unwrap_or_else(|_| "1800".to_string())
+            .parse()
+            .unwrap_or(1800),
+        prover_concurrency: std::env::var("PROVER_CONCURRENCY")
+            .unwrap_or_else(|_| "4".to_string())
+            .parse()
+            .unwrap_or(4),
+        validate_deposits_on_ton: std::env::var("VALIDATE_DEPOSITS_ON_TON")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .unwrap_or(false),
+        adaptive_batch_sizing: std::env::var("ADAPTIVE_BATCH_SIZING")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .unwrap_or(false),
+        batch_min_size: std::env::var("BATCH_MIN_SIZE")
+            .unwrap_or_else(|_| "1".to_string())
+            .parse()
+            .unwrap_or(1),
+        batch_max_size: std::env::var("BATCH_MAX_SIZE")
+            .unwrap_or_else(|_| "50".to_string())
+            .parse()
+            .unwrap_or(50),
+        batch_max_bytes: std::env::var("BATCH_MAX_BYTES")
+            .unwrap_or_else(|_| "1232".to_string())
+            .parse()
+            .unwrap_or(1232),
+        batch_max_compute_units: std::env::var("BATCH_MAX_COMPUTE_UNITS")
+            .unwrap_or_else(|_| "12000000".to_string())
+            .parse()
+            .unwrap_or(12_000_000),
+        priority_batch_size: std::env::var("PRIORITY_BATCH_SIZE")
+            .unwrap_or_else(|_| "1".to_string())
+            .parse()
+            .unwrap_or(1),
+        priority_batch_max_age_secs: std::env::var("PRIORITY_BATCH_MAX_AGE_SECS")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()
+            .unwrap_or(10),
+        priority_fee_threshold: std::env::var("PRIORITY_FEE_THRESHOLD")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse()
+            .unwrap_or(0),
     };
     
     // Create and start submission manager
@@ -45,8 +319,12 @@ async fn main() -> Result<(), Box<dyn Error>> {
     
     println!("✅ Submission Manager with Solana client initialized, starting HTTP server...");
     
-    // Start HTTP server (this will block)
-    manager.start_http_server().await?;
+    // Serve until Ctrl-C, then shut down the pipeline cleanly
+    manager
+        .run_until(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
     
     Ok(())
 }
\ No newline at end of file
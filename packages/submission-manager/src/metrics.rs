@@ -1,4 +1,4 @@
-use prometheus::{Counter, Gauge, Histogram, HistogramOpts,  Registry};
+use prometheus::{Counter, Gauge, GaugeVec, Histogram, HistogramOpts, Opts, Registry};
 
 pub struct BridgeMetrics {
     // Counters
@@ -33,10 +33,41 @@ pub struct BridgeMetrics {
     pub last_successful_batch_time: Gauge,
     pub last_failure_time: Gauge,
     pub empty_queue_checks: Counter,
+    pub database_size_bytes: Gauge,
+    pub database_vacuums: Counter,
+    pub rate_limited_checks: Counter,
     
     // Retry metrics
     pub batch_retries: Counter,
     pub max_retries_exceeded: Counter,
+    pub bridge_paused: Gauge,
+    pub root_relay_pushes: Counter,
+    pub root_relay_disagreements: Counter,
+    pub onchain_events_verified: Gauge,
+    pub event_counter_drift: Gauge,
+    pub spend_guard_tripped: Gauge,
+    pub handoff_prove_backlog: Gauge,
+    pub handoff_submit_backlog: Gauge,
+    pub external_proofs_accepted: Counter,
+    pub external_proofs_rejected: Counter,
+    pub db_mirror_parity_mismatches: Gauge,
+    pub invariant_violations: Gauge,
+    pub solana_rpc_active_endpoint: Gauge,
+    pub solana_rpc_failovers: Gauge,
+    pub deposits_reconciled: Counter,
+    pub payer_balance_lamports: Gauge,
+    pub proofs_rejected_locally: Counter,
+    pub circuit_service_up: GaugeVec,
+    pub circuit_service_failures: GaugeVec,
+    pub circuit_service_proving_ms: GaugeVec,
+    pub circuit_service_queue_depth: GaugeVec,
+    pub circuit_service_in_flight: GaugeVec,
+    pub circuit_service_expected_wait_ms: GaugeVec,
+    pub proofs_reproved_expired: Counter,
+    pub deposits_rejected_on_ton: Counter,
+    pub batch_target_size: Gauge,
+    pub deposits_rebatched: Counter,
+    pub batches_split: Counter,
 }
 
 impl BridgeMetrics {
@@ -77,9 +108,43 @@ impl BridgeMetrics {
             last_successful_batch_time: Gauge::new("last_successful_batch_time", "Timestamp of last successful batch")?,
             last_failure_time: Gauge::new("last_failure_time", "Timestamp of last failure")?,
             empty_queue_checks: Counter::new("empty_queue_checks_total", "Total empty queue checks")?,
+            database_size_bytes: Gauge::new("database_size_bytes", "SQLite database file size in bytes")?,
+            database_vacuums: Counter::new("database_vacuums_total", "Total SQLite VACUUM runs")?,
+            rate_limited_checks: Counter::new("rate_limited_checks_total", "Total queue checks held back by the submission rate limiter")?,
             
             batch_retries: Counter::new("batch_retries_total", "Total batch retries")?,
             max_retries_exceeded: Counter::new("max_retries_exceeded_total", "Total max retries exceeded")?,
+            bridge_paused: Gauge::new("bridge_paused", "1 while the on-chain program is paused")?,
+            root_relay_pushes: Counter::new("root_relay_pushes_total", "TON roots pushed from the light-client contract")?,
+            root_relay_disagreements: Counter::new("root_relay_disagreements_total", "Checkpoints where the light-client and listener roots differed")?,
+            onchain_events_verified: Gauge::new("onchain_events_verified", "LcState.events_verified as last read from the program")?,
+            event_counter_drift: Gauge::new("event_counter_drift", "Completed deposits in the database minus events verified on-chain")?,
+            spend_guard_tripped: Gauge::new("spend_guard_tripped", "1 while the fee spend guard is halting submissions")?,
+            handoff_prove_backlog: Gauge::new("handoff_prove_backlog", "Deposits waiting for a prover process")?,
+            handoff_submit_backlog: Gauge::new("handoff_submit_backlog", "Sealed batches waiting for the submitter process")?,
+            external_proofs_accepted: Counter::new("external_proofs_accepted_total", "Externally generated proofs verified and admitted to batching")?,
+            external_proofs_rejected: Counter::new("external_proofs_rejected_total", "Externally generated proofs that failed verification")?,
+            db_mirror_parity_mismatches: Gauge::new("db_mirror_parity_mismatches", "Deposit rows differing from the Postgres mirror at the last parity check")?,
+            invariant_violations: Gauge::new("invariant_violations", "Invariants violated at the last audit")?,
+            solana_rpc_active_endpoint: Gauge::new("solana_rpc_active_endpoint", "Index of the Solana RPC endpoint in use")?,
+            solana_rpc_failovers: Gauge::new("solana_rpc_failovers", "Solana RPC failovers since startup")?,
+            deposits_reconciled: Counter::new("deposits_reconciled_total", "Deposits completed from program events")?,
+            payer_balance_lamports: Gauge::new("payer_balance_lamports", "Relayer fee payer balance")?,
+            proofs_rejected_locally: Counter::new("proofs_rejected_locally_total", "Circuit service proofs failing local verification")?,
+            circuit_service_up: GaugeVec::new(Opts::new("circuit_service_up", "1 unless the service is backing off"), &["service"])?,
+            circuit_service_failures: GaugeVec::new(Opts::new("circuit_service_failures", "Consecutive failures"), &["service"])?,
+            circuit_service_proving_ms: GaugeVec::new(Opts::new("circuit_service_proving_ms", "Rolling proving time"), &["service"])?,
+            circuit_service_queue_depth: GaugeVec::new(Opts::new("circuit_service_queue_depth", "Reported queue depth"), &["service"])?,
+            circuit_service_in_flight: GaugeVec::new(Opts::new("circuit_service_in_flight", "Our outstanding jobs"), &["service"])?,
+            circuit_service_expected_wait_ms: GaugeVec::new(
+                Opts::new("circuit_service_expected_wait_ms", "Scheduling score"),
+                &["service"],
+            )?,
+            proofs_reproved_expired: Counter::new("proofs_reproved_expired_total", "Proofs re-requested after their TON root rotated out")?,
+            deposits_rejected_on_ton: Counter::new("deposits_rejected_on_ton_total", "Deposits their TON transaction does not back")?,
+            batch_target_size: Gauge::new("batch_target_size", "Deposits the open batch is sized for")?,
+            deposits_rebatched: Counter::new("deposits_rebatched_total", "Deposits retried in a new batch after others in their batch landed")?,
+            batches_split: Counter::new("batches_split_total", "Batches split in two after exceeding transaction size or compute limits")?,
         };
 
         // Register ALL metrics
@@ -110,9 +175,40 @@ impl BridgeMetrics {
         registry.register(Box::new(metrics.last_successful_batch_time.clone()))?;
         registry.register(Box::new(metrics.last_failure_time.clone()))?;
         registry.register(Box::new(metrics.empty_queue_checks.clone()))?;
+        registry.register(Box::new(metrics.database_size_bytes.clone()))?;
+        registry.register(Box::new(metrics.database_vacuums.clone()))?;
+        registry.register(Box::new(metrics.rate_limited_checks.clone()))?;
         
         registry.register(Box::new(metrics.batch_retries.clone()))?;
         registry.register(Box::new(metrics.max_retries_exceeded.clone()))?;
+        registry.register(Box::new(metrics.bridge_paused.clone()))?;
+        registry.register(Box::new(metrics.root_relay_pushes.clone()))?;
+        registry.register(Box::new(metrics.root_relay_disagreements.clone()))?;
+        registry.register(Box::new(metrics.onchain_events_verified.clone()))?;
+        registry.register(Box::new(metrics.event_counter_drift.clone()))?;
+        registry.register(Box::new(metrics.spend_guard_tripped.clone()))?;
+        registry.register(Box::new(metrics.handoff_prove_backlog.clone()))?;
+        registry.register(Box::new(metrics.handoff_submit_backlog.clone()))?;
+        registry.register(Box::new(metrics.external_proofs_accepted.clone()))?;
+        registry.register(Box::new(metrics.external_proofs_rejected.clone()))?;
+        registry.register(Box::new(metrics.db_mirror_parity_mismatches.clone()))?;
+        registry.register(Box::new(metrics.invariant_violations.clone()))?;
+        registry.register(Box::new(metrics.solana_rpc_active_endpoint.clone()))?;
+        registry.register(Box::new(metrics.solana_rpc_failovers.clone()))?;
+        registry.register(Box::new(metrics.deposits_reconciled.clone()))?;
+        registry.register(Box::new(metrics.payer_balance_lamports.clone()))?;
+        registry.register(Box::new(metrics.proofs_rejected_locally.clone()))?;
+        registry.register(Box::new(metrics.circuit_service_up.clone()))?;
+        registry.register(Box::new(metrics.circuit_service_failures.clone()))?;
+        registry.register(Box::new(metrics.circuit_service_proving_ms.clone()))?;
+        registry.register(Box::new(metrics.circuit_service_queue_depth.clone()))?;
+        registry.register(Box::new(metrics.circuit_service_in_flight.clone()))?;
+        registry.register(Box::new(metrics.circuit_service_expected_wait_ms.clone()))?;
+        registry.register(Box::new(metrics.proofs_reproved_expired.clone()))?;
+        registry.register(Box::new(metrics.deposits_rejected_on_ton.clone()))?;
+        registry.register(Box::new(metrics.batch_target_size.clone()))?;
+        registry.register(Box::new(metrics.deposits_rebatched.clone()))?;
+        registry.register(Box::new(metrics.batches_split.clone()))?;
 
         Ok(metrics)
     }
@@ -0,0 +1,152 @@
+use crate::database::DatabaseService;
+use crate::event_bus::PipelineEvent;
+use crate::{OrchestratorError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Where a user wants status messages delivered
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct NotificationTarget {
+    pub channel: String, // "telegram" (address = chat id) | "email" (address = email)
+    pub address: String,
+}
+
+/// Targets can be attached to a single deposit or to every deposit of an API key
+pub const SCOPE_DEPOSIT: &str = "deposit";
+pub const SCOPE_API_KEY: &str = "api_key";
+
+#[derive(Clone)]
+struct TelegramChannel {
+    bot_token: String,
+}
+
+/// Sends through an HTTP email relay: `POST {api_url}` with `{from, to, subject, text}`
+#[derive(Clone)]
+struct EmailChannel {
+    api_url: String,
+    api_key: String,
+    from: String,
+}
+
+/// Messages users when their transfers complete or fail, driven off the event bus
+#[derive(Clone)]
+pub struct Notifier {
+    database: DatabaseService,
+    client: reqwest::Client,
+    telegram: Option<TelegramChannel>,
+    email: Option<EmailChannel>,
+}
+
+impl Notifier {
+    pub fn new(database: DatabaseService, config: &crate::OrchestratorConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap();
+
+        let telegram = (!config.telegram_bot_token.is_empty()).then(|| TelegramChannel {
+            bot_token: config.telegram_bot_token.clone(),
+        });
+        let email = (!config.email_api_url.is_empty()).then(|| EmailChannel {
+            api_url: config.email_api_url.clone(),
+            api_key: config.email_api_key.clone(),
+            from: config.email_from.clone(),
+        });
+
+        Self {
+            database,
+            client,
+            telegram,
+            email,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.telegram.is_some() || self.email.is_some()
+    }
+
+    /// Consume pipeline events until the bus closes
+    pub async fn run(self, mut events: broadcast::Receiver<PipelineEvent>) {
+        loop {
+            let (deposit_ids, subject, body) = match events.recv().await {
+                Ok(PipelineEvent::BatchConfirmed { signature, deposit_ids }) => (
+                    deposit_ids,
+                    "Transfer completed",
+                    format!("Your TON → Solana transfer has completed. Solana transaction: {}", signature),
+                ),
+                Ok(PipelineEvent::BatchFailed { deposit_ids, reason }) => (
+                    deposit_ids,
+                    "Transfer failed",
+                    format!("Your TON → Solana transfer failed: {}. A refund will be attempted.", reason),
+                ),
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    log::warn!("Notifier lagged behind the event bus, {} events skipped", skipped);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+
+            for deposit_id in &deposit_ids {
+                let message = format!("Deposit {}: {}", deposit_id, body);
+                if let Err(e) = self.notify_deposit(deposit_id, subject, &message).await {
+                    log::warn!("Failed to notify for deposit {}: {}", deposit_id, e);
+                }
+            }
+        }
+    }
+
+    async fn notify_deposit(&self, deposit_id: &str, subject: &str, message: &str) -> Result<()> {
+        // Per-deposit targets win over the API key's defaults
+        let mut targets = self.database.get_notification_targets(SCOPE_DEPOSIT, deposit_id).await?;
+        if targets.is_empty() {
+            if let Some(api_key) = self.database.get_request_api_key(deposit_id).await? {
+                targets = self.database.get_notification_targets(SCOPE_API_KEY, &api_key).await?;
+            }
+        }
+
+        for target in targets {
+            match target.channel.as_str() {
+                "telegram" => self.send_telegram(&target.address, message).await?,
+                "email" => self.send_email(&target.address, subject, message).await?,
+                other => log::warn!("Unknown notification channel {} for deposit {}", other, deposit_id),
+            }
+        }
+        Ok(())
+    }
+
+    async fn send_telegram(&self, chat_id: &str, message: &str) -> Result<()> {
+        let Some(telegram) = &self.telegram else {
+            return Ok(());
+        };
+
+        self.client
+            .post(&format!("https://api.telegram.org/bot{}/sendMessage", telegram.bot_token))
+            .json(&json!({ "chat_id": chat_id, "text": message }))
+            .send()
+            .await
+            .map_err(OrchestratorError::NetworkError)?
+            .error_for_status()
+            .map_err(OrchestratorError::NetworkError)?;
+        Ok(())
+    }
+
+    async fn send_email(&self, to: &str, subject: &str, message: &str) -> Result<()> {
+        let Some(email) = &self.email else {
+            return Ok(());
+        };
+
+        self.client
+            .post(&email.api_url)
+            .bearer_auth(&email.api_key)
+            .json(&json!({ "from": email.from, "to": to, "subject": subject, "text": message }))
+            .send()
+            .await
+            .map_err(OrchestratorError::NetworkError)?
+            .error_for_status()
+            .map_err(OrchestratorError::NetworkError)?;
+        Ok(())
+    }
+}
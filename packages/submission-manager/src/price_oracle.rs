@@ -0,0 +1,65 @@
+use crate::{OrchestratorError, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// USD reference rates at a point in time
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RateSnapshot {
+    pub ton_usd: f64,
+    pub sol_usd: f64,
+}
+
+/// Client for a CoinGecko-compatible `simple/price` API
+#[derive(Clone)]
+pub struct PriceOracle {
+    api_url: String,
+    client: reqwest::Client,
+}
+
+impl PriceOracle {
+    const TON_ID: &'static str = "the-open-network";
+    const SOL_ID: &'static str = "solana";
+
+    /// Returns `None` when no oracle is configured
+    pub fn new(api_url: &str) -> Option<Self> {
+        if api_url.is_empty() {
+            return None;
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap();
+
+        Some(Self {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            client,
+        })
+    }
+
+    pub async fn fetch_rates(&self) -> Result<RateSnapshot> {
+        let ids = format!("{},{}", Self::TON_ID, Self::SOL_ID);
+        let body: serde_json::Value = self.client
+            .get(&format!("{}/api/v3/simple/price", self.api_url))
+            .query(&[("ids", ids.as_str()), ("vs_currencies", "usd")])
+            .send()
+            .await
+            .map_err(OrchestratorError::NetworkError)?
+            .error_for_status()
+            .map_err(OrchestratorError::NetworkError)?
+            .json()
+            .await
+            .map_err(OrchestratorError::NetworkError)?;
+
+        let rate = |id: &str| {
+            body[id]["usd"].as_f64().ok_or_else(|| {
+                OrchestratorError::ConfigurationError(format!("Price oracle returned no USD rate for {}", id))
+            })
+        };
+
+        Ok(RateSnapshot {
+            ton_usd: rate(Self::TON_ID)?,
+            sol_usd: rate(Self::SOL_ID)?,
+        })
+    }
+}
@@ -0,0 +1,49 @@
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+use crate::Deposit;
+
+/// Commitment to a recipient for privacy-mode deposits; must match
+/// `ZKVerifier::recipient_commitment` in the program
+pub fn recipient_commitment(recipient: &Pubkey, salt: &[u8; 32]) -> [u8; 32] {
+    solana_sdk::hash::hashv(&[b"RECIPIENT", recipient.as_ref(), salt]).to_bytes()
+}
+
+/// For privacy-mode deposits, check the revealed recipient and salt open the
+/// commitment the TON payload carried. Returns the reason on mismatch.
+pub fn check_recipient_reveal(deposit: &Deposit) -> Result<(), String> {
+    let Some(commitment) = &deposit.recipient_commitment else {
+        return Ok(());
+    };
+
+    let commitment = decode_32(commitment).map_err(|e| format!("Invalid recipient commitment: {}", e))?;
+    let salt = deposit
+        .recipient_salt
+        .as_deref()
+        .ok_or_else(|| "Privacy-mode deposit is missing the recipient salt".to_string())
+        .and_then(|salt| decode_32(salt).map_err(|e| format!("Invalid recipient salt: {}", e)))?;
+    let recipient = Pubkey::from_str(&deposit.recipient_solana)
+        .map_err(|e| format!("Invalid Solana recipient: {}", e))?;
+
+    if recipient_commitment(&recipient, &salt) != commitment {
+        return Err("Revealed recipient does not match the deposit commitment".to_string());
+    }
+
+    Ok(())
+}
+
+/// Salt a privacy-mode deposit reveals when it is claimed; zero for public deposits
+pub fn recipient_salt(deposit: &Deposit) -> Result<[u8; 32], String> {
+    match (&deposit.recipient_commitment, deposit.recipient_salt.as_deref()) {
+        (None, _) => Ok([0u8; 32]),
+        (Some(_), Some(salt)) => decode_32(salt).map_err(|e| format!("Invalid recipient salt: {}", e)),
+        (Some(_), None) => Err("Privacy-mode deposit is missing the recipient salt".to_string()),
+    }
+}
+
+fn decode_32(value: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(value.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+    bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| format!("expected 32 bytes, got {}", b.len()))
+}
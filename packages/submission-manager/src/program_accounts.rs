@@ -0,0 +1,173 @@
+use borsh::BorshDeserialize;
+use solana_sdk::pubkey::Pubkey;
+use crate::{OrchestratorError, Result};
+
+// Mirrors of the program's account types; field order must match state.rs and
+// lib.rs. Each account starts with Anchor's 8-byte discriminator.
+
+/// `LcState`: the program's singleton configuration and counters
+#[derive(Debug, Clone, PartialEq, BorshDeserialize)]
+pub struct LcState {
+    pub admin: Pubkey,
+    pub last_verified_slot: u64,
+    pub vk_id: u32,
+    pub ton_state_root: [u8; 32],
+    pub relayer: Pubkey,
+    pub previous_vk_id: u32,
+    pub previous_vk_expires_at: i64,
+    pub fee_bps: u16,
+    pub nullifier_mode: u8,
+    pub pending_admin: Pubkey,
+    pub root_approval_threshold: u8,
+    pub paused: bool,
+    pub governance_delay_secs: i64,
+    pub next_action_id: u64,
+    pub event_close_age_secs: i64,
+    pub version: u8,
+    pub events_verified: u64,
+    pub total_amount_bridged: u128,
+    pub header_chain_only: bool,
+    pub bump: u8,
+    pub mint_bump: u8,
+    pub treasury_bump: u8,
+    pub claim_tip_bps: u16,
+    pub poseidon_from_vk_id: u32,
+    pub deposit_tree_root: [u8; 32],
+}
+
+impl LcState {
+    pub const SIZE: usize = 32 + 8 + 4 + 32 + 32 + 4 + 8 + 2 + 1 + 32 + 1 + 1 + 8 + 8 + 8 + 8 + 1 + 8 + 16 + 1 + 3 + 2 + 4 + 32;
+    /// Data length (with discriminator) of layouts that carry the event counters
+    pub const EVENT_COUNTERS_END: usize = 8 + 32 + 8 + 4 + 32 + 32 + 4 + 8 + 2 + 1 + 32 + 1 + 1 + 8 + 8 + 8 + 1 + 8 + 16;
+
+    /// Decode any layout version: fields an older account lacks read as zero,
+    /// as they do on-chain after `migrate_state`
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let body = strip_discriminator("LcState", data)?;
+        let mut padded = body.to_vec();
+        padded.resize(padded.len().max(Self::SIZE), 0);
+        Self::deserialize(&mut padded.as_slice()).map_err(|e| invalid("LcState", e))
+    }
+}
+
+/// `EventState`: created when a TON event is verified; its existence means consumed
+#[derive(Debug, Clone, PartialEq, BorshDeserialize)]
+pub struct EventState {
+    pub consumed: bool,
+    pub event_id: [u8; 32],
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub ton_tx_hash: [u8; 32],
+    pub ton_sender: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub leaf_index: u64,
+    pub payer: Pubkey,
+    pub consumed_at: i64,
+}
+
+impl EventState {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let mut body = strip_discriminator("EventState", data)?;
+        Self::deserialize(&mut body).map_err(|e| invalid("EventState", e))
+    }
+}
+
+/// `NullifierState`: created alongside the event, outliving it after `close_event`
+#[derive(Debug, Clone, PartialEq, BorshDeserialize)]
+pub struct NullifierState {
+    pub consumed: bool,
+    pub nullifier: [u8; 32],
+    pub ton_tx_hash: [u8; 32],
+}
+
+impl NullifierState {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let mut body = strip_discriminator("NullifierState", data)?;
+        Self::deserialize(&mut body).map_err(|e| invalid("NullifierState", e))
+    }
+}
+
+/// `NullifierBitmap`: consumed bits for one page of leaf positions in the TON
+/// deposit tree (NULLIFIER_MODE_BITMAP)
+#[derive(Debug, Clone, PartialEq, BorshDeserialize)]
+pub struct NullifierBitmap {
+    pub page: u64,
+    pub bits: [u8; NullifierBitmap::PAGE_BYTES],
+}
+
+impl NullifierBitmap {
+    pub const PAGE_BYTES: usize = 1024;
+    pub const BITS_PER_PAGE: u64 = (Self::PAGE_BYTES * 8) as u64;
+    /// Account space, including the discriminator
+    pub const SPACE: usize = 8 + 8 + Self::PAGE_BYTES;
+
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let mut body = strip_discriminator("NullifierBitmap", data)?;
+        Self::deserialize(&mut body).map_err(|e| invalid("NullifierBitmap", e))
+    }
+
+    /// Page holding `leaf_index`'s bit
+    pub fn page_for(leaf_index: u64) -> u64 {
+        leaf_index / Self::BITS_PER_PAGE
+    }
+
+    pub fn is_set(&self, leaf_index: u64) -> bool {
+        let bit = leaf_index % Self::BITS_PER_PAGE;
+        self.bits[(bit / 8) as usize] & (1u8 << (bit % 8)) != 0
+    }
+}
+
+/// Capacity of the `HeaderChain` ring buffer
+pub const HEADER_CHAIN_CAPACITY: usize = 32;
+
+/// `TonBlockHeader`: a committee-signed TON masterchain block header
+#[derive(Debug, Clone, Copy, Default, PartialEq, BorshDeserialize)]
+pub struct TonBlockHeader {
+    pub seqno: u32,
+    pub root_hash: [u8; 32],
+    pub file_hash: [u8; 32],
+}
+
+/// `HeaderChain`: zero-copy, but its `repr(C)` layout has no padding and so
+/// reads the same as borsh
+#[derive(Debug, Clone, PartialEq, BorshDeserialize)]
+pub struct HeaderChain {
+    pub headers: [TonBlockHeader; HEADER_CHAIN_CAPACITY],
+    pub latest: TonBlockHeader,
+    pub len: u8,
+    pub next: u8,
+    pub _padding: [u8; 2],
+}
+
+impl HeaderChain {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let mut body = strip_discriminator("HeaderChain", data)?;
+        Self::deserialize(&mut body).map_err(|e| invalid("HeaderChain", e))
+    }
+
+    /// Root hashes event proofs may anchor to, newest first
+    pub fn roots(&self) -> Vec<[u8; 32]> {
+        let len = (self.len as usize).min(HEADER_CHAIN_CAPACITY);
+        (1..=len)
+            .map(|age| (self.next as usize + HEADER_CHAIN_CAPACITY - age) % HEADER_CHAIN_CAPACITY)
+            .map(|slot| self.headers[slot].root_hash)
+            .collect()
+    }
+}
+
+/// Anchor account discriminator: `sha256("account:<name>")[..8]`
+fn discriminator(name: &str) -> [u8; 8] {
+    let preimage = format!("account:{}", name);
+    solana_sdk::hash::hash(preimage.as_bytes()).to_bytes()[..8].try_into().unwrap()
+}
+
+fn strip_discriminator<'a>(name: &str, data: &'a [u8]) -> Result<&'a [u8]> {
+    match data.split_first_chunk::<8>() {
+        Some((prefix, body)) if *prefix == discriminator(name) => Ok(body),
+        _ => Err(OrchestratorError::ConfigurationError(format!("account is not a {}", name))),
+    }
+}
+
+fn invalid(name: &str, error: std::io::Error) -> OrchestratorError {
+    OrchestratorError::ConfigurationError(format!("cannot decode {}: {}", name, error))
+}
@@ -0,0 +1,148 @@
+use serde::Serialize;
+use solana_client::client_error::ClientError;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+
+/// Anchor numbers `#[error_code]` variants from here, in declaration order
+const ANCHOR_ERROR_OFFSET: u32 = 6000;
+/// System program `AccountAlreadyInUse`: the event or nullifier PDA exists
+const ACCOUNT_ALREADY_IN_USE: u32 = 0;
+
+// Positions in the program's `ZkError`; must match zk_verifier.rs
+const BAD_PROOF: u32 = 0;
+const INVALID_ANCHOR_ROOT: u32 = 2;
+const EVENT_ALREADY_CONSUMED: u32 = 3;
+const INVALID_EVENT_ID: u32 = 6;
+const VK_VERSION_NOT_ALLOWED: u32 = 18;
+const INVALID_INCLUSION_PROOF: u32 = 24;
+const INVALID_RECIPIENT: u32 = 28;
+const BRIDGE_PAUSED: u32 = 39;
+const TOKEN_DISABLED: u32 = 52;
+const AMOUNT_OUT_OF_BOUNDS: u32 = 54;
+const VOLUME_CAP_EXCEEDED: u32 = 56;
+const TOKEN_PAUSED: u32 = 57;
+const ANCHOR_NOT_IN_HEADER_CHAIN: u32 = 61;
+
+/// Why the program rejected a deposit, in terms a bridge user can act on.
+/// The snake_case name is the status detail code stored and served by the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgramFailure {
+    AlreadyBridged,
+    RootMismatch,
+    BridgePaused,
+    TokenDisabled,
+    AmountOutOfBounds,
+    VolumeCapExceeded,
+    ProofRejected,
+    VerifyingKeyRetired,
+    RecipientMismatch,
+}
+
+impl ProgramFailure {
+    pub const ALL: [ProgramFailure; 9] = [
+        ProgramFailure::AlreadyBridged,
+        ProgramFailure::RootMismatch,
+        ProgramFailure::BridgePaused,
+        ProgramFailure::TokenDisabled,
+        ProgramFailure::AmountOutOfBounds,
+        ProgramFailure::VolumeCapExceeded,
+        ProgramFailure::ProofRejected,
+        ProgramFailure::VerifyingKeyRetired,
+        ProgramFailure::RecipientMismatch,
+    ];
+
+    /// Classify a custom error code returned by the bridge instruction
+    pub fn from_error_code(code: u32) -> Option<Self> {
+        if code == ACCOUNT_ALREADY_IN_USE {
+            return Some(ProgramFailure::AlreadyBridged);
+        }
+        match code.checked_sub(ANCHOR_ERROR_OFFSET)? {
+            EVENT_ALREADY_CONSUMED => Some(ProgramFailure::AlreadyBridged),
+            INVALID_ANCHOR_ROOT | INVALID_INCLUSION_PROOF | ANCHOR_NOT_IN_HEADER_CHAIN => {
+                Some(ProgramFailure::RootMismatch)
+            }
+            BRIDGE_PAUSED => Some(ProgramFailure::BridgePaused),
+            TOKEN_DISABLED | TOKEN_PAUSED => Some(ProgramFailure::TokenDisabled),
+            AMOUNT_OUT_OF_BOUNDS => Some(ProgramFailure::AmountOutOfBounds),
+            VOLUME_CAP_EXCEEDED => Some(ProgramFailure::VolumeCapExceeded),
+            BAD_PROOF | INVALID_EVENT_ID => Some(ProgramFailure::ProofRejected),
+            VK_VERSION_NOT_ALLOWED => Some(ProgramFailure::VerifyingKeyRetired),
+            INVALID_RECIPIENT => Some(ProgramFailure::RecipientMismatch),
+            _ => None,
+        }
+    }
+
+    /// The program failure behind an RPC error, if the transaction reached the program
+    pub fn from_client_error(error: &ClientError) -> Option<Self> {
+        match error.get_transaction_error()? {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => Self::from_error_code(code),
+            _ => None,
+        }
+    }
+
+    /// The failure behind a simulated transaction's error, falling back to the
+    /// program logs when the error itself carries no custom code
+    pub fn from_simulation(error: &TransactionError, logs: &[String]) -> Option<Self> {
+        match error {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => Self::from_error_code(*code),
+            _ => None,
+        }
+        .or_else(|| Self::from_logs(logs))
+    }
+
+    /// Classify the last error Anchor or the system program logged
+    pub fn from_logs(logs: &[String]) -> Option<Self> {
+        logs.iter().rev().find_map(|line| {
+            if line.contains("already in use") {
+                return Some(ProgramFailure::AlreadyBridged);
+            }
+            let number = line.split("Error Number: ").nth(1)?;
+            let code = number.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()?;
+            Self::from_error_code(code)
+        })
+    }
+
+    /// Whether resubmitting the same deposit can never succeed. Roots, pauses
+    /// and volume windows change on their own; everything else needs a new proof
+    /// or an operator.
+    pub fn is_permanent(self) -> bool {
+        !matches!(
+            self,
+            ProgramFailure::RootMismatch | ProgramFailure::BridgePaused | ProgramFailure::VolumeCapExceeded
+        )
+    }
+
+    pub fn detail_code(self) -> &'static str {
+        match self {
+            ProgramFailure::AlreadyBridged => "already_bridged",
+            ProgramFailure::RootMismatch => "root_mismatch",
+            ProgramFailure::BridgePaused => "bridge_paused",
+            ProgramFailure::TokenDisabled => "token_disabled",
+            ProgramFailure::AmountOutOfBounds => "amount_out_of_bounds",
+            ProgramFailure::VolumeCapExceeded => "volume_cap_exceeded",
+            ProgramFailure::ProofRejected => "proof_rejected",
+            ProgramFailure::VerifyingKeyRetired => "verifying_key_retired",
+            ProgramFailure::RecipientMismatch => "recipient_mismatch",
+        }
+    }
+
+    pub fn from_detail_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|failure| failure.detail_code() == code)
+    }
+
+    /// Explanation shown to the person who made the deposit
+    pub fn message(self) -> &'static str {
+        match self {
+            ProgramFailure::AlreadyBridged => "This deposit has already been bridged to Solana.",
+            ProgramFailure::RootMismatch => "The TON block holding this deposit is not yet known to the bridge on Solana.",
+            ProgramFailure::BridgePaused => "The bridge is paused; the deposit will be processed when it resumes.",
+            ProgramFailure::TokenDisabled => "Bridging of this token is currently disabled.",
+            ProgramFailure::AmountOutOfBounds => "The amount is outside the limits allowed for this token.",
+            ProgramFailure::VolumeCapExceeded => "The bridge's volume limit has been reached; try again later.",
+            ProgramFailure::ProofRejected => "The proof for this deposit was rejected on Solana.",
+            ProgramFailure::VerifyingKeyRetired => "The deposit was proven with a circuit the bridge no longer accepts.",
+            ProgramFailure::RecipientMismatch => "The Solana recipient does not match the one committed to on TON.",
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+/// Prefix Anchor's `emit!` writes event payloads under
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Anchor event discriminator: `sha256("event:<Name>")[..8]`
+pub fn event_discriminator(name: &str) -> [u8; 8] {
+    let preimage = format!("event:{}", name);
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&solana_sdk::hash::hash(preimage.as_bytes()).to_bytes()[..8]);
+    discriminator
+}
+
+// Mirrors of the program's `#[event]` structs; field order must match lib.rs
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, Serialize)]
+pub struct TonEventVerified {
+    pub event_id: [u8; 32],
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub ton_tx_hash: [u8; 32],
+    pub ton_sender: [u8; 32],
+    pub fee: u64,
+    pub sequence: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, Serialize)]
+pub struct AggregateProofVerified {
+    pub events_root: [u8; 32],
+    pub anchor_root: [u8; 32],
+    pub event_count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, Serialize)]
+pub struct TonHeaderVerified {
+    pub seqno: u32,
+    pub root_hash: [u8; 32],
+    pub file_hash: [u8; 32],
+    pub signatures: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, Serialize)]
+pub struct RootUpdated {
+    pub previous_root: [u8; 32],
+    pub new_root: [u8; 32],
+    pub last_verified_slot: u64,
+    pub updated_by: Pubkey,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, Serialize)]
+pub struct VkRotated {
+    pub previous_vk_id: u32,
+    pub new_vk_id: u32,
+    pub previous_vk_expires_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, Serialize)]
+pub struct RelayerAdded {
+    pub relayer: Pubkey,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, Serialize)]
+pub struct RelayerRemoved {
+    pub relayer: Pubkey,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, Serialize)]
+pub struct FeeBpsUpdated {
+    pub old_fee_bps: u16,
+    pub new_fee_bps: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, Serialize)]
+pub struct TonRootApproved {
+    pub root: [u8; 32],
+    pub relayer: Pubkey,
+    pub approvals: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, Serialize)]
+pub struct TonRootCommitted {
+    pub root: [u8; 32],
+    pub approvals: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, Serialize)]
+pub struct BridgePausedChanged {
+    pub paused: bool,
+    pub admin: Pubkey,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, Serialize)]
+pub struct TokenPausedChanged {
+    pub token_id: [u8; 32],
+    pub paused: bool,
+    pub admin: Pubkey,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, Serialize)]
+pub struct WithdrawalRequested {
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub amount: u64,
+    pub ton_recipient: [u8; 32],
+    pub refundable_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, Serialize)]
+pub struct WithdrawalFinalized {
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub ton_tx_hash: [u8; 32],
+    pub relayer: Pubkey,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, Serialize)]
+pub struct WithdrawalRefunded {
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub amount: u64,
+}
+
+/// A decoded program event. Events this manager does not model yet come
+/// back as `Unknown` rather than failing the whole transaction.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum ProgramEvent {
+    TonEventVerified(TonEventVerified),
+    AggregateProofVerified(AggregateProofVerified),
+    TonHeaderVerified(TonHeaderVerified),
+    TonRootApproved(TonRootApproved),
+    TonRootCommitted(TonRootCommitted),
+    RootUpdated(RootUpdated),
+    VkRotated(VkRotated),
+    RelayerAdded(RelayerAdded),
+    RelayerRemoved(RelayerRemoved),
+    FeeBpsUpdated(FeeBpsUpdated),
+    BridgePausedChanged(BridgePausedChanged),
+    TokenPausedChanged(TokenPausedChanged),
+    WithdrawalRequested(WithdrawalRequested),
+    WithdrawalFinalized(WithdrawalFinalized),
+    WithdrawalRefunded(WithdrawalRefunded),
+    Unknown { discriminator: [u8; 8] },
+}
+
+impl ProgramEvent {
+    /// Decode `discriminator || borsh payload`; `None` if a known event's payload is malformed
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < 8 {
+            return None;
+        }
+        let (discriminator, mut payload) = data.split_at(8);
+        let discriminator: [u8; 8] = discriminator.try_into().ok()?;
+
+        macro_rules! try_event {
+            ($($name:ident),*) => {
+                $(
+                    if discriminator == event_discriminator(stringify!($name)) {
+                        return $name::deserialize(&mut payload).ok().map(ProgramEvent::$name);
+                    }
+                )*
+            };
+        }
+        try_event!(
+            TonEventVerified,
+            AggregateProofVerified,
+            TonHeaderVerified,
+            TonRootApproved,
+            TonRootCommitted,
+            RootUpdated,
+            VkRotated,
+            RelayerAdded,
+            RelayerRemoved,
+            FeeBpsUpdated,
+            BridgePausedChanged,
+            TokenPausedChanged,
+            WithdrawalRequested,
+            WithdrawalFinalized,
+            WithdrawalRefunded
+        );
+
+        Some(ProgramEvent::Unknown { discriminator })
+    }
+
+    /// Decode one transaction log line; `None` for lines that carry no event
+    pub fn from_log_line(line: &str) -> Option<Self> {
+        let encoded = line.strip_prefix(PROGRAM_DATA_PREFIX)?;
+        Self::decode(&decode_base64(encoded.trim())?)
+    }
+}
+
+/// Every event in a transaction's log messages, in emission order
+pub fn decode_logs<S: AsRef<str>>(logs: &[S]) -> Vec<ProgramEvent> {
+    logs.iter().filter_map(|line| ProgramEvent::from_log_line(line.as_ref())).collect()
+}
+
+/// Standard-alphabet base64 with optional padding, as the runtime logs it
+fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    fn value(c: u8) -> Option<u32> {
+        match c {
+            b'A'..=b'Z' => Some((c - b'A') as u32),
+            b'a'..=b'z' => Some((c - b'a' + 26) as u32),
+            b'0'..=b'9' => Some((c - b'0' + 52) as u32),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+
+    let input = input.trim_end_matches('=').as_bytes();
+    if input.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(input.len() * 3 / 4);
+    for chunk in input.chunks(4) {
+        let mut bits = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            bits |= value(*c)? << (18 - 6 * i);
+        }
+        let bytes = bits.to_be_bytes();
+        out.extend_from_slice(&bytes[1..chunk.len()]);
+    }
+    Some(out)
+}
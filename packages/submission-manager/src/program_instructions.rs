@@ -0,0 +1,262 @@
+use borsh::BorshSerialize;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use crate::batch_manifest::{LEAF_PREFIX, NODE_PREFIX};
+use crate::proof::{InclusionPath, Proof};
+use crate::public_inputs::{
+    scalar_int, DepositInputs, INPUT_DOMAIN, INPUT_FEE_BPS, INPUT_TOKEN_ID, INPUT_TON_SENDER, INPUT_VK_VERSION,
+};
+use crate::types::Deposit;
+use crate::{OrchestratorError, Result};
+
+/// SPL Token program, owner of the wrapped TON mint and treasury
+pub const TOKEN_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+/// Associated Token Account program, creator of recipient token accounts
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+// PDA seeds; must match the program's `SEED` constants
+pub const LC_STATE_SEED: &[u8] = b"lc_state";
+pub const HEADER_CHAIN_SEED: &[u8] = b"header_chain";
+pub const VERIFYING_KEY_SEED: &[u8] = b"vk";
+pub const TOKEN_CONFIG_SEED: &[u8] = b"token_config";
+pub const VOLUME_LIMIT_SEED: &[u8] = b"volume_limit";
+pub const VOLUME_LIMIT_GLOBAL: &[u8] = b"global";
+pub const EVENT_SEED: &[u8] = b"event";
+pub const NULLIFIER_SEED: &[u8] = b"nullifier";
+pub const NULLIFIER_BITMAP_SEED: &[u8] = b"nullifier_bitmap";
+pub const WRAPPED_TON_MINT_SEED: &[u8] = b"wrapped_ton";
+pub const TREASURY_SEED: &[u8] = b"treasury";
+
+/// Most events one `verify_ton_event_batch` instruction takes
+pub const MAX_BATCH_EVENTS: usize = 8;
+/// `remaining_accounts` per batched event: event PDA, nullifier PDA, recipient, recipient ATA
+pub const BATCH_ACCOUNTS_PER_EVENT: usize = 4;
+
+/// `LcState.nullifier_mode`: one `NullifierState` PDA per event, via `verify_ton_event`
+pub const NULLIFIER_MODE_PDA: u8 = 0;
+/// `LcState.nullifier_mode`: a bit in a paged `NullifierBitmap`, via `verify_ton_event_compact`
+pub const NULLIFIER_MODE_BITMAP: u8 = 1;
+
+/// Anchor instruction data: `sha256("global:<name>")[..8]` followed by the borsh args
+pub fn instruction_data(name: &str, args: &[u8]) -> Vec<u8> {
+    let preimage = format!("global:{}", name);
+    let mut data = solana_sdk::hash::hash(preimage.as_bytes()).to_bytes()[..8].to_vec();
+    data.extend_from_slice(args);
+    data
+}
+
+/// Associated token account of `owner` for `mint`
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+// Mirrors of the program's instruction argument types; field order must match
+// zk_verifier.rs, state.rs and merkle.rs
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize)]
+pub struct ZKProof {
+    pub a: [u8; 64],
+    pub b: [u8; 128],
+    pub c: [u8; 64],
+}
+
+impl From<&Proof> for ZKProof {
+    fn from(proof: &Proof) -> Self {
+        Self { a: proof.a, b: proof.b, c: proof.c }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize)]
+pub struct EventPublicInputs {
+    pub domain: [u8; 32],
+    pub anchor_root: [u8; 32],
+    pub event_id: [u8; 32],
+    pub token_id: [u8; 32],
+    pub amount_in_ton: u64,
+    pub recipient_solana: Pubkey,
+    pub fee_bps: u16,
+    pub vk_version: u32,
+    pub ton_tx_hash: [u8; 32],
+    pub ton_sender: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub recipient_commitment: [u8; 32], // Zero unless the deposit is in privacy mode
+}
+
+impl EventPublicInputs {
+    /// Instruction inputs for `deposit` proven by `proof` against `anchor_root`.
+    /// The deposit's own inputs come from `DepositInputs`, which the proof was
+    /// requested and checked with; domain, token id, fee and key version (and
+    /// the sender, if its address did not decode) are read back from the
+    /// proof's scalars, so they must be canonical there.
+    pub fn for_deposit(deposit: &Deposit, proof: &Proof, anchor_root: [u8; 32]) -> Result<Self> {
+        let inputs = DepositInputs::from_deposit(deposit)?;
+        inputs.check(&deposit.deposit_id, &proof.public_inputs)?;
+        let scalars = &proof.public_inputs;
+        let event_id = inputs
+            .event_id
+            .ok_or_else(|| OrchestratorError::InvalidProof(format!("deposit {} has no event_id", deposit.deposit_id)))?;
+        let nullifier = inputs
+            .nullifier
+            .ok_or_else(|| OrchestratorError::InvalidProof(format!("deposit {} has no nullifier", deposit.deposit_id)))?;
+
+        Ok(Self {
+            domain: scalars[INPUT_DOMAIN],
+            anchor_root,
+            event_id,
+            token_id: scalars[INPUT_TOKEN_ID],
+            amount_in_ton: inputs.amount,
+            recipient_solana: Pubkey::from_str(&deposit.recipient_solana).map_err(|e| {
+                OrchestratorError::InvalidProof(format!("recipient {} is not a Solana address: {}", deposit.recipient_solana, e))
+            })?,
+            fee_bps: scalar_int(&scalars[INPUT_FEE_BPS]) as u16,
+            vk_version: scalar_int(&scalars[INPUT_VK_VERSION]) as u32,
+            ton_tx_hash: inputs.ton_tx_hash,
+            ton_sender: inputs.ton_sender.unwrap_or(scalars[INPUT_TON_SENDER]),
+            nullifier,
+            recipient_commitment: match deposit.recipient_commitment {
+                Some(_) => inputs.recipient_binding,
+                None => [0u8; 32],
+            },
+        })
+    }
+}
+
+/// Merkle path of the TON transaction under `anchor_root`
+#[derive(Debug, Clone, Default, PartialEq, BorshSerialize)]
+pub struct TxInclusionProof {
+    pub leaf_index: u64,
+    pub siblings: Vec<[u8; 32]>,
+}
+
+impl From<&InclusionPath> for TxInclusionProof {
+    fn from(path: &InclusionPath) -> Self {
+        Self { leaf_index: path.leaf_index, siblings: path.siblings.clone() }
+    }
+}
+
+impl TxInclusionProof {
+    /// Whether the path leads from `ton_tx_hash` to `root` the way
+    /// `merkle::verify_inclusion` walks it: bit `i` of `leaf_index` puts the
+    /// running hash on the right at level `i`
+    pub fn leads_to(&self, ton_tx_hash: &[u8; 32], root: &[u8; 32]) -> bool {
+        if self.siblings.len() < 64 && self.leaf_index >> self.siblings.len() != 0 {
+            return false;
+        }
+
+        let mut node = solana_sdk::hash::hashv(&[LEAF_PREFIX, ton_tx_hash]).to_bytes();
+        for (level, sibling) in self.siblings.iter().enumerate() {
+            node = if (self.leaf_index >> level) & 1 == 1 {
+                solana_sdk::hash::hashv(&[NODE_PREFIX, sibling, &node]).to_bytes()
+            } else {
+                solana_sdk::hash::hashv(&[NODE_PREFIX, &node, sibling]).to_bytes()
+            };
+        }
+        node == *root
+    }
+}
+
+/// `verify_ton_event(proof, public_inputs, inclusion)` arguments
+#[derive(Debug, Clone, PartialEq, BorshSerialize)]
+pub struct VerifyTonEventArgs {
+    pub proof: ZKProof,
+    pub public_inputs: EventPublicInputs,
+    pub inclusion: TxInclusionProof,
+}
+
+impl VerifyTonEventArgs {
+    pub fn instruction_data(&self) -> Vec<u8> {
+        instruction_data("verify_ton_event", &borsh::to_vec(self).expect("borsh serialization into a Vec cannot fail"))
+    }
+}
+
+/// `verify_ton_event_compact(proof, public_inputs, inclusion, recipient_salt)` arguments
+#[derive(Debug, Clone, PartialEq, BorshSerialize)]
+pub struct VerifyTonEventCompactArgs {
+    pub proof: ZKProof,
+    pub public_inputs: EventPublicInputs,
+    pub inclusion: TxInclusionProof,
+    pub recipient_salt: [u8; 32], // Zero unless the deposit is in privacy mode
+}
+
+impl VerifyTonEventCompactArgs {
+    pub fn instruction_data(&self) -> Vec<u8> {
+        instruction_data(
+            "verify_ton_event_compact",
+            &borsh::to_vec(self).expect("borsh serialization into a Vec cannot fail"),
+        )
+    }
+}
+
+/// One event of a `verify_ton_event_batch` call
+#[derive(Debug, Clone, PartialEq, BorshSerialize)]
+pub struct BatchedEvent {
+    pub proof: ZKProof,
+    pub public_inputs: EventPublicInputs,
+    pub inclusion: TxInclusionProof,
+    pub recipient_salt: [u8; 32], // Zero unless the deposit is in privacy mode
+}
+
+/// `verify_ton_event_batch(events)` arguments
+#[derive(Debug, Clone, PartialEq, BorshSerialize)]
+pub struct VerifyTonEventBatchArgs {
+    pub events: Vec<BatchedEvent>,
+}
+
+impl VerifyTonEventBatchArgs {
+    pub fn instruction_data(&self) -> Vec<u8> {
+        instruction_data(
+            "verify_ton_event_batch",
+            &borsh::to_vec(self).expect("borsh serialization into a Vec cannot fail"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::hashv;
+
+    fn leaf(ton_tx_hash: &[u8; 32]) -> [u8; 32] {
+        hashv(&[LEAF_PREFIX, ton_tx_hash]).to_bytes()
+    }
+
+    fn node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        hashv(&[NODE_PREFIX, left, right]).to_bytes()
+    }
+
+    #[test]
+    fn inclusion_path_from_the_service_leads_to_a_four_leaf_root() {
+        let txs = [[1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32]];
+        let leaves = txs.map(|tx| leaf(&tx));
+        let (left, right) = (node(&leaves[0], &leaves[1]), node(&leaves[2], &leaves[3]));
+        let root = node(&left, &right);
+
+        let response = serde_json::json!({
+            "proof": hex::encode([0xAAu8; 256]),
+            "publicSignals": ["0x01"],
+            "inclusionProof": {
+                "leafIndex": 2,
+                "siblings": [hex::encode(leaves[3]), hex::encode(left)],
+            },
+        });
+        let proof = Proof::from_service_response(&response).unwrap();
+        let inclusion = TxInclusionProof::from(&proof.inclusion);
+
+        assert_eq!(inclusion, TxInclusionProof { leaf_index: 2, siblings: vec![leaves[3], left] });
+        assert!(inclusion.leads_to(&txs[2], &root));
+        assert!(!inclusion.leads_to(&txs[3], &root));
+        assert!(!TxInclusionProof { leaf_index: 3, ..inclusion.clone() }.leads_to(&txs[2], &root));
+        assert!(!TxInclusionProof::default().leads_to(&txs[2], &root));
+
+        // Borsh layout the program's `TxInclusionProof` reads: u64 index, u32 count, siblings
+        let encoded = borsh::to_vec(&inclusion).unwrap();
+        assert_eq!(&encoded[..8], &2u64.to_le_bytes());
+        assert_eq!(&encoded[8..12], &2u32.to_le_bytes());
+        assert_eq!(&encoded[12..44], &leaves[3]);
+        assert_eq!(&encoded[44..], &left);
+    }
+}
@@ -0,0 +1,238 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_program::alt_bn128::prelude::{alt_bn128_addition, alt_bn128_pairing};
+
+/// Circuit version produced by the current circuit service
+pub const CURRENT_CIRCUIT_VERSION: u32 = 1;
+
+/// BN254 base field modulus (big-endian): every point coordinate is below it
+pub const BN254_FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+/// Groth16 proof over BN254 plus the public inputs it was generated for.
+/// Layout of `a`/`b`/`c` matches the program's `ZKProof`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Proof {
+    #[serde(with = "hex_array")]
+    pub a: [u8; 64],  // G1 point
+    #[serde(with = "hex_array")]
+    pub b: [u8; 128], // G2 point
+    #[serde(with = "hex_array")]
+    pub c: [u8; 64],  // G1 point
+    #[serde(with = "hex_array_vec")]
+    pub public_inputs: Vec<[u8; 32]>,
+    pub circuit_version: u32,
+    #[serde(default)]
+    pub inclusion: InclusionPath, // Proofs cached before paths were recorded read as a lone leaf
+}
+
+/// Merkle path of the deposit's TON transaction under the root the proof is
+/// anchored to, as the circuit service reports it in `inclusionProof`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionPath {
+    pub leaf_index: u64,
+    #[serde(with = "hex_array_vec")]
+    pub siblings: Vec<[u8; 32]>, // Leaf level first
+}
+
+impl Proof {
+    /// Deterministic stand-in used when mock proving is enabled
+    pub fn mock(public_inputs: Vec<[u8; 32]>) -> Self {
+        Self {
+            a: [0xAA; 64],
+            b: [0xAA; 128],
+            c: [0xAA; 64],
+            public_inputs,
+            circuit_version: CURRENT_CIRCUIT_VERSION,
+            inclusion: InclusionPath::default(),
+        }
+    }
+
+    /// Parse a circuit service's `generate-proof` response. `proof` holds this
+    /// type's own JSON, a snarkjs proof (`pi_a`/`pi_b`/`pi_c` with the inputs
+    /// in `publicSignals`), or the hex of `a | b | c` (inputs likewise). The
+    /// transaction's Merkle path comes from `inclusionProof`, when present.
+    pub fn from_service_response(response: &serde_json::Value) -> Result<Self, String> {
+        let inclusion = match response.get("inclusionProof") {
+            Some(path) => Some(
+                serde_json::from_value::<InclusionPath>(path.clone()).map_err(|e| format!("inclusionProof: {}", e))?,
+            ),
+            None => None,
+        };
+        let proof = &response["proof"];
+        let (a, b, c) = if proof.get("pi_a").is_some() {
+            let snarkjs: SnarkjsProof = serde_json::from_value(proof.clone()).map_err(|e| e.to_string())?;
+            (snarkjs_g1(&snarkjs.pi_a)?, snarkjs_g2(&snarkjs.pi_b)?, snarkjs_g1(&snarkjs.pi_c)?)
+        } else if let Some(encoded) = proof.as_str() {
+            let bytes: [u8; 256] = hex_array::decode(encoded)?;
+            (bytes[..64].try_into().unwrap(), bytes[64..192].try_into().unwrap(), bytes[192..].try_into().unwrap())
+        } else {
+            let mut proof: Self = serde_json::from_value(proof.clone()).map_err(|e| e.to_string())?;
+            if let Some(inclusion) = inclusion {
+                proof.inclusion = inclusion;
+            }
+            return Ok(proof);
+        };
+
+        let public_inputs = response["publicSignals"]
+            .as_array()
+            .ok_or("response has no publicSignals")?
+            .iter()
+            .map(|signal| signal.as_str().ok_or("public signal is not a string".to_string()).and_then(field_element))
+            .collect::<Result<Vec<_>, _>>()?;
+        let circuit_version = response["circuitVersion"].as_u64().map_or(CURRENT_CIRCUIT_VERSION, |v| v as u32);
+
+        Ok(Self { a, b, c, public_inputs, circuit_version, inclusion: inclusion.unwrap_or_default() })
+    }
+
+    /// Check that `a`, `b` and `c` are canonical BN254 points, so a malformed
+    /// proof is refused before a transaction is spent on it
+    pub fn check_encoding(&self) -> Result<(), String> {
+        for (name, point) in [("a", &self.a[..]), ("b", &self.b[..]), ("c", &self.c[..])] {
+            if point.chunks_exact(32).any(|coordinate| coordinate >= &BN254_FIELD_MODULUS[..]) {
+                return Err(format!("{} has a coordinate outside the base field", name));
+            }
+        }
+
+        // The syscalls reject points off their curve: adding the point at
+        // infinity checks a G1 point, pairing with it checks a G2 point
+        for (name, point) in [("a", &self.a), ("c", &self.c)] {
+            let mut input = [0u8; 128];
+            input[..64].copy_from_slice(point);
+            alt_bn128_addition(&input).map_err(|_| format!("{} is not a point on G1", name))?;
+        }
+        let mut input = [0u8; 192];
+        input[64..].copy_from_slice(&self.b);
+        alt_bn128_pairing(&input).map_err(|_| "b is not a point on G2".to_string())?;
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        borsh::to_vec(self).expect("borsh serialization of Proof cannot fail")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> std::io::Result<Self> {
+        Self::try_from_slice(bytes)
+    }
+}
+
+/// snarkjs `proof.json`: projective coordinates as decimal strings
+#[derive(Deserialize)]
+struct SnarkjsProof {
+    pi_a: Vec<String>,
+    pi_b: Vec<Vec<String>>,
+    pi_c: Vec<String>,
+}
+
+/// `[x, y, z]` with z = 1 (affine) or 0 (infinity) -> `x | y`
+fn snarkjs_g1(point: &[String]) -> Result<[u8; 64], String> {
+    let [x, y, z] = point else {
+        return Err(format!("G1 point has {} coordinates, expected 3", point.len()));
+    };
+    let mut bytes = [0u8; 64];
+    match z.as_str() {
+        "0" => {}
+        "1" => {
+            bytes[..32].copy_from_slice(&field_element(x)?);
+            bytes[32..].copy_from_slice(&field_element(y)?);
+        }
+        _ => return Err("G1 point is not in affine form".to_string()),
+    }
+    Ok(bytes)
+}
+
+/// `[[x0, x1], [y0, y1], z]` -> `x1 | x0 | y1 | y0`: the syscalls (like
+/// EIP-197) take the imaginary part of each Fq2 coordinate first
+fn snarkjs_g2(point: &[Vec<String>]) -> Result<[u8; 128], String> {
+    let [x, y, z] = point else {
+        return Err(format!("G2 point has {} coordinates, expected 3", point.len()));
+    };
+    let mut bytes = [0u8; 128];
+    match z.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["0", "0"] => {}
+        ["1", "0"] => {
+            for (i, part) in [&x[..], &y[..]].into_iter().enumerate() {
+                let [real, imaginary] = part else {
+                    return Err("G2 coordinate is not a pair".to_string());
+                };
+                bytes[i * 64..i * 64 + 32].copy_from_slice(&field_element(imaginary)?);
+                bytes[i * 64 + 32..i * 64 + 64].copy_from_slice(&field_element(real)?);
+            }
+        }
+        _ => return Err("G2 point is not in affine form".to_string()),
+    }
+    Ok(bytes)
+}
+
+/// Big-endian bytes of a field element written in decimal (snarkjs) or 0x-hex
+fn field_element(value: &str) -> Result<[u8; 32], String> {
+    let mut bytes = [0u8; 32];
+    if let Some(encoded) = value.strip_prefix("0x") {
+        let decoded = hex::decode(encoded).map_err(|e| e.to_string())?;
+        if decoded.len() > 32 {
+            return Err(format!("{} does not fit 32 bytes", value));
+        }
+        bytes[32 - decoded.len()..].copy_from_slice(&decoded);
+        return Ok(bytes);
+    }
+
+    if value.is_empty() || !value.bytes().all(|digit| digit.is_ascii_digit()) {
+        return Err(format!("{} is not a decimal field element", value));
+    }
+    for digit in value.bytes() {
+        let mut carry = (digit - b'0') as u32;
+        for byte in bytes.iter_mut().rev() {
+            let product = *byte as u32 * 10 + carry;
+            *byte = product as u8;
+            carry = product >> 8;
+        }
+        if carry != 0 {
+            return Err(format!("{} does not fit 32 bytes", value));
+        }
+    }
+    Ok(bytes)
+}
+
+/// Serde helpers encoding fixed-size byte arrays as hex strings
+mod hex_array {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, const N: usize>(bytes: &[u8; N], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error> {
+        let s = String::deserialize(deserializer)?;
+        decode(&s).map_err(D::Error::custom)
+    }
+
+    pub fn decode<const N: usize>(s: &str) -> Result<[u8; N], String> {
+        let bytes = hex::decode(s.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+        bytes
+            .try_into()
+            .map_err(|b: Vec<u8>| format!("expected {} bytes, got {}", N, b.len()))
+    }
+}
+
+mod hex_array_vec {
+    use serde::{de::Error, ser::SerializeSeq, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(items: &[[u8; 32]], serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(items.len()))?;
+        for item in items {
+            seq.serialize_element(&hex::encode(item))?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<[u8; 32]>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|s| super::hex_array::decode(s).map_err(D::Error::custom))
+            .collect()
+    }
+}
@@ -1,19 +1,35 @@
 use crate::{OrchestratorError, Result};
+use crate::circuit_grpc::{is_grpc_url, CircuitGrpcClient};
+use crate::database::DatabaseService;
+use crate::proof::{Proof, CURRENT_CIRCUIT_VERSION};
+use crate::prover_selection::{ProverHealth, ProverSelector};
+use crate::public_inputs::DepositInputs;
+use crate::ton_transactions::TonTransactionValidator;
 use serde_json::json;
-use std::time::Duration;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 
 
 #[derive(Clone)]  
 pub struct ProofOrchestrator {
-    circuit_service_url: String,
+    provers: ProverSelector,
     client: reqwest::Client,
+    mock_proving: bool,
+    quorum: usize, // Services that must prove the same public inputs for a deposit
+    database: Option<DatabaseService>, // Caches proofs and records asynchronous proof jobs
+    job_poll_interval: Duration,
+    job_timeout: Duration,
+    anchor_root: Arc<RwLock<Option<[u8; 32]>>>, // TON root new proofs are requested against
+    ton_validator: Option<TonTransactionValidator>,
 }
 
 impl ProofOrchestrator {
-    pub fn new(validators: Vec<String>, _validator_count: usize) -> Self {
-        let circuit_service_url = validators.first()
-            .cloned()
-            .unwrap_or_else(|| "http://localhost:8080".to_string());
+    pub fn new(validators: Vec<String>, validator_count: usize, mock_proving: bool) -> Self {
+        let circuit_service_urls = if validators.is_empty() {
+            vec!["http://localhost:8080".to_string()]
+        } else {
+            validators
+        };
 
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(30))
@@ -21,27 +37,337 @@ impl ProofOrchestrator {
             .unwrap();
 
         Self {
-            circuit_service_url,
+            provers: ProverSelector::new(circuit_service_urls),
             client,
+            mock_proving,
+            quorum: validator_count.max(1),
+            database: None,
+            job_poll_interval: Duration::from_secs(5),
+            job_timeout: Duration::from_secs(1800),
+            anchor_root: Arc::new(RwLock::new(None)),
+            ton_validator: None,
         }
     }
 
-    pub async fn generate_proof(&self, deposit: &crate::Deposit) -> Result<String> {
+    /// Cache proofs and record asynchronous proof jobs in `database`, polling
+    /// each job every `poll_interval` for up to `timeout` from its creation
+    pub fn with_database(mut self, database: DatabaseService, poll_interval: Duration, timeout: Duration) -> Self {
+        self.database = Some(database);
+        self.job_poll_interval = poll_interval;
+        self.job_timeout = timeout;
+        self
+    }
+
+    /// Check each deposit against its TON transaction before proving it
+    pub fn with_ton_validator(mut self, validator: TonTransactionValidator) -> Self {
+        self.ton_validator = Some(validator);
+        self
+    }
+
+    /// Request new proofs against `root`, the TON root the program holds now.
+    /// Until set, circuit services pick the root themselves.
+    pub fn set_anchor_root(&self, root: [u8; 32]) {
+        *self.anchor_root.write().unwrap() = Some(root);
+    }
+
+    fn deposit_inputs(&self, deposit: &crate::Deposit) -> Result<DepositInputs> {
+        let mut inputs = DepositInputs::from_deposit(deposit)?;
+        inputs.anchor_root = *self.anchor_root.read().unwrap();
+        Ok(inputs)
+    }
+
+    /// Rolling proving time per circuit service
+    pub fn prover_latencies(&self) -> Vec<(String, Option<f64>)> {
+        self.provers.latencies()
+    }
+
+    /// Backoff state and proving time per circuit service
+    pub fn prover_health(&self) -> Vec<ProverHealth> {
+        self.provers.health()
+    }
+
+    /// Ask every HTTP circuit service for its queue depth, which the
+    /// scheduler weighs with proving time. Unreachable services are left to
+    /// the failure backoff of actual jobs.
+    pub async fn refresh_prover_status(&self) {
+        let checks = (0..self.provers.service_count())
+            .filter(|&prover| !is_grpc_url(self.provers.service_url(prover)))
+            .map(|prover| async move { (prover, self.fetch_queue_depth(self.provers.service_url(prover)).await) });
+        for (prover, depth) in futures_util::future::join_all(checks).await {
+            match depth {
+                Ok(depth) => self.provers.record_status(prover, depth),
+                Err(e) => log::debug!("No status from circuit service {}: {}", self.provers.service_url(prover), e),
+            }
+        }
+    }
+
+    async fn fetch_queue_depth(&self, service_url: &str) -> Result<u64> {
+        let status: serde_json::Value = self.client
+            .get(&format!("{}/status", service_url))
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        status["queueDepth"]
+            .as_u64()
+            .or_else(|| status["queue_depth"].as_u64())
+            .ok_or_else(|| OrchestratorError::ConfigurationError(format!("{}/status has no queueDepth", service_url)))
+    }
+
+    pub async fn generate_proof(&self, deposit: &crate::Deposit) -> Result<Proof> {
         log::info!("Generating proof for deposit: {}", deposit.deposit_id);
 
         let proof_request = json!({
-            "publicInputs": [
-                deposit.deposit_id,
-                deposit.ton_tx_hash,
-                deposit.sender_address,
-                deposit.recipient_solana,
-                deposit.amount
-            ]
+            "publicInputs": self.deposit_inputs(deposit)?.to_request()
         });
 
+        // A re-submitted deposit or a batch retried after a crash asks for the same
+        // inputs again; a proof against a rotated-out root is keyed apart by its anchor
+        let cache_key = solana_sdk::hash::hash(&serde_json::to_vec(&proof_request)?).to_string();
+        if let Some(proof) = self.cached_proof(&cache_key).await? {
+            log::info!("♻️ Reusing cached proof for deposit {}", deposit.deposit_id);
+            return Ok(proof);
+        }
+
+        // A deposit its TON transaction doesn't back would only yield a useless proof
+        if let Some(validator) = &self.ton_validator {
+            validator.validate(deposit).await?;
+        }
+
+        let proof = if self.quorum > 1 {
+            self.prove_with_quorum(deposit, &proof_request).await?
+        } else {
+            self.prove_with_failover(deposit, &proof_request).await?
+        };
+
+        // Mock proofs must never be served once real proving is switched on
+        if let (Some(database), false) = (&self.database, self.mock_proving) {
+            database.cache_proof(&cache_key, &deposit.deposit_id, &serde_json::to_string(&proof)?).await?;
+        }
+        Ok(proof)
+    }
+
+    /// Proof cached for `cache_key`, if it is for the current circuit
+    async fn cached_proof(&self, cache_key: &str) -> Result<Option<Proof>> {
+        let Some(database) = &self.database else {
+            return Ok(None);
+        };
+        let Some(cached) = database.get_cached_proof(cache_key).await? else {
+            return Ok(None);
+        };
+        let proof: Proof = serde_json::from_str(&cached.proof)?;
+        Ok((proof.circuit_version == CURRENT_CIRCUIT_VERSION).then_some(proof))
+    }
+
+    /// Prove on the best service, failing over to each other service in turn
+    /// so one being down does not fail the deposit
+    async fn prove_with_failover(
+        &self,
+        deposit: &crate::Deposit,
+        proof_request: &serde_json::Value,
+    ) -> Result<Proof> {
+        let mut tried = Vec::new();
+        let mut prover = self.provers.select();
+        loop {
+            let error = match self.prove_on(prover, deposit, proof_request).await {
+                Ok(proof) => return Ok(proof),
+                Err(e) => e,
+            };
+            tried.push(prover);
+            let Some(next) = self.provers.select_excluding(&tried) else {
+                return Err(error);
+            };
+            log::warn!(
+                "Circuit service {} failed for deposit {} ({}), failing over to {}",
+                self.provers.service_url(prover),
+                deposit.deposit_id,
+                error,
+                self.provers.service_url(next)
+            );
+            prover = next;
+        }
+    }
+
+    /// Have every circuit service prove the deposit and accept a proof once
+    /// `quorum` of them agree on its public inputs. Groth16 proofs are
+    /// randomized, so the inputs they commit to are what is compared.
+    async fn prove_with_quorum(&self, deposit: &crate::Deposit, proof_request: &serde_json::Value) -> Result<Proof> {
+        let attempts = futures_util::future::join_all(
+            (0..self.provers.service_count()).map(|prover| self.prove_on(prover, deposit, proof_request)),
+        )
+        .await;
+
+        let mut candidates: Vec<(Proof, usize)> = Vec::new();
+        for (prover, attempt) in attempts.into_iter().enumerate() {
+            let proof = match attempt {
+                Ok(proof) => proof,
+                Err(e) => {
+                    log::warn!(
+                        "Circuit service {} could not prove deposit {}: {}",
+                        self.provers.service_url(prover),
+                        deposit.deposit_id,
+                        e
+                    );
+                    continue;
+                }
+            };
+            let agreeing = candidates.iter_mut().find(|(candidate, _)| {
+                candidate.public_inputs == proof.public_inputs && candidate.circuit_version == proof.circuit_version
+            });
+            match agreeing {
+                Some((_, votes)) => *votes += 1,
+                None => candidates.push((proof, 1)),
+            }
+        }
+        if candidates.len() > 1 {
+            log::warn!("⚠️ Circuit services disagree on the public inputs of deposit {}", deposit.deposit_id);
+        }
+
+        match candidates.into_iter().max_by_key(|(_, votes)| *votes) {
+            Some((proof, votes)) if votes >= self.quorum => Ok(proof),
+            best => Err(OrchestratorError::InsufficientSignatures {
+                current: best.map_or(0, |(_, votes)| votes),
+                required: self.quorum,
+            }),
+        }
+    }
+
+    async fn prove_on(
+        &self,
+        prover: usize,
+        deposit: &crate::Deposit,
+        proof_request: &serde_json::Value,
+    ) -> Result<Proof> {
+        let started = Instant::now();
+        let service_url = self.provers.service_url(prover);
+        self.provers.start_job(prover);
+        let requested = if is_grpc_url(service_url) {
+            self.request_proof_grpc(service_url, proof_request).await
+        } else {
+            self.request_proof(service_url, &deposit.deposit_id, proof_request).await
+        };
+        let proof_data = match requested {
+            Ok(data) => {
+                self.provers.record_success(prover, started.elapsed());
+                data
+            }
+            Err(e) => {
+                self.provers.record_failure(prover);
+                return Err(e);
+            }
+        };
+
+        let proof = match Proof::from_service_response(&proof_data) {
+            Ok(proof) => proof,
+            Err(_) if self.mock_proving => {
+                return Ok(Proof::mock(self.deposit_inputs(deposit)?.circuit_scalars()))
+            }
+            Err(e) => {
+                return Err(OrchestratorError::InvalidProof(format!(
+                    "malformed proof for deposit {}: {}",
+                    deposit.deposit_id, e
+                )))
+            }
+        };
+        if !self.mock_proving {
+            proof.check_encoding().map_err(|e| {
+                OrchestratorError::InvalidProof(format!("proof for deposit {}: {}", deposit.deposit_id, e))
+            })?;
+        }
+        Ok(proof)
+    }
+
+    /// Ask `service_url` for a proof. A service answering with a `jobId`
+    /// instead proves asynchronously: the job is recorded, so a restart resumes
+    /// polling it rather than proving again, and polled until it settles.
+    async fn request_proof(
+        &self,
+        service_url: &str,
+        deposit_id: &str,
+        proof_request: &serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        let job = match &self.database {
+            Some(database) => database.get_proof_job(deposit_id, service_url).await?,
+            None => None,
+        };
+        let (job_id, created_at) = match job {
+            Some(job) => {
+                log::info!("⏳ Resuming proof job {} for deposit {} on {}", job.job_id, deposit_id, service_url);
+                (job.job_id, job.created_at)
+            }
+            None => {
+                let response = self.post_proof_request(service_url, proof_request).await?;
+                let job_id = match response["jobId"].as_str() {
+                    Some(job_id) if response.get("proof").is_none() => job_id.to_string(),
+                    _ => return Ok(response),
+                };
+                if let Some(database) = &self.database {
+                    database.save_proof_job(deposit_id, service_url, &job_id).await?;
+                }
+                log::info!("⏳ Proof job {} started for deposit {} on {}", job_id, deposit_id, service_url);
+                (job_id, chrono::Utc::now().timestamp())
+            }
+        };
+
+        let settled = self.poll_proof_job(service_url, &job_id, created_at).await;
+        if let Some(database) = &self.database {
+            database.delete_proof_job(deposit_id, service_url).await?;
+        }
+        settled
+    }
+
+    /// Ask a gRPC `service_url` for a proof. The call streams for as long as
+    /// proving takes, so unlike HTTP there is no job to record and resume.
+    async fn request_proof_grpc(&self, service_url: &str, proof_request: &serde_json::Value) -> Result<serde_json::Value> {
+        let mut client = CircuitGrpcClient::connect(service_url).await?;
+        client.prove(proof_request, self.job_timeout).await
+    }
+
+    /// Poll a job until it completes, fails, or outlives the job timeout.
+    /// Unreachable status checks are retried: the job keeps running meanwhile.
+    async fn poll_proof_job(&self, service_url: &str, job_id: &str, created_at: i64) -> Result<serde_json::Value> {
+        let deadline = created_at + self.job_timeout.as_secs() as i64;
+        loop {
+            match self.fetch_job_status(service_url, job_id).await {
+                Ok(status) => match status["status"].as_str() {
+                    Some("completed") => return Ok(status),
+                    Some("failed") => {
+                        return Err(OrchestratorError::InvalidProof(format!(
+                            "proof job {} failed: {}",
+                            job_id,
+                            status["error"].as_str().unwrap_or("no reason given")
+                        )))
+                    }
+                    _ => {} // Queued or running
+                },
+                Err(e) => log::warn!("Could not check proof job {} on {}: {}", job_id, service_url, e),
+            }
+
+            if chrono::Utc::now().timestamp() >= deadline {
+                return Err(OrchestratorError::ProofTimeout {
+                    job_id: job_id.to_string(),
+                    secs: self.job_timeout.as_secs(),
+                });
+            }
+            tokio::time::sleep(self.job_poll_interval).await;
+        }
+    }
+
+    async fn fetch_job_status(&self, service_url: &str, job_id: &str) -> Result<serde_json::Value> {
         let response = self.client
-            .post(&format!("{}/generate-proof", self.circuit_service_url))
-            .json(&proof_request)
+            .get(&format!("{}/proof-status/{}", service_url, job_id))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(response.json().await?)
+    }
+
+    async fn post_proof_request(&self, service_url: &str, proof_request: &serde_json::Value) -> Result<serde_json::Value> {
+        let response = self.client
+            .post(&format!("{}/generate-proof", service_url))
+            .json(proof_request)
             .send()
             .await
             .map_err(OrchestratorError::NetworkError)?;
@@ -52,13 +378,8 @@ impl ProofOrchestrator {
             ));
         }
 
-        let proof_data: serde_json::Value = response.json()
+        response.json()
             .await
-            .map_err(OrchestratorError::NetworkError)?;
-
-        Ok(proof_data["proof"]
-            .as_str()
-            .unwrap_or("mock_proof")
-            .to_string())
+            .map_err(OrchestratorError::NetworkError)
     }
 }
\ No newline at end of file
@@ -0,0 +1,166 @@
+use crate::proof::{Proof, BN254_FIELD_MODULUS, CURRENT_CIRCUIT_VERSION};
+use crate::public_inputs::DepositInputs;
+use crate::types::Deposit;
+use crate::{OrchestratorError, Result};
+use solana_program::alt_bn128::prelude::{alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing};
+
+/// Groth16 key in the byte layout uploaded to the program's `VerifyingKey`
+/// account: alpha (G1) | beta, gamma, delta (G2) | IC count (u32 LE) | IC (G1)
+#[derive(Debug, Clone)]
+struct Groth16Key {
+    alpha_g1: [u8; 64],
+    beta_g2: [u8; 128],
+    gamma_g2: [u8; 128],
+    delta_g2: [u8; 128],
+    ic: Vec<[u8; 64]>,
+}
+
+impl Groth16Key {
+    const HEADER_LEN: usize = 64 + 128 * 3 + 4;
+
+    fn parse(data: &[u8]) -> std::result::Result<Self, String> {
+        if data.len() < Self::HEADER_LEN {
+            return Err(format!("{} bytes is shorter than the key header", data.len()));
+        }
+        let ic_len = u32::from_le_bytes(data[448..452].try_into().unwrap()) as usize;
+        if ic_len == 0 || data.len() != Self::HEADER_LEN + ic_len * 64 {
+            return Err(format!("{} bytes does not hold {} IC points", data.len(), ic_len));
+        }
+
+        Ok(Self {
+            alpha_g1: data[0..64].try_into().unwrap(),
+            beta_g2: data[64..192].try_into().unwrap(),
+            gamma_g2: data[192..320].try_into().unwrap(),
+            delta_g2: data[320..448].try_into().unwrap(),
+            ic: data[Self::HEADER_LEN..]
+                .chunks_exact(64)
+                .map(|point| point.try_into().unwrap())
+                .collect(),
+        })
+    }
+}
+
+/// Checks proofs generated outside the bridge's circuit services before their
+/// deposits are batched, with the same pairing check the program runs
+#[derive(Debug, Clone)]
+pub struct ProofVerifier {
+    key: Option<Groth16Key>,
+    mock_proving: bool,
+}
+
+impl ProofVerifier {
+    /// Load the key from `path`, raw bytes or hex. Without a usable key,
+    /// externally submitted proofs are refused unless mock proving is on.
+    pub fn load(path: &str, mock_proving: bool) -> Self {
+        let key = match std::fs::read(path) {
+            Ok(bytes) => {
+                let text = String::from_utf8_lossy(&bytes);
+                let bytes = hex::decode(text.trim().trim_start_matches("0x")).unwrap_or(bytes);
+                match Groth16Key::parse(&bytes) {
+                    Ok(key) => Some(key),
+                    Err(e) => {
+                        log::warn!("⚠️  Verification key {} unusable, external proofs disabled: {}", path, e);
+                        None
+                    }
+                }
+            }
+            Err(e) => {
+                log::warn!("⚠️  Verification key {} not readable, external proofs disabled: {}", path, e);
+                None
+            }
+        };
+
+        Self { key, mock_proving }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.key.is_some() || self.mock_proving
+    }
+
+    /// Check that `proof` was generated for `deposit` and verifies against the key
+    pub fn verify(&self, deposit: &Deposit, proof: &Proof) -> Result<()> {
+        if proof.circuit_version != CURRENT_CIRCUIT_VERSION {
+            return Err(OrchestratorError::InvalidProof(format!(
+                "circuit version {} is not the current version {}",
+                proof.circuit_version, CURRENT_CIRCUIT_VERSION
+            )));
+        }
+        DepositInputs::from_deposit(deposit)?.check(&deposit.deposit_id, &proof.public_inputs)?;
+
+        match &self.key {
+            Some(key) => Self::verify_groth16(key, proof),
+            None if self.mock_proving => {
+                log::warn!("⚠️  MOCK proof verification for deposit {}", deposit.deposit_id);
+                Ok(())
+            }
+            None => Err(OrchestratorError::ConfigurationError(
+                "no verification key loaded for external proofs".to_string(),
+            )),
+        }
+    }
+
+    /// `e(-A, B) · e(alpha, beta) · e(vk_x, gamma) · e(C, delta) == 1`
+    fn verify_groth16(key: &Groth16Key, proof: &Proof) -> Result<()> {
+        let bn128_error = |e| OrchestratorError::InvalidProof(format!("alt_bn128 operation failed: {:?}", e));
+        if key.ic.len() != proof.public_inputs.len() + 1 {
+            return Err(OrchestratorError::InvalidProof(format!(
+                "verification key expects {} public inputs, proof has {}",
+                key.ic.len() - 1,
+                proof.public_inputs.len()
+            )));
+        }
+
+        // vk_x = IC[0] + sum(x_i * IC[i+1])
+        let mut vk_x = key.ic[0].to_vec();
+        for (scalar, ic) in proof.public_inputs.iter().zip(key.ic[1..].iter()) {
+            let mut mul_input = [0u8; 96];
+            mul_input[..64].copy_from_slice(ic);
+            mul_input[64..].copy_from_slice(scalar);
+            let term = alt_bn128_multiplication(&mul_input).map_err(bn128_error)?;
+
+            let mut add_input = [0u8; 128];
+            add_input[..64].copy_from_slice(&vk_x);
+            add_input[64..].copy_from_slice(&term);
+            vk_x = alt_bn128_addition(&add_input).map_err(bn128_error)?;
+        }
+
+        let mut pairing_input = Vec::with_capacity(4 * 192);
+        pairing_input.extend_from_slice(&negate_g1(&proof.a));
+        pairing_input.extend_from_slice(&proof.b);
+        pairing_input.extend_from_slice(&key.alpha_g1);
+        pairing_input.extend_from_slice(&key.beta_g2);
+        pairing_input.extend_from_slice(&vk_x);
+        pairing_input.extend_from_slice(&key.gamma_g2);
+        pairing_input.extend_from_slice(&proof.c);
+        pairing_input.extend_from_slice(&key.delta_g2);
+
+        let result = alt_bn128_pairing(&pairing_input).map_err(bn128_error)?;
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        if result.as_slice() != one {
+            return Err(OrchestratorError::InvalidProof("pairing check failed".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// (x, y) -> (x, p - y); the point at infinity is its own negation
+fn negate_g1(point: &[u8; 64]) -> [u8; 64] {
+    let mut negated = *point;
+    if point.iter().all(|&b| b == 0) {
+        return negated;
+    }
+
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let diff = BN254_FIELD_MODULUS[i] as i16 - point[32 + i] as i16 - borrow;
+        if diff < 0 {
+            negated[32 + i] = (diff + 256) as u8;
+            borrow = 1;
+        } else {
+            negated[32 + i] = diff as u8;
+            borrow = 0;
+        }
+    }
+    negated
+}
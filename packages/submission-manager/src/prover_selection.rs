@@ -0,0 +1,211 @@
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Weight of the newest sample in the rolling proving time
+const EWMA_ALPHA: f64 = 0.2;
+/// Scores are scaled by up to this much noise so a slightly slower service
+/// still gets occasional jobs and its measurement doesn't go stale
+const SELECTION_JITTER: f64 = 0.15;
+/// A service not measured for this long is tried again regardless of score
+const STALE_AFTER: Duration = Duration::from_secs(300);
+/// A failing service is skipped for this long, doubling per consecutive failure
+const BACKOFF_BASE: Duration = Duration::from_secs(5);
+const BACKOFF_MAX: Duration = Duration::from_secs(300);
+/// A queue depth reported by a service's `/status` is trusted for this long
+const STATUS_MAX_AGE: Duration = Duration::from_secs(90);
+
+#[derive(Debug, Default, Clone)]
+struct ServiceStats {
+    avg_proving_ms: Option<f64>,
+    last_measured: Option<Instant>,
+    consecutive_failures: u32,
+    cooldown_until: Option<Instant>,
+    queue_depth: Option<u64>, // Jobs queued or running as the service last reported
+    status_at: Option<Instant>,
+    in_flight: u32, // Jobs this manager has outstanding on the service
+}
+
+impl ServiceStats {
+    fn is_healthy(&self, now: Instant) -> bool {
+        self.cooldown_until.map_or(true, |until| now >= until)
+    }
+
+    fn is_stale(&self, now: Instant) -> bool {
+        self.last_measured.map_or(true, |at| now.duration_since(at) >= STALE_AFTER)
+    }
+
+    /// Jobs ahead of a new one: the reported queue while it is fresh, and at
+    /// least those this manager knows it has outstanding
+    fn pending(&self, now: Instant) -> u64 {
+        let reported = match self.status_at {
+            Some(at) if now.duration_since(at) < STATUS_MAX_AGE => self.queue_depth.unwrap_or(0),
+            _ => 0,
+        };
+        reported.max(self.in_flight as u64)
+    }
+
+    /// Expected time until a new job's proof is back: the rolling proving
+    /// time for it and every job ahead of it
+    fn expected_wait_ms(&self, now: Instant) -> Option<f64> {
+        self.avg_proving_ms.map(|avg| avg * (1 + self.pending(now)) as f64)
+    }
+}
+
+/// One circuit service as reported by health and metrics
+#[derive(Debug, Clone, Serialize)]
+pub struct ProverHealth {
+    pub url: String,
+    pub healthy: bool, // Not backing off after failures
+    pub consecutive_failures: u32,
+    pub avg_proving_ms: Option<f64>,
+    pub retry_in_secs: Option<u64>, // Until the backoff ends
+    pub queue_depth: Option<u64>,   // As last reported on `/status`
+    pub in_flight: u32,
+    pub expected_wait_ms: Option<f64>, // What the scheduler ranks services by
+}
+
+/// Routes proof jobs to the circuit service expected to return a proof
+/// soonest, given its rolling proving time and queue depth, skipping
+/// services that keep failing
+#[derive(Clone)]
+pub struct ProverSelector {
+    services: Vec<String>,
+    stats: Arc<Mutex<Vec<ServiceStats>>>,
+}
+
+impl ProverSelector {
+    pub fn new(services: Vec<String>) -> Self {
+        let stats = vec![ServiceStats::default(); services.len()];
+        Self {
+            services,
+            stats: Arc::new(Mutex::new(stats)),
+        }
+    }
+
+    pub fn service_count(&self) -> usize {
+        self.services.len()
+    }
+
+    pub fn service_url(&self, index: usize) -> &str {
+        &self.services[index]
+    }
+
+    /// Index of the service the next job should go to
+    pub fn select(&self) -> usize {
+        self.select_excluding(&[]).unwrap_or(0)
+    }
+
+    /// Best service not in `tried`, for failing a job over; `None` once every
+    /// service was tried
+    pub fn select_excluding(&self, tried: &[usize]) -> Option<usize> {
+        let untried: Vec<usize> = (0..self.services.len()).filter(|i| !tried.contains(i)).collect();
+        if untried.len() <= 1 {
+            return untried.first().copied();
+        }
+
+        let now = Instant::now();
+        let stats = self.stats.lock().unwrap();
+        let healthy: Vec<usize> = untried.iter().copied().filter(|&i| stats[i].is_healthy(now)).collect();
+        // With every service backing off, keep trying rather than stall the pipeline
+        let candidates: Vec<usize> = if healthy.is_empty() { untried } else { healthy };
+
+        if let Some(&stale) = candidates.iter().find(|&&i| stats[i].is_stale(now)) {
+            return Some(stale);
+        }
+
+        candidates
+            .into_iter()
+            .map(|i| {
+                let wait = stats[i].expected_wait_ms(now).unwrap_or(0.0);
+                (i, wait * (1.0 + SELECTION_JITTER * jitter_sample(i)))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+
+    /// A job was sent to the service; `record_success` or `record_failure` ends it
+    pub fn start_job(&self, index: usize) {
+        self.stats.lock().unwrap()[index].in_flight += 1;
+    }
+
+    /// Queue depth the service reported on `/status`
+    pub fn record_status(&self, index: usize, queue_depth: u64) {
+        let mut stats = self.stats.lock().unwrap();
+        stats[index].queue_depth = Some(queue_depth);
+        stats[index].status_at = Some(Instant::now());
+    }
+
+    pub fn record_success(&self, index: usize, elapsed: Duration) {
+        let mut stats = self.stats.lock().unwrap();
+        let entry = &mut stats[index];
+        entry.in_flight = entry.in_flight.saturating_sub(1);
+        let sample = elapsed.as_secs_f64() * 1000.0;
+        entry.avg_proving_ms = Some(match entry.avg_proving_ms {
+            Some(avg) => avg + EWMA_ALPHA * (sample - avg),
+            None => sample,
+        });
+        entry.last_measured = Some(Instant::now());
+        entry.consecutive_failures = 0;
+        entry.cooldown_until = None;
+    }
+
+    pub fn record_failure(&self, index: usize) {
+        let mut stats = self.stats.lock().unwrap();
+        let entry = &mut stats[index];
+        entry.in_flight = entry.in_flight.saturating_sub(1);
+        entry.consecutive_failures += 1;
+        let backoff = BACKOFF_BASE
+            .saturating_mul(1 << (entry.consecutive_failures - 1).min(16))
+            .min(BACKOFF_MAX);
+        entry.cooldown_until = Some(Instant::now() + backoff);
+        log::warn!(
+            "Circuit service {} failed {} times in a row, backing off for {}s",
+            self.services[index],
+            entry.consecutive_failures,
+            backoff.as_secs()
+        );
+    }
+
+    pub fn health(&self) -> Vec<ProverHealth> {
+        let now = Instant::now();
+        let stats = self.stats.lock().unwrap();
+        self.services
+            .iter()
+            .zip(stats.iter())
+            .map(|(url, stats)| ProverHealth {
+                url: url.clone(),
+                healthy: stats.is_healthy(now),
+                consecutive_failures: stats.consecutive_failures,
+                avg_proving_ms: stats.avg_proving_ms,
+                retry_in_secs: stats
+                    .cooldown_until
+                    .filter(|until| *until > now)
+                    .map(|until| until.duration_since(now).as_secs()),
+                queue_depth: stats.queue_depth,
+                in_flight: stats.in_flight,
+                expected_wait_ms: stats.expected_wait_ms(now),
+            })
+            .collect()
+    }
+
+    /// Rolling proving time per service in ms, `None` until first measured
+    pub fn latencies(&self) -> Vec<(String, Option<f64>)> {
+        let stats = self.stats.lock().unwrap();
+        self.services
+            .iter()
+            .cloned()
+            .zip(stats.iter().map(|s| s.avg_proving_ms))
+            .collect()
+    }
+}
+
+/// Cheap per-call noise in [0, 1); selection only needs it to be uneven, not random
+fn jitter_sample(salt: usize) -> f64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as u64)
+        .unwrap_or(0);
+    let mixed = (nanos ^ (salt as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    (mixed >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,203 @@
+use base64::Engine;
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use crate::types::Deposit;
+use crate::{OrchestratorError, Result};
+
+/// Public inputs per event proof, in the program's circuit order
+pub const EVENT_PUBLIC_INPUT_COUNT: usize = 11;
+// Positions in `zk_verifier::public_input_scalars`
+pub const INPUT_DOMAIN: usize = 0;
+pub const INPUT_ANCHOR_ROOT: usize = 1;
+pub const INPUT_EVENT_ID: usize = 2;
+pub const INPUT_TOKEN_ID: usize = 3;
+pub const INPUT_AMOUNT: usize = 4;
+pub const INPUT_RECIPIENT: usize = 5;
+pub const INPUT_FEE_BPS: usize = 6;
+pub const INPUT_VK_VERSION: usize = 7;
+pub const INPUT_TON_TX_HASH: usize = 8;
+pub const INPUT_TON_SENDER: usize = 9;
+pub const INPUT_NULLIFIER: usize = 10;
+
+/// Same encoding as the program: hashes lose their top three bits so they
+/// stay below the scalar field modulus
+pub fn hash_scalar(bytes: &[u8; 32]) -> [u8; 32] {
+    let mut scalar = *bytes;
+    scalar[0] &= 0x1f;
+    scalar
+}
+
+/// Integer public inputs are big-endian and left-padded
+pub fn int_scalar(value: u64) -> [u8; 32] {
+    let mut scalar = [0u8; 32];
+    scalar[24..].copy_from_slice(&value.to_be_bytes());
+    scalar
+}
+
+pub fn scalar_int(scalar: &[u8; 32]) -> u64 {
+    u64::from_be_bytes(scalar[24..].try_into().unwrap())
+}
+
+pub fn decode_hash(name: &str, value: &str) -> Result<[u8; 32]> {
+    hex::decode(value.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| OrchestratorError::InvalidProof(format!("deposit {} is not a 32-byte hex hash", name)))
+}
+
+/// `zk_verifier::generate_nullifier`: `sha256("NULLIFIER" || ton_tx_hash || ton_sender)`
+pub fn generate_nullifier(ton_tx_hash: &[u8; 32], ton_sender: &[u8; 32]) -> [u8; 32] {
+    let mut preimage = Vec::with_capacity(9 + 64);
+    preimage.extend_from_slice(b"NULLIFIER");
+    preimage.extend_from_slice(ton_tx_hash);
+    preimage.extend_from_slice(ton_sender);
+    solana_sdk::hash::hashv(&[&preimage]).to_bytes()
+}
+
+/// Account id of a TON address, in raw (`0:<hex>`) or user-friendly
+/// (base64, 36 bytes: flags | workchain | account id | crc16) form
+pub fn ton_account_id(address: &str) -> Option<[u8; 32]> {
+    if let Some((_, account)) = address.split_once(':') {
+        return decode_hash("ton_sender", account).ok();
+    }
+    let bytes = base64::engine::general_purpose::URL_SAFE
+        .decode(address)
+        .or_else(|_| base64::engine::general_purpose::STANDARD.decode(address))
+        .ok()?;
+    (bytes.len() == 36).then(|| bytes[2..34].try_into().unwrap())
+}
+
+/// Which of the `accepted` roots a proof's public `inputs` are anchored to;
+/// `None` once its root rotated out of the program's history
+pub fn proof_anchor(inputs: &[[u8; 32]], accepted: &[[u8; 32]]) -> Option<[u8; 32]> {
+    let anchor = inputs.get(INPUT_ANCHOR_ROOT)?;
+    accepted.iter().copied().find(|root| hash_scalar(root) == *anchor)
+}
+
+/// The public inputs a deposit determines on its own, plus the anchor root
+/// when the prover was told which one to prove against. Domain, token id, fee
+/// and verifying key version come from the program's state at proving time;
+/// sender and nullifier are `None` when the deposit's sender address cannot
+/// be decoded and the indexer did not supply them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepositInputs {
+    pub anchor_root: Option<[u8; 32]>,
+    pub event_id: Option<[u8; 32]>,
+    pub amount: u64,
+    pub recipient_binding: [u8; 32],
+    pub ton_tx_hash: [u8; 32],
+    pub ton_sender: Option<[u8; 32]>,
+    pub nullifier: Option<[u8; 32]>,
+}
+
+impl DepositInputs {
+    pub fn from_deposit(deposit: &Deposit) -> Result<Self> {
+        let amount = deposit.amount.parse().map_err(|_| {
+            OrchestratorError::InvalidProof(format!("deposit amount {} is not an integer", deposit.amount))
+        })?;
+        let ton_tx_hash = decode_hash("ton_tx_hash", &deposit.ton_tx_hash)?;
+        let ton_sender = ton_account_id(&deposit.sender_address);
+
+        // Privacy-mode deposits are proven against the recipient commitment only
+        let recipient_binding = match &deposit.recipient_commitment {
+            Some(commitment) => decode_hash("recipient_commitment", commitment)?,
+            None => Pubkey::from_str(&deposit.recipient_solana)
+                .map_err(|e| {
+                    OrchestratorError::InvalidProof(format!(
+                        "recipient {} is not a Solana address: {}",
+                        deposit.recipient_solana, e
+                    ))
+                })?
+                .to_bytes(),
+        };
+
+        let nullifier = match &deposit.nullifier {
+            Some(nullifier) => Some(decode_hash("nullifier", nullifier)?),
+            None => ton_sender.map(|sender| generate_nullifier(&ton_tx_hash, &sender)),
+        };
+
+        Ok(Self {
+            anchor_root: None,
+            event_id: deposit.event_id.as_deref().map(|id| decode_hash("event_id", id)).transpose()?,
+            amount,
+            recipient_binding,
+            ton_tx_hash,
+            ton_sender,
+            nullifier,
+        })
+    }
+
+    /// Known inputs as (circuit position, name, field element)
+    pub fn scalars(&self) -> Vec<(usize, &'static str, [u8; 32])> {
+        let mut scalars = vec![
+            (INPUT_AMOUNT, "amount", int_scalar(self.amount)),
+            (INPUT_RECIPIENT, "recipient_binding", hash_scalar(&self.recipient_binding)),
+            (INPUT_TON_TX_HASH, "ton_tx_hash", hash_scalar(&self.ton_tx_hash)),
+        ];
+        if let Some(anchor_root) = &self.anchor_root {
+            scalars.push((INPUT_ANCHOR_ROOT, "anchor_root", hash_scalar(anchor_root)));
+        }
+        if let Some(event_id) = &self.event_id {
+            scalars.push((INPUT_EVENT_ID, "event_id", hash_scalar(event_id)));
+        }
+        if let Some(sender) = &self.ton_sender {
+            scalars.push((INPUT_TON_SENDER, "ton_sender", hash_scalar(sender)));
+        }
+        if let Some(nullifier) = &self.nullifier {
+            scalars.push((INPUT_NULLIFIER, "nullifier", hash_scalar(nullifier)));
+        }
+        scalars.sort_by_key(|(index, _, _)| *index);
+        scalars
+    }
+
+    /// All inputs in circuit order, zero where the program's state supplies the value
+    pub fn circuit_scalars(&self) -> Vec<[u8; 32]> {
+        let mut inputs = vec![[0u8; 32]; EVENT_PUBLIC_INPUT_COUNT];
+        for (index, _, scalar) in self.scalars() {
+            inputs[index] = scalar;
+        }
+        inputs
+    }
+
+    /// `publicInputs` of a proof request: raw values the circuit hashes, and
+    /// the field elements it must expose for them
+    pub fn to_request(&self) -> serde_json::Value {
+        let hex = |bytes: &Option<[u8; 32]>| bytes.map(hex::encode);
+        json!({
+            "anchorRoot": hex(&self.anchor_root),
+            "eventId": hex(&self.event_id),
+            "amount": self.amount.to_string(),
+            "recipientBinding": hex::encode(self.recipient_binding),
+            "tonTxHash": hex::encode(self.ton_tx_hash),
+            "tonSender": hex(&self.ton_sender),
+            "nullifier": hex(&self.nullifier),
+            "scalars": self
+                .scalars()
+                .into_iter()
+                .map(|(index, name, scalar)| json!({"index": index, "name": name, "value": hex::encode(scalar)}))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    /// A proof's `inputs` must carry every field element this deposit determines
+    pub fn check(&self, deposit_id: &str, inputs: &[[u8; 32]]) -> Result<()> {
+        if inputs.len() != EVENT_PUBLIC_INPUT_COUNT {
+            return Err(OrchestratorError::InvalidProof(format!(
+                "expected {} public inputs for deposit {}, got {}",
+                EVENT_PUBLIC_INPUT_COUNT,
+                deposit_id,
+                inputs.len()
+            )));
+        }
+        for (index, name, scalar) in self.scalars() {
+            if inputs[index] != scalar {
+                return Err(OrchestratorError::InvalidProof(format!(
+                    "public input {} does not match deposit {}",
+                    name, deposit_id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
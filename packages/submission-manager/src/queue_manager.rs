@@ -13,11 +13,19 @@ impl QueueManager {
     }
 
     pub async fn enqueue_batch(&mut self, batch: Batch) {
-        self.batches.push(batch);
-        log::info!("Enqueuing batch with {} deposits", self.batches.last().unwrap().deposits.len());
+        self.push(batch);
     }
 
     pub async fn dequeue_batch(&mut self) -> Option<Batch> {
+        self.pop()
+    }
+
+    pub fn push(&mut self, batch: Batch) {
+        log::info!("Enqueuing batch with {} deposits", batch.deposits.len());
+        self.batches.push(batch);
+    }
+
+    pub fn pop(&mut self) -> Option<Batch> {
         if self.batches.is_empty() {
             None
         } else {
@@ -25,10 +33,28 @@ impl QueueManager {
         }
     }
 
+    pub fn peek_batch(&self) -> Option<&Batch> {
+        self.batches.first()
+    }
+
+    pub fn batches(&self) -> &[Batch] {
+        &self.batches
+    }
+
+    /// Replace the queue with batches restored from a snapshot, keeping any
+    /// batches queued since startup behind them
+    pub fn restore(&mut self, mut batches: Vec<Batch>) {
+        batches.append(&mut self.batches);
+        self.batches = batches;
+    }
+
     pub async fn get_queue_stats(&self) -> crate::types::QueueStats {
-        let total_batches = self.batches.len();
+        self.queue_stats()
+    }
+
+    pub fn queue_stats(&self) -> crate::types::QueueStats {
         let total_deposits: usize = self.batches.iter().map(|b| b.deposits.len()).sum();
-        
+
         crate::types::QueueStats {
             pending: total_deposits,
             processing: 0,
@@ -36,4 +62,4 @@ impl QueueManager {
             total: total_deposits,
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,61 @@
+use crate::types::Batch;
+use crate::{OrchestratorError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Format written by this manager. Additive changes (new optional fields)
+/// keep `MIN_READER_VERSION`; bump it only when older managers would
+/// misread a snapshot.
+pub const QUEUE_SNAPSHOT_VERSION: u32 = 1;
+pub const MIN_READER_VERSION: u32 = 1;
+
+/// Persisted copy of the submission queue. Unknown fields are ignored on
+/// decode so a snapshot written by a newer manager stays readable as long
+/// as its `min_reader_version` allows it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueueSnapshot {
+    pub version: u32,
+    pub min_reader_version: u32,
+    pub written_by: String,
+    pub batches: Vec<Batch>,
+}
+
+/// Version fields only, read before committing to a full decode
+#[derive(Deserialize)]
+struct SnapshotHeader {
+    version: u32,
+    #[serde(default)]
+    min_reader_version: Option<u32>,
+}
+
+pub fn encode(batches: &[Batch]) -> Result<String> {
+    let snapshot = QueueSnapshot {
+        version: QUEUE_SNAPSHOT_VERSION,
+        min_reader_version: MIN_READER_VERSION,
+        written_by: env!("CARGO_PKG_VERSION").to_string(),
+        batches: batches.to_vec(),
+    };
+    Ok(serde_json::to_string(&snapshot)?)
+}
+
+/// Decode a stored snapshot, refusing formats this manager can't safely read
+pub fn decode(raw: &str) -> Result<Vec<Batch>> {
+    let header: SnapshotHeader = serde_json::from_str(raw)?;
+    let min_reader_version = header.min_reader_version.unwrap_or(header.version);
+    if min_reader_version > QUEUE_SNAPSHOT_VERSION {
+        return Err(OrchestratorError::UnsupportedSnapshotVersion {
+            version: header.version,
+            min_reader_version,
+        });
+    }
+
+    let snapshot: QueueSnapshot = serde_json::from_str(raw)?;
+    if snapshot.version > QUEUE_SNAPSHOT_VERSION {
+        log::info!(
+            "Reading queue snapshot v{} written by manager {} (this manager writes v{})",
+            snapshot.version,
+            snapshot.written_by,
+            QUEUE_SNAPSHOT_VERSION
+        );
+    }
+    Ok(snapshot.batches)
+}
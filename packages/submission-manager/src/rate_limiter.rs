@@ -0,0 +1,65 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Base fee charged per transaction signature
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+const MINUTE: Duration = Duration::from_secs(60);
+const HOUR: Duration = Duration::from_secs(3600);
+
+/// Sliding-window caps on batch submissions and fee spend, checked before
+/// each batch is dequeued. A limit of zero disables that check.
+#[derive(Debug, Clone)]
+pub struct SubmissionRateLimiter {
+    max_batches_per_minute: u32,
+    max_lamports_per_hour: u64,
+    submissions: VecDeque<Instant>,
+    spend: VecDeque<(Instant, u64)>,
+}
+
+impl SubmissionRateLimiter {
+    pub fn new(max_batches_per_minute: u32, max_lamports_per_hour: u64) -> Self {
+        Self {
+            max_batches_per_minute,
+            max_lamports_per_hour,
+            submissions: VecDeque::new(),
+            spend: VecDeque::new(),
+        }
+    }
+
+    /// Whether a batch with the given estimated cost may be submitted now
+    pub fn allows(&mut self, estimated_lamports: u64) -> bool {
+        let now = Instant::now();
+        self.prune(now);
+
+        if self.max_batches_per_minute > 0 && self.submissions.len() as u32 >= self.max_batches_per_minute {
+            return false;
+        }
+
+        if self.max_lamports_per_hour > 0 && self.lamports_spent_last_hour() + estimated_lamports > self.max_lamports_per_hour {
+            return false;
+        }
+
+        true
+    }
+
+    /// Record a submission attempt against both windows
+    pub fn record(&mut self, lamports: u64) {
+        let now = Instant::now();
+        self.submissions.push_back(now);
+        self.spend.push_back((now, lamports));
+    }
+
+    pub fn lamports_spent_last_hour(&self) -> u64 {
+        self.spend.iter().map(|(_, lamports)| lamports).sum()
+    }
+
+    fn prune(&mut self, now: Instant) {
+        while matches!(self.submissions.front(), Some(t) if now.duration_since(*t) >= MINUTE) {
+            self.submissions.pop_front();
+        }
+        while matches!(self.spend.front(), Some((t, _)) if now.duration_since(*t) >= HOUR) {
+            self.spend.pop_front();
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use crate::database::{DatabaseService, RefundRecord};
+use crate::{OrchestratorError, Result};
+use serde_json::json;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Stable id of the refund of `deposit_id`, the same on every attempt, which
+/// the wallet service deduplicates refund requests by
+pub fn refund_id(deposit_id: &str) -> String {
+    hex::encode(solana_sdk::hash::hashv(&[b"zk-bridge-refund", deposit_id.as_bytes()]).to_bytes())
+}
+
+/// Thin client for the TON wallet service that signs and sends refund transfers
+#[derive(Clone)]
+pub struct TonWalletClient {
+    wallet_url: String,
+    client: reqwest::Client,
+}
+
+impl TonWalletClient {
+    pub fn new(wallet_url: &str) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        Self {
+            wallet_url: wallet_url.trim_end_matches('/').to_string(),
+            client,
+        }
+    }
+
+    /// Send the refund transfer and return the TON transaction hash. The
+    /// request carries the refund's idempotency key, so a retry of one the
+    /// wallet already sent returns that transfer instead of sending another.
+    pub async fn send_refund(&self, refund: &RefundRecord) -> Result<String> {
+        let refund_id = refund_id(&refund.deposit_id);
+        let request = json!({
+            "depositId": refund.deposit_id,
+            "refundId": refund_id,
+            "destination": refund.ton_destination,
+            "amount": refund.amount,
+            "comment": format!("zk-bridge refund: {}", refund.reason),
+        });
+
+        let response = self.client
+            .post(&format!("{}/refund", self.wallet_url))
+            .header("Idempotency-Key", &refund_id)
+            .json(&request)
+            .send()
+            .await
+            .map_err(OrchestratorError::NetworkError)?;
+
+        let response = response
+            .error_for_status()
+            .map_err(OrchestratorError::NetworkError)?;
+
+        let body: serde_json::Value = response.json()
+            .await
+            .map_err(OrchestratorError::NetworkError)?;
+
+        body["txHash"]
+            .as_str()
+            .map(|hash| hash.to_string())
+            .ok_or_else(|| OrchestratorError::RefundFailed {
+                deposit_id: refund.deposit_id.clone(),
+                reason: "wallet response missing txHash".to_string(),
+            })
+    }
+
+    /// TON transaction hash of the refund if the wallet service already sent
+    /// it; `None` if it has no record of the refund or has not sent it yet
+    pub async fn refund_status(&self, refund: &RefundRecord) -> Result<Option<String>> {
+        let response = self.client
+            .get(&format!("{}/refund/{}", self.wallet_url, refund_id(&refund.deposit_id)))
+            .send()
+            .await
+            .map_err(OrchestratorError::NetworkError)?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let response = response
+            .error_for_status()
+            .map_err(OrchestratorError::NetworkError)?;
+
+        let body: serde_json::Value = response.json()
+            .await
+            .map_err(OrchestratorError::NetworkError)?;
+
+        Ok(body["txHash"].as_str().map(|hash| hash.to_string()))
+    }
+}
+
+/// Drives refund tasks for terminally failed deposits through the TON wallet
+#[derive(Clone)]
+pub struct RefundEngine {
+    wallet: TonWalletClient,
+    max_attempts: u32,
+}
+
+impl RefundEngine {
+    pub fn new(wallet_url: &str, max_attempts: u32) -> Self {
+        Self {
+            wallet: TonWalletClient::new(wallet_url),
+            max_attempts,
+        }
+    }
+
+    /// Run every refund whose backoff has elapsed
+    pub async fn process_due_refunds(&self, database: &DatabaseService) -> Result<()> {
+        for refund in database.get_due_refunds().await? {
+            self.process_refund(database, refund).await?;
+        }
+        Ok(())
+    }
+
+    async fn process_refund(&self, database: &DatabaseService, refund: RefundRecord) -> Result<()> {
+        log::info!("💸 Refunding deposit {} to {}", refund.deposit_id, refund.ton_destination);
+
+        let outcome = match self.sent_earlier(&refund).await {
+            Ok(Some(tx_hash)) => {
+                log::info!("Refund for deposit {} was already sent by an earlier attempt", refund.deposit_id);
+                Ok(tx_hash)
+            }
+            Ok(None) => self.wallet.send_refund(&refund).await,
+            // Unknown whether it went out: sending again could pay twice
+            Err(e) => Err(e),
+        };
+        match outcome {
+            Ok(tx_hash) => {
+                database.complete_refund(&refund.deposit_id, &tx_hash).await?;
+                log::info!("✅ Deposit {} refunded on TON: {}", refund.deposit_id, tx_hash);
+            }
+            Err(e) => {
+                let attempts = refund.attempts as u32 + 1;
+                if attempts >= self.max_attempts {
+                    log::error!("❌ Refund for deposit {} gave up after {} attempts: {}", refund.deposit_id, attempts, e);
+                    database.record_refund_attempt(&refund.deposit_id, "failed", &e.to_string(), 0).await?;
+                    database.update_deposit_status(
+                        &refund.deposit_id,
+                        "refund_failed",
+                        Some(&format!("Refund failed after {} attempts: {}", attempts, e)),
+                    ).await?;
+                } else {
+                    let next_attempt_at = Self::now() + Self::backoff(attempts).as_secs() as i64;
+                    log::warn!("Refund for deposit {} failed (attempt {}), retrying later: {}", refund.deposit_id, attempts, e);
+                    database.record_refund_attempt(&refund.deposit_id, "pending", &e.to_string(), next_attempt_at).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Transfer an earlier attempt sent even though it reported failure, e.g. a
+    /// timeout or a 5xx after the wallet service had already acted on it
+    async fn sent_earlier(&self, refund: &RefundRecord) -> Result<Option<String>> {
+        if refund.attempts == 0 {
+            return Ok(None);
+        }
+        self.wallet.refund_status(refund).await
+    }
+
+    /// Exponential backoff starting at 30s, capped at one hour
+    fn backoff(attempts: u32) -> Duration {
+        let secs = 30u64.saturating_mul(1 << attempts.min(7));
+        Duration::from_secs(secs.min(3600))
+    }
+
+    fn now() -> i64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{OrchestratorError, Result};
+use solana_sdk::signature::Keypair;
+use std::path::{Path, PathBuf};
+
+/// Where the relayer keypair was loaded from, for the startup log
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeypairSource {
+    Base58,              // RELAYER_PRIVATE_KEY
+    File(PathBuf),       // RELAYER_KEYPAIR_PATH, or a path in SOLANA_KEYPAIR
+    Env,                 // SOLANA_KEYPAIR holding the key itself
+    CliConfig(PathBuf),  // keypair_path of the Solana CLI config, or its id.json default
+    Ephemeral,           // Mock proving only: a fresh, unfunded key
+}
+
+impl std::fmt::Display for KeypairSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeypairSource::Base58 => write!(f, "RELAYER_PRIVATE_KEY"),
+            KeypairSource::File(path) => write!(f, "{}", path.display()),
+            KeypairSource::Env => write!(f, "SOLANA_KEYPAIR"),
+            KeypairSource::CliConfig(path) => write!(f, "Solana CLI keypair {}", path.display()),
+            KeypairSource::Ephemeral => write!(f, "ephemeral key"),
+        }
+    }
+}
+
+/// Load the relayer keypair from the first source that is set: a base58 key,
+/// a keypair file, `SOLANA_KEYPAIR` (a path, a JSON byte array or base58), then
+/// the Solana CLI's keypair. A set source that can't be read is an error
+/// rather than a fall-through, so a typo never submits from another key.
+pub fn load(
+    private_key: Option<&str>,
+    keypair_path: Option<&str>,
+    solana_keypair: Option<&str>,
+    allow_ephemeral: bool,
+) -> Result<(Keypair, KeypairSource)> {
+    if let Some(key) = private_key.filter(|key| !key.trim().is_empty()) {
+        return Ok((parse_base58(key.trim(), "RELAYER_PRIVATE_KEY")?, KeypairSource::Base58));
+    }
+
+    if let Some(path) = keypair_path.filter(|path| !path.trim().is_empty()) {
+        let path = expand_home(path.trim());
+        return Ok((read_keypair_file(&path)?, KeypairSource::File(path)));
+    }
+
+    if let Some(value) = solana_keypair.map(str::trim).filter(|value| !value.is_empty()) {
+        if value.starts_with('[') {
+            return Ok((parse_json_bytes(value, "SOLANA_KEYPAIR")?, KeypairSource::Env));
+        }
+        let path = expand_home(value);
+        if path.exists() {
+            return Ok((read_keypair_file(&path)?, KeypairSource::File(path)));
+        }
+        return Ok((parse_base58(value, "SOLANA_KEYPAIR")?, KeypairSource::Env));
+    }
+
+    if let Some(path) = cli_keypair_path().filter(|path| path.exists()) {
+        return Ok((read_keypair_file(&path)?, KeypairSource::CliConfig(path)));
+    }
+
+    if allow_ephemeral {
+        log::warn!("⚠️  No relayer keypair configured, submitting from an ephemeral key (mock proving)");
+        return Ok((Keypair::new(), KeypairSource::Ephemeral));
+    }
+    Err(OrchestratorError::ConfigurationError(
+        "no relayer keypair: set RELAYER_PRIVATE_KEY, RELAYER_KEYPAIR_PATH or SOLANA_KEYPAIR, \
+         or configure the Solana CLI keypair"
+            .to_string(),
+    ))
+}
+
+/// `keypair_path` from `~/.config/solana/cli/config.yml`, else the CLI's default `id.json`
+fn cli_keypair_path() -> Option<PathBuf> {
+    let home = PathBuf::from(std::env::var_os("HOME")?);
+    let config_dir = home.join(".config").join("solana");
+
+    let configured = std::fs::read_to_string(config_dir.join("cli").join("config.yml"))
+        .ok()
+        .and_then(|config| {
+            config.lines().find_map(|line| {
+                let value = line.trim().strip_prefix("keypair_path:")?;
+                Some(value.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+            })
+        })
+        .filter(|path| !path.is_empty());
+
+    Some(match configured {
+        Some(path) => expand_home(&path),
+        None => config_dir.join("id.json"),
+    })
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Solana CLI keypair file: a JSON array of the 64 secret key bytes
+fn read_keypair_file(path: &Path) -> Result<Keypair> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        OrchestratorError::ConfigurationError(format!("cannot read keypair file {}: {}", path.display(), e))
+    })?;
+    parse_json_bytes(&contents, &path.display().to_string())
+}
+
+fn parse_json_bytes(json: &str, source: &str) -> Result<Keypair> {
+    let bytes: Vec<u8> = serde_json::from_str(json.trim()).map_err(|e| {
+        OrchestratorError::ConfigurationError(format!("{} is not a JSON keypair byte array: {}", source, e))
+    })?;
+    Keypair::from_bytes(&bytes)
+        .map_err(|e| OrchestratorError::ConfigurationError(format!("{} holds an invalid keypair: {}", source, e)))
+}
+
+fn parse_base58(key: &str, source: &str) -> Result<Keypair> {
+    let bytes = solana_sdk::bs58::decode(key)
+        .into_vec()
+        .map_err(|e| OrchestratorError::ConfigurationError(format!("{} is not base58: {}", source, e)))?;
+    Keypair::from_bytes(&bytes)
+        .map_err(|e| OrchestratorError::ConfigurationError(format!("{} holds an invalid keypair: {}", source, e)))
+}
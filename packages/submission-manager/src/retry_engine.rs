@@ -1,3 +1,5 @@
+use crate::OrchestratorError;
+
 pub struct RetryEngine {
     max_retries: usize,
 }
@@ -11,6 +13,11 @@ impl RetryEngine {
     pub fn should_retry(&self, current_retries: usize) -> bool {
         current_retries < self.max_retries
     }
+
+    /// Retry only transient failures, and only while attempts remain
+    pub fn should_retry_error(&self, current_retries: usize, error: &OrchestratorError) -> bool {
+        !error.is_permanent() && self.should_retry(current_retries)
+    }
 }
 
 impl Clone for RetryEngine {
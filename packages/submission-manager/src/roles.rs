@@ -0,0 +1,81 @@
+use crate::proof::Proof;
+use crate::types::Deposit;
+use crate::{OrchestratorError, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Handoff stage for accepted deposits waiting for a prover process
+pub const STAGE_PROVE: &str = "prove";
+/// Handoff stage for deposits whose proof was submitted externally and verified
+pub const STAGE_VERIFIED: &str = "verified";
+/// Handoff stage for sealed batches waiting for the submitter process
+pub const STAGE_SUBMIT: &str = "submit";
+
+/// Payload of a `STAGE_VERIFIED` item: the deposit with its checked proof
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvedDeposit {
+    pub deposit: Deposit,
+    pub proof: Proof,
+}
+
+/// Claims older than this are assumed to belong to a crashed process
+pub const HANDOFF_RECLAIM_SECS: u64 = 600;
+
+/// Which pipeline stages this process runs. Split roles share the SQLite
+/// database: the ingestion tier hands accepted deposits to the provers, and
+/// provers hand sealed batches to the submitter, through the
+/// `pipeline_handoff` table. Several `ingest` and `prove` processes can run
+/// side by side; `submit` processes coordinate through the submitter lease.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManagerRole {
+    Ingest,
+    Prove,
+    Submit,
+    All,
+}
+
+impl ManagerRole {
+    /// Validates and stores deposits received over HTTP
+    pub fn runs_ingest(self) -> bool {
+        matches!(self, ManagerRole::Ingest | ManagerRole::All)
+    }
+
+    /// Generates proofs, holds large transfers and seals batches
+    pub fn runs_prover(self) -> bool {
+        matches!(self, ManagerRole::Prove | ManagerRole::All)
+    }
+
+    /// Submits sealed batches to Solana and drives refunds
+    pub fn runs_submitter(self) -> bool {
+        matches!(self, ManagerRole::Submit | ManagerRole::All)
+    }
+}
+
+impl FromStr for ManagerRole {
+    type Err = OrchestratorError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "ingest" => Ok(ManagerRole::Ingest),
+            "prove" => Ok(ManagerRole::Prove),
+            "submit" => Ok(ManagerRole::Submit),
+            "all" => Ok(ManagerRole::All),
+            other => Err(OrchestratorError::ConfigurationError(format!(
+                "Unknown role '{}', expected ingest, prove, submit or all",
+                other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ManagerRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManagerRole::Ingest => write!(f, "ingest"),
+            ManagerRole::Prove => write!(f, "prove"),
+            ManagerRole::Submit => write!(f, "submit"),
+            ManagerRole::All => write!(f, "all"),
+        }
+    }
+}
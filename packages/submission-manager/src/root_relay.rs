@@ -0,0 +1,169 @@
+use crate::metrics::BridgeMetrics;
+use crate::solana_client::SolanaClient;
+use crate::{OrchestratorError, Result};
+use serde_json::json;
+use std::time::Duration;
+
+/// A TON state root as published by the light-client contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublishedRoot {
+    pub seqno: u64,
+    pub root: [u8; 32],
+}
+
+/// Reads the root from a TON light-client/checkpoint contract through a
+/// toncenter v3 compatible API (`runGetMethod`). The get-method must return
+/// the root as its first stack entry and the checkpoint seqno as its second.
+#[derive(Clone)]
+pub struct LightClientSource {
+    api_url: String,
+    contract_address: String,
+    get_method: String,
+    client: reqwest::Client,
+}
+
+impl LightClientSource {
+    pub fn new(api_url: &str, contract_address: &str, get_method: &str) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap();
+
+        Self {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            contract_address: contract_address.to_string(),
+            get_method: get_method.to_string(),
+            client,
+        }
+    }
+
+    pub async fn fetch(&self) -> Result<PublishedRoot> {
+        let body: serde_json::Value = self.client
+            .post(&format!("{}/api/v3/runGetMethod", self.api_url))
+            .json(&json!({
+                "address": self.contract_address,
+                "method": self.get_method,
+                "stack": [],
+            }))
+            .send()
+            .await
+            .map_err(OrchestratorError::NetworkError)?
+            .error_for_status()
+            .map_err(OrchestratorError::NetworkError)?
+            .json()
+            .await
+            .map_err(OrchestratorError::NetworkError)?;
+
+        let malformed = |what: &str| {
+            OrchestratorError::ConfigurationError(format!(
+                "{}.{} via {}: {}",
+                self.contract_address, self.get_method, self.api_url, what
+            ))
+        };
+
+        if body["exit_code"].as_i64().unwrap_or(0) != 0 {
+            return Err(malformed(&format!("exit code {}", body["exit_code"])));
+        }
+        let stack = body["stack"].as_array().ok_or_else(|| malformed("no stack"))?;
+        let root_hex = stack
+            .first()
+            .and_then(|entry| entry["value"].as_str())
+            .ok_or_else(|| malformed("no root on stack"))?;
+        let seqno_hex = stack
+            .get(1)
+            .and_then(|entry| entry["value"].as_str())
+            .ok_or_else(|| malformed("no seqno on stack"))?;
+
+        Ok(PublishedRoot {
+            seqno: u64::from_str_radix(seqno_hex.trim_start_matches("0x"), 16)
+                .map_err(|_| malformed("seqno is not a number"))?,
+            root: parse_num_256(root_hex).ok_or_else(|| malformed("root is not a 256-bit number"))?,
+        })
+    }
+}
+
+/// TVM integers come back as `0x`-prefixed hex without leading zeros
+fn parse_num_256(value: &str) -> Option<[u8; 32]> {
+    let digits = value.trim_start_matches("0x");
+    if digits.len() > 64 {
+        return None;
+    }
+    let bytes = hex::decode(format!("{:0>64}", digits)).ok()?;
+    bytes.try_into().ok()
+}
+
+/// Pushes light-client roots to the program, but only roots that the
+/// listener's own TON node reports for the same checkpoint
+pub struct RootRelay {
+    light_client: LightClientSource,
+    listener: LightClientSource,
+    interval: Duration,
+    last_pushed_seqno: Option<u64>,
+}
+
+impl RootRelay {
+    pub fn new(light_client: LightClientSource, listener: LightClientSource, interval_secs: u64) -> Self {
+        Self {
+            light_client,
+            listener,
+            interval: Duration::from_secs(interval_secs),
+            last_pushed_seqno: None,
+        }
+    }
+
+    pub async fn run(mut self, solana_client: SolanaClient, metrics: BridgeMetrics) {
+        let mut interval = tokio::time::interval(self.interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.relay_once(&solana_client, &metrics).await {
+                log::warn!("Root relay round failed: {}", e);
+            }
+        }
+    }
+
+    /// Push the published root if both sources agree on it and it is new.
+    /// Returns the pushed root, if any.
+    pub async fn relay_once(&mut self, solana_client: &SolanaClient, metrics: &BridgeMetrics) -> Result<Option<[u8; 32]>> {
+        let published = self.light_client.fetch().await?;
+        let observed = self.listener.fetch().await?;
+
+        if published.seqno != observed.seqno {
+            // One side is lagging; wait until both have seen the same checkpoint
+            log::debug!(
+                "Root relay sources at different checkpoints ({} vs {}), skipping",
+                published.seqno,
+                observed.seqno
+            );
+            return Ok(None);
+        }
+        if published.root != observed.root {
+            metrics.root_relay_disagreements.inc();
+            log::error!(
+                "🚨 Root sources disagree at checkpoint {}: light client {}, listener {}",
+                published.seqno,
+                hex::encode(published.root),
+                hex::encode(observed.root)
+            );
+            return Ok(None);
+        }
+
+        if self.last_pushed_seqno.is_some_and(|seqno| published.seqno <= seqno) {
+            return Ok(None);
+        }
+        if solana_client.fetch_ton_state_root().await? == published.root {
+            self.last_pushed_seqno = Some(published.seqno);
+            return Ok(None);
+        }
+
+        let signature = solana_client.push_ton_root(&published.root).await?;
+        self.last_pushed_seqno = Some(published.seqno);
+        metrics.root_relay_pushes.inc();
+        log::info!(
+            "🌳 Pushed TON root {} (checkpoint {}): {}",
+            hex::encode(published.root),
+            published.seqno,
+            signature
+        );
+        Ok(Some(published.root))
+    }
+}
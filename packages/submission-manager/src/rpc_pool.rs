@@ -0,0 +1,211 @@
+use serde::Serialize;
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_request::RpcError;
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Weight of the newest sample in the rolling latency and error rate
+const EWMA_ALPHA: f64 = 0.2;
+const FAILURES_BEFORE_COOLDOWN: u32 = 3;
+const FAILURE_COOLDOWN: Duration = Duration::from_secs(60);
+/// JSON-RPC "node is unhealthy / behind": another node may well answer
+const NODE_UNHEALTHY: i64 = -32005;
+
+#[derive(Debug, Default, Clone)]
+struct EndpointStats {
+    avg_latency_ms: Option<f64>,
+    error_rate: f64,
+    requests: u64,
+    errors: u64,
+    consecutive_failures: u32,
+    cooldown_until: Option<Instant>,
+}
+
+impl EndpointStats {
+    fn is_healthy(&self, now: Instant) -> bool {
+        self.cooldown_until.map_or(true, |until| now >= until)
+    }
+
+    /// Lower is better: latency inflated by how often the endpoint errors
+    fn score(&self) -> f64 {
+        self.avg_latency_ms.unwrap_or(0.0) * (1.0 + 10.0 * self.error_rate)
+    }
+}
+
+/// One Solana RPC endpoint as reported by health and metrics
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointHealth {
+    pub url: String,
+    pub active: bool,
+    pub healthy: bool,
+    pub avg_latency_ms: Option<f64>,
+    pub error_rate: f64,
+    pub requests: u64,
+    pub errors: u64,
+}
+
+/// Solana RPC endpoints in preference order. Calls go to the active one; a
+/// transport failure is retried on the best-scoring other endpoint, which
+/// then stays active until it fails in turn.
+pub struct RpcPool {
+    urls: Vec<String>,
+    clients: Vec<Arc<RpcClient>>,
+    stats: Mutex<Vec<EndpointStats>>,
+    commitment: CommitmentConfig,
+    active: AtomicUsize,
+    failovers: AtomicU64,
+}
+
+impl RpcPool {
+    pub fn new(
+        urls: &[String],
+        commitment: CommitmentConfig,
+        request_timeout: Duration,
+        confirm_timeout: Duration,
+    ) -> Arc<Self> {
+        let clients = urls
+            .iter()
+            .map(|url| {
+                Arc::new(RpcClient::new_with_timeouts_and_commitment(
+                    url.clone(),
+                    request_timeout,
+                    commitment,
+                    confirm_timeout,
+                ))
+            })
+            .collect();
+        Arc::new(Self {
+            urls: urls.to_vec(),
+            clients,
+            stats: Mutex::new(vec![EndpointStats::default(); urls.len()]),
+            commitment,
+            active: AtomicUsize::new(0),
+            failovers: AtomicU64::new(0),
+        })
+    }
+
+    pub fn active_url(&self) -> &str {
+        &self.urls[self.active.load(Ordering::Relaxed)]
+    }
+
+    pub fn commitment(&self) -> CommitmentConfig {
+        self.commitment
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// Times a call moved to another endpoint since startup
+    pub fn failovers(&self) -> u64 {
+        self.failovers.load(Ordering::Relaxed)
+    }
+
+    /// Run `call` against the active endpoint, failing over to the others on
+    /// transport errors. Errors the cluster itself returned (a program
+    /// rejection, a missing account) are answered the same by every node and
+    /// come straight back.
+    pub async fn call<T, F, Fut>(&self, call: F) -> Result<T, ClientError>
+    where
+        F: Fn(Arc<RpcClient>) -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let mut index = self.active_index();
+        let mut attempts = 0;
+        loop {
+            let started = Instant::now();
+            let result = call(self.clients[index].clone()).await;
+            attempts += 1;
+
+            match result {
+                Err(e) if is_transport_error(&e) => {
+                    self.record(index, started.elapsed(), false);
+                    if attempts >= self.urls.len() {
+                        return Err(e);
+                    }
+                    let next = self.next_endpoint(index);
+                    log::warn!("Solana RPC {} failed ({}), failing over to {}", self.urls[index], e, self.urls[next]);
+                    self.failovers.fetch_add(1, Ordering::Relaxed);
+                    self.active.store(next, Ordering::Relaxed);
+                    index = next;
+                }
+                result => {
+                    self.record(index, started.elapsed(), true);
+                    return result;
+                }
+            }
+        }
+    }
+
+    /// Best-scoring endpoint other than `failed`, preferring those not cooling down
+    fn next_endpoint(&self, failed: usize) -> usize {
+        let now = Instant::now();
+        let stats = self.stats.lock().unwrap();
+        let others = (0..stats.len()).filter(|&i| i != failed);
+        let best = |candidates: &mut dyn Iterator<Item = usize>| {
+            candidates.min_by(|&a, &b| stats[a].score().total_cmp(&stats[b].score()))
+        };
+        best(&mut others.clone().filter(|&i| stats[i].is_healthy(now)))
+            .or_else(|| best(&mut others.clone()))
+            .unwrap_or(failed)
+    }
+
+    fn record(&self, index: usize, elapsed: Duration, ok: bool) {
+        let mut stats = self.stats.lock().unwrap();
+        let entry = &mut stats[index];
+        entry.requests += 1;
+        entry.error_rate += EWMA_ALPHA * (if ok { 0.0 } else { 1.0 } - entry.error_rate);
+
+        if ok {
+            let sample = elapsed.as_secs_f64() * 1000.0;
+            entry.avg_latency_ms = Some(match entry.avg_latency_ms {
+                Some(avg) => avg + EWMA_ALPHA * (sample - avg),
+                None => sample,
+            });
+            entry.consecutive_failures = 0;
+            entry.cooldown_until = None;
+        } else {
+            entry.errors += 1;
+            entry.consecutive_failures += 1;
+            if entry.consecutive_failures >= FAILURES_BEFORE_COOLDOWN {
+                entry.cooldown_until = Some(Instant::now() + FAILURE_COOLDOWN);
+            }
+        }
+    }
+
+    pub fn endpoints(&self) -> Vec<EndpointHealth> {
+        let now = Instant::now();
+        let active = self.active_index();
+        let stats = self.stats.lock().unwrap();
+        self.urls
+            .iter()
+            .zip(stats.iter())
+            .enumerate()
+            .map(|(i, (url, stats))| EndpointHealth {
+                url: url.clone(),
+                active: i == active,
+                healthy: stats.is_healthy(now),
+                avg_latency_ms: stats.avg_latency_ms,
+                error_rate: stats.error_rate,
+                requests: stats.requests,
+                errors: stats.errors,
+            })
+            .collect()
+    }
+}
+
+/// Whether the request never got a verdict from the cluster, so another
+/// endpoint might answer it
+fn is_transport_error(error: &ClientError) -> bool {
+    match error.kind() {
+        ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) | ClientErrorKind::Middleware(_) => true,
+        ClientErrorKind::Custom(_) => true, // Our confirmation timeout
+        ClientErrorKind::RpcError(RpcError::RpcRequestError(_)) => true,
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => *code == NODE_UNHEALTHY,
+        _ => false,
+    }
+}
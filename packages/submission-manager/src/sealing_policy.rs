@@ -0,0 +1,169 @@
+use crate::gas_optimizer::GasOptimizer;
+use crate::types::{Batch, OrchestratorConfig};
+use crate::{OrchestratorError, Result};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Decides when the open batch is sealed and queued for submission.
+/// Implementations must be deterministic given the batch and `now`.
+pub trait SealingPolicy: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn should_seal(&self, batch: &Batch, now: DateTime<Utc>) -> bool;
+}
+
+fn batch_age(batch: &Batch, now: DateTime<Utc>) -> Duration {
+    (now - batch.created_at).to_std().unwrap_or_default()
+}
+
+fn total_fees(batch: &Batch) -> u64 {
+    batch.deposits
+        .iter()
+        .map(|d| d.fee_est.parse::<u64>().unwrap_or(0))
+        .sum()
+}
+
+fn total_value(batch: &Batch) -> u64 {
+    batch.deposits
+        .iter()
+        .map(|d| d.amount.parse::<u64>().unwrap_or(0))
+        .fold(0, u64::saturating_add)
+}
+
+/// Seal only once the batch is full
+pub struct SizePolicy {
+    pub max_size: usize,
+}
+
+impl SealingPolicy for SizePolicy {
+    fn name(&self) -> &'static str {
+        "size"
+    }
+
+    fn should_seal(&self, batch: &Batch, _now: DateTime<Utc>) -> bool {
+        batch.deposits.len() >= self.max_size
+    }
+}
+
+/// Seal when full or when the oldest deposit has waited `max_age`
+pub struct TimePolicy {
+    pub max_size: usize,
+    pub max_age: Duration,
+}
+
+impl SealingPolicy for TimePolicy {
+    fn name(&self) -> &'static str {
+        "time"
+    }
+
+    fn should_seal(&self, batch: &Batch, now: DateTime<Utc>) -> bool {
+        batch.deposits.len() >= self.max_size || batch_age(batch, now) >= self.max_age
+    }
+}
+
+/// Seal once the collected user fees reach `min_total_fee`, falling back to size/age limits
+pub struct FeeThresholdPolicy {
+    pub max_size: usize,
+    pub max_age: Duration,
+    pub min_total_fee: u64,
+}
+
+impl SealingPolicy for FeeThresholdPolicy {
+    fn name(&self) -> &'static str {
+        "fee"
+    }
+
+    fn should_seal(&self, batch: &Batch, now: DateTime<Utc>) -> bool {
+        batch.deposits.len() >= self.max_size
+            || batch_age(batch, now) >= self.max_age
+            || total_fees(batch) >= self.min_total_fee
+    }
+}
+
+/// Seal once the deposited value waiting in the batch reaches `min_total_value`,
+/// so large amounts are not held back for a full batch; falls back to size/age limits
+pub struct ValueThresholdPolicy {
+    pub max_size: usize,
+    pub max_age: Duration,
+    pub min_total_value: u64,
+}
+
+impl SealingPolicy for ValueThresholdPolicy {
+    fn name(&self) -> &'static str {
+        "value"
+    }
+
+    fn should_seal(&self, batch: &Batch, now: DateTime<Utc>) -> bool {
+        batch.deposits.len() >= self.max_size
+            || batch_age(batch, now) >= self.max_age
+            || total_value(batch) >= self.min_total_value
+    }
+}
+
+/// Seal once the amortized submission cost per deposit (from current
+/// GasOptimizer fee data) drops to `target_cost_per_deposit`, falling back to size/age limits
+pub struct CostOptimalPolicy {
+    pub max_size: usize,
+    pub max_age: Duration,
+    pub target_cost_per_deposit: u64,
+    pub gas_optimizer: GasOptimizer,
+}
+
+impl SealingPolicy for CostOptimalPolicy {
+    fn name(&self) -> &'static str {
+        "cost"
+    }
+
+    fn should_seal(&self, batch: &Batch, now: DateTime<Utc>) -> bool {
+        let deposits = batch.deposits.len();
+        if deposits == 0 {
+            return false;
+        }
+        if deposits >= self.max_size || batch_age(batch, now) >= self.max_age {
+            return true;
+        }
+
+        let per_deposit = self.gas_optimizer.estimate_transaction_cost(deposits) / deposits as u64;
+        per_deposit <= self.target_cost_per_deposit
+    }
+}
+
+/// Build the policy named by `config.sealing_policy`
+pub fn from_config(config: &OrchestratorConfig, gas_optimizer: GasOptimizer) -> Result<Arc<dyn SealingPolicy>> {
+    // Adaptive sizing seals at its own target; the policy only enforces the hard cap
+    let max_size = if config.adaptive_batch_sizing {
+        config.batch_max_size
+    } else {
+        config.batch_size
+    };
+    let max_age = Duration::from_secs(config.batch_max_age_secs);
+
+    let policy: Arc<dyn SealingPolicy> = match config.sealing_policy.as_str() {
+        "size" => Arc::new(SizePolicy { max_size }),
+        "time" => Arc::new(TimePolicy { max_size, max_age }),
+        "fee" => Arc::new(FeeThresholdPolicy {
+            max_size,
+            max_age,
+            min_total_fee: config.seal_min_total_fee,
+        }),
+        "value" => Arc::new(ValueThresholdPolicy {
+            max_size,
+            max_age,
+            min_total_value: config.seal_min_total_value,
+        }),
+        "cost" => Arc::new(CostOptimalPolicy {
+            max_size,
+            max_age,
+            target_cost_per_deposit: config.seal_target_cost_per_deposit,
+            gas_optimizer,
+        }),
+        other => {
+            return Err(OrchestratorError::ConfigurationError(format!(
+                "Unknown sealing policy '{}', expected size, time, fee, value or cost",
+                other
+            )))
+        }
+    };
+
+    Ok(policy)
+}
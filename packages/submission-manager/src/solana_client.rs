@@ -1,162 +1,1357 @@
-use solana_client::rpc_client::RpcClient;
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::nonce_utils;
 use solana_sdk::{
     commitment_config::CommitmentConfig, 
+    compute_budget::ComputeBudgetInstruction,
+    nonce,
+    system_instruction,
     signature::Keypair, 
     signer::Signer,
-    transaction::Transaction,
-    instruction::Instruction,
+    address_lookup_table::{self, state::AddressLookupTable, AddressLookupTableAccount},
+    hash::Hash,
+    message::{v0, Message, VersionedMessage},
+    transaction::{TransactionError, VersionedTransaction},
+    instruction::{AccountMeta, Instruction, InstructionError},
+    packet::PACKET_DATA_SIZE,
     pubkey::Pubkey,
 };
+use solana_transaction_status::{TransactionConfirmationStatus, UiTransactionEncoding};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use crate::gas_optimizer::{self, GasOptimizer};
+use crate::health_monitor::EventCounters;
+use crate::jito::{JitoClient, MAX_BUNDLE_TRANSACTIONS};
+use crate::program_events::{self, ProgramEvent};
 use crate::{OrchestratorError, Result};
+use crate::proof::Proof;
+use crate::rpc_pool::{EndpointHealth, RpcPool};
+use crate::program_accounts::{EventState, HeaderChain, LcState, NullifierBitmap, NullifierState};
+use crate::public_inputs::{hash_scalar, proof_anchor};
+use crate::program_errors::ProgramFailure;
+use crate::program_instructions::{
+    associated_token_address, instruction_data, BatchedEvent, EventPublicInputs, TxInclusionProof,
+    VerifyTonEventArgs, VerifyTonEventBatchArgs, VerifyTonEventCompactArgs, ZKProof, ASSOCIATED_TOKEN_PROGRAM_ID,
+    EVENT_SEED, HEADER_CHAIN_SEED, LC_STATE_SEED, MAX_BATCH_EVENTS, NULLIFIER_BITMAP_SEED, NULLIFIER_MODE_BITMAP,
+    NULLIFIER_MODE_PDA, NULLIFIER_SEED, TOKEN_CONFIG_SEED, TOKEN_PROGRAM_ID, TREASURY_SEED, VERIFYING_KEY_SEED,
+    VOLUME_LIMIT_GLOBAL, VOLUME_LIMIT_SEED, WRAPPED_TON_MINT_SEED,
+};
+
+
+/// Account space (including discriminator) of the program's per-deposit PDAs
+pub const EVENT_ACCOUNT_SPACE: usize = 8 + 1 + 32 + 32 + 8 + 32 + 32 + 8 + 32 + 8 + 32 + 8;
+pub const NULLIFIER_ACCOUNT_SPACE: usize = 8 + 1 + 32 + 32 + 8;
+/// Compute unit limits for transactions other than deposit verification
+const ROOT_UPDATE_COMPUTE_UNITS: u32 = 50_000;
+const TRANSFER_COMPUTE_UNITS: u32 = 300;
+const LOOKUP_TABLE_COMPUTE_UNITS: u32 = 20_000;
+/// Largest compute unit limit a transaction may request
+const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+
+/// A verification instruction, for one deposit or a whole batch, that passed simulation
+#[derive(Debug, Clone)]
+pub struct PreparedDeposit {
+    pub instruction: Instruction,
+    pub compute_units: u32, // Simulated consumption plus headroom
+}
+
+/// Where a landed transaction stands on its way to finality
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignatureFinality {
+    Unknown,        // Not (or no longer) known to the cluster, e.g. dropped with its fork
+    Confirming,     // Processed or confirmed, not yet rooted
+    Finalized,
+    Failed(String), // Executed with an error
+}
+
+/// Outcome of one deposit of a submitted batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepositSubmission {
+    pub deposit_id: String,
+    pub outcome: std::result::Result<String, String>, // Transaction signature, or why it failed
+    pub failure: Option<ProgramFailure>,              // Set when the program itself rejected it
+    pub limit_exceeded: bool,                         // Its transaction is over the size or compute limit
+}
 
+/// How long RPC work may take before it is abandoned
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RpcTimeouts {
+    pub request: Duration,      // Any single RPC request
+    pub confirmation: Duration, // Sending a transaction until it reaches the commitment level
+}
+
+impl Default for RpcTimeouts {
+    fn default() -> Self {
+        Self { request: Duration::from_secs(30), confirmation: Duration::from_secs(90) }
+    }
+}
 
 pub struct SolanaClient {
-    rpc_client: RpcClient,
+    rpc: Arc<RpcPool>, // Shared by clones, with each endpoint's connection pool and health
+    timeouts: RpcTimeouts,
     keypair: Keypair,
     program_id: Pubkey,
-    bridge_account: Pubkey,
+    gas_optimizer: GasOptimizer, // Prices the compute budget of every transaction
+    nonce_account: Option<Pubkey>, // Durable nonce the relayer's transactions are signed against
+    nonce_lock: Arc<tokio::sync::Mutex<()>>, // One transaction per nonce value in flight
+    jito: Option<JitoClient>, // Batches go out as tipped bundles instead of through the RPC
+    lookup_table: Option<AddressLookupTableAccount>, // Set: transactions are v0 messages loading from it
+    underfunded: Arc<AtomicBool>, // Payer fell below its minimum balance or could not pay a fee
 }
 
 impl SolanaClient {
-    pub fn new(rpc_url: &str, program_id: &str, bridge_account: &str, private_key: Option<&str>) -> Result<Self> {
-        let rpc_client = RpcClient::new_with_commitment(
-            rpc_url.to_string(),
-            CommitmentConfig::confirmed(),
-        );
+    /// `rpc_urls` in failover order; the first is used until it fails
+    pub fn new(
+        rpc_urls: &[String],
+        program_id: &str,
+        commitment: &str,
+        keypair: Keypair,
+        timeouts: RpcTimeouts,
+    ) -> Result<Self> {
+        let commitment = CommitmentConfig::from_str(commitment)
+            .map_err(|e| OrchestratorError::ConfigurationError(format!("Invalid commitment level: {}", e)))?;
 
-        let keypair = if let Some(pk) = private_key {
-            Keypair::from_base58_string(pk)
-        } else {
-            Keypair::new()
-        };
+        if rpc_urls.is_empty() {
+            return Err(OrchestratorError::ConfigurationError("no Solana RPC URL configured".to_string()));
+        }
+        let rpc = RpcPool::new(rpc_urls, commitment, timeouts.request, timeouts.confirmation);
 
         let program_id = Pubkey::from_str(program_id)
             .map_err(|e| OrchestratorError::ConfigurationError(format!("Invalid program ID: {}", e)))?;
 
-        let bridge_account = Pubkey::from_str(bridge_account)
-            .map_err(|e| OrchestratorError::ConfigurationError(format!("Invalid bridge account: {}", e)))?;
-
         Ok(Self {
-            rpc_client,
+            rpc,
+            timeouts,
             keypair,
             program_id,
-            bridge_account,
+            gas_optimizer: GasOptimizer::new(0),
+            nonce_account: None,
+            nonce_lock: Arc::new(tokio::sync::Mutex::new(())),
+            jito: None,
+            lookup_table: None,
+            underfunded: Arc::new(AtomicBool::new(false)),
         })
     }
 
-     pub async fn submit_batch(&self, batch: &crate::Batch) -> Result<String> {
+    /// Send deposit batches through a Jito block engine, tipping `tip_lamports` per bundle
+    pub fn with_jito(mut self, jito: JitoClient) -> Self {
+        self.jito = Some(jito);
+        self
+    }
+
+    /// Sign transactions against `nonce_account` instead of a recent blockhash,
+    /// so a signed transaction stays valid however long it takes to land
+    pub fn with_nonce_account(mut self, nonce_account: &str) -> Result<Self> {
+        let nonce_account = Pubkey::from_str(nonce_account)
+            .map_err(|e| OrchestratorError::ConfigurationError(format!("Invalid nonce account: {}", e)))?;
+        self.nonce_account = Some(nonce_account);
+        Ok(self)
+    }
+
+    /// Check the configured nonce account is initialized and advanceable by the relayer
+    pub async fn check_nonce_account(&self) -> Result<()> {
+        let Some(nonce_account) = self.nonce_account else {
+            return Ok(());
+        };
+        let nonce = self.fetch_nonce(&nonce_account).await?;
+        if nonce.authority != self.keypair.pubkey() {
+            return Err(OrchestratorError::ConfigurationError(format!(
+                "nonce account {} is advanced by {}, not the relayer {}",
+                nonce_account,
+                nonce.authority,
+                self.keypair.pubkey()
+            )));
+        }
+        Ok(())
+    }
+
+    async fn fetch_nonce(&self, nonce_account: &Pubkey) -> std::result::Result<nonce::state::Data, ClientError> {
+        let account = self.rpc.call(|rpc| async move { rpc.get_account(nonce_account).await }).await?;
+        nonce_utils::data_from_account(&account)
+            .map_err(|e| ClientErrorKind::Custom(format!("nonce account {}: {}", nonce_account, e)).into())
+    }
+
+    /// Send transactions as v0 messages that load the program's static accounts
+    /// from an address lookup table, leaving room for the per-deposit PDAs. The
+    /// table is created when `table` is unset and extended with any static
+    /// account it lacks; the relayer must be its authority.
+    pub async fn with_lookup_table(mut self, table: Option<&str>) -> Result<Self> {
+        let table = match table {
+            Some(table) => Pubkey::from_str(table)
+                .map_err(|e| OrchestratorError::ConfigurationError(format!("Invalid lookup table: {}", e)))?,
+            None => self.create_lookup_table().await?,
+        };
+
+        let mut addresses = self.fetch_lookup_table(&table).await?;
+        let missing: Vec<Pubkey> = self
+            .static_accounts()
+            .await?
+            .into_iter()
+            .filter(|account| !addresses.contains(account))
+            .collect();
+        if !missing.is_empty() {
+            log::info!("📇 Extending lookup table {} with {} accounts", table, missing.len());
+            self.extend_lookup_table(&table, &missing).await?;
+            addresses = self.fetch_lookup_table(&table).await?;
+        }
+
+        self.lookup_table = Some(AddressLookupTableAccount { key: table, addresses });
+        Ok(self)
+    }
+
+    async fn create_lookup_table(&self) -> Result<Pubkey> {
+        // The table address derives from a slot the cluster still has a hash for
+        let recent_slot = self
+            .rpc
+            .call(|rpc| async move { rpc.get_slot_with_commitment(CommitmentConfig::finalized()).await })
+            .await?;
+        let relayer = self.keypair.pubkey();
+        let (instruction, table) = address_lookup_table::instruction::create_lookup_table(relayer, relayer, recent_slot);
+        let signature = self.send_with_compute_budget(&[instruction], LOOKUP_TABLE_COMPUTE_UNITS).await?;
+        log::info!("📇 Created address lookup table {} in {}", table, signature);
+        Ok(table)
+    }
+
+    async fn extend_lookup_table(&self, table: &Pubkey, addresses: &[Pubkey]) -> Result<()> {
+        // Keeps each extension well inside the transaction size limit
+        const ADDRESSES_PER_EXTENSION: usize = 20;
+
+        let relayer = self.keypair.pubkey();
+        for chunk in addresses.chunks(ADDRESSES_PER_EXTENSION) {
+            let instruction =
+                address_lookup_table::instruction::extend_lookup_table(*table, relayer, Some(relayer), chunk.to_vec());
+            self.send_with_compute_budget(&[instruction], LOOKUP_TABLE_COMPUTE_UNITS).await?;
+        }
+
+        // New entries resolve only from the slot after the extension
+        let extended_at = self.rpc.call(|rpc| async move { rpc.get_slot().await }).await?;
+        while self.rpc.call(|rpc| async move { rpc.get_slot().await }).await? <= extended_at {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+        Ok(())
+    }
+
+    async fn fetch_lookup_table(&self, table: &Pubkey) -> Result<Vec<Pubkey>> {
+        let data = self
+            .fetch_account_data(table)
+            .await?
+            .ok_or_else(|| OrchestratorError::ConfigurationError(format!("lookup table {} does not exist", table)))?;
+        let decoded = AddressLookupTable::deserialize(&data)
+            .map_err(|e| OrchestratorError::ConfigurationError(format!("cannot decode lookup table {}: {}", table, e)))?;
+        Ok(decoded.addresses.to_vec())
+    }
+
+    /// Accounts every `verify_ton_event` transaction references whatever the
+    /// deposit. The program id stays out: an invoked program must be a static key.
+    async fn static_accounts(&self) -> Result<Vec<Pubkey>> {
+        let pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &self.program_id).0;
+        let state = self.fetch_lc_state().await?;
+
+        let mut accounts = vec![
+            self.state_pda(),
+            pda(&[HEADER_CHAIN_SEED]),
+            self.verifying_key_pda(state.vk_id),
+            pda(&[VOLUME_LIMIT_SEED, VOLUME_LIMIT_GLOBAL]),
+            pda(&[WRAPPED_TON_MINT_SEED]),
+            pda(&[TREASURY_SEED]),
+            TOKEN_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+            solana_sdk::system_program::ID,
+        ];
+        // Proofs under the previous key are accepted until it expires
+        if state.previous_vk_id != state.vk_id {
+            accounts.push(self.verifying_key_pda(state.previous_vk_id));
+        }
+        Ok(accounts)
+    }
+
+    /// Share the fee conditions the rest of the manager prices submissions with
+    pub fn with_gas_optimizer(mut self, gas_optimizer: GasOptimizer) -> Self {
+        self.gas_optimizer = gas_optimizer;
+        self
+    }
+
+    /// Sign and send `instructions` behind a compute unit limit and the current
+    /// priority fee, so transactions keep landing while the cluster is congested.
+    /// With a nonce account the transaction is signed once against the nonce, and
+    /// a resend after an endpoint failure is the same transaction even once
+    /// blockhashes have expired; it can only land once.
+    async fn send_with_compute_budget(
+        &self,
+        instructions: &[Instruction],
+        compute_units: u32,
+    ) -> std::result::Result<solana_sdk::signature::Signature, ClientError> {
+        // The nonce moves on when a transaction lands, so hold it until this one settles
+        let _nonce_guard = match self.nonce_account {
+            Some(_) => Some(self.nonce_lock.lock().await),
+            None => None,
+        };
+        let transaction = &match self.nonce_account {
+            Some(nonce_account) => self.durable_transaction(&nonce_account, instructions, compute_units).await?,
+            None => self.budgeted_transaction(instructions, compute_units).await?,
+        };
+        let confirmation = self.timeouts.confirmation;
+        self.rpc
+            .call(|rpc| async move {
+                // The RPC client only bounds the initial wait; this bounds the whole confirmation
+                match tokio::time::timeout(confirmation, rpc.send_and_confirm_transaction(transaction)).await {
+                    Ok(confirmed) => confirmed,
+                    Err(_) => Err(ClientErrorKind::Custom(format!(
+                        "transaction {} not confirmed within {:?}",
+                        transaction.signatures[0], confirmation
+                    ))
+                    .into()),
+                }
+            })
+            .await
+    }
+
+    /// Dry-run `instructions` exactly as they would be sent and return the
+    /// compute units they consumed. A program rejection comes back typed, so
+    /// nothing is paid for a transaction that was going to fail.
+    async fn simulate_with_compute_budget(&self, instructions: &[Instruction], compute_units: u32) -> Result<u64> {
+        let transaction = &self.budgeted_transaction(instructions, compute_units).await?;
+        let size = bincode::serialized_size(transaction).unwrap_or(0) as usize;
+        if size > PACKET_DATA_SIZE {
+            return Err(OrchestratorError::ResourceLimitExceeded(format!(
+                "transaction is {} bytes, over the {}-byte packet limit",
+                size, PACKET_DATA_SIZE
+            )));
+        }
+        let simulation = self
+            .rpc
+            .call(|rpc| async move { rpc.simulate_transaction(transaction).await })
+            .await?
+            .value;
+        let logs = simulation.logs.unwrap_or_default();
+
+        if let Some(error) = simulation.err {
+            if is_insufficient_funds(&error) {
+                return Err(self.insufficient_funds(format!("simulation failed: {}", error)));
+            }
+            if is_compute_exceeded(&error, &logs) {
+                return Err(OrchestratorError::ResourceLimitExceeded(format!(
+                    "simulation ran out of its {} compute units: {}",
+                    compute_units, error
+                )));
+            }
+            return Err(match ProgramFailure::from_simulation(&error, &logs) {
+                Some(failure) => OrchestratorError::ProgramRejected(failure),
+                None => OrchestratorError::SimulationFailed { error: error.to_string(), logs },
+            });
+        }
+        Ok(simulation.units_consumed.unwrap_or(compute_units as u64))
+    }
+
+    async fn budgeted_transaction(
+        &self,
+        instructions: &[Instruction],
+        compute_units: u32,
+    ) -> std::result::Result<VersionedTransaction, ClientError> {
+        let budgeted = self.with_compute_budget(instructions, compute_units);
+        let recent_blockhash = self
+            .rpc
+            .call(|rpc| async move { rpc.get_latest_blockhash().await })
+            .await?;
+        self.compile_transaction(&budgeted, recent_blockhash)
+    }
+
+    /// Signed against the nonce's stored blockhash; advancing the nonce must be
+    /// the first instruction
+    async fn durable_transaction(
+        &self,
+        nonce_account: &Pubkey,
+        instructions: &[Instruction],
+        compute_units: u32,
+    ) -> std::result::Result<VersionedTransaction, ClientError> {
+        let nonce = self.fetch_nonce(nonce_account).await?;
+        let mut durable = vec![system_instruction::advance_nonce_account(nonce_account, &self.keypair.pubkey())];
+        durable.extend(self.with_compute_budget(instructions, compute_units));
+        self.compile_transaction(&durable, nonce.blockhash())
+    }
+
+    /// Sign `instructions` as a v0 message resolving accounts through the
+    /// lookup table when one is configured, as a legacy message otherwise
+    fn compile_transaction(
+        &self,
+        instructions: &[Instruction],
+        blockhash: Hash,
+    ) -> std::result::Result<VersionedTransaction, ClientError> {
+        let payer = self.keypair.pubkey();
+        let message = match &self.lookup_table {
+            Some(table) => VersionedMessage::V0(
+                v0::Message::try_compile(&payer, instructions, std::slice::from_ref(table), blockhash)
+                    .map_err(|e| ClientError::from(ClientErrorKind::Custom(format!("cannot compile v0 message: {}", e))))?,
+            ),
+            None => VersionedMessage::Legacy(Message::new_with_blockhash(instructions, Some(&payer), &blockhash)),
+        };
+        Ok(VersionedTransaction::try_new(message, &[&self.keypair])?)
+    }
+
+    fn with_compute_budget(&self, instructions: &[Instruction], compute_units: u32) -> Vec<Instruction> {
+        let mut budgeted = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(compute_units),
+            ComputeBudgetInstruction::set_compute_unit_price(self.gas_optimizer.priority_fee()),
+        ];
+        budgeted.extend_from_slice(instructions);
+        budgeted
+    }
+
+    /// Prioritization fees (micro-lamports per CU) recently paid for the program's state account
+    pub async fn fetch_recent_priority_fees(&self) -> Result<Vec<u64>> {
+        let fees = self
+            .rpc
+            .call(|rpc| async move { rpc.get_recent_prioritization_fees(&[self.state_pda()]).await })
+            .await?;
+        Ok(fees.into_iter().map(|fee| fee.prioritization_fee).collect())
+    }
+
+    /// Health of each RPC endpoint, the active one flagged
+    pub fn rpc_endpoints(&self) -> Vec<EndpointHealth> {
+        self.rpc.endpoints()
+    }
+
+    pub fn active_rpc_url(&self) -> &str {
+        self.rpc.active_url()
+    }
+
+    /// Calls moved to another RPC endpoint since startup
+    pub fn rpc_failovers(&self) -> u64 {
+        self.rpc.failovers()
+    }
+
+    /// Address lookup table transactions are compiled against, if any
+    pub fn lookup_table(&self) -> Option<Pubkey> {
+        self.lookup_table.as_ref().map(|table| table.key)
+    }
+
+    pub fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    pub fn relayer_pubkey(&self) -> Pubkey {
+        self.keypair.pubkey()
+    }
+
+    /// Submit `batch` and report each deposit's outcome: as one
+    /// `verify_ton_event_batch` transaction when it qualifies (see
+    /// `submit_batched`), otherwise every deposit as its own verification
+    /// transaction. There a deposit failing does not stop the rest, and one
+    /// failing simulation is never sent.
+    pub async fn submit_batch(&self, batch: &crate::Batch) -> Result<Vec<DepositSubmission>> {
         log::info!("Submitting batch with {} deposits to Solana", batch.deposits.len());
-        
-        // For now, use mock implementation for batch submission
-        // You can replace this with real batch processing later
-        let mock_signature = format!("solana_tx_batch_{}_{}", 
-            batch.deposits.len(), 
-            chrono::Utc::now().timestamp());
+        if let Some(results) = self.submit_batched(batch).await? {
+            return Ok(results);
+        }
 
-        log::info!("✅ Batch submitted to Solana: {}", mock_signature);
-        
-        Ok(mock_signature)
+        let simulations = self.simulate_batch(batch).await?;
+        let outcomes = match &self.jito {
+            Some(jito) => self.send_bundles(jito, simulations).await,
+            None => {
+                let mut outcomes = Vec::with_capacity(simulations.len());
+                for (deposit, simulation) in batch.deposits.iter().zip(simulations) {
+                    outcomes.push(match simulation {
+                        // The payer ran dry mid-batch: the rest would fail the same way
+                        Ok(_) if self.payer_underfunded() => Err(OrchestratorError::InsufficientFunds(format!(
+                            "payer {} ran out of funds earlier in the batch",
+                            self.keypair.pubkey()
+                        ))),
+                        Ok(prepared) => self.send_prepared_deposit(deposit, &prepared).await,
+                        Err(e) => Err(e),
+                    });
+                }
+                outcomes
+            }
+        };
+
+        let mut results = Vec::with_capacity(batch.deposits.len());
+        for (deposit, outcome) in batch.deposits.iter().zip(outcomes) {
+            if let Err(e) = &outcome {
+                log::warn!("Deposit {} was not verified on Solana: {}", deposit.deposit_id, e);
+            }
+            results.push(DepositSubmission {
+                deposit_id: deposit.deposit_id.clone(),
+                failure: match &outcome {
+                    Err(OrchestratorError::ProgramRejected(failure)) => Some(*failure),
+                    _ => None,
+                },
+                limit_exceeded: matches!(outcome, Err(OrchestratorError::ResourceLimitExceeded(_))),
+                outcome: outcome.map_err(|e| e.to_string()),
+            });
+        }
+
+        let landed = results.iter().filter(|result| result.outcome.is_ok()).count();
+        log::info!("✅ {}/{} deposits of the batch landed on Solana", landed, results.len());
+        Ok(results)
     }
 
+    /// Submit `batch` as one `verify_ton_event_batch` transaction, every
+    /// deposit landing or failing with it. Only PDA-mode batches of 2 to
+    /// `MAX_BATCH_EVENTS` deposits sharing a token and key version qualify,
+    /// and only while none of them is already consumed, expired or rejected
+    /// in simulation; otherwise `None` leaves them to their own transactions,
+    /// which report each deposit's failure. A batch over the transaction size
+    /// or compute limit fails as a whole with `ResourceLimitExceeded`, so the
+    /// caller can split it. Jito bundles keep per-deposit transactions.
+    async fn submit_batched(&self, batch: &crate::Batch) -> Result<Option<Vec<DepositSubmission>>> {
+        let deposits = batch.deposits.len();
+        if self.jito.is_some() || !(2..=MAX_BATCH_EVENTS).contains(&deposits) || batch.proofs.len() != deposits {
+            return Ok(None);
+        }
+        if self.fetch_lc_state().await?.nullifier_mode != NULLIFIER_MODE_PDA {
+            return Ok(None);
+        }
+
+        let accepted = self.fetch_anchor_roots().await?;
+        let mut events = Vec::with_capacity(deposits);
+        for (deposit, proof) in batch.deposits.iter().zip(&batch.proofs) {
+            let Some(anchor_root) = proof_anchor(&proof.public_inputs, &accepted) else {
+                return Ok(None);
+            };
+            let Ok(public_inputs) = EventPublicInputs::for_deposit(deposit, proof, anchor_root) else {
+                return Ok(None);
+            };
+            let inclusion = TxInclusionProof::from(&proof.inclusion);
+            let Ok(recipient_salt) = crate::privacy::recipient_salt(deposit) else {
+                return Ok(None);
+            };
+            if !inclusion.leads_to(&public_inputs.ton_tx_hash, &public_inputs.anchor_root)
+                || self.is_consumed_on_chain(NULLIFIER_MODE_PDA, &public_inputs, &inclusion).await?
+            {
+                return Ok(None);
+            }
+            events.push(BatchedEvent { proof: ZKProof::from(proof), public_inputs, inclusion, recipient_salt });
+        }
+        // The program reads one token config and verifying key for the whole batch
+        let first = &events[0].public_inputs;
+        if events.iter().any(|event| {
+            event.public_inputs.token_id != first.token_id || event.public_inputs.vk_version != first.vk_version
+        }) {
+            return Ok(None);
+        }
+
+        let instruction = self.verify_ton_event_batch_instruction(events).await;
+        let prepared = match self.prepare(instruction, deposits).await {
+            Ok(prepared) => prepared,
+            Err(e @ OrchestratorError::ResourceLimitExceeded(_)) => return Err(e),
+            Err(e) => {
+                log::warn!("Batch {} cannot go out as one transaction, sending per deposit: {}", batch.id, e);
+                return Ok(None);
+            }
+        };
+
+        log::info!("Submitting batch {} as one verify_ton_event_batch transaction", batch.id);
+        let outcome = self.send_prepared(&prepared).await;
+        if let Ok(signature) = &outcome {
+            log::info!("✅ {} deposits of batch {} verified on Solana: {}", deposits, batch.id, signature);
+        }
+        Ok(Some(
+            batch
+                .deposits
+                .iter()
+                .map(|deposit| DepositSubmission {
+                    deposit_id: deposit.deposit_id.clone(),
+                    failure: match &outcome {
+                        Err(OrchestratorError::ProgramRejected(failure)) => Some(*failure),
+                        _ => None,
+                    },
+                    limit_exceeded: false,
+                    outcome: outcome.as_ref().map(String::clone).map_err(|e| e.to_string()),
+                })
+                .collect(),
+        ))
+    }
+
+    /// Send the deposits that passed simulation as Jito bundles, in deposit
+    /// order. Bundles are atomic, so a deposit lands only with its bundle mates.
+    async fn send_bundles(&self, jito: &JitoClient, simulations: Vec<Result<PreparedDeposit>>) -> Vec<Result<String>> {
+        let mut outcomes = Vec::with_capacity(simulations.len());
+        let mut ready = Vec::new();
+        for (index, simulation) in simulations.into_iter().enumerate() {
+            match simulation {
+                Ok(prepared) => {
+                    ready.push((index, prepared));
+                    outcomes.push(Ok(String::new())); // Replaced once its bundle settles
+                }
+                Err(e) => outcomes.push(Err(e)),
+            }
+        }
+
+        for chunk in ready.chunks(MAX_BUNDLE_TRANSACTIONS) {
+            match self.send_bundle(jito, chunk).await {
+                Ok(settled) => {
+                    for ((index, _), outcome) in chunk.iter().zip(settled) {
+                        outcomes[*index] = outcome;
+                    }
+                }
+                Err(e) => {
+                    log::warn!("Bundle of {} deposits did not land: {}", chunk.len(), e);
+                    for (index, _) in chunk {
+                        outcomes[*index] = Err(OrchestratorError::BatchProcessingFailed { reason: e.to_string() });
+                    }
+                }
+            }
+        }
+        outcomes
+    }
+
+    /// Sign one transaction per deposit, tipping in the last, and wait for the bundle to settle.
+    /// Bundles use recent blockhashes: a durable nonce would serialize them behind each other.
+    async fn send_bundle(&self, jito: &JitoClient, deposits: &[(usize, PreparedDeposit)]) -> Result<Vec<Result<String>>> {
+        let mut transactions = Vec::with_capacity(deposits.len());
+        for (position, (_, prepared)) in deposits.iter().enumerate() {
+            let mut instructions = vec![prepared.instruction.clone()];
+            let mut compute_units = prepared.compute_units;
+            if position + 1 == deposits.len() {
+                instructions.push(jito.tip_instruction(&self.keypair.pubkey()));
+                compute_units += TRANSFER_COMPUTE_UNITS;
+            }
+            transactions.push(self.budgeted_transaction(&instructions, compute_units).await?);
+        }
+
+        let bundle_id = jito.send_bundle(&transactions).await?;
+        log::info!("📦 Sent Jito bundle {} with {} deposits", bundle_id, transactions.len());
+
+        let signatures: Vec<String> = transactions.iter().map(|transaction| transaction.signatures[0].to_string()).collect();
+        self.await_confirmations(&signatures).await
+    }
+
+    /// Poll until every signature reached the commitment level or failed
+    async fn await_confirmations(&self, signatures: &[String]) -> Result<Vec<Result<String>>> {
+        const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+        let deadline = tokio::time::Instant::now() + self.timeouts.confirmation;
+        let commitment = self.rpc.commitment();
+        let parsed = &signatures
+            .iter()
+            .map(|signature| solana_sdk::signature::Signature::from_str(signature).unwrap_or_default())
+            .collect::<Vec<_>>();
+        loop {
+            let statuses = self
+                .rpc
+                .call(|rpc| async move { rpc.get_signature_statuses(parsed).await })
+                .await?
+                .value;
+            let settled: Option<Vec<Result<String>>> = statuses
+                .iter()
+                .zip(signatures)
+                .map(|(status, signature)| {
+                    let status = status.as_ref()?;
+                    match &status.err {
+                        Some(err) => Some(Err(match ProgramFailure::from_simulation(err, &[]) {
+                            Some(failure) => OrchestratorError::ProgramRejected(failure),
+                            None => OrchestratorError::BatchProcessingFailed {
+                                reason: format!("transaction {} failed: {}", signature, err),
+                            },
+                        })),
+                        None if status.satisfies_commitment(commitment) => Some(Ok(signature.clone())),
+                        None => None,
+                    }
+                })
+                .collect();
+            if let Some(settled) = settled {
+                return Ok(settled);
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                return Err(OrchestratorError::BatchProcessingFailed {
+                    reason: format!("bundle not confirmed within {:?}", self.timeouts.confirmation),
+                });
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Simulate the verification transaction of every deposit of `batch`, in
+    /// deposit order, through the instruction `LcState.nullifier_mode` calls
+    /// for. Each entry is the transaction ready to send with the compute it
+    /// needs, or why the program would reject it.
+    pub async fn simulate_batch(&self, batch: &crate::Batch) -> Result<Vec<Result<PreparedDeposit>>> {
+        if batch.proofs.len() != batch.deposits.len() {
+            return Err(OrchestratorError::BatchProcessingFailed {
+                reason: format!("{} proofs for {} deposits", batch.proofs.len(), batch.deposits.len()),
+            });
+        }
+
+        // Proofs anchored to a root that rotated out are re-proven before
+        // submission; any left would only fail on-chain
+        let accepted = self.fetch_anchor_roots().await?;
+        let nullifier_mode = self.fetch_lc_state().await?.nullifier_mode;
+
+        let mut simulations = Vec::with_capacity(batch.deposits.len());
+        for (deposit, proof) in batch.deposits.iter().zip(&batch.proofs) {
+            let inclusion = TxInclusionProof::from(&proof.inclusion);
+            let public_inputs = match proof_anchor(&proof.public_inputs, &accepted) {
+                Some(anchor_root) => EventPublicInputs::for_deposit(deposit, proof, anchor_root),
+                None => Err(OrchestratorError::ProofExpired(deposit.deposit_id.clone())),
+            };
+            // The program checks the path too; refusing it here saves the simulation
+            let public_inputs = public_inputs.and_then(|public_inputs| {
+                if inclusion.leads_to(&public_inputs.ton_tx_hash, &public_inputs.anchor_root) {
+                    Ok(public_inputs)
+                } else {
+                    Err(OrchestratorError::InvalidProof(format!(
+                        "inclusion path of deposit {} does not lead to its anchor root",
+                        deposit.deposit_id
+                    )))
+                }
+            });
+            let simulation = match public_inputs {
+                // Already minted, e.g. by another relayer: nothing to simulate or pay for
+                Ok(public_inputs) if self.is_consumed_on_chain(nullifier_mode, &public_inputs, &inclusion).await? => {
+                    Err(OrchestratorError::ProgramRejected(ProgramFailure::AlreadyBridged))
+                }
+                Ok(public_inputs) => match crate::privacy::recipient_salt(deposit) {
+                    Ok(salt) => match self.deposit_instruction(nullifier_mode, proof, &public_inputs, &inclusion, salt).await {
+                        Ok(instruction) => self.prepare(instruction, 1).await,
+                        Err(e) => Err(e),
+                    },
+                    Err(reason) => Err(OrchestratorError::InvalidProof(format!("deposit {}: {}", deposit.deposit_id, reason))),
+                },
+                Err(e) => Err(e),
+            };
+            if let Err(e) = &simulation {
+                log::warn!("Simulation rejected deposit {}: {}", deposit.deposit_id, e);
+            }
+            simulations.push(simulation);
+        }
+        Ok(simulations)
+    }
+
+    /// Simulate an instruction verifying `deposits` deposits
+    async fn prepare(&self, instruction: Instruction, deposits: usize) -> Result<PreparedDeposit> {
+        let budget = (gas_optimizer::COMPUTE_UNITS_PER_DEPOSIT * deposits as u64).min(MAX_COMPUTE_UNITS as u64) as u32;
+        let consumed = self.simulate_with_compute_budget(std::slice::from_ref(&instruction), budget).await?;
+
+        // Headroom over the simulation, as state may move before the transaction lands
+        let compute_units = (consumed + consumed / 10).clamp(1, MAX_COMPUTE_UNITS as u64) as u32;
+        Ok(PreparedDeposit { instruction, compute_units })
+    }
+
+    /// Submit one deposit's proof through the instruction the program's
+    /// nullifier mode calls for, simulating it first
     pub async fn submit_verified_deposit(
         &self,
         deposit: &crate::Deposit,
-        proof: &str,
-        verification_key: &str
+        proof: &Proof,
+        public_inputs: &EventPublicInputs,
+        inclusion: &TxInclusionProof,
     ) -> Result<String> {
-        log::info!("Submitting verified deposit {} to Solana ZK program", deposit.deposit_id);
+        let nullifier_mode = self.fetch_lc_state().await?.nullifier_mode;
+        let salt = crate::privacy::recipient_salt(deposit)
+            .map_err(|reason| OrchestratorError::InvalidProof(format!("deposit {}: {}", deposit.deposit_id, reason)))?;
+        let instruction = self.deposit_instruction(nullifier_mode, proof, public_inputs, inclusion, salt).await?;
+        let prepared = self.prepare(instruction, 1).await?;
+        self.send_prepared_deposit(deposit, &prepared).await
+    }
 
-        // Create instruction for ZK program
-        let instruction = self.create_verify_and_deposit_instruction(deposit, proof, verification_key).await?;
+    async fn send_prepared_deposit(&self, deposit: &crate::Deposit, prepared: &PreparedDeposit) -> Result<String> {
+        log::info!("Submitting verified deposit {} to Solana ZK program", deposit.deposit_id);
+        let signature = self.send_prepared(prepared).await?;
+        log::info!("✅ Deposit {} submitted to Solana ZK program: {}", deposit.deposit_id, signature);
+        
+        Ok(signature)
+    }
 
-        let mut transaction = Transaction::new_with_payer(
-            &[instruction],
-            Some(&self.keypair.pubkey()),
-        );
+    /// Send a simulated instruction; a program rejection keeps its reason for the deposit status
+    async fn send_prepared(&self, prepared: &PreparedDeposit) -> Result<String> {
+        let instructions = std::slice::from_ref(&prepared.instruction);
+        let signature = self.send_with_compute_budget(instructions, prepared.compute_units).await.map_err(|e| {
+            if e.get_transaction_error().as_ref().is_some_and(is_insufficient_funds) {
+                return self.insufficient_funds(e.to_string());
+            }
+            match ProgramFailure::from_client_error(&e) {
+                Some(failure) => OrchestratorError::ProgramRejected(failure),
+                None => e.into(),
+            }
+        })?;
+        Ok(signature.to_string())
+    }
 
-        let recent_blockhash = self.rpc_client.get_latest_blockhash()?;
-        transaction.sign(&[&self.keypair], recent_blockhash);
+    /// The instruction verifying one deposit under `nullifier_mode`:
+    /// `verify_ton_event_compact` once the program records consumed events in
+    /// bitmaps, `verify_ton_event` otherwise
+    pub async fn deposit_instruction(
+        &self,
+        nullifier_mode: u8,
+        proof: &Proof,
+        public_inputs: &EventPublicInputs,
+        inclusion: &TxInclusionProof,
+        recipient_salt: [u8; 32],
+    ) -> Result<Instruction> {
+        if nullifier_mode == NULLIFIER_MODE_BITMAP {
+            self.verify_ton_event_compact_instruction(proof, public_inputs, inclusion, recipient_salt).await
+        } else {
+            self.verify_ton_event_instruction(proof, public_inputs, inclusion).await
+        }
+    }
 
-        // Submit transaction
-        let signature = self.rpc_client.send_and_confirm_transaction(&transaction)?;
+    /// `verify_ton_event` with its accounts in the program's declaration order
+    pub async fn verify_ton_event_instruction(
+        &self,
+        proof: &Proof,
+        public_inputs: &EventPublicInputs,
+        inclusion: &TxInclusionProof,
+    ) -> Result<Instruction> {
+        let args = VerifyTonEventArgs {
+            proof: ZKProof::from(proof),
+            public_inputs: public_inputs.clone(),
+            inclusion: inclusion.clone(),
+        };
 
-        log::info!("✅ Deposit {} submitted to Solana ZK program: {}", deposit.deposit_id, signature);
-        
-        Ok(signature.to_string())
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: self.verify_ton_event_accounts(public_inputs).await?,
+            data: args.instruction_data(),
+        })
     }
 
-    async fn create_verify_and_deposit_instruction(
+    /// `verify_ton_event_compact` with its accounts in the program's declaration order
+    pub async fn verify_ton_event_compact_instruction(
         &self,
-        deposit: &crate::Deposit,
-        proof: &str,
-        verification_key: &str
+        proof: &Proof,
+        public_inputs: &EventPublicInputs,
+        inclusion: &TxInclusionProof,
+        recipient_salt: [u8; 32],
     ) -> Result<Instruction> {
-        // This should match your Solana program's instruction structure
-        // Based on your solana-program/src/lib.rs
-        
+        let args = VerifyTonEventCompactArgs {
+            proof: ZKProof::from(proof),
+            public_inputs: public_inputs.clone(),
+            inclusion: inclusion.clone(),
+            recipient_salt,
+        };
+
+        let pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &self.program_id).0;
+        let wrapped_mint = pda(&[WRAPPED_TON_MINT_SEED]);
         let accounts = vec![
-            solana_sdk::instruction::AccountMeta::new(self.bridge_account, false),
-            solana_sdk::instruction::AccountMeta::new(self.keypair.pubkey(), true),
+            AccountMeta::new(self.state_pda(), false),
+            AccountMeta::new_readonly(self.verifying_key_pda(public_inputs.vk_version), false),
+            AccountMeta::new_readonly(pda(&[TOKEN_CONFIG_SEED, &public_inputs.token_id]), false),
+            AccountMeta::new(pda(&[VOLUME_LIMIT_SEED, VOLUME_LIMIT_GLOBAL]), false),
+            AccountMeta::new(pda(&[VOLUME_LIMIT_SEED, &public_inputs.token_id]), false),
+            AccountMeta::new(self.nullifier_bitmap_pda(NullifierBitmap::page_for(inclusion.leaf_index)), false),
+            AccountMeta::new_readonly(self.nullifier_pda(&public_inputs.nullifier), false),
+            AccountMeta::new(wrapped_mint, false),
+            AccountMeta::new(pda(&[TREASURY_SEED]), false),
+            AccountMeta::new_readonly(public_inputs.recipient_solana, false),
+            AccountMeta::new(associated_token_address(&public_inputs.recipient_solana, &wrapped_mint), false),
+            AccountMeta::new(self.keypair.pubkey(), true),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
         ];
 
-        // Convert proof data to bytes (this depends on your ZK program interface)
-        let proof_data = self.serialize_proof_data(deposit, proof, verification_key);
-
         Ok(Instruction {
             program_id: self.program_id,
             accounts,
-            data: proof_data,
+            data: args.instruction_data(),
         })
     }
 
-    fn serialize_proof_data(&self, deposit: &crate::Deposit, proof: &str, verification_key: &str) -> Vec<u8> {
-        // This should match your Solana program's expected proof format
-        // Based on your solana-program/src/verify.rs
-        
-        let mut data = Vec::new();
-        
-        // Add proof (this is a simplified version)
-        data.extend_from_slice(proof.as_bytes());
-        data.extend_from_slice(&[b';']); // separator
-        data.extend_from_slice(verification_key.as_bytes());
-        data.extend_from_slice(&[b';']); // separator
-        
-        // Add deposit data
-        data.extend_from_slice(deposit.deposit_id.as_bytes());
-        data.extend_from_slice(&[b';']);
-        data.extend_from_slice(deposit.ton_tx_hash.as_bytes());
-        data.extend_from_slice(&[b';']);
-        data.extend_from_slice(deposit.recipient_solana.as_bytes());
-        data.extend_from_slice(&[b';']);
-        data.extend_from_slice(deposit.amount.as_bytes());
-
-        data
-    }
-
-    pub async fn get_bridge_state(&self) -> Result<()> {
-        // Fetch bridge state from Solana program
-        let account_data = self.rpc_client.get_account_data(&self.bridge_account)?;
-        log::info!("Bridge account data: {} bytes", account_data.len());
-        Ok(())
+    /// `verify_ton_event_batch` with its accounts in the program's declaration
+    /// order, then each event's `(event, nullifier, recipient, recipient ATA)`
+    /// group. The key version and token config are the first event's.
+    pub async fn verify_ton_event_batch_instruction(&self, events: Vec<BatchedEvent>) -> Instruction {
+        let pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &self.program_id).0;
+        let wrapped_mint = pda(&[WRAPPED_TON_MINT_SEED]);
+        let first = &events[0].public_inputs;
+
+        let mut accounts = vec![
+            AccountMeta::new(self.state_pda(), false),
+            AccountMeta::new_readonly(self.header_chain_or_absent().await, false),
+            AccountMeta::new_readonly(self.verifying_key_pda(first.vk_version), false),
+            AccountMeta::new_readonly(pda(&[TOKEN_CONFIG_SEED, &first.token_id]), false),
+            AccountMeta::new(pda(&[VOLUME_LIMIT_SEED, VOLUME_LIMIT_GLOBAL]), false),
+            AccountMeta::new(pda(&[VOLUME_LIMIT_SEED, &first.token_id]), false),
+            AccountMeta::new(wrapped_mint, false),
+            AccountMeta::new(pda(&[TREASURY_SEED]), false),
+            AccountMeta::new(self.keypair.pubkey(), true),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+        ];
+        for event in &events {
+            let public_inputs = &event.public_inputs;
+            accounts.extend([
+                AccountMeta::new(self.event_pda(&public_inputs.event_id), false),
+                AccountMeta::new(self.nullifier_pda(&public_inputs.nullifier), false),
+                AccountMeta::new_readonly(public_inputs.recipient_solana, false),
+                AccountMeta::new(associated_token_address(&public_inputs.recipient_solana, &wrapped_mint), false),
+            ]);
+        }
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: VerifyTonEventBatchArgs { events }.instruction_data(),
+        }
+    }
+
+    /// The `HeaderChain` PDA if it exists; the program id, which stands in for
+    /// an optional account not provided, otherwise
+    async fn header_chain_or_absent(&self) -> Pubkey {
+        let header_chain_pda = Pubkey::find_program_address(&[HEADER_CHAIN_SEED], &self.program_id).0;
+        let header_chain_exists = self.rpc.call(|rpc| async move { rpc.get_account(&header_chain_pda).await }).await.is_ok();
+        if header_chain_exists {
+            header_chain_pda
+        } else {
+            self.program_id
+        }
+    }
+
+    async fn verify_ton_event_accounts(&self, public_inputs: &EventPublicInputs) -> Result<Vec<AccountMeta>> {
+        let pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &self.program_id).0;
+        let wrapped_mint = pda(&[WRAPPED_TON_MINT_SEED]);
+        let header_chain = self.header_chain_or_absent().await;
+
+        Ok(vec![
+            AccountMeta::new(self.state_pda(), false),
+            AccountMeta::new_readonly(header_chain, false),
+            AccountMeta::new_readonly(self.verifying_key_pda(public_inputs.vk_version), false),
+            AccountMeta::new_readonly(pda(&[TOKEN_CONFIG_SEED, &public_inputs.token_id]), false),
+            AccountMeta::new(pda(&[VOLUME_LIMIT_SEED, VOLUME_LIMIT_GLOBAL]), false),
+            AccountMeta::new(pda(&[VOLUME_LIMIT_SEED, &public_inputs.token_id]), false),
+            AccountMeta::new(self.event_pda(&public_inputs.event_id), false),
+            AccountMeta::new(self.nullifier_pda(&public_inputs.nullifier), false),
+            AccountMeta::new(wrapped_mint, false),
+            AccountMeta::new(pda(&[TREASURY_SEED]), false),
+            AccountMeta::new_readonly(public_inputs.recipient_solana, false),
+            AccountMeta::new(associated_token_address(&public_inputs.recipient_solana, &wrapped_mint), false),
+            AccountMeta::new_readonly(self.program_id, false), // claimer_token_account: no claim tip
+            AccountMeta::new(self.keypair.pubkey(), true),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+        ])
+    }
+
+    /// The program's `LcState`, decoded
+    pub async fn fetch_lc_state(&self) -> Result<LcState> {
+        let state_pda = self.state_pda();
+        let account_data = self
+            .rpc
+            .call(|rpc| async move { rpc.get_account_data(&state_pda).await })
+            .await?;
+        LcState::decode(&account_data)
+    }
+
+    /// Whether the program's emergency pause is engaged
+    pub async fn fetch_paused(&self) -> Result<bool> {
+        Ok(self.fetch_lc_state().await?.paused)
+    }
+
+    /// Read the TON state root currently committed in the program's `LcState` PDA
+    pub async fn fetch_ton_state_root(&self) -> Result<[u8; 32]> {
+        Ok(self.fetch_lc_state().await?.ton_state_root)
+    }
+
+    /// TON roots the program accepts as an anchor, newest first. In bitmap mode
+    /// that is only the deposit tree root `verify_ton_event_compact` requires;
+    /// otherwise the current root unless the program only trusts the header
+    /// chain, then the chain's.
+    pub async fn fetch_anchor_roots(&self) -> Result<Vec<[u8; 32]>> {
+        let state = self.fetch_lc_state().await?;
+        if state.nullifier_mode == NULLIFIER_MODE_BITMAP {
+            let root = state.deposit_tree_root;
+            return Ok(if root == [0u8; 32] { Vec::new() } else { vec![root] });
+        }
+        let chain_pda = Pubkey::find_program_address(&[HEADER_CHAIN_SEED], &self.program_id).0;
+        let chain = match self.rpc.call(|rpc| async move { rpc.get_account_data(&chain_pda).await }).await {
+            Ok(data) => Some(HeaderChain::decode(&data)?),
+            Err(_) => None, // Not initialized
+        };
+
+        let mut roots = Vec::new();
+        if !state.header_chain_only {
+            roots.push(state.ton_state_root);
+        }
+        roots.extend(chain.map(|chain| chain.roots()).unwrap_or_default());
+        Ok(roots)
+    }
+
+    /// Lamports left on the relayer key, which pays every submission
+    pub async fn fetch_relayer_balance(&self) -> Result<u64> {
+        Ok(self.rpc.call(|rpc| async move { rpc.get_balance(&self.relayer_pubkey()).await }).await?)
+    }
+
+    /// The payer's balance, or `InsufficientFunds` below `min_lamports`. Marks
+    /// the payer underfunded, or funded again, for `payer_underfunded`.
+    pub async fn check_payer_balance(&self, min_lamports: u64) -> Result<u64> {
+        let balance = self.fetch_relayer_balance().await?;
+        if balance < min_lamports {
+            return Err(self.insufficient_funds(format!("holds {} lamports, below {}", balance, min_lamports)));
+        }
+        self.underfunded.store(false, Ordering::Relaxed);
+        Ok(balance)
+    }
+
+    /// Whether the last balance check or submission found the payer unable to pay
+    pub fn payer_underfunded(&self) -> bool {
+        self.underfunded.load(Ordering::Relaxed)
+    }
+
+    fn insufficient_funds(&self, detail: String) -> OrchestratorError {
+        self.underfunded.store(true, Ordering::Relaxed);
+        OrchestratorError::InsufficientFunds(format!("payer {} {}", self.keypair.pubkey(), detail))
+    }
+
+    /// Read the program's monotonic `events_verified` / `total_amount_bridged` counters
+    pub async fn fetch_event_counters(&self) -> Result<EventCounters> {
+        let state_pda = self.state_pda();
+        let account_data = self
+            .rpc
+            .call(|rpc| async move { rpc.get_account_data(&state_pda).await })
+            .await?;
+
+        // Older layouts would decode the counters as zero rather than missing
+        if account_data.len() < LcState::EVENT_COUNTERS_END {
+            return Err(OrchestratorError::ConfigurationError(format!(
+                "LcState account {} has no event counters ({} bytes); run migrate_state",
+                state_pda,
+                account_data.len()
+            )));
+        }
+        let state = LcState::decode(&account_data)?;
+
+        Ok(EventCounters {
+            events_verified: state.events_verified,
+            total_amount_bridged: state.total_amount_bridged,
+        })
+    }
+
+    /// Relayer approvals the program requires per root (`LcState.root_approval_threshold`)
+    pub async fn fetch_root_approval_threshold(&self) -> Result<u8> {
+        Ok(self.fetch_lc_state().await?.root_approval_threshold)
+    }
+
+    /// The `EventState` of `event_id`, if the program has verified that event
+    pub async fn fetch_event_state(&self, event_id: &[u8; 32]) -> Result<Option<EventState>> {
+        let event_pda = self.event_pda(event_id);
+        match self.fetch_account_data(&event_pda).await? {
+            Some(data) => Ok(Some(EventState::decode(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The `NullifierState` of `nullifier`, if the program has consumed it
+    pub async fn fetch_nullifier_state(&self, nullifier: &[u8; 32]) -> Result<Option<NullifierState>> {
+        let nullifier_pda = self.nullifier_pda(nullifier);
+        match self.fetch_account_data(&nullifier_pda).await? {
+            Some(data) => Ok(Some(NullifierState::decode(&data)?)),
+            None => Ok(None),
+        }
     }
+
+    /// The `NullifierBitmap` page holding `page`'s leaf positions, if allocated
+    pub async fn fetch_nullifier_bitmap(&self, page: u64) -> Result<Option<NullifierBitmap>> {
+        let bitmap_pda = self.nullifier_bitmap_pda(page);
+        match self.fetch_account_data(&bitmap_pda).await? {
+            Some(data) => Ok(Some(NullifierBitmap::decode(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Whether the program already consumed this event or its nullifier. The
+    /// nullifier record outlives the event, which may be closed for its rent;
+    /// in bitmap mode the event's bit under `inclusion.leaf_index` is checked too.
+    pub async fn is_consumed_on_chain(
+        &self,
+        nullifier_mode: u8,
+        public_inputs: &EventPublicInputs,
+        inclusion: &TxInclusionProof,
+    ) -> Result<bool> {
+        if self.fetch_event_state(&public_inputs.event_id).await?.is_some_and(|event| event.consumed) {
+            return Ok(true);
+        }
+        if self
+            .fetch_nullifier_state(&public_inputs.nullifier)
+            .await?
+            .is_some_and(|nullifier| nullifier.consumed)
+        {
+            return Ok(true);
+        }
+        if nullifier_mode != NULLIFIER_MODE_BITMAP {
+            return Ok(false);
+        }
+        Ok(self
+            .fetch_nullifier_bitmap(NullifierBitmap::page_for(inclusion.leaf_index))
+            .await?
+            .is_some_and(|bitmap| bitmap.is_set(inclusion.leaf_index)))
+    }
+
+    /// Data of an account, or `None` if it does not exist
+    async fn fetch_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let account = self
+            .rpc
+            .call(|rpc| async move { rpc.get_account_with_commitment(address, rpc.commitment()).await })
+            .await?
+            .value;
+        Ok(account.map(|account| account.data))
+    }
+
+    /// Commit a TON root as this relayer: `update_ton_root` when a single
+    /// signer suffices, otherwise this relayer's `approve_ton_root` vote
+    pub async fn push_ton_root(&self, root: &[u8; 32]) -> Result<String> {
+        let state_pda = self.state_pda();
+        let (relayer_set_pda, _) = Pubkey::find_program_address(&[b"relayer_set"], &self.program_id);
+
+        let instruction = if self.fetch_root_approval_threshold().await? > 1 {
+            let (pending_root_pda, _) = Pubkey::find_program_address(&[b"pending_root", root], &self.program_id);
+            Instruction {
+                program_id: self.program_id,
+                accounts: vec![
+                    AccountMeta::new(state_pda, false),
+                    AccountMeta::new_readonly(relayer_set_pda, false),
+                    AccountMeta::new(pending_root_pda, false),
+                    AccountMeta::new(self.keypair.pubkey(), true),
+                    AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+                ],
+                data: instruction_data("approve_ton_root", root),
+            }
+        } else {
+            // Optional account: the program id stands in for "not provided"
+            let relayer_set_exists = self.rpc.call(|rpc| async move { rpc.get_account(&relayer_set_pda).await }).await.is_ok();
+            let relayer_set = if relayer_set_exists {
+                relayer_set_pda
+            } else {
+                self.program_id
+            };
+            Instruction {
+                program_id: self.program_id,
+                accounts: vec![
+                    AccountMeta::new(state_pda, false),
+                    AccountMeta::new_readonly(relayer_set, false),
+                    AccountMeta::new_readonly(self.keypair.pubkey(), true),
+                ],
+                data: instruction_data("update_ton_root", root),
+            }
+        };
+
+        let signature = self.send_with_compute_budget(&[instruction], ROOT_UPDATE_COMPUTE_UNITS).await?;
+        Ok(signature.to_string())
+    }
+
+    /// Where each of `signatures` stands on its way to finality, in order
+    pub async fn get_signature_finality(&self, signatures: &[String]) -> Result<Vec<SignatureFinality>> {
+        // The RPC's limit on signatures per status request
+        const STATUSES_PER_REQUEST: usize = 256;
+
+        let mut finality = Vec::with_capacity(signatures.len());
+        for chunk in signatures.chunks(STATUSES_PER_REQUEST) {
+            let parsed = &chunk
+                .iter()
+                .map(|signature| solana_sdk::signature::Signature::from_str(signature).unwrap_or_default())
+                .collect::<Vec<_>>();
+            let statuses = self
+                .rpc
+                .call(|rpc| async move { rpc.get_signature_statuses_with_history(parsed).await })
+                .await?;
+            finality.extend(statuses.value.into_iter().map(|status| match status {
+                None => SignatureFinality::Unknown,
+                Some(status) => match (status.err, status.confirmation_status) {
+                    (Some(err), _) => SignatureFinality::Failed(err.to_string()),
+                    (None, Some(TransactionConfirmationStatus::Finalized)) => SignatureFinality::Finalized,
+                    (None, _) => SignatureFinality::Confirming,
+                },
+            }));
+        }
+        Ok(finality)
+    }
+
+    /// Slot a transaction landed in, if the cluster knows the signature
+    pub async fn get_signature_slot(&self, signature: &str) -> Result<Option<u64>> {
+        let Ok(signature) = solana_sdk::signature::Signature::from_str(signature) else {
+            return Ok(None);
+        };
+
+        let statuses = self
+            .rpc
+            .call(|rpc| async move { rpc.get_signature_statuses(&[signature]).await })
+            .await?;
+        Ok(statuses.value.into_iter().next().flatten().map(|status| status.slot))
+    }
+
+    /// Rent-exempt lamports each deposit costs: its event and nullifier
+    /// accounts, or in bitmap mode its share of a bitmap page
+    pub async fn rent_per_deposit(&self) -> Result<u64> {
+        if self.fetch_lc_state().await?.nullifier_mode == NULLIFIER_MODE_BITMAP {
+            let page_rent = self
+                .rpc
+                .call(|rpc| async move { rpc.get_minimum_balance_for_rent_exemption(NullifierBitmap::SPACE).await })
+                .await?;
+            return Ok(page_rent.div_ceil(NullifierBitmap::BITS_PER_PAGE));
+        }
+
+        let event_rent = self
+            .rpc
+            .call(|rpc| async move { rpc.get_minimum_balance_for_rent_exemption(EVENT_ACCOUNT_SPACE).await })
+            .await?;
+        let nullifier_rent = self
+            .rpc
+            .call(|rpc| async move { rpc.get_minimum_balance_for_rent_exemption(NULLIFIER_ACCOUNT_SPACE).await })
+            .await?;
+        Ok(event_rent + nullifier_rent)
+    }
+
+    /// Fee actually charged for a landed transaction
+    pub async fn get_transaction_fee(&self, signature: &str) -> Result<Option<u64>> {
+        let Ok(signature) = solana_sdk::signature::Signature::from_str(signature) else {
+            return Ok(None);
+        };
+
+        let transaction = self
+            .rpc
+            .call(|rpc| async move { rpc.get_transaction(&signature, UiTransactionEncoding::Json).await })
+            .await?;
+        Ok(transaction.transaction.meta.map(|meta| meta.fee))
+    }
+
+    /// Program events emitted by a landed transaction; `None` if the cluster
+    /// does not know the signature or kept no logs for it
+    pub async fn get_transaction_events(&self, signature: &str) -> Result<Option<Vec<ProgramEvent>>> {
+        let Ok(signature) = solana_sdk::signature::Signature::from_str(signature) else {
+            return Ok(None);
+        };
+
+        let transaction = self
+            .rpc
+            .call(|rpc| async move { rpc.get_transaction(&signature, UiTransactionEncoding::Json).await })
+            .await?;
+        let logs: Option<Vec<String>> = transaction.transaction.meta.and_then(|meta| meta.log_messages.into());
+        Ok(logs.map(|logs| program_events::decode_logs(&logs)))
+    }
+
+    /// Whether a transaction landed successfully: `None` if the cluster does not know it
+    pub async fn signature_succeeded(&self, signature: &str) -> Result<Option<bool>> {
+        let Ok(signature) = solana_sdk::signature::Signature::from_str(signature) else {
+            return Ok(None);
+        };
+
+        let status = self
+            .rpc
+            .call(|rpc| async move { rpc.get_signature_status(&signature).await })
+            .await?;
+        Ok(status.map(|result| result.is_ok()))
+    }
+
+    /// Top up each target to its rent-exempt minimum with plain transfers, so the
+    /// program later only has to allocate and assign it. Returns the signatures
+    /// of the funding transactions (none if everything was already funded).
+    pub async fn prefund_accounts(&self, targets: &[(Pubkey, usize)]) -> Result<Vec<String>> {
+        const TRANSFERS_PER_TRANSACTION: usize = 16;
+
+        let addresses = &targets.iter().map(|(address, _)| *address).collect::<Vec<Pubkey>>();
+        let accounts = self
+            .rpc
+            .call(|rpc| async move { rpc.get_multiple_accounts(addresses).await })
+            .await?;
+
+        let mut transfers = Vec::new();
+        for ((address, space), account) in targets.iter().zip(accounts) {
+            // Already allocated by the program: nothing to pre-create
+            if account.as_ref().is_some_and(|a| !a.data.is_empty()) {
+                continue;
+            }
+            let rent = self
+                .rpc
+                .call(|rpc| async move { rpc.get_minimum_balance_for_rent_exemption(*space).await })
+                .await?;
+            let balance = account.map_or(0, |a| a.lamports);
+            if balance < rent {
+                transfers.push(solana_sdk::system_instruction::transfer(
+                    &self.keypair.pubkey(),
+                    address,
+                    rent - balance,
+                ));
+            }
+        }
+
+        let mut signatures = Vec::new();
+        for chunk in transfers.chunks(TRANSFERS_PER_TRANSACTION) {
+            let compute_units = TRANSFER_COMPUTE_UNITS * chunk.len() as u32;
+            signatures.push(self.send_with_compute_budget(chunk, compute_units).await?.to_string());
+        }
+
+        Ok(signatures)
+    }
+
+    /// The program's singleton `LcState` PDA
+    pub fn state_pda(&self) -> Pubkey {
+        Pubkey::find_program_address(&[LC_STATE_SEED], &self.program_id).0
+    }
+
+    /// `VerifyingKey` PDA holding the Groth16 key for `vk_version`
+    pub fn verifying_key_pda(&self, vk_version: u32) -> Pubkey {
+        Pubkey::find_program_address(&[VERIFYING_KEY_SEED, &vk_version.to_le_bytes()], &self.program_id).0
+    }
+
+    /// `NullifierState` PDA the program creates for `nullifier`, seeded with
+    /// the reduced scalar
+    pub fn nullifier_pda(&self, nullifier: &[u8; 32]) -> Pubkey {
+        Pubkey::find_program_address(&[NULLIFIER_SEED, &hash_scalar(nullifier)], &self.program_id).0
+    }
+
+    /// `NullifierBitmap` PDA of bitmap page `page`
+    pub fn nullifier_bitmap_pda(&self, page: u64) -> Pubkey {
+        Pubkey::find_program_address(&[NULLIFIER_BITMAP_SEED, &page.to_le_bytes()], &self.program_id).0
+    }
+
+    /// `EventState` PDA the program creates for `event_id`, seeded with the
+    /// reduced scalar
+    pub fn event_pda(&self, event_id: &[u8; 32]) -> Pubkey {
+        Pubkey::find_program_address(&[EVENT_SEED, &hash_scalar(event_id)], &self.program_id).0
+    }
+
+    /// Sign arbitrary bytes with the submitter key, returning (pubkey, signature) in base58
+    pub fn sign_message(&self, message: &[u8]) -> (String, String) {
+        let signature = self.keypair.sign_message(message);
+        (self.keypair.pubkey().to_string(), signature.to_string())
+    }
+
+    /// The bridge's on-chain state: admin, last verified slot, TON root and relayer
+    pub async fn get_bridge_state(&self) -> Result<LcState> {
+        let state = self.fetch_lc_state().await?;
+        log::info!(
+            "Bridge state: admin {}, relayer {}, last verified slot {}, TON root {}",
+            state.admin,
+            state.relayer,
+            state.last_verified_slot,
+            hex::encode(state.ton_state_root)
+        );
+        Ok(state)
+    }
+}
+
+/// Whether the cluster refused a transaction because the payer cannot cover its fee or rent
+fn is_insufficient_funds(error: &TransactionError) -> bool {
+    matches!(error, TransactionError::InsufficientFundsForFee | TransactionError::InsufficientFundsForRent { .. })
+}
+
+/// The runtime stopped the transaction at its compute unit limit
+fn is_compute_exceeded(error: &TransactionError, logs: &[String]) -> bool {
+    matches!(error, TransactionError::InstructionError(_, InstructionError::ComputationalBudgetExceeded))
+        || logs.iter().any(|line| line.contains("exceeded CUs meter"))
 }
 
 impl Clone for SolanaClient {
     fn clone(&self) -> Self {
-        // Create a new RpcClient with the same URL
-        let rpc_client = RpcClient::new(self.rpc_client.url().to_string());
-        
         // Clone the keypair by serializing/deserializing
         let keypair_bytes = self.keypair.to_bytes();
         let keypair = Keypair::from_bytes(&keypair_bytes)
             .expect("Failed to clone keypair");
         
         SolanaClient {
-            rpc_client,
+            rpc: self.rpc.clone(),
+            timeouts: self.timeouts,
             keypair,
             program_id: self.program_id,
-            bridge_account: self.bridge_account,
+            gas_optimizer: self.gas_optimizer.clone(),
+            nonce_account: self.nonce_account,
+            nonce_lock: self.nonce_lock.clone(),
+            jito: self.jito.clone(),
+            lookup_table: self.lookup_table.clone(),
+            underfunded: self.underfunded.clone(),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,122 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+const HOUR: Duration = Duration::from_secs(3600);
+const DAY: Duration = Duration::from_secs(24 * 3600);
+
+/// Snapshot reported by the admin API
+#[derive(Debug, Clone, Serialize)]
+pub struct SpendGuardStatus {
+    pub tripped: Option<String>,
+    pub lamports_spent_last_hour: u64,
+    pub lamports_spent_last_day: u64,
+    pub hourly_budget: u64,
+    pub daily_budget: u64,
+}
+
+/// Hard cap on fee-payer spend over rolling hour and day windows. Unlike the
+/// rate limiter, which only delays the next batch, the guard latches once a
+/// budget would be exceeded and refuses every submission until an operator
+/// resets it. A budget of zero disables that window.
+#[derive(Debug)]
+pub struct SpendGuard {
+    hourly_budget: u64,
+    daily_budget: u64,
+    spend: VecDeque<(Instant, u64)>,
+    tripped: Option<String>,
+}
+
+impl SpendGuard {
+    pub fn new(hourly_budget: u64, daily_budget: u64) -> Self {
+        Self {
+            hourly_budget,
+            daily_budget,
+            spend: VecDeque::new(),
+            tripped: None,
+        }
+    }
+
+    /// Check a submission of `estimated_lamports` against both budgets. The
+    /// first refusal trips the guard; returns the reason when refused.
+    pub fn check(&mut self, estimated_lamports: u64) -> Result<(), String> {
+        if let Some(reason) = &self.tripped {
+            return Err(reason.clone());
+        }
+
+        let now = Instant::now();
+        self.prune(now);
+
+        let hourly = self.spent_within(now, HOUR);
+        if self.hourly_budget > 0 && hourly + estimated_lamports > self.hourly_budget {
+            return Err(self.trip(format!(
+                "hourly fee budget exhausted: {} + {} > {} lamports",
+                hourly, estimated_lamports, self.hourly_budget
+            )));
+        }
+
+        let daily = self.spent_within(now, DAY);
+        if self.daily_budget > 0 && daily + estimated_lamports > self.daily_budget {
+            return Err(self.trip(format!(
+                "daily fee budget exhausted: {} + {} > {} lamports",
+                daily, estimated_lamports, self.daily_budget
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Count lamports spent by the fee payer, whether or not the transaction landed
+    pub fn record(&mut self, lamports: u64) {
+        if lamports > 0 {
+            self.spend.push_back((Instant::now(), lamports));
+        }
+    }
+
+    pub fn tripped(&self) -> Option<&str> {
+        self.tripped.as_deref()
+    }
+
+    /// Clear a tripped guard. Recorded spend is kept, so an unchanged budget
+    /// trips again on the next submission that would exceed it.
+    pub fn reset(&mut self) {
+        self.tripped = None;
+    }
+
+    pub fn status(&self) -> SpendGuardStatus {
+        SpendGuardStatus {
+            tripped: self.tripped.clone(),
+            lamports_spent_last_hour: self.lamports_spent_last_hour(),
+            lamports_spent_last_day: self.lamports_spent_last_day(),
+            hourly_budget: self.hourly_budget,
+            daily_budget: self.daily_budget,
+        }
+    }
+
+    pub fn lamports_spent_last_hour(&self) -> u64 {
+        self.spent_within(Instant::now(), HOUR)
+    }
+
+    pub fn lamports_spent_last_day(&self) -> u64 {
+        self.spent_within(Instant::now(), DAY)
+    }
+
+    fn trip(&mut self, reason: String) -> String {
+        self.tripped = Some(reason.clone());
+        reason
+    }
+
+    fn spent_within(&self, now: Instant, window: Duration) -> u64 {
+        self.spend
+            .iter()
+            .filter(|(t, _)| now.duration_since(*t) < window)
+            .map(|(_, lamports)| lamports)
+            .sum()
+    }
+
+    fn prune(&mut self, now: Instant) {
+        while matches!(self.spend.front(), Some((t, _)) if now.duration_since(*t) >= DAY) {
+            self.spend.pop_front();
+        }
+    }
+}
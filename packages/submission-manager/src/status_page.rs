@@ -0,0 +1,125 @@
+use crate::alerts::{self, AlertReport};
+use crate::database::StatusNoticeRecord;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// How long `GET /status.json` may be served from cache, by us and by CDNs
+pub const STATUS_CACHE_SECS: i64 = 30;
+/// Window the average bridging time is computed over
+pub const BRIDGING_TIME_WINDOW_SECS: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceState {
+    Operational,
+    Degraded,
+    Outage,
+    Maintenance,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoticeKind {
+    Incident,
+    Maintenance,
+}
+
+impl NoticeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NoticeKind::Incident => "incident",
+            NoticeKind::Maintenance => "maintenance",
+        }
+    }
+}
+
+/// Operator-written notice shown on the public status page
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusNotice {
+    pub kind: String,
+    pub message: String,
+    pub starts_at: i64,
+    pub ends_at: Option<i64>,
+}
+
+impl StatusNotice {
+    fn in_effect(&self, now: i64) -> bool {
+        self.starts_at <= now && self.ends_at.map_or(true, |ends_at| now < ends_at)
+    }
+}
+
+impl From<StatusNoticeRecord> for StatusNotice {
+    fn from(record: StatusNoticeRecord) -> Self {
+        Self {
+            kind: record.kind,
+            message: record.message,
+            starts_at: record.starts_at,
+            ends_at: record.ends_at,
+        }
+    }
+}
+
+/// Body of `GET /status.json`. Coarse on purpose: internal alert names and
+/// messages stay behind `/api/alerts`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceStatus {
+    pub status: ServiceState,
+    pub average_bridging_secs: Option<u64>, // Completed deposits over the last 24 hours
+    pub notice: Option<StatusNotice>,
+    pub updated_at: i64,
+}
+
+impl ServiceStatus {
+    pub fn new(report: &AlertReport, average_bridging_secs: Option<f64>, notice: Option<StatusNotice>) -> Self {
+        let now = report.checked_at;
+        let mut status = if report.firing.iter().any(|alert| alert.name == alerts::ALERT_BREAKER_OPEN) {
+            ServiceState::Outage // Submissions are halted
+        } else if report.firing.is_empty() {
+            ServiceState::Operational
+        } else {
+            ServiceState::Degraded
+        };
+
+        // Maintenance replaces the computed state; an incident is at least degraded
+        if let Some(notice) = notice.as_ref().filter(|notice| notice.in_effect(now)) {
+            if notice.kind == NoticeKind::Maintenance.as_str() {
+                status = ServiceState::Maintenance;
+            } else if status == ServiceState::Operational {
+                status = ServiceState::Degraded;
+            }
+        }
+
+        Self {
+            status,
+            average_bridging_secs: average_bridging_secs.map(|secs| secs.round() as u64),
+            notice,
+            updated_at: now,
+        }
+    }
+}
+
+/// Last computed status, shared by every clone so repeated page loads don't
+/// each hit the RPC and database
+#[derive(Clone, Default)]
+pub struct StatusCache {
+    last: Arc<Mutex<Option<ServiceStatus>>>,
+}
+
+impl StatusCache {
+    pub fn get(&self, now: i64) -> Option<ServiceStatus> {
+        self.last
+            .lock()
+            .unwrap()
+            .clone()
+            .filter(|status| now - status.updated_at < STATUS_CACHE_SECS)
+    }
+
+    pub fn put(&self, status: ServiceStatus) {
+        *self.last.lock().unwrap() = Some(status);
+    }
+
+    /// Forget the cached status, e.g. after the notice changed
+    pub fn invalidate(&self) {
+        *self.last.lock().unwrap() = None;
+    }
+}
@@ -0,0 +1,80 @@
+use crate::public_inputs::ton_account_id;
+use crate::types::Deposit;
+use crate::{OrchestratorError, Result};
+use std::time::Duration;
+
+/// Checks deposits against the TON transaction they claim, through a
+/// toncenter v3 compatible API, so bogus deposits never reach a prover
+#[derive(Clone)]
+pub struct TonTransactionValidator {
+    api_url: String,
+    client: reqwest::Client,
+}
+
+impl TonTransactionValidator {
+    pub fn new(api_url: &str) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap();
+
+        Self {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            client,
+        }
+    }
+
+    /// The transaction must exist and not have aborted. For native TON its
+    /// inbound message must also come from the deposit's sender and carry its
+    /// amount; a jetton deposit's amount and sender sit in the transfer
+    /// notification body, which is left to the circuit.
+    pub async fn validate(&self, deposit: &Deposit) -> Result<()> {
+        let invalid = |reason: String| {
+            OrchestratorError::InvalidDeposit(format!("deposit {}: {}", deposit.deposit_id, reason))
+        };
+        let hash = deposit.ton_tx_hash.trim_start_matches("0x");
+        let transaction = self.fetch(hash).await?.ok_or_else(|| invalid(format!("TON tx {} not found", hash)))?;
+
+        if transaction["description"]["aborted"].as_bool() == Some(true) {
+            return Err(invalid(format!("TON tx {} aborted", hash)));
+        }
+        if deposit.jetton_master.is_some() {
+            return Ok(());
+        }
+
+        let in_msg = &transaction["in_msg"];
+        let source = in_msg["source"].as_str().unwrap_or_default();
+        match (ton_account_id(source), ton_account_id(&deposit.sender_address)) {
+            (Some(source), Some(sender)) if source == sender => {}
+            (_, None) => return Err(invalid(format!("sender {} is not a TON address", deposit.sender_address))),
+            _ => {
+                return Err(invalid(format!(
+                    "TON tx {} was sent by {}, not {}",
+                    hash, source, deposit.sender_address
+                )))
+            }
+        }
+
+        let value = in_msg["value"].as_str().unwrap_or_default();
+        if value != deposit.amount {
+            return Err(invalid(format!("TON tx {} carries {} nanotons, not {}", hash, value, deposit.amount)));
+        }
+        Ok(())
+    }
+
+    async fn fetch(&self, hash: &str) -> Result<Option<serde_json::Value>> {
+        let body: serde_json::Value = self.client
+            .get(&format!("{}/api/v3/transactions", self.api_url))
+            .query(&[("hash", hash), ("limit", "1")])
+            .send()
+            .await
+            .map_err(OrchestratorError::NetworkError)?
+            .error_for_status()
+            .map_err(OrchestratorError::NetworkError)?
+            .json()
+            .await
+            .map_err(OrchestratorError::NetworkError)?;
+
+        Ok(body["transactions"].as_array().and_then(|transactions| transactions.first()).cloned())
+    }
+}
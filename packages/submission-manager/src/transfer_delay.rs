@@ -0,0 +1,79 @@
+use crate::proof::Proof;
+use crate::types::Deposit;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+struct HeldDeposit {
+    deposit: Deposit,
+    proof: Proof,
+    release_at: Instant,
+}
+
+/// Holds proven large deposits for a cooling-off window before they may be
+/// batched, so an operator or automated risk check can cancel them first
+#[derive(Clone)]
+pub struct LargeTransferDelay {
+    threshold: u64,
+    delay: Duration,
+    held: Arc<RwLock<HashMap<String, HeldDeposit>>>,
+}
+
+impl LargeTransferDelay {
+    /// `threshold` of 0 disables the delay
+    pub fn new(threshold: u64, delay_secs: u64) -> Self {
+        Self {
+            threshold,
+            delay: Duration::from_secs(delay_secs),
+            held: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub fn applies_to(&self, deposit: &Deposit) -> bool {
+        self.threshold > 0
+            && !self.delay.is_zero()
+            && deposit.amount.parse::<u64>().is_ok_and(|amount| amount > self.threshold)
+    }
+
+    pub fn hold(&self, deposit: Deposit, proof: Proof) {
+        let release_at = Instant::now() + self.delay;
+        self.held.write().unwrap().insert(
+            deposit.deposit_id.clone(),
+            HeldDeposit { deposit, proof, release_at },
+        );
+    }
+
+    /// Remove and return every deposit whose window has elapsed
+    pub fn take_released(&self) -> Vec<(Deposit, Proof)> {
+        let now = Instant::now();
+        let mut held = self.held.write().unwrap();
+        let due: Vec<String> = held
+            .iter()
+            .filter(|(_, h)| h.release_at <= now)
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        due.into_iter()
+            .filter_map(|id| held.remove(&id))
+            .map(|h| (h.deposit, h.proof))
+            .collect()
+    }
+
+    /// Drop a held deposit; returns it if it was still inside its window
+    pub fn cancel(&self, deposit_id: &str) -> Option<Deposit> {
+        self.held.write().unwrap().remove(deposit_id).map(|h| h.deposit)
+    }
+
+    /// Time left before a held deposit is released, if it is held
+    pub fn remaining(&self, deposit_id: &str) -> Option<Duration> {
+        self.held
+            .read()
+            .unwrap()
+            .get(deposit_id)
+            .map(|h| h.release_at.saturating_duration_since(Instant::now()))
+    }
+
+    pub fn held_count(&self) -> usize {
+        self.held.read().unwrap().len()
+    }
+}
@@ -1,5 +1,10 @@
 use serde::{Deserialize, Serialize};
 use chrono;
+use uuid::Uuid;
+use crate::environment::Environment;
+use crate::roles::ManagerRole;
+use crate::proof::Proof;
+use crate::{OrchestratorError, Result};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueueStats {
@@ -11,6 +16,7 @@ pub struct QueueStats {
 
 #[derive(Debug, Clone)]
 pub struct OrchestratorConfig {
+    pub environment: Environment,
     pub batch_size: usize,
     pub max_retries: u32,  // Keep as u32
     pub health_check_interval: u64,
@@ -18,13 +24,213 @@ pub struct OrchestratorConfig {
     pub validator_count: usize,
     pub validators: Vec<String>,
 
-    pub solana_rpc_url: String,
+    pub solana_rpc_urls: Vec<String>, // Failover order; the first is preferred
     pub solana_program_id: String,
-    pub solana_bridge_account: String,
+    pub solana_commitment: String,
     pub verification_key: String, // For ZK verification
+    pub mock_proving: bool,
+
+    pub max_deposit_amount: u64,
+    pub ton_wallet_url: String,
+    pub refund_max_attempts: u32,
+
+    pub db_checkpoint_interval_secs: u64,
+    pub db_vacuum_interval_secs: u64,
+    pub db_maintenance_window_start_hour: u32, // UTC, inclusive
+    pub db_maintenance_window_end_hour: u32,   // UTC, exclusive
+    // SQLite pragmas applied to every pooled connection
+    pub db_journal_mode: String,   // wal | delete | truncate | persist | memory | off
+    pub db_busy_timeout_ms: u64,   // Wait this long for a competing writer instead of failing
+    pub db_synchronous: String,    // off | normal | full | extra
+    // Postgres database deposits are migrated to, see db_migration; empty = none
+    pub db_mirror_url: String,
+    pub db_mirror_parity_interval_secs: u64,
+
+    pub max_batches_per_minute: u32, // 0 = unlimited
+    pub max_lamports_per_hour: u64,  // 0 = unlimited
+    pub fee_budget_lamports_per_hour: u64, // hard stop, 0 = unlimited
+    pub fee_budget_lamports_per_day: u64,  // hard stop, 0 = unlimited
+
+    pub sealing_policy: String, // size | time | fee | value | cost
+    pub batch_max_age_secs: u64, // Open batches older than this are stale and sealed
+    pub batch_process_interval_secs: u64, // Tick of the loop submitting queued batches
+    pub seal_min_total_fee: u64,
+    pub seal_min_total_value: u64, // Nanotons deposited in the open batch that seal it under `value`
+    pub seal_target_cost_per_deposit: u64,
+
+    // Deposits above this amount wait out a cancellable delay before batching (0 disables)
+    pub large_transfer_threshold: u64,
+    pub large_transfer_delay_secs: u64,
+
+    // Exact repeats of an ingestion request are answered idempotently inside
+    // this window and rejected as replays after it
+    pub idempotency_window_secs: u64,
+    // Key required for /api/admin endpoints; empty disables them
+    pub admin_api_key: String,
+
+    // Relayer keypair this manager submits with; must be in the program's relayer set.
+    // First set wins: base58 key, keypair file, SOLANA_KEYPAIR, Solana CLI keypair
+    pub relayer_private_key: Option<String>,
+    pub relayer_keypair_path: Option<String>,
+    pub solana_keypair: Option<String>, // Path, JSON byte array or base58
+
+    // Pre-fund event/nullifier PDAs in a separate transaction before verification
+    pub precreate_accounts: bool,
+
+    // TON API (toncenter v3 compatible) used to resolve jetton metadata
+    pub ton_api_url: String,
+    pub jetton_metadata_ttl_secs: u64,
+    // JSON file listing supported jettons; empty accepts any resolvable jetton
+    pub token_registry_path: String,
+
+    // CoinGecko-compatible price API for fiat reporting; empty disables rate snapshots
+    pub price_oracle_url: String,
+
+    // Optional user notification channels; empty disables each
+    pub telegram_bot_token: String,
+    pub email_api_url: String,
+    pub email_api_key: String,
+    pub email_from: String,
+
+    // TON light-client contract whose published root is relayed to the program;
+    // empty disables the root relay. Roots are only pushed when the listener's
+    // TON API reports the same root for the same checkpoint.
+    pub ton_light_client_address: String,
+    pub ton_light_client_get_method: String,
+    pub root_listener_api_url: String,
+    pub root_relay_interval_secs: u64,
+
+    // Sender or recipient addresses whose deposits are refused (refunded)
+    pub quarantined_addresses: Vec<String>,
+
+    pub archive_dir: String,          // Cold storage for finalized deposits; empty = keep everything in the DB
+    pub archive_ipfs_api_url: String, // Optional Kubo API to also add archives to IPFS
+
+    pub submitter_lease_ttl_secs: u64,   // Submitter lease lifetime; must outlast the 10s submitter tick
+    pub request_handover_on_start: bool, // Ask a running instance to hand over instead of waiting for its lease to expire
+
+    // Pipeline stages this process runs: ingest | prove | submit | all
+    pub role: ManagerRole,
+
+    // Thresholds for GET /api/alerts; 0 disables each check
+    pub alert_fee_payer_min_lamports: u64,
+    pub alert_root_stale_secs: u64,
+    pub deposit_sla_secs: u64, // Longest a deposit may wait pending/delayed
+
+    // Interval of the bridge-wide invariant audit, see invariants; 0 disables
+    pub invariant_check_interval_secs: u64,
+
+    // Ceiling on the compute-unit price bid on submissions; 0 = uncapped
+    pub max_priority_fee_micro_lamports: u64,
+
+    pub solana_rpc_timeout_secs: u64,     // Per RPC request
+    pub solana_confirm_timeout_secs: u64, // Per transaction, from send to confirmation
+
+    pub solana_nonce_account: Option<String>, // Durable nonce advanced by the relayer; unset uses recent blockhashes
+
+    pub finality_timeout_secs: u64, // Unseen this long before finalizing, a transaction counts as dropped
+
+    pub solana_ws_url: String, // Program event subscription; empty derives it from the first RPC URL
+
+    pub jito_block_engine_url: String, // Empty sends through the RPC endpoints
+    pub jito_tip_lamports: u64,
+
+    pub solana_use_lookup_table: bool, // Send v0 transactions through an address lookup table
+    pub solana_lookup_table: Option<String>, // Existing table; unset creates one at startup
+
+    pub min_payer_balance_lamports: u64, // Submissions pause below this; 0 only pauses on failed fees
+
+    pub verify_proofs_locally: bool, // Pairing-check circuit service proofs before batching them
+
+    pub proof_job_poll_secs: u64,    // How often asynchronous proof jobs are polled
+    pub proof_job_timeout_secs: u64, // Age at which a proof job is abandoned
+
+    pub prover_concurrency: usize, // Deposits each prover process proves at once
+
+    // Check each deposit's TON transaction on `ton_api_url` before proving it
+    pub validate_deposits_on_ton: bool,
+
+    // Adaptive batch sizing: batches grow with the proving backlog between
+    // these bounds, capped by the transaction bytes and compute they carry
+    pub adaptive_batch_sizing: bool,
+    pub batch_min_size: usize,
+    pub batch_max_size: usize,
+    pub batch_max_bytes: usize,
+    pub batch_max_compute_units: u64,
+
+    // Priority lane: deposits flagged by the API, or paying at least
+    // `priority_fee_threshold` (0 = no fee tier), seal in their own small,
+    // short-lived batches
+    pub priority_batch_size: usize,
+    pub priority_batch_max_age_secs: u64,
+    pub priority_fee_threshold: u64,
 }
 
-#[derive(Debug, Clone)]
+impl OrchestratorConfig {
+    /// Safety interlocks that must hold before the manager is allowed to start
+    pub fn validate(&self) -> Result<()> {
+        if self.environment == Environment::Mainnet && self.mock_proving {
+            return Err(OrchestratorError::ConfigurationError(
+                "mock proving mode cannot be enabled with the mainnet preset".to_string(),
+            ));
+        }
+
+        if self.validator_count == 0 || self.validator_count > self.validators.len().max(1) {
+            return Err(OrchestratorError::ConfigurationError(format!(
+                "circuit quorum of {} needs between 1 and {} circuit services",
+                self.validator_count,
+                self.validators.len().max(1)
+            )));
+        }
+
+        if self.verify_proofs_locally && self.verification_key.is_empty() {
+            return Err(OrchestratorError::ConfigurationError(
+                "local proof verification needs VERIFICATION_KEY".to_string(),
+            ));
+        }
+
+        if !self.ton_light_client_address.is_empty() && self.root_listener_api_url.is_empty() {
+            return Err(OrchestratorError::ConfigurationError(
+                "root relay needs ROOT_LISTENER_API_URL to cross-check the light-client root".to_string(),
+            ));
+        }
+
+        if self.submitter_lease_ttl_secs <= 10 {
+            return Err(OrchestratorError::ConfigurationError(
+                "submitter_lease_ttl_secs must be longer than the 10s submitter tick".to_string(),
+            ));
+        }
+
+        if self.batch_size == 0 {
+            return Err(OrchestratorError::ConfigurationError(
+                "batch_size must be greater than zero".to_string(),
+            ));
+        }
+
+        if self.priority_batch_size == 0 {
+            return Err(OrchestratorError::ConfigurationError(
+                "priority_batch_size must be greater than zero".to_string(),
+            ));
+        }
+
+        if self.batch_process_interval_secs == 0 {
+            return Err(OrchestratorError::ConfigurationError(
+                "batch_process_interval_secs must be greater than zero".to_string(),
+            ));
+        }
+
+        if self.adaptive_batch_sizing && (self.batch_min_size == 0 || self.batch_min_size > self.batch_max_size) {
+            return Err(OrchestratorError::ConfigurationError(format!(
+                "batch_min_size ({}) must be between 1 and batch_max_size ({})",
+                self.batch_min_size, self.batch_max_size
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Deposit {
     pub deposit_id: String,
     pub ton_tx_hash: String,
@@ -34,6 +240,47 @@ pub struct Deposit {
     pub fee_est: String,
     pub nonce: String,
     pub created_at: u64,
+    pub recipient_commitment: Option<String>, // Privacy mode: hex hash(recipient || salt)
+    pub recipient_salt: Option<String>,       // Privacy mode: hex salt revealed at claim time
+    pub event_id: Option<String>,             // Hex event id as computed by the TON indexer
+    pub nullifier: Option<String>,            // Hex nullifier as computed by the TON indexer
+    pub jetton_master: Option<String>,        // TON jetton master address; None for native TON
+    #[serde(default)]
+    pub priority: bool,                       // Batched in the priority lane
+}
+
+/// Why a deposit would be rejected at ingestion; `code` is the stable API error code
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationIssue {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    pub fn new(code: &'static str, message: String) -> Self {
+        Self { code, message }
+    }
+}
+
+/// Outcome of checking an ingestion request against the request log
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestAdmission {
+    New,
+    /// Exact repeat inside the idempotency window; answer as before, don't reprocess
+    Duplicate,
+    /// Exact repeat after the idempotency window
+    Replay,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CostEstimate {
+    pub deposits: usize,
+    pub base_fee_lamports: u64,
+    pub priority_fee_lamports: u64,
+    pub rent_lamports: u64,
+    pub total_lamports: u64,
+    pub priority_fee_micro_lamports: u64,
+    pub recent_cost_per_deposit: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -46,10 +293,20 @@ pub struct SystemHealth {
     pub last_batch_time: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Batch {
+    #[serde(default = "Uuid::new_v4")] // Batches queued before ids were assigned
+    pub id: Uuid,
     pub deposits: Vec<Deposit>,
-    pub proofs: Vec<String>,
+    pub proofs: Vec<Proof>,
     pub created_at: chrono::DateTime<chrono::Utc>,
      pub retry_count: usize,
+}
+
+impl Batch {
+    /// Stable identity of the batch's contents, unchanged across retries
+    pub fn content_id(&self) -> String {
+        let ids: Vec<&[u8]> = self.deposits.iter().map(|d| d.deposit_id.as_bytes()).collect();
+        hex::encode(solana_sdk::hash::hashv(&ids).to_bytes())
+    }
 }
\ No newline at end of file
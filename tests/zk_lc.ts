@@ -0,0 +1,542 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import {
+  AddressLookupTableAccount,
+  AddressLookupTableProgram,
+  Keypair,
+  PublicKey,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+} from "@solana/web3.js";
+import { expect } from "chai";
+import { createHash } from "crypto";
+import { ZkLc } from "../target/types/zk_lc";
+
+// Every describe block shares one validator and one LcState, and switching to
+// bitmap nullifiers is one-way: blocks that need PDA mode must come before the
+// "bitmap nullifier mode" block at the end of this file.
+
+const TOKEN_PROGRAM_ID = new PublicKey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+const VK_ID = 1;
+const EVENT_PUBLIC_INPUT_COUNT = 11;
+const NULLIFIER_MODE_BITMAP = 1;
+const BITS_PER_PAGE = 1024 * 8;
+
+const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+const program = anchor.workspace.ZkLc as Program<ZkLc>;
+const admin = provider.wallet.publicKey;
+
+const sha256 = (...parts: Buffer[]) => createHash("sha256").update(Buffer.concat(parts)).digest();
+const bytes = (buffer: Buffer) => Array.from(buffer);
+const pda = (...seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+// A public-input hash as the proof sees it, top three bits cleared; event and
+// nullifier accounts are seeded with this rather than the raw bytes
+function scalar(hash: number[]): Buffer {
+  const reduced = Buffer.from(hash);
+  reduced[0] &= 0x1f;
+  return reduced;
+}
+
+const TOKEN_ID = sha256(Buffer.from("TON"));
+const DOMAIN = sha256(Buffer.from("zk-bridge test domain"));
+
+const statePda = pda(Buffer.from("lc_state"));
+const wrappedMint = pda(Buffer.from("wrapped_ton"));
+const treasury = pda(Buffer.from("treasury"));
+
+function u64Le(value: number | bigint): Buffer {
+  const buffer = Buffer.alloc(8);
+  buffer.writeBigUInt64LE(BigInt(value));
+  return buffer;
+}
+
+function u32Le(value: number): Buffer {
+  const buffer = Buffer.alloc(4);
+  buffer.writeUInt32LE(value);
+  return buffer;
+}
+
+function associatedTokenAddress(owner: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [owner.toBuffer(), TOKEN_PROGRAM_ID.toBuffer(), wrappedMint.toBuffer()],
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  )[0];
+}
+
+// Merkle tree over TON transaction hashes, as merkle.rs hashes it
+const hashLeaf = (txHash: Buffer) => sha256(Buffer.from([0x00]), txHash);
+const hashNode = (left: Buffer, right: Buffer) => sha256(Buffer.from([0x01]), left, right);
+
+class TxTree {
+  private levels: Buffer[][];
+
+  // `txHashes.length` must be a power of two
+  constructor(txHashes: Buffer[]) {
+    this.levels = [txHashes.map(hashLeaf)];
+    while (this.levels[this.levels.length - 1].length > 1) {
+      const level = this.levels[this.levels.length - 1];
+      const next: Buffer[] = [];
+      for (let i = 0; i < level.length; i += 2) {
+        next.push(hashNode(level[i], level[i + 1]));
+      }
+      this.levels.push(next);
+    }
+  }
+
+  get root(): Buffer {
+    return this.levels[this.levels.length - 1][0];
+  }
+
+  inclusion(leafIndex: number) {
+    const siblings = this.levels.slice(0, -1).map((level, depth) => level[(leafIndex >> depth) ^ 1]);
+    return { leafIndex: new BN(leafIndex), siblings: siblings.map(bytes) };
+  }
+}
+
+// A deposit's transaction hash and nullifier, derived from a label
+function deposit(label: string) {
+  return {
+    txHash: sha256(Buffer.from(`tx:${label}`)),
+    nullifier: sha256(Buffer.from(`nullifier:${label}`)),
+  };
+}
+
+// `event_id` as ZKVerifier::hash_event_components computes it with SHA-256
+function eventId(recipient: PublicKey, amount: number, feeBps: number): Buffer {
+  const fee = Buffer.alloc(2);
+  fee.writeUInt16LE(feeBps);
+  return sha256(
+    Buffer.from("TON_EVENT"),
+    TOKEN_ID,
+    u64Le(amount),
+    recipient.toBuffer(),
+    fee,
+    u32Le(VK_ID),
+    DOMAIN
+  );
+}
+
+// Mock verification only refuses an all-zero proof
+const MOCK_PROOF = { a: Array(64).fill(1), b: Array(128).fill(1), c: Array(64).fill(1) };
+
+// `feeBps` is the depositor's fee ceiling, which protocol fee and claim tip share
+function publicInputs(
+  root: Buffer,
+  recipient: PublicKey,
+  amount: number,
+  { txHash, nullifier }: { txHash: Buffer; nullifier: Buffer },
+  feeBps = 0
+) {
+  return {
+    domain: bytes(DOMAIN),
+    anchorRoot: bytes(root),
+    eventId: bytes(eventId(recipient, amount, feeBps)),
+    tokenId: bytes(TOKEN_ID),
+    amountInTon: new BN(amount),
+    recipientSolana: recipient,
+    feeBps,
+    vkVersion: VK_ID,
+    tonTxHash: bytes(txHash),
+    tonSender: bytes(sha256(Buffer.from("ton sender"))),
+    nullifier: bytes(nullifier),
+    recipientCommitment: Array(32).fill(0),
+  };
+}
+
+async function expectFailure(attempt: Promise<unknown>, code: string) {
+  try {
+    await attempt;
+  } catch (err: any) {
+    const detail = `${err}\n${(err.logs ?? []).join("\n")}`;
+    expect(detail).to.contain(code);
+    return;
+  }
+  expect.fail(`expected ${code}`);
+}
+
+async function setTonRoot(root: Buffer) {
+  await program.methods
+    .updateTonRoot(bytes(root))
+    .accountsPartial({ state: statePda, relayerSet: null, signer: admin })
+    .rpc();
+}
+
+async function setDepositTreeRoot(root: Buffer) {
+  await program.methods
+    .updateDepositTreeRoot(bytes(root))
+    .accountsPartial({ state: statePda, relayerSet: null, signer: admin })
+    .rpc();
+}
+
+// Initialize the program once per validator: state, a finalized mock-shaped
+// verifying key, wrapped mint, treasury, and an enabled, uncapped token
+async function ensureBridge() {
+  if (await provider.connection.getAccountInfo(statePda)) {
+    return;
+  }
+
+  await program.methods
+    .initState(VK_ID, Array(32).fill(0), admin)
+    .accountsPartial({ payer: admin })
+    .rpc();
+
+  // alpha, beta, gamma, delta, then ic_len and one IC point per input plus one
+  const icLen = EVENT_PUBLIC_INPUT_COUNT + 1;
+  const key = Buffer.alloc(448 + 4 + icLen * 64);
+  key.writeUInt32LE(icLen, 448);
+  const split = 600;
+  await program.methods
+    .uploadVerifyingKey(VK_ID, key.length, key.subarray(0, split))
+    .accountsPartial({ admin })
+    .rpc();
+  await program.methods
+    .appendVkChunk(VK_ID, split, key.subarray(split))
+    .accountsPartial({ admin })
+    .rpc();
+  await program.methods.finalizeVerifyingKey(VK_ID).accountsPartial({ admin }).rpc();
+
+  await program.methods.initWrappedMint().accountsPartial({ admin }).rpc();
+  await program.methods.initTreasury().accountsPartial({ admin }).rpc();
+  await program.methods
+    .configureToken(bytes(TOKEN_ID), wrappedMint, new BN(1), new BN(0), true)
+    .accountsPartial({ admin })
+    .rpc();
+  await program.methods.setGlobalVolumeCap(new BN(0)).accountsPartial({ admin }).rpc();
+  await program.methods
+    .setTokenVolumeCap(bytes(TOKEN_ID), new BN(0))
+    .accountsPartial({ admin })
+    .rpc();
+}
+
+// A proof and its accounts overflow a legacy transaction, so verification
+// instructions go out as v0 messages loading the shared accounts from a table
+let lookupTable: AddressLookupTableAccount | undefined;
+
+async function sharedLookupTable(): Promise<AddressLookupTableAccount> {
+  if (lookupTable) {
+    return lookupTable;
+  }
+  const connection = provider.connection;
+  const slot = await connection.getSlot("finalized");
+  const [create, table] = AddressLookupTableProgram.createLookupTable({
+    authority: admin,
+    payer: admin,
+    recentSlot: slot,
+  });
+  const extend = AddressLookupTableProgram.extendLookupTable({
+    lookupTable: table,
+    authority: admin,
+    payer: admin,
+    addresses: [
+      statePda,
+      pda(Buffer.from("vk"), u32Le(VK_ID)),
+      pda(Buffer.from("token_config"), TOKEN_ID),
+      pda(Buffer.from("volume_limit"), Buffer.from("global")),
+      pda(Buffer.from("volume_limit"), TOKEN_ID),
+      wrappedMint,
+      treasury,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+      anchor.web3.SystemProgram.programId,
+    ],
+  });
+  await provider.sendAndConfirm(new anchor.web3.Transaction().add(create, extend));
+
+  // A table is only usable from the slot after its last extension
+  const extendedAt = await connection.getSlot();
+  while ((await connection.getSlot()) <= extendedAt) {
+    await new Promise((resolve) => setTimeout(resolve, 400));
+  }
+  lookupTable = (await connection.getAddressLookupTable(table)).value!;
+  return lookupTable;
+}
+
+async function sendV0(instruction: TransactionInstruction): Promise<string> {
+  const table = await sharedLookupTable();
+  const { blockhash } = await provider.connection.getLatestBlockhash();
+  const message = new TransactionMessage({
+    payerKey: admin,
+    recentBlockhash: blockhash,
+    instructions: [instruction],
+  }).compileToV0Message([table]);
+  return provider.sendAndConfirm(new VersionedTransaction(message));
+}
+
+type Event = {
+  proof: typeof MOCK_PROOF;
+  publicInputs: ReturnType<typeof publicInputs>;
+  inclusion: ReturnType<TxTree["inclusion"]>;
+};
+
+function verifyTonEvent({ proof, publicInputs, inclusion }: Event, claimerTokenAccount: PublicKey | null = null) {
+  return program.methods
+    .verifyTonEvent(proof, publicInputs, inclusion)
+    .accountsPartial({
+      headerChain: null,
+      recipient: publicInputs.recipientSolana,
+      claimerTokenAccount,
+      payer: admin,
+    })
+    .instruction()
+    .then((instruction) => sendV0(instruction));
+}
+
+function verifyTonEventBatch(events: Event[]) {
+  const remainingAccounts = events.flatMap(({ publicInputs }) => [
+    { pubkey: pda(Buffer.from("event"), scalar(publicInputs.eventId)), isWritable: true, isSigner: false },
+    { pubkey: pda(Buffer.from("nullifier"), scalar(publicInputs.nullifier)), isWritable: true, isSigner: false },
+    { pubkey: publicInputs.recipientSolana, isWritable: false, isSigner: false },
+    { pubkey: associatedTokenAddress(publicInputs.recipientSolana), isWritable: true, isSigner: false },
+  ]);
+  return program.methods
+    .verifyTonEventBatch(events.map((event) => ({ ...event, recipientSalt: Array(32).fill(0) })))
+    .accountsPartial({ headerChain: null, payer: admin })
+    .remainingAccounts(remainingAccounts)
+    .instruction()
+    .then((instruction) => sendV0(instruction));
+}
+
+function verifyTonEventCompact({ proof, publicInputs, inclusion }: Event) {
+  return program.methods
+    .verifyTonEventCompact(proof, publicInputs, inclusion, Array(32).fill(0))
+    .accountsPartial({
+      nullifierBitmap: bitmapPage(inclusion.leafIndex.toNumber()),
+      nullifierAccount: pda(Buffer.from("nullifier"), scalar(publicInputs.nullifier)),
+      recipient: publicInputs.recipientSolana,
+      payer: admin,
+    })
+    .instruction()
+    .then((instruction) => sendV0(instruction));
+}
+
+function bitmapPage(leafIndex: number): PublicKey {
+  return pda(Buffer.from("nullifier_bitmap"), u64Le(Math.floor(leafIndex / BITS_PER_PAGE)));
+}
+
+// The wrapped-TON account of `owner`, created if missing
+async function ensureTokenAccount(owner: PublicKey): Promise<PublicKey> {
+  const account = associatedTokenAddress(owner);
+  const createIdempotent = new TransactionInstruction({
+    programId: ASSOCIATED_TOKEN_PROGRAM_ID,
+    keys: [
+      { pubkey: admin, isSigner: true, isWritable: true },
+      { pubkey: account, isSigner: false, isWritable: true },
+      { pubkey: owner, isSigner: false, isWritable: false },
+      { pubkey: wrappedMint, isSigner: false, isWritable: false },
+      { pubkey: anchor.web3.SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.from([1]),
+  });
+  await provider.sendAndConfirm(new anchor.web3.Transaction().add(createIdempotent));
+  return account;
+}
+
+// Events the program emitted in the transaction `signature`
+async function programEvents(signature: string) {
+  await provider.connection.confirmTransaction(signature, "confirmed");
+  const transaction = await provider.connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const parser = new anchor.EventParser(program.programId, program.coder);
+  return Array.from(parser.parseLogs(transaction?.meta?.logMessages ?? []));
+}
+
+async function balance(owner: PublicKey): Promise<bigint> {
+  const amount = await provider.connection.getTokenAccountBalance(associatedTokenAddress(owner));
+  return BigInt(amount.value.amount);
+}
+
+before(ensureBridge);
+
+describe("verify_ton_event_batch", () => {
+  const recipient = Keypair.generate().publicKey;
+  const batchDeposit = deposit("batch");
+  const tree = new TxTree([
+    deposit("batch-0").txHash,
+    deposit("batch-1").txHash,
+    batchDeposit.txHash,
+    deposit("batch-3").txHash,
+  ]);
+
+  // A Groth16 proof with its inputs and path takes over half a packet, so a
+  // transaction carries one batched event
+  it("verifies an event through its remaining-account group", async () => {
+    await setTonRoot(tree.root);
+    const inputs = publicInputs(tree.root, recipient, 1_500, batchDeposit);
+    await verifyTonEventBatch([{ proof: MOCK_PROOF, publicInputs: inputs, inclusion: tree.inclusion(2) }]);
+
+    expect(await balance(recipient)).to.equal(1_500n);
+    const event = await program.account.eventState.fetch(pda(Buffer.from("event"), scalar(inputs.eventId)));
+    expect(event.leafIndex.toNumber()).to.equal(2);
+  });
+
+  it("refuses an event already consumed", async () => {
+    const inputs = publicInputs(tree.root, recipient, 1_500, batchDeposit);
+    await expectFailure(
+      verifyTonEventBatch([{ proof: MOCK_PROOF, publicInputs: inputs, inclusion: tree.inclusion(2) }]),
+      "EventAlreadyConsumed"
+    );
+  });
+
+  it("refuses the event again with a high bit of a hash flipped", async () => {
+    const inputs = publicInputs(tree.root, recipient, 1_500, batchDeposit);
+    const flippedNullifier = { ...inputs, nullifier: [...inputs.nullifier] };
+    flippedNullifier.nullifier[0] ^= 0x80;
+    await expectFailure(
+      verifyTonEventBatch([{ proof: MOCK_PROOF, publicInputs: flippedNullifier, inclusion: tree.inclusion(2) }]),
+      "EventAlreadyConsumed"
+    );
+
+    const flippedEventId = { ...inputs, eventId: [...inputs.eventId] };
+    flippedEventId.eventId[0] ^= 0x80;
+    await expectFailure(
+      verifyTonEventBatch([{ proof: MOCK_PROOF, publicInputs: flippedEventId, inclusion: tree.inclusion(2) }]),
+      "InvalidEventId"
+    );
+    expect(await balance(recipient)).to.equal(1_500n);
+  });
+});
+
+describe("claim tip", () => {
+  const recipient = Keypair.generate().publicKey;
+  const claimDeposit = deposit("claim");
+  const tree = new TxTree([claimDeposit.txHash, deposit("claim-1").txHash]);
+  const tipBps = 100;
+
+  after(() => program.methods.setClaimTipBps(0).accountsPartial({ admin }).rpc());
+
+  it("mints the tip to a claimer passing their own token account", async () => {
+    await program.methods.setClaimTipBps(tipBps).accountsPartial({ admin }).rpc();
+    const claimerTokenAccount = await ensureTokenAccount(admin);
+    const claimerBefore = await balance(admin);
+
+    await setTonRoot(tree.root);
+    const inputs = publicInputs(tree.root, recipient, 10_000, claimDeposit, tipBps);
+    const signature = await verifyTonEvent(
+      { proof: MOCK_PROOF, publicInputs: inputs, inclusion: tree.inclusion(0) },
+      claimerTokenAccount
+    );
+
+    expect(await balance(recipient)).to.equal(9_900n);
+    expect((await balance(admin)) - claimerBefore).to.equal(100n);
+    const paid = (await programEvents(signature)).find((event) => event.name === "claimTipPaid");
+    expect(paid, "ClaimTipPaid emitted").to.exist;
+    expect(paid!.data.claimer.equals(admin)).to.be.true;
+    expect(paid!.data.tip.toNumber()).to.equal(100);
+  });
+
+  it("refuses a tip the depositor's fee ceiling leaves no room for", async () => {
+    const inputs = publicInputs(tree.root, recipient, 10_000, deposit("claim-1"));
+
+    await expectFailure(
+      verifyTonEvent(
+        { proof: MOCK_PROOF, publicInputs: inputs, inclusion: tree.inclusion(1) },
+        associatedTokenAddress(admin)
+      ),
+      "InvalidFee"
+    );
+  });
+});
+
+describe("bitmap nullifier mode", () => {
+  const recipient = Keypair.generate().publicKey;
+  const pdaDeposit = deposit("pda");
+  const bitmapDeposit = deposit("bitmap");
+  const laterDeposit = deposit("bitmap-2");
+  // The bridge's append-only deposit tree, then the same tree grown by two
+  const depositTree = new TxTree([pdaDeposit.txHash, bitmapDeposit.txHash]);
+  const grownTree = new TxTree([
+    pdaDeposit.txHash,
+    bitmapDeposit.txHash,
+    laterDeposit.txHash,
+    deposit("bitmap-3").txHash,
+  ]);
+
+  it("verifies a deposit through verify_ton_event before the switch", async () => {
+    await setTonRoot(depositTree.root);
+    const inputs = publicInputs(depositTree.root, recipient, 1_000, pdaDeposit);
+    await verifyTonEvent({ proof: MOCK_PROOF, publicInputs: inputs, inclusion: depositTree.inclusion(0) });
+
+    expect(await balance(recipient)).to.equal(1_000n);
+  });
+
+  it("refuses replaying a PDA-mode deposit through verify_ton_event_compact", async () => {
+    await program.methods
+      .setNullifierMode(NULLIFIER_MODE_BITMAP)
+      .accountsPartial({ admin })
+      .rpc();
+
+    await setDepositTreeRoot(depositTree.root);
+    const inputs = publicInputs(depositTree.root, recipient, 1_000, pdaDeposit);
+    await expectFailure(
+      verifyTonEventCompact({ proof: MOCK_PROOF, publicInputs: inputs, inclusion: depositTree.inclusion(0) }),
+      "EventAlreadyConsumed"
+    );
+  });
+
+  it("verifies through verify_ton_event_compact without per-event accounts", async () => {
+    const inputs = publicInputs(depositTree.root, recipient, 2_000, bitmapDeposit);
+    await verifyTonEventCompact({ proof: MOCK_PROOF, publicInputs: inputs, inclusion: depositTree.inclusion(1) });
+
+    expect(await balance(recipient)).to.equal(3_000n);
+    expect(await provider.connection.getAccountInfo(pda(Buffer.from("event"), scalar(inputs.eventId)))).to.be.null;
+    const page = await program.account.nullifierBitmap.fetch(bitmapPage(1));
+    expect(page.bits[0] & 0b10).to.equal(0b10);
+  });
+
+  it("refuses replaying a bitmap-mode deposit through verify_ton_event", async () => {
+    await setTonRoot(depositTree.root);
+    const inputs = publicInputs(depositTree.root, recipient, 2_000, bitmapDeposit);
+
+    await expectFailure(
+      verifyTonEvent({ proof: MOCK_PROOF, publicInputs: inputs, inclusion: depositTree.inclusion(1) }),
+      "NullifierModeMismatch"
+    );
+  });
+
+  it("refuses the same deposit under a later root of the grown tree", async () => {
+    await setDepositTreeRoot(grownTree.root);
+    const inputs = publicInputs(grownTree.root, recipient, 2_000, bitmapDeposit);
+
+    await expectFailure(
+      verifyTonEventCompact({ proof: MOCK_PROOF, publicInputs: inputs, inclusion: grownTree.inclusion(1) }),
+      "EventAlreadyConsumed"
+    );
+  });
+
+  // Leaf 1 of another tree is a different deposit than leaf 1 of the deposit
+  // tree; it must neither mint under that root nor be blocked by the set bit
+  it("keys bitmap bits by position in the deposit tree only", async () => {
+    const otherTree = new TxTree([deposit("other-0").txHash, laterDeposit.txHash]);
+    await setTonRoot(otherTree.root);
+    const otherInputs = publicInputs(otherTree.root, recipient, 4_000, laterDeposit);
+    await expectFailure(
+      verifyTonEventCompact({ proof: MOCK_PROOF, publicInputs: otherInputs, inclusion: otherTree.inclusion(1) }),
+      "InvalidAnchorRoot"
+    );
+
+    const inputs = publicInputs(grownTree.root, recipient, 4_000, laterDeposit);
+    await verifyTonEventCompact({ proof: MOCK_PROOF, publicInputs: inputs, inclusion: grownTree.inclusion(2) });
+    expect(await balance(recipient)).to.equal(7_000n);
+
+    // Nor does the claimed deposit mint again under the other root
+    await expectFailure(
+      verifyTonEventCompact({ proof: MOCK_PROOF, publicInputs: otherInputs, inclusion: otherTree.inclusion(1) }),
+      "InvalidAnchorRoot"
+    );
+  });
+
+  it("cannot be switched back to nullifier PDAs", async () => {
+    await expectFailure(
+      program.methods.setNullifierMode(0).accountsPartial({ admin }).rpc(),
+      "NullifierModeIrreversible"
+    );
+  });
+});